@@ -2,7 +2,7 @@
 
 extern crate test;
 
-use bio::io::{fasta, fastx};
+use bio::io::{fasta, fastq, fastx};
 use rand::distr::Alphanumeric;
 use rand::rngs::StdRng;
 use rand::Rng;
@@ -14,14 +14,12 @@ const BASES: &[u8] = b"ACTG";
 const ID_LEN: usize = 10;
 const DESC_LEN: usize = 20;
 const SEQ_LEN: usize = 100;
-const FASTA_SIZE: usize = 100;
+const FASTA_SIZE: usize = 1000;
 
-fn gen_random_fasta() -> io::Result<Vec<u8>> {
+fn gen_random_records() -> Vec<(String, String, Vec<u8>)> {
     let mut rng = StdRng::seed_from_u64(42);
-    let mut raw_writer = Vec::new();
-    {
-        let mut w = fasta::Writer::new(&mut raw_writer);
-        for _ in 0..FASTA_SIZE {
+    (0..FASTA_SIZE)
+        .map(|_| {
             let id: String = (0..ID_LEN)
                 .map(|_| char::from(rng.sample(&Alphanumeric)))
                 .collect();
@@ -37,12 +35,34 @@ fn gen_random_fasta() -> io::Result<Vec<u8>> {
                 })
                 .collect();
 
+            (id, desc, seq)
+        })
+        .collect()
+}
+
+fn gen_random_fasta() -> io::Result<Vec<u8>> {
+    let mut raw_writer = Vec::new();
+    {
+        let mut w = fasta::Writer::new(&mut raw_writer);
+        for (id, desc, seq) in gen_random_records() {
             w.write_record(&fasta::Record::with_attrs(&id, Some(&desc), &seq))?;
         }
     }
     Ok(raw_writer)
 }
 
+fn gen_random_fastq() -> io::Result<Vec<u8>> {
+    let mut raw_writer = Vec::new();
+    {
+        let mut w = fastq::Writer::new(&mut raw_writer);
+        for (id, desc, seq) in gen_random_records() {
+            let qual = vec![b'I'; seq.len()];
+            w.write_record(&fastq::Record::with_attrs(&id, Some(&desc), &seq, &qual))?;
+        }
+    }
+    Ok(raw_writer)
+}
+
 fn fastx_count_bases<T, E, I>(records: I) -> Result<usize, E>
 where
     T: fastx::Record,
@@ -69,6 +89,42 @@ where
     Ok(nb_bases)
 }
 
+fn fasta_ref_count_bases<R>(mut records: fasta::RefRecords<R>) -> io::Result<usize>
+where
+    R: io::BufRead,
+{
+    let mut nb_bases = 0;
+    while let Some(result) = records.next() {
+        let record = result?;
+        nb_bases += record.seq().len();
+    }
+    Ok(nb_bases)
+}
+
+fn fastq_count_bases<R>(records: fastq::Records<R>) -> fastq::Result<usize>
+where
+    R: io::BufRead,
+{
+    let mut nb_bases = 0;
+    for result in records {
+        let record = result?;
+        nb_bases += record.seq().len();
+    }
+    Ok(nb_bases)
+}
+
+fn fastq_ref_count_bases<R>(mut records: fastq::RefRecords<R>) -> fastq::Result<usize>
+where
+    R: io::BufRead,
+{
+    let mut nb_bases = 0;
+    while let Some(result) = records.next() {
+        let record = result?;
+        nb_bases += record.seq().len();
+    }
+    Ok(nb_bases)
+}
+
 fn fastx_check<T, E, I>(records: I) -> Result<(), String>
 where
     T: fastx::Record,
@@ -104,6 +160,39 @@ fn bench_fasta_count(b: &mut Bencher) -> io::Result<()> {
     Ok(())
 }
 
+#[bench]
+fn bench_fasta_ref_count(b: &mut Bencher) -> io::Result<()> {
+    let mut data = io::Cursor::new(gen_random_fasta()?);
+    b.iter(|| {
+        data.set_position(0);
+        let records = fasta::Reader::new(&mut data).ref_records();
+        fasta_ref_count_bases(records).unwrap();
+    });
+    Ok(())
+}
+
+#[bench]
+fn bench_fastq_count(b: &mut Bencher) -> io::Result<()> {
+    let mut data = io::Cursor::new(gen_random_fastq()?);
+    b.iter(|| {
+        data.set_position(0);
+        let records = fastq::Reader::new(&mut data).records();
+        fastq_count_bases(records).unwrap();
+    });
+    Ok(())
+}
+
+#[bench]
+fn bench_fastq_ref_count(b: &mut Bencher) -> io::Result<()> {
+    let mut data = io::Cursor::new(gen_random_fastq()?);
+    b.iter(|| {
+        data.set_position(0);
+        let records = fastq::Reader::new(&mut data).ref_records();
+        fastq_ref_count_bases(records).unwrap();
+    });
+    Ok(())
+}
+
 #[bench]
 fn bench_fastx_fasta_count(b: &mut Bencher) -> io::Result<()> {
     let mut data = io::Cursor::new(gen_random_fasta()?);
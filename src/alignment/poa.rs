@@ -16,6 +16,8 @@
 //! For a modern reference implementation, see poapy:
 //! https://github.com/ljdursi/poapy
 //!
+//! Graphs can be exported to and loaded from GFA with [`io::gfa`](crate::io::gfa).
+//!
 //! # Example
 //!
 //! ```
@@ -289,6 +291,16 @@ impl<F: MatchFunc> Aligner<F> {
         }
     }
 
+    /// Create new instance continuing from an existing partial order alignment, e.g. one
+    /// obtained from a multiple sequence alignment.
+    pub fn from_poa(poa: Poa<F>) -> Self {
+        Aligner {
+            traceback: Traceback::new(),
+            query: vec![],
+            poa,
+        }
+    }
+
     /// Get the alignment of the last query to the graph and add to graph.
     pub fn add_to_graph(&mut self) -> &mut Self {
         let alignment = self.poa.recalculate_alignment(&self.traceback);
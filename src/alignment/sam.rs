@@ -0,0 +1,1015 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Export of pairwise alignments to CIGAR strings, MD/NM tags and SAM text.
+//!
+//! An [`Alignment`] as returned by [`pairwise::Aligner`](crate::alignment::pairwise::Aligner)
+//! is converted into a [`Cigar`] using `=`/`X` operations, with unaligned prefixes and
+//! suffixes of the query (e.g. resulting from `Scoring::xclip`) rendered as clips.
+//! The MD and NM tags are computed from the alignment and the reference sequence, and
+//! [`Record`]s can be written as plain SAM lines via [`Writer`], with a [`Header`] built
+//! from the sequences of a [`fasta::Index`].
+//!
+//! The reverse direction is provided by [`operations_from_cigar_md`], which recovers the
+//! [`AlignmentOperation`]s from a CIGAR string and an MD tag.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::alignment::pairwise::Aligner;
+//! use bio::alignment::sam::{self, Cigar, Header, Record, Writer};
+//! use bio::io::fasta;
+//!
+//! let x = b"TTACGTAGGA";
+//! let y = b"CCCACGTTGGACCC";
+//! let score = |a: u8, b: u8| if a == b { 1i32 } else { -1i32 };
+//! let mut aligner = Aligner::new(-5, -1, score);
+//! let alignment = aligner.local(x, y);
+//!
+//! let cigar = Cigar::from_alignment(&alignment, false);
+//! assert_eq!(cigar.to_string(), "2S4=1X3=");
+//! assert_eq!(sam::md(&alignment, y), "4T3");
+//! assert_eq!(sam::nm(&alignment), 1);
+//!
+//! let record = Record::from_alignment("read1", "ref", &alignment, x, y);
+//! let sequences = vec![fasta::Sequence {
+//!     name: "ref".to_owned(),
+//!     len: y.len() as u64,
+//! }];
+//! let mut writer = Writer::new(Vec::new());
+//! writer.write_header(&Header::from_sequences(&sequences)).unwrap();
+//! writer.write_record(&record).unwrap();
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::mem;
+use std::path::Path;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::alignment::{Alignment, AlignmentOperation};
+use crate::io::fasta;
+use crate::utils::TextSlice;
+
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    #[error("invalid CIGAR operation '{}'", char::from(*op))]
+    InvalidCigarOp { op: u8 },
+    #[error("CIGAR operation '{}' is missing a length", char::from(*op))]
+    MissingCigarLen { op: u8 },
+    #[error("CIGAR string {cigar} ends with a length without operation")]
+    MissingCigarOp { cigar: String },
+    #[error("CIGAR string {cigar} contains a length that does not fit into 32 bits")]
+    CigarLenOverflow { cigar: String },
+    #[error("invalid MD tag: {md}")]
+    InvalidMd { md: String },
+    #[error("CIGAR operation '{}' cannot be represented as alignment operation", char::from(*op))]
+    UnsupportedCigarOp { op: u8 },
+    #[error("CIGAR string and MD tag describe different reference bases")]
+    CigarMdMismatch,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A single CIGAR operation together with its length.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum CigarOp {
+    /// Alignment match (`M`), either a sequence match or mismatch.
+    Match(u32),
+    /// Insertion into the reference (`I`).
+    Ins(u32),
+    /// Deletion from the reference (`D`).
+    Del(u32),
+    /// Skipped region of the reference (`N`).
+    RefSkip(u32),
+    /// Soft clipping (`S`); clipped bases are kept in the SAM sequence.
+    SoftClip(u32),
+    /// Hard clipping (`H`); clipped bases are removed from the SAM sequence.
+    HardClip(u32),
+    /// Padding (`P`).
+    Pad(u32),
+    /// Sequence match (`=`).
+    Equal(u32),
+    /// Sequence mismatch (`X`).
+    Diff(u32),
+}
+
+impl CigarOp {
+    /// Create an operation from its SAM character and length.
+    pub fn new(op: u8, len: u32) -> Result<Self> {
+        Ok(match op {
+            b'M' => CigarOp::Match(len),
+            b'I' => CigarOp::Ins(len),
+            b'D' => CigarOp::Del(len),
+            b'N' => CigarOp::RefSkip(len),
+            b'S' => CigarOp::SoftClip(len),
+            b'H' => CigarOp::HardClip(len),
+            b'P' => CigarOp::Pad(len),
+            b'=' => CigarOp::Equal(len),
+            b'X' => CigarOp::Diff(len),
+            _ => return Err(Error::InvalidCigarOp { op }),
+        })
+    }
+
+    /// The length of the operation.
+    pub fn len(&self) -> u32 {
+        match *self {
+            CigarOp::Match(l)
+            | CigarOp::Ins(l)
+            | CigarOp::Del(l)
+            | CigarOp::RefSkip(l)
+            | CigarOp::SoftClip(l)
+            | CigarOp::HardClip(l)
+            | CigarOp::Pad(l)
+            | CigarOp::Equal(l)
+            | CigarOp::Diff(l) => l,
+        }
+    }
+
+    /// Whether the operation has length zero.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The SAM character of the operation.
+    pub fn char(&self) -> char {
+        match *self {
+            CigarOp::Match(_) => 'M',
+            CigarOp::Ins(_) => 'I',
+            CigarOp::Del(_) => 'D',
+            CigarOp::RefSkip(_) => 'N',
+            CigarOp::SoftClip(_) => 'S',
+            CigarOp::HardClip(_) => 'H',
+            CigarOp::Pad(_) => 'P',
+            CigarOp::Equal(_) => '=',
+            CigarOp::Diff(_) => 'X',
+        }
+    }
+
+    /// Whether the operation consumes bases of the query sequence.
+    pub fn consumes_query(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match(_)
+                | CigarOp::Ins(_)
+                | CigarOp::SoftClip(_)
+                | CigarOp::Equal(_)
+                | CigarOp::Diff(_)
+        )
+    }
+
+    /// Whether the operation consumes bases of the reference sequence.
+    pub fn consumes_reference(&self) -> bool {
+        matches!(
+            self,
+            CigarOp::Match(_)
+                | CigarOp::Del(_)
+                | CigarOp::RefSkip(_)
+                | CigarOp::Equal(_)
+                | CigarOp::Diff(_)
+        )
+    }
+
+    /// Return the same kind of operation with the given length.
+    fn with_len(&self, len: u32) -> Self {
+        // the character is always valid, hence unwrapping is fine
+        CigarOp::new(self.char() as u8, len).unwrap()
+    }
+}
+
+impl fmt::Display for CigarOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.len(), self.char())
+    }
+}
+
+/// A CIGAR string, i.e. a run-length encoded sequence of [`CigarOp`]s.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Cigar(Vec<CigarOp>);
+
+impl Cigar {
+    /// Create a new, empty CIGAR string.
+    pub fn new() -> Self {
+        Cigar(Vec::new())
+    }
+
+    /// Calculate the CIGAR string of a pairwise alignment, with `x` being the query and
+    /// `y` the reference. Matches and substitutions are encoded as `=` and `X`.
+    /// Query bases outside of the aligned region are clipped, using soft clips unless
+    /// `hard_clip` is set. Unlike `Alignment::cigar`, all alignment modes are supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::alignment::sam::Cigar;
+    /// use bio::alignment::AlignmentOperation::*;
+    /// use bio::alignment::{Alignment, AlignmentMode};
+    ///
+    /// let alignment = Alignment {
+    ///     score: 5,
+    ///     xstart: 3,
+    ///     ystart: 0,
+    ///     xend: 9,
+    ///     yend: 10,
+    ///     ylen: 10,
+    ///     xlen: 10,
+    ///     operations: vec![Match, Match, Match, Subst, Ins, Ins, Del, Del],
+    ///     mode: AlignmentMode::Local,
+    /// };
+    /// assert_eq!(Cigar::from_alignment(&alignment, false).to_string(), "3S3=1X2I2D1S");
+    /// assert_eq!(Cigar::from_alignment(&alignment, true).to_string(), "3H3=1X2I2D1H");
+    /// ```
+    pub fn from_alignment(alignment: &Alignment, hard_clip: bool) -> Self {
+        let clip = if hard_clip {
+            CigarOp::HardClip
+        } else {
+            CigarOp::SoftClip
+        };
+        let mut cigar = Cigar::new();
+        cigar.push_len(clip, alignment.xstart);
+        for op in &alignment.operations {
+            match op {
+                AlignmentOperation::Match => cigar.push(CigarOp::Equal(1)),
+                AlignmentOperation::Subst => cigar.push(CigarOp::Diff(1)),
+                AlignmentOperation::Ins => cigar.push(CigarOp::Ins(1)),
+                AlignmentOperation::Del => cigar.push(CigarOp::Del(1)),
+                // clipping is expressed via xstart and xend
+                AlignmentOperation::Xclip(_) | AlignmentOperation::Yclip(_) => (),
+            }
+        }
+        cigar.push_len(clip, alignment.xlen.saturating_sub(alignment.xend));
+        cigar
+    }
+
+    /// Append an operation, merging it with the last one if both are of the same kind.
+    /// Operations of length zero are ignored. If the merged length would not fit into 32 bits,
+    /// the operation is appended separately.
+    pub fn push(&mut self, op: CigarOp) {
+        if op.is_empty() {
+            return;
+        }
+        if let Some(last) = self.0.last_mut() {
+            if mem::discriminant(last) == mem::discriminant(&op) {
+                if let Some(len) = last.len().checked_add(op.len()) {
+                    *last = last.with_len(len);
+                    return;
+                }
+            }
+        }
+        self.0.push(op);
+    }
+
+    /// Append an operation of the given kind and length, split into several operations if the
+    /// length does not fit into 32 bits.
+    fn push_len(&mut self, op: fn(u32) -> CigarOp, mut len: usize) {
+        while len > 0 {
+            let op_len = u32::try_from(len).unwrap_or(u32::MAX);
+            self.push(op(op_len));
+            len -= op_len as usize;
+        }
+    }
+
+    /// Return a copy in which sequence matches (`=`) and mismatches (`X`) are merged
+    /// into alignment matches (`M`), as expected by some downstream tools.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::alignment::sam::Cigar;
+    ///
+    /// let cigar: Cigar = "2S4=1X3=1I".parse().unwrap();
+    /// assert_eq!(cigar.merge_matches().to_string(), "2S8M1I");
+    /// ```
+    pub fn merge_matches(&self) -> Self {
+        let mut cigar = Cigar::new();
+        for op in &self.0 {
+            match op {
+                CigarOp::Equal(l) | CigarOp::Diff(l) => cigar.push(CigarOp::Match(*l)),
+                _ => cigar.push(*op),
+            }
+        }
+        cigar
+    }
+
+    /// Return the operations of this CIGAR string.
+    pub fn ops(&self) -> &[CigarOp] {
+        &self.0
+    }
+
+    /// Check if the CIGAR string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of query bases covered by the CIGAR string (including soft clips).
+    pub fn query_len(&self) -> u64 {
+        self.0
+            .iter()
+            .filter(|op| op.consumes_query())
+            .map(|op| op.len() as u64)
+            .sum()
+    }
+
+    /// Number of reference bases covered by the CIGAR string.
+    pub fn reference_len(&self) -> u64 {
+        self.0
+            .iter()
+            .filter(|op| op.consumes_reference())
+            .map(|op| op.len() as u64)
+            .sum()
+    }
+}
+
+impl fmt::Display for Cigar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "*");
+        }
+        for op in &self.0 {
+            write!(f, "{}", op)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Cigar {
+    type Err = Error;
+
+    /// Parse a CIGAR string. A single `*` denotes an empty CIGAR string.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut cigar = Cigar::new();
+        if s == "*" {
+            return Ok(cigar);
+        }
+        let mut len: Option<u32> = None;
+        for &c in s.as_bytes() {
+            if c.is_ascii_digit() {
+                len = Some(
+                    len.unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|len| len.checked_add((c - b'0') as u32))
+                        .ok_or_else(|| Error::CigarLenOverflow {
+                            cigar: s.to_owned(),
+                        })?,
+                );
+            } else {
+                let op_len = len.take().ok_or(Error::MissingCigarLen { op: c })?;
+                cigar.0.push(CigarOp::new(c, op_len)?);
+            }
+        }
+        if len.is_some() {
+            return Err(Error::MissingCigarOp {
+                cigar: s.to_owned(),
+            });
+        }
+        Ok(cigar)
+    }
+}
+
+/// Calculate the edit distance to the reference (NM tag) of the given alignment,
+/// i.e. the number of substituted, inserted and deleted bases.
+pub fn nm(alignment: &Alignment) -> u32 {
+    alignment
+        .operations
+        .iter()
+        .filter(|op| {
+            matches!(
+                op,
+                AlignmentOperation::Subst | AlignmentOperation::Ins | AlignmentOperation::Del
+            )
+        })
+        .count() as u32
+}
+
+/// Calculate the MD tag of the given alignment against the reference `y`.
+///
+/// Mismatching and deleted reference bases are reported in upper case.
+pub fn md(alignment: &Alignment, y: TextSlice<'_>) -> String {
+    let mut md = String::new();
+    let mut matches = 0;
+    let mut in_del = false;
+    let mut j = alignment.ystart;
+    for op in &alignment.operations {
+        match op {
+            AlignmentOperation::Match => {
+                matches += 1;
+                in_del = false;
+                j += 1;
+            }
+            AlignmentOperation::Subst => {
+                md.push_str(&matches.to_string());
+                md.push(char::from(y[j].to_ascii_uppercase()));
+                matches = 0;
+                in_del = false;
+                j += 1;
+            }
+            AlignmentOperation::Del => {
+                if !in_del {
+                    md.push_str(&matches.to_string());
+                    md.push('^');
+                    matches = 0;
+                    in_del = true;
+                }
+                md.push(char::from(y[j].to_ascii_uppercase()));
+                j += 1;
+            }
+            AlignmentOperation::Ins => in_del = false,
+            AlignmentOperation::Xclip(_) | AlignmentOperation::Yclip(_) => (),
+        }
+    }
+    md.push_str(&matches.to_string());
+    md
+}
+
+/// An event on a reference base as described by an MD tag.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum MdEvent {
+    Match,
+    Mismatch,
+    Deletion,
+}
+
+/// Parse an MD tag into runs of events on consecutive reference bases.
+/// Tags covering more than `ref_len` reference bases are rejected while parsing.
+fn parse_md(md: &str, ref_len: u64) -> Result<Vec<(MdEvent, u64)>> {
+    let invalid = || Error::InvalidMd { md: md.to_owned() };
+    let mut runs = Vec::new();
+    let mut total: u64 = 0;
+    let mut bytes = md.as_bytes().iter().peekable();
+    while let Some(&c) = bytes.next() {
+        let run = if c.is_ascii_digit() {
+            let mut n = (c - b'0') as u64;
+            while let Some(d) = bytes.next_if(|d| d.is_ascii_digit()) {
+                n = n
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add((d - b'0') as u64))
+                    .ok_or_else(invalid)?;
+            }
+            (MdEvent::Match, n)
+        } else if c == b'^' {
+            let mut deleted = 0;
+            while bytes.next_if(|d| d.is_ascii_alphabetic()).is_some() {
+                deleted += 1;
+            }
+            if deleted == 0 {
+                return Err(invalid());
+            }
+            (MdEvent::Deletion, deleted)
+        } else if c.is_ascii_alphabetic() {
+            (MdEvent::Mismatch, 1)
+        } else {
+            return Err(invalid());
+        };
+        total = total.saturating_add(run.1);
+        if total > ref_len {
+            return Err(Error::CigarMdMismatch);
+        }
+        if run.1 > 0 {
+            runs.push(run);
+        }
+    }
+    Ok(runs)
+}
+
+/// Recover the alignment operations from a CIGAR string and the corresponding MD tag.
+///
+/// The MD tag is required to distinguish matches from substitutions in `M` operations.
+/// Clipping and padding operations are skipped, i.e. the returned operations cover the
+/// aligned region only, as for alignments computed in local or semiglobal mode.
+///
+/// # Example
+///
+/// ```
+/// use bio::alignment::sam::{operations_from_cigar_md, Cigar};
+/// use bio::alignment::AlignmentOperation::*;
+///
+/// let cigar: Cigar = "2S3M1I2M2D1M".parse().unwrap();
+/// let ops = operations_from_cigar_md(&cigar, "1A3^GT1").unwrap();
+/// assert_eq!(
+///     ops,
+///     [Match, Subst, Match, Ins, Match, Match, Del, Del, Match]
+/// );
+/// ```
+pub fn operations_from_cigar_md(cigar: &Cigar, md: &str) -> Result<Vec<AlignmentOperation>> {
+    let mut runs = parse_md(md, cigar.reference_len())?.into_iter();
+    let mut run = None;
+    let mut events = std::iter::from_fn(|| loop {
+        match run {
+            Some((event, n)) if n > 0 => {
+                run = Some((event, n - 1));
+                return Some(event);
+            }
+            _ => run = Some(runs.next()?),
+        }
+    });
+    let mut ops = Vec::new();
+    for op in cigar.ops() {
+        match op {
+            CigarOp::Match(l) | CigarOp::Equal(l) | CigarOp::Diff(l) => {
+                for _ in 0..*l {
+                    match events.next() {
+                        Some(MdEvent::Match) => ops.push(AlignmentOperation::Match),
+                        Some(MdEvent::Mismatch) => ops.push(AlignmentOperation::Subst),
+                        _ => return Err(Error::CigarMdMismatch),
+                    }
+                }
+            }
+            CigarOp::Del(l) => {
+                for _ in 0..*l {
+                    match events.next() {
+                        Some(MdEvent::Deletion) => ops.push(AlignmentOperation::Del),
+                        _ => return Err(Error::CigarMdMismatch),
+                    }
+                }
+            }
+            CigarOp::Ins(l) => ops.resize(ops.len() + *l as usize, AlignmentOperation::Ins),
+            CigarOp::RefSkip(_) => return Err(Error::UnsupportedCigarOp { op: b'N' }),
+            CigarOp::SoftClip(_) | CigarOp::HardClip(_) | CigarOp::Pad(_) => (),
+        }
+    }
+    if events.next().is_some() {
+        return Err(Error::CigarMdMismatch);
+    }
+    Ok(ops)
+}
+
+/// The value of an optional SAM field.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Aux {
+    Char(u8),
+    Int(i64),
+    Float(f32),
+    String(String),
+}
+
+impl fmt::Display for Aux {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aux::Char(c) => write!(f, "A:{}", char::from(*c)),
+            Aux::Int(i) => write!(f, "i:{}", i),
+            Aux::Float(v) => write!(f, "f:{}", v),
+            Aux::String(s) => write!(f, "Z:{}", s),
+        }
+    }
+}
+
+/// A SAM header, consisting of an `@HD` line and further header lines.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Header {
+    lines: Vec<String>,
+}
+
+impl Default for Header {
+    fn default() -> Self {
+        Header::new()
+    }
+}
+
+impl Header {
+    /// Create a header that only contains the `@HD` line.
+    pub fn new() -> Self {
+        Header {
+            lines: vec!["@HD\tVN:1.6\tSO:unsorted".to_owned()],
+        }
+    }
+
+    /// Create a header with one `@SQ` line per given reference sequence.
+    pub fn from_sequences(sequences: &[fasta::Sequence]) -> Self {
+        let mut header = Header::new();
+        for sequence in sequences {
+            header
+                .lines
+                .push(format!("@SQ\tSN:{}\tLN:{}", sequence.name, sequence.len));
+        }
+        header
+    }
+
+    /// Create a header with one `@SQ` line per sequence of the given FASTA index.
+    pub fn from_index(index: &fasta::Index) -> Self {
+        Header::from_sequences(&index.sequences())
+    }
+
+    /// Append a header line, e.g. a `@PG` or `@RG` record.
+    pub fn push_line(&mut self, line: &str) {
+        self.lines.push(line.to_owned());
+    }
+
+    /// Return the header lines.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// A SAM record describing the alignment of a query against a reference sequence.
+/// Mate information (`RNEXT`, `PNEXT`, `TLEN`) is not supported and written as unavailable.
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Record {
+    qname: String,
+    flag: u16,
+    rname: String,
+    pos: u64,
+    mapq: u8,
+    cigar: Cigar,
+    seq: Vec<u8>,
+    qual: Option<Vec<u8>>,
+    tags: Vec<([u8; 2], Aux)>,
+}
+
+impl Record {
+    /// Create a record from a pairwise alignment of the query `x` against the reference `y`.
+    ///
+    /// The record is mapped on the forward strand with a mapping quality of 255 (unavailable),
+    /// clipped query bases are soft clipped and the tags `NM`, `MD` and `AS` are set.
+    pub fn from_alignment(
+        qname: &str,
+        rname: &str,
+        alignment: &Alignment,
+        x: TextSlice<'_>,
+        y: TextSlice<'_>,
+    ) -> Self {
+        Record {
+            qname: qname.to_owned(),
+            flag: 0,
+            rname: rname.to_owned(),
+            pos: alignment.ystart as u64 + 1,
+            mapq: 255,
+            cigar: Cigar::from_alignment(alignment, false),
+            seq: x.to_vec(),
+            qual: None,
+            tags: vec![
+                (*b"NM", Aux::Int(nm(alignment) as i64)),
+                (*b"MD", Aux::String(md(alignment, y))),
+                (*b"AS", Aux::Int(alignment.score as i64)),
+            ],
+        }
+    }
+
+    /// Return the query name.
+    pub fn qname(&self) -> &str {
+        &self.qname
+    }
+
+    /// Return the bitwise flag.
+    pub fn flag(&self) -> u16 {
+        self.flag
+    }
+
+    /// Set the bitwise flag, e.g. `0x10` for reverse strand alignments.
+    pub fn set_flag(&mut self, flag: u16) {
+        self.flag = flag;
+    }
+
+    /// Return the reference sequence name.
+    pub fn rname(&self) -> &str {
+        &self.rname
+    }
+
+    /// Return the 1-based leftmost mapping position.
+    pub fn pos(&self) -> u64 {
+        self.pos
+    }
+
+    /// Return the mapping quality.
+    pub fn mapq(&self) -> u8 {
+        self.mapq
+    }
+
+    /// Set the mapping quality.
+    pub fn set_mapq(&mut self, mapq: u8) {
+        self.mapq = mapq;
+    }
+
+    /// Return the CIGAR string.
+    pub fn cigar(&self) -> &Cigar {
+        &self.cigar
+    }
+
+    /// Set the CIGAR string, e.g. to replace `=`/`X` with `M` via `Cigar::merge_matches`.
+    pub fn set_cigar(&mut self, cigar: Cigar) {
+        self.cigar = cigar;
+    }
+
+    /// Return the query sequence.
+    pub fn seq(&self) -> TextSlice<'_> {
+        &self.seq
+    }
+
+    /// Return the base qualities (Phred+33) if present.
+    pub fn qual(&self) -> Option<&[u8]> {
+        self.qual.as_deref()
+    }
+
+    /// Set the base qualities (Phred+33).
+    pub fn set_qual(&mut self, qual: &[u8]) {
+        self.qual = Some(qual.to_vec());
+    }
+
+    /// Return the value of the optional field with the given tag.
+    pub fn tag(&self, tag: &[u8; 2]) -> Option<&Aux> {
+        self.tags.iter().find(|(t, _)| t == tag).map(|(_, v)| v)
+    }
+
+    /// Set an optional field, replacing any existing value for the same tag.
+    pub fn set_tag(&mut self, tag: &[u8; 2], value: Aux) {
+        match self.tags.iter_mut().find(|(t, _)| t == tag) {
+            Some(entry) => entry.1 = value,
+            None => self.tags.push((*tag, value)),
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    /// Format the record as a SAM line, including the trailing newline.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // sequence and qualities are given as bytes, invalid UTF-8 is replaced
+        let seq = if self.seq.is_empty() {
+            "*".into()
+        } else {
+            String::from_utf8_lossy(&self.seq)
+        };
+        let qual = match &self.qual {
+            Some(qual) => String::from_utf8_lossy(qual),
+            None => "*".into(),
+        };
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t*\t0\t0\t{}\t{}",
+            self.qname, self.flag, self.rname, self.pos, self.mapq, self.cigar, seq, qual
+        )?;
+        for (tag, value) in &self.tags {
+            write!(
+                f,
+                "\t{}{}:{}",
+                char::from(tag[0]),
+                char::from(tag[1]),
+                value
+            )?;
+        }
+        writeln!(f)
+    }
+}
+
+/// A SAM writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    writer: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given `io::Write`.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            writer: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write the SAM header. This has to happen before any record is written.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        write!(self.writer, "{}", header)
+    }
+
+    /// Write a SAM record.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        write!(self.writer, "{}", record)
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::pairwise::{Aligner, Scoring, MIN_SCORE};
+    use crate::alignment::AlignmentMode;
+    use crate::alignment::AlignmentOperation::*;
+
+    fn score(a: u8, b: u8) -> i32 {
+        if a == b {
+            1
+        } else {
+            -1
+        }
+    }
+
+    #[test]
+    fn test_cigar_from_semiglobal_alignment() {
+        let x = b"ACCGTGGAT";
+        let y = b"AAAAACCGTTGAT";
+        let mut aligner = Aligner::new(-5, -1, score);
+        let alignment = aligner.semiglobal(x, y);
+        assert_eq!(
+            Cigar::from_alignment(&alignment, false).to_string(),
+            "5=1X3="
+        );
+        assert_eq!(md(&alignment, y), "5T3");
+        assert_eq!(nm(&alignment), 1);
+    }
+
+    #[test]
+    fn test_cigar_from_custom_alignment_with_clips() {
+        let scoring = Scoring::from_scores(-5, -1, 1, -3)
+            .xclip_prefix(-1)
+            .xclip_suffix(MIN_SCORE)
+            .yclip(0);
+        let x = b"GGGGGGACGTACGTACGT";
+        let y = b"AAAAACGTACGTACGTAAAA";
+        let mut aligner = Aligner::with_scoring(scoring);
+        let alignment = aligner.custom(x, y);
+        assert_eq!(alignment.operations[..2], [Yclip(4), Xclip(6)]);
+
+        let cigar = Cigar::from_alignment(&alignment, false);
+        assert_eq!(cigar.to_string(), "6S12=");
+        assert_eq!(cigar.query_len(), x.len() as u64);
+        assert_eq!(cigar.reference_len(), 12);
+        assert_eq!(md(&alignment, y), "12");
+        assert_eq!(Cigar::from_alignment(&alignment, true).to_string(), "6H12=");
+    }
+
+    #[test]
+    fn test_md_deletions_and_insertions() {
+        let alignment = Alignment {
+            score: 0,
+            ystart: 1,
+            xstart: 0,
+            yend: 10,
+            xend: 6,
+            ylen: 10,
+            xlen: 6,
+            operations: vec![
+                Match, Del, Del, Subst, Match, Ins, Del, Match, Del, Ins, Del,
+            ],
+            mode: AlignmentMode::Semiglobal,
+        };
+        let y = b"aCGTacgTAC";
+        assert_eq!(md(&alignment, y), "1^GT0A1^G1^A0^C0");
+        assert_eq!(nm(&alignment), 8);
+    }
+
+    #[test]
+    fn test_cigar_parse_display_roundtrip() {
+        for s in &["*", "10M", "3S4=1X2I2D1N5H", "1P1M"] {
+            let cigar: Cigar = s.parse().unwrap();
+            assert_eq!(&cigar.to_string(), s);
+        }
+        assert_eq!(
+            "10".parse::<Cigar>(),
+            Err(Error::MissingCigarOp {
+                cigar: "10".to_owned()
+            })
+        );
+        assert_eq!(
+            "4294967295M".parse::<Cigar>().unwrap().ops(),
+            &[CigarOp::Match(u32::MAX)]
+        );
+        assert_eq!(
+            "4294967296M".parse::<Cigar>(),
+            Err(Error::CigarLenOverflow {
+                cigar: "4294967296M".to_owned()
+            })
+        );
+        assert_eq!(
+            "99999999999999999999M".parse::<Cigar>(),
+            Err(Error::CigarLenOverflow {
+                cigar: "99999999999999999999M".to_owned()
+            })
+        );
+        assert_eq!(
+            "M".parse::<Cigar>(),
+            Err(Error::MissingCigarLen { op: b'M' })
+        );
+        assert_eq!(
+            "3Q".parse::<Cigar>(),
+            Err(Error::InvalidCigarOp { op: b'Q' })
+        );
+    }
+
+    #[test]
+    fn test_cigar_push_merges_ops() {
+        let mut cigar = Cigar::new();
+        cigar.push(CigarOp::Equal(2));
+        cigar.push(CigarOp::Equal(3));
+        cigar.push(CigarOp::Del(0));
+        cigar.push(CigarOp::Diff(1));
+        assert_eq!(cigar.ops(), &[CigarOp::Equal(5), CigarOp::Diff(1)]);
+        assert_eq!(cigar.merge_matches().ops(), &[CigarOp::Match(6)]);
+
+        let mut cigar = Cigar::new();
+        cigar.push(CigarOp::Match(u32::MAX));
+        cigar.push(CigarOp::Match(1));
+        assert_eq!(cigar.ops(), &[CigarOp::Match(u32::MAX), CigarOp::Match(1)]);
+        assert_eq!(cigar.reference_len(), u32::MAX as u64 + 1);
+    }
+
+    #[test]
+    fn test_roundtrip_operations() {
+        let x = b"TTTGCCAGTACGTTTAAGGA";
+        let y = b"CCCCGCAGTACCGTTAAGGACCCC";
+        let mut aligner = Aligner::new(-5, -1, score);
+        let alignment = aligner.local(x, y);
+        let cigar = Cigar::from_alignment(&alignment, false);
+        let md = md(&alignment, y);
+
+        let ops = operations_from_cigar_md(&cigar, &md).unwrap();
+        assert_eq!(ops, alignment.operations);
+        let ops = operations_from_cigar_md(&cigar.merge_matches(), &md).unwrap();
+        assert_eq!(ops, alignment.operations);
+    }
+
+    #[test]
+    fn test_operations_from_cigar_md_errors() {
+        let cigar: Cigar = "4M".parse().unwrap();
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "5"),
+            Err(Error::CigarMdMismatch)
+        );
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "1^A2"),
+            Err(Error::CigarMdMismatch)
+        );
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "2^3"),
+            Err(Error::InvalidMd {
+                md: "2^3".to_owned()
+            })
+        );
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "99999999999999999999"),
+            Err(Error::InvalidMd {
+                md: "99999999999999999999".to_owned()
+            })
+        );
+        // MD runs longer than the CIGAR string are rejected before they are expanded
+        let cigar: Cigar = "10M".parse().unwrap();
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "99999999999"),
+            Err(Error::CigarMdMismatch)
+        );
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "9^AAAAAAAAAAAAAAAAAAAA"),
+            Err(Error::CigarMdMismatch)
+        );
+        let cigar: Cigar = "2M2N2M".parse().unwrap();
+        assert_eq!(
+            operations_from_cigar_md(&cigar, "4"),
+            Err(Error::UnsupportedCigarOp { op: b'N' })
+        );
+    }
+
+    #[test]
+    fn test_writer() {
+        let x = b"ACGTTAGG";
+        let y = b"GGACGTAAGGTT";
+        let mut aligner = Aligner::new(-5, -1, score);
+        let alignment = aligner.semiglobal(x, y);
+        let mut record = Record::from_alignment("read1", "chr1", &alignment, x, y);
+        record.set_qual(b"IIIIIIII");
+        record.set_mapq(60);
+        record.set_tag(b"AS", Aux::Int(42));
+        record.set_tag(b"tp", Aux::Char(b'P'));
+        assert_eq!(record.tag(b"NM"), Some(&Aux::Int(1)));
+
+        let index = fasta::Index::new(&b"chr1\t12\t6\t12\t13\nchr2\t5\t25\t5\t6\n"[..]).unwrap();
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&Header::from_index(&index)).unwrap();
+        writer.write_record(&record).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(
+            std::str::from_utf8(writer.writer.get_ref()).unwrap(),
+            "@HD\tVN:1.6\tSO:unsorted\n\
+             @SQ\tSN:chr1\tLN:12\n\
+             @SQ\tSN:chr2\tLN:5\n\
+             read1\t0\tchr1\t3\t60\t4=1X3=\t*\t0\t0\tACGTTAGG\tIIIIIIII\t\
+             NM:i:1\tMD:Z:4A3\tAS:i:42\ttp:A:P\n"
+        );
+
+        record.set_qual(b"IIII\xffIII");
+        assert!(record.to_string().contains("\tIIII\u{fffd}III\t"));
+    }
+}
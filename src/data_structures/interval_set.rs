@@ -0,0 +1,829 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Genomic interval arithmetic on BED records, in the spirit of
+//! [bedtools](https://bedtools.readthedocs.io).
+//!
+//! An `IntervalSet` indexes a collection of `io::bed::Record`s per chromosome with an
+//! `ArrayBackedIntervalTree` and offers the usual set operations: merging, intersecting,
+//! subtracting, complementing against chromosome sizes, closest-feature and windowed queries.
+//! All coordinates are 0-based and half-open, as in BED.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::data_structures::interval_set::{IntervalSet, OverlapCriteria};
+//! use bio::io::bed;
+//!
+//! let genes = b"chr1\t100\t200\tgeneA\nchr1\t150\t300\tgeneB\nchr1\t500\t600\tgeneC\n";
+//! let peaks = b"chr1\t180\t220\tpeak1\nchr1\t700\t800\tpeak2\n";
+//! let genes: IntervalSet = bed::Reader::new(&genes[..])
+//!     .records()
+//!     .map(|r| r.unwrap())
+//!     .collect();
+//! let peaks: IntervalSet = bed::Reader::new(&peaks[..])
+//!     .records()
+//!     .map(|r| r.unwrap())
+//!     .collect();
+//!
+//! let merged = genes.merge(0, false);
+//! assert_eq!(merged.len(), 2);
+//! assert_eq!((merged[0].start(), merged[0].end()), (100, 300));
+//!
+//! let hits = genes.intersect(&peaks, &OverlapCriteria::new());
+//! assert_eq!(hits.len(), 2);
+//! assert_eq!((hits[0].start(), hits[0].end()), (180, 200));
+//! assert_eq!(hits[0].name(), Some("geneA"));
+//!
+//! let closest = peaks.closest(&genes, Default::default());
+//! assert_eq!(closest[1].hits[0].name(), Some("geneC"));
+//! assert_eq!(closest[1].distance, Some(101));
+//! ```
+
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use bio_types::annot::contig::Contig;
+use bio_types::strand::Strand;
+
+use crate::data_structures::annot_map::AnnotMap;
+use crate::data_structures::interval_tree::ArrayBackedIntervalTree;
+use crate::io::bed;
+
+/// How the strands of two features have to relate for them to be considered together.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strandedness {
+    /// Strand is not taken into account.
+    #[default]
+    Ignore,
+    /// Both features have to be on the same, known strand (bedtools `-s`).
+    Same,
+    /// Both features have to be on opposite, known strands (bedtools `-S`).
+    Opposite,
+}
+
+impl Strandedness {
+    /// Return true if the strands of `a` and `b` are compatible.
+    pub fn matches(self, a: &bed::Record, b: &bed::Record) -> bool {
+        match self {
+            Strandedness::Ignore => true,
+            Strandedness::Same => {
+                strand_key(a) != b'.' && strand_key(b) != b'.' && strand_key(a) == strand_key(b)
+            }
+            Strandedness::Opposite => {
+                strand_key(a) != b'.' && strand_key(b) != b'.' && strand_key(a) != strand_key(b)
+            }
+        }
+    }
+}
+
+/// Criteria for two features to be reported as overlapping.
+///
+/// By default, a single shared base is sufficient and strand is ignored.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct OverlapCriteria {
+    min_fraction_a: f64,
+    min_fraction_b: f64,
+    reciprocal: bool,
+    either: bool,
+    strandedness: Strandedness,
+}
+
+impl OverlapCriteria {
+    /// Create criteria requiring at least one overlapping base.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum overlap as fraction of the query feature `a` (bedtools `-f`).
+    pub fn min_fraction_a(mut self, fraction: f64) -> Self {
+        self.min_fraction_a = fraction;
+        self
+    }
+
+    /// Minimum overlap as fraction of the database feature `b` (bedtools `-F`).
+    pub fn min_fraction_b(mut self, fraction: f64) -> Self {
+        self.min_fraction_b = fraction;
+        self
+    }
+
+    /// Require the fraction given for `a` for `b` as well (bedtools `-r`).
+    pub fn reciprocal(mut self, reciprocal: bool) -> Self {
+        self.reciprocal = reciprocal;
+        self
+    }
+
+    /// Accept the overlap if either of the fraction requirements holds (bedtools `-e`).
+    pub fn either(mut self, either: bool) -> Self {
+        self.either = either;
+        self
+    }
+
+    /// Set how the strands of both features have to relate.
+    pub fn strandedness(mut self, strandedness: Strandedness) -> Self {
+        self.strandedness = strandedness;
+        self
+    }
+
+    /// Return true if `a` and `b` overlap according to these criteria.
+    pub fn matches(&self, a: &bed::Record, b: &bed::Record) -> bool {
+        if a.chrom() != b.chrom() || !self.strandedness.matches(a, b) {
+            return false;
+        }
+        let overlap = overlap(a, b);
+        if overlap == 0 {
+            return false;
+        }
+        let min_fraction_b = if self.reciprocal {
+            self.min_fraction_a
+        } else {
+            self.min_fraction_b
+        };
+        let fraction_ok =
+            |fraction: f64, record: &bed::Record| overlap as f64 >= fraction * len(record) as f64;
+        let a_ok = fraction_ok(self.min_fraction_a, a);
+        let b_ok = fraction_ok(min_fraction_b, b);
+        if self.either {
+            a_ok || b_ok
+        } else {
+            a_ok && b_ok
+        }
+    }
+}
+
+/// The features of another set closest to a query feature, as returned by
+/// `IntervalSet::closest`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Closest<'a> {
+    /// The query feature.
+    pub query: &'a bed::Record,
+    /// All features at the minimal distance, ordered by start position.
+    pub hits: Vec<&'a bed::Record>,
+    /// Distance to the hits, following the bedtools convention: overlapping features have
+    /// distance 0 and book-ended features distance 1. `None` if no feature was found on the
+    /// chromosome.
+    pub distance: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct ChromIndex {
+    tree: ArrayBackedIntervalTree<u64, usize>,
+    /// Record indices ordered by start position.
+    by_start: Vec<usize>,
+    /// Record indices ordered by end position.
+    by_end: Vec<usize>,
+}
+
+/// A set of BED records, indexed per chromosome for interval arithmetic.
+///
+/// Records keep their input order; operations iterating over the query set report results in
+/// that order, while operations producing new intervals (merge, complement) report them sorted
+/// by position per chromosome, with chromosomes in order of first appearance.
+#[derive(Debug, Clone)]
+pub struct IntervalSet {
+    records: Vec<bed::Record>,
+    chroms: HashMap<String, ChromIndex>,
+    chrom_order: Vec<String>,
+}
+
+impl Default for IntervalSet {
+    fn default() -> Self {
+        IntervalSet::new(vec![])
+    }
+}
+
+impl FromIterator<bed::Record> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = bed::Record>>(iter: I) -> Self {
+        IntervalSet::new(iter.into_iter().collect())
+    }
+}
+
+impl From<Vec<bed::Record>> for IntervalSet {
+    fn from(records: Vec<bed::Record>) -> Self {
+        IntervalSet::new(records)
+    }
+}
+
+impl IntervalSet {
+    /// Create a new interval set from the given records.
+    pub fn new(records: Vec<bed::Record>) -> Self {
+        let mut chrom_order = Vec::new();
+        let mut members: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            members
+                .entry(record.chrom().to_owned())
+                .or_insert_with(|| {
+                    chrom_order.push(record.chrom().to_owned());
+                    Vec::new()
+                })
+                .push(i);
+        }
+
+        let chroms = members
+            .into_iter()
+            .map(|(chrom, indices)| {
+                let tree = indices.iter().map(|&i| (range(&records[i]), i)).collect();
+                let mut by_start = indices.clone();
+                by_start.sort_by_key(|&i| (records[i].start(), records[i].end(), i));
+                let mut by_end = indices;
+                by_end.sort_by_key(|&i| (records[i].end(), records[i].start(), i));
+                (
+                    chrom,
+                    ChromIndex {
+                        tree,
+                        by_start,
+                        by_end,
+                    },
+                )
+            })
+            .collect();
+
+        IntervalSet {
+            records,
+            chroms,
+            chrom_order,
+        }
+    }
+
+    /// The records of this set, in input order.
+    pub fn records(&self) -> &[bed::Record] {
+        &self.records
+    }
+
+    /// Number of records in this set.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Return true if the set contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Chromosomes of this set, in order of first appearance.
+    pub fn chroms(&self) -> &[String] {
+        &self.chrom_order
+    }
+
+    /// Find all records overlapping the region `start..end` on `chrom`, ordered by position.
+    pub fn find(&self, chrom: &str, start: u64, end: u64) -> Vec<&bed::Record> {
+        let index = match self.chroms.get(chrom) {
+            Some(index) => index,
+            None => return vec![],
+        };
+        let mut hits: Vec<usize> = index
+            .tree
+            .find(start..max(start, end))
+            .iter()
+            .map(|entry| *entry.data())
+            .collect();
+        hits.sort_by_key(|&i| (self.records[i].start(), self.records[i].end(), i));
+        hits.into_iter().map(|i| &self.records[i]).collect()
+    }
+
+    /// Merge overlapping records, and records at most `distance` bases apart, into single
+    /// intervals (bedtools `merge -d`). Book-ended records are always merged.
+    ///
+    /// If `stranded` is true, only records on the same strand are merged and the resulting
+    /// records carry that strand in BED6 format.
+    pub fn merge(&self, distance: u64, stranded: bool) -> Vec<bed::Record> {
+        let mut merged = Vec::new();
+        for chrom in &self.chrom_order {
+            let mut chrom_merged = Vec::new();
+            let strands: &[u8] = if stranded { b"+-." } else { b"*" };
+            for &strand in strands {
+                for (start, end) in self.merged_ranges(chrom, distance, |record| {
+                    strand == b'*' || strand_key(record) == strand
+                }) {
+                    let mut record = interval(chrom, start, end);
+                    if stranded {
+                        record.set_name(".");
+                        record.set_score("0");
+                        record.push_aux(&char::from(strand).to_string());
+                    }
+                    chrom_merged.push(record);
+                }
+            }
+            chrom_merged.sort_by_key(|record| (record.start(), record.end()));
+            merged.extend(chrom_merged);
+        }
+        merged
+    }
+
+    /// Report the overlapping parts of each record of this set with the records of `other`
+    /// that satisfy `criteria` (bedtools `intersect`). Each reported piece keeps all fields of
+    /// the record from this set.
+    pub fn intersect(&self, other: &IntervalSet, criteria: &OverlapCriteria) -> Vec<bed::Record> {
+        let mut pieces = Vec::new();
+        for a in &self.records {
+            for b in other.overlaps(a, criteria) {
+                let mut piece = a.clone();
+                piece.set_start(max(a.start(), b.start()));
+                piece.set_end(min(a.end(), b.end()));
+                pieces.push(piece);
+            }
+        }
+        pieces
+    }
+
+    /// Report each record of this set that overlaps at least one record of `other` satisfying
+    /// `criteria` (bedtools `intersect -u`).
+    pub fn overlapping(
+        &self,
+        other: &IntervalSet,
+        criteria: &OverlapCriteria,
+    ) -> Vec<&bed::Record> {
+        self.records
+            .iter()
+            .filter(|a| !other.overlaps(a, criteria).is_empty())
+            .collect()
+    }
+
+    /// Report each record of this set that overlaps no record of `other` satisfying
+    /// `criteria` (bedtools `intersect -v`).
+    pub fn non_overlapping(
+        &self,
+        other: &IntervalSet,
+        criteria: &OverlapCriteria,
+    ) -> Vec<&bed::Record> {
+        self.records
+            .iter()
+            .filter(|a| other.overlaps(a, criteria).is_empty())
+            .collect()
+    }
+
+    /// Remove the parts of each record of this set that are covered by records of `other`
+    /// satisfying `criteria` (bedtools `subtract`). A record may be split into several pieces,
+    /// each keeping all fields of the original record.
+    pub fn subtract(&self, other: &IntervalSet, criteria: &OverlapCriteria) -> Vec<bed::Record> {
+        let mut pieces = Vec::new();
+        for a in &self.records {
+            let mut pos = a.start();
+            for b in other.overlaps(a, criteria) {
+                if b.start() > pos {
+                    let mut piece = a.clone();
+                    piece.set_start(pos);
+                    piece.set_end(b.start());
+                    pieces.push(piece);
+                }
+                pos = max(pos, b.end());
+            }
+            if pos == a.start() {
+                pieces.push(a.clone());
+            } else if pos < a.end() {
+                let mut piece = a.clone();
+                piece.set_start(pos);
+                pieces.push(piece);
+            }
+        }
+        pieces
+    }
+
+    /// Report the regions of the given chromosomes not covered by any record
+    /// (bedtools `complement`). Chromosomes are reported in the given order; records on
+    /// chromosomes missing from `chrom_sizes` are ignored.
+    pub fn complement<I, S>(&self, chrom_sizes: I) -> Vec<bed::Record>
+    where
+        I: IntoIterator<Item = (S, u64)>,
+        S: AsRef<str>,
+    {
+        let mut gaps = Vec::new();
+        for (chrom, size) in chrom_sizes {
+            let chrom = chrom.as_ref();
+            let mut pos = 0;
+            for (start, end) in self.merged_ranges(chrom, 0, |_| true) {
+                if start >= size {
+                    break;
+                }
+                if start > pos {
+                    gaps.push(interval(chrom, pos, start));
+                }
+                pos = max(pos, end);
+            }
+            if pos < size {
+                gaps.push(interval(chrom, pos, size));
+            }
+        }
+        gaps
+    }
+
+    /// For each record of this set, report the closest records of `other` on the same
+    /// chromosome whose strand relates as given by `strandedness` (bedtools `closest -d`).
+    /// Ties are all reported.
+    pub fn closest<'a>(
+        &'a self,
+        other: &'a IntervalSet,
+        strandedness: Strandedness,
+    ) -> Vec<Closest<'a>> {
+        self.records
+            .iter()
+            .map(|a| other.closest_to(a, strandedness))
+            .collect()
+    }
+
+    /// Report all pairs of a record of this set and a record of `other` lying within `left`
+    /// bases upstream or `right` bases downstream of it (bedtools `window -l -r`). Like in
+    /// bedtools, upstream and downstream refer to the reference, not to the feature strand.
+    pub fn window<'a>(
+        &'a self,
+        other: &'a IntervalSet,
+        left: u64,
+        right: u64,
+        strandedness: Strandedness,
+    ) -> Vec<(&'a bed::Record, &'a bed::Record)> {
+        let mut pairs = Vec::new();
+        for a in &self.records {
+            let start = a.start().saturating_sub(left);
+            let end = max(a.start(), a.end()).saturating_add(right);
+            pairs.extend(
+                other
+                    .find(a.chrom(), start, end)
+                    .into_iter()
+                    .filter(|b| strandedness.matches(a, b))
+                    .map(|b| (a, b)),
+            );
+        }
+        pairs
+    }
+
+    /// Build an `AnnotMap` over clones of the records, keyed by their chromosome.
+    pub fn to_annot_map(&self) -> AnnotMap<String, bed::Record> {
+        let mut map = AnnotMap::new();
+        for record in &self.records {
+            map.insert_at(record.clone(), &Contig::from(record));
+        }
+        map
+    }
+
+    /// Records overlapping `query` according to `criteria`, ordered by position.
+    fn overlaps(&self, query: &bed::Record, criteria: &OverlapCriteria) -> Vec<&bed::Record> {
+        self.find(query.chrom(), query.start(), query.end())
+            .into_iter()
+            .filter(|b| criteria.matches(query, b))
+            .collect()
+    }
+
+    /// Merged `(start, end)` ranges of the records on `chrom` accepted by `filter`.
+    fn merged_ranges<F>(&self, chrom: &str, distance: u64, filter: F) -> Vec<(u64, u64)>
+    where
+        F: Fn(&bed::Record) -> bool,
+    {
+        let index = match self.chroms.get(chrom) {
+            Some(index) => index,
+            None => return vec![],
+        };
+        let mut ranges: Vec<(u64, u64)> = Vec::new();
+        for record in index.by_start.iter().map(|&i| &self.records[i]) {
+            if !filter(record) {
+                continue;
+            }
+            let (start, end) = (record.start(), max(record.start(), record.end()));
+            match ranges.last_mut() {
+                Some(last) if start <= last.1.saturating_add(distance) => last.1 = max(last.1, end),
+                _ => ranges.push((start, end)),
+            }
+        }
+        ranges
+    }
+
+    fn closest_to<'a>(&'a self, query: &'a bed::Record, strandedness: Strandedness) -> Closest<'a> {
+        let mut closest = Closest {
+            query,
+            hits: vec![],
+            distance: None,
+        };
+        let index = match self.chroms.get(query.chrom()) {
+            Some(index) => index,
+            None => return closest,
+        };
+
+        closest.hits = self
+            .find(query.chrom(), query.start(), query.end())
+            .into_iter()
+            .filter(|b| strandedness.matches(query, b))
+            .collect();
+        if !closest.hits.is_empty() {
+            closest.distance = Some(0);
+            return closest;
+        }
+
+        let query_end = max(query.start(), query.end());
+        // Nearest records ending at or before the query start.
+        let upstream_end = index
+            .by_end
+            .partition_point(|&i| self.records[i].end() <= query.start());
+        let mut upstream: Vec<&bed::Record> = Vec::new();
+        for record in index.by_end[..upstream_end]
+            .iter()
+            .rev()
+            .map(|&i| &self.records[i])
+        {
+            if upstream.first().is_some_and(|u| u.end() != record.end()) {
+                break;
+            }
+            if strandedness.matches(query, record) {
+                upstream.push(record);
+            }
+        }
+        // Nearest records starting at or after the query end.
+        let downstream_start = index
+            .by_start
+            .partition_point(|&i| self.records[i].start() < query_end);
+        let mut downstream: Vec<&bed::Record> = Vec::new();
+        for record in index.by_start[downstream_start..]
+            .iter()
+            .map(|&i| &self.records[i])
+        {
+            if downstream
+                .first()
+                .is_some_and(|d| d.start() != record.start())
+            {
+                break;
+            }
+            if strandedness.matches(query, record) {
+                downstream.push(record);
+            }
+        }
+
+        let upstream_distance = upstream.first().map(|u| query.start() - u.end() + 1);
+        let downstream_distance = downstream.first().map(|d| d.start() - query_end + 1);
+        let distance = match (upstream_distance, downstream_distance) {
+            (Some(u), Some(d)) => min(u, d),
+            (u, d) => match u.or(d) {
+                Some(distance) => distance,
+                None => return closest,
+            },
+        };
+        if upstream_distance == Some(distance) {
+            closest.hits.extend(upstream.into_iter().rev());
+        }
+        if downstream_distance == Some(distance) {
+            closest.hits.extend(downstream);
+        }
+        closest.hits.sort_by_key(|b| (b.start(), b.end()));
+        closest.distance = Some(distance);
+        closest
+    }
+}
+
+/// Strand of a record as `+`, `-` or `.` (unknown).
+fn strand_key(record: &bed::Record) -> u8 {
+    match record.strand() {
+        Some(Strand::Forward) => b'+',
+        Some(Strand::Reverse) => b'-',
+        _ => b'.',
+    }
+}
+
+/// Length of a record, treating inverted coordinates as empty.
+fn len(record: &bed::Record) -> u64 {
+    record.end().saturating_sub(record.start())
+}
+
+/// Number of bases shared by two records on the same chromosome.
+fn overlap(a: &bed::Record, b: &bed::Record) -> u64 {
+    min(a.end(), b.end()).saturating_sub(max(a.start(), b.start()))
+}
+
+fn range(record: &bed::Record) -> std::ops::Range<u64> {
+    record.start()..max(record.start(), record.end())
+}
+
+fn interval(chrom: &str, start: u64, end: u64) -> bed::Record {
+    let mut record = bed::Record::new();
+    record.set_chrom(chrom);
+    record.set_start(start);
+    record.set_end(end);
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(chrom: &str, start: u64, end: u64, name: &str, strand: &str) -> bed::Record {
+        let mut record = interval(chrom, start, end);
+        record.set_name(name);
+        record.set_score("0");
+        record.push_aux(strand);
+        record
+    }
+
+    fn coords(records: &[bed::Record]) -> Vec<(&str, u64, u64)> {
+        records
+            .iter()
+            .map(|r| (r.chrom(), r.start(), r.end()))
+            .collect()
+    }
+
+    fn names<'a>(records: &[&'a bed::Record]) -> Vec<&'a str> {
+        records.iter().map(|r| r.name().unwrap()).collect()
+    }
+
+    fn a() -> IntervalSet {
+        vec![
+            record("chr1", 10, 20, "a1", "+"),
+            record("chr1", 15, 30, "a2", "-"),
+            record("chr1", 30, 40, "a3", "+"),
+            record("chr1", 100, 200, "a4", "+"),
+            record("chr2", 0, 50, "a5", "-"),
+        ]
+        .into()
+    }
+
+    fn b() -> IntervalSet {
+        vec![
+            record("chr1", 18, 35, "b1", "+"),
+            record("chr1", 150, 160, "b2", "-"),
+            record("chr1", 250, 300, "b3", "+"),
+            record("chr2", 60, 70, "b4", "-"),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn test_find() {
+        let set = a();
+        assert_eq!(names(&set.find("chr1", 18, 31)), vec!["a1", "a2", "a3"]);
+        assert!(set.find("chr1", 40, 100).is_empty());
+        assert!(set.find("chr3", 0, 100).is_empty());
+    }
+
+    #[test]
+    fn test_merge() {
+        let set = a();
+        assert_eq!(
+            coords(&set.merge(0, false)),
+            vec![("chr1", 10, 40), ("chr1", 100, 200), ("chr2", 0, 50)]
+        );
+        assert_eq!(
+            coords(&set.merge(60, false)),
+            vec![("chr1", 10, 200), ("chr2", 0, 50)]
+        );
+
+        let stranded = set.merge(0, true);
+        assert_eq!(
+            coords(&stranded),
+            vec![
+                ("chr1", 10, 20),
+                ("chr1", 15, 30),
+                ("chr1", 30, 40),
+                ("chr1", 100, 200),
+                ("chr2", 0, 50)
+            ]
+        );
+        assert_eq!(stranded[1].strand(), Some(Strand::Reverse));
+    }
+
+    #[test]
+    fn test_intersect() {
+        let (a, b) = (a(), b());
+        let hits = a.intersect(&b, &OverlapCriteria::new());
+        assert_eq!(
+            coords(&hits),
+            vec![
+                ("chr1", 18, 20),
+                ("chr1", 18, 30),
+                ("chr1", 30, 35),
+                ("chr1", 150, 160)
+            ]
+        );
+        assert_eq!(hits[3].name(), Some("a4"));
+
+        let same = OverlapCriteria::new().strandedness(Strandedness::Same);
+        assert_eq!(
+            coords(&a.intersect(&b, &same)),
+            vec![("chr1", 18, 20), ("chr1", 30, 35)]
+        );
+        let opposite = OverlapCriteria::new().strandedness(Strandedness::Opposite);
+        assert_eq!(
+            coords(&a.intersect(&b, &opposite)),
+            vec![("chr1", 18, 30), ("chr1", 150, 160)]
+        );
+    }
+
+    #[test]
+    fn test_overlap_fractions() {
+        let (a, b) = (a(), b());
+        // a2 covers 12 of its 15 bases with b1, a3 5 of 10, but b1 only shares 5 of 17 with a3.
+        let half = OverlapCriteria::new().min_fraction_a(0.5);
+        assert_eq!(names(&a.overlapping(&b, &half)), vec!["a2", "a3"]);
+        let reciprocal = half.reciprocal(true);
+        assert_eq!(names(&a.overlapping(&b, &reciprocal)), vec!["a2"]);
+        let strict = OverlapCriteria::new()
+            .min_fraction_a(0.5)
+            .min_fraction_b(0.9);
+        assert!(a.overlapping(&b, &strict).is_empty());
+        let either = strict.either(true);
+        assert_eq!(names(&a.overlapping(&b, &either)), vec!["a2", "a3", "a4"]);
+        assert_eq!(
+            names(&a.non_overlapping(&b, &OverlapCriteria::new())),
+            vec!["a5"]
+        );
+    }
+
+    #[test]
+    fn test_subtract() {
+        let (a, b) = (a(), b());
+        let rest = a.subtract(&b, &OverlapCriteria::new());
+        assert_eq!(
+            coords(&rest),
+            vec![
+                ("chr1", 10, 18),
+                ("chr1", 15, 18),
+                ("chr1", 35, 40),
+                ("chr1", 100, 150),
+                ("chr1", 160, 200),
+                ("chr2", 0, 50)
+            ]
+        );
+        assert_eq!(rest[4].name(), Some("a4"));
+
+        let same = OverlapCriteria::new().strandedness(Strandedness::Same);
+        assert_eq!(a.subtract(&b, &same)[1].start(), 15);
+    }
+
+    #[test]
+    fn test_complement() {
+        let set = a();
+        let gaps = set.complement(vec![("chr1", 150), ("chr2", 50), ("chr3", 10)]);
+        assert_eq!(
+            coords(&gaps),
+            vec![("chr1", 0, 10), ("chr1", 40, 100), ("chr3", 0, 10)]
+        );
+    }
+
+    #[test]
+    fn test_closest() {
+        let (a, b) = (a(), b());
+        let closest = a.closest(&b, Strandedness::Ignore);
+        assert_eq!(closest.len(), 5);
+        assert_eq!(names(&closest[0].hits), vec!["b1"]);
+        assert_eq!(closest[0].distance, Some(0));
+        assert_eq!(names(&closest[3].hits), vec!["b2"]);
+        assert_eq!(names(&closest[4].hits), vec!["b4"]);
+        assert_eq!(closest[4].distance, Some(11));
+
+        let query: IntervalSet = vec![record("chr1", 40, 60, "q", "+")].into();
+        let closest = query.closest(&b, Strandedness::Ignore);
+        assert_eq!(names(&closest[0].hits), vec!["b1"]);
+        assert_eq!(closest[0].distance, Some(6));
+        let closest = query.closest(&b, Strandedness::Opposite);
+        assert_eq!(names(&closest[0].hits), vec!["b2"]);
+        assert_eq!(closest[0].distance, Some(91));
+
+        // Ties up- and downstream, book-ended features have distance 1.
+        let query: IntervalSet = vec![record("chr1", 200, 250, "q", "+")].into();
+        let closest = query.closest(&b, Strandedness::Ignore);
+        assert_eq!(names(&closest[0].hits), vec!["b3"]);
+        assert_eq!(closest[0].distance, Some(1));
+
+        let query: IntervalSet = vec![record("chr3", 0, 10, "q", "+")].into();
+        let closest = query.closest(&b, Strandedness::Ignore);
+        assert!(closest[0].hits.is_empty());
+        assert_eq!(closest[0].distance, None);
+    }
+
+    #[test]
+    fn test_closest_ties() {
+        let b: IntervalSet = vec![
+            record("chr1", 0, 10, "left", "+"),
+            record("chr1", 5, 10, "left2", "-"),
+            record("chr1", 30, 40, "right", "+"),
+        ]
+        .into();
+        let query: IntervalSet = vec![record("chr1", 15, 25, "q", "+")].into();
+        let closest = query.closest(&b, Strandedness::Ignore);
+        assert_eq!(names(&closest[0].hits), vec!["left", "left2", "right"]);
+        assert_eq!(closest[0].distance, Some(6));
+        let closest = query.closest(&b, Strandedness::Same);
+        assert_eq!(names(&closest[0].hits), vec!["left", "right"]);
+    }
+
+    #[test]
+    fn test_window() {
+        let (a, b) = (a(), b());
+        let pairs = a.window(&b, 0, 10, Strandedness::Ignore);
+        let pairs: Vec<_> = pairs
+            .iter()
+            .map(|(a, b)| (a.name().unwrap(), b.name().unwrap()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("a1", "b1"), ("a2", "b1"), ("a3", "b1"), ("a4", "b2")]
+        );
+        assert_eq!(a.window(&b, 0, 0, Strandedness::Same).len(), 2);
+    }
+
+    #[test]
+    fn test_to_annot_map() {
+        let map = a().to_annot_map();
+        let query = Contig::new("chr1".to_owned(), 25, 10, Strand::Unknown);
+        let mut hits: Vec<_> = map.find(&query).map(|e| e.data().name().unwrap()).collect();
+        hits.sort_unstable();
+        assert_eq!(hits, vec!["a2", "a3"]);
+    }
+}
@@ -11,6 +11,7 @@ pub mod bitenc;
 pub mod bwt;
 pub mod fmindex;
 pub mod interpolation_table;
+pub mod interval_set;
 pub mod interval_tree;
 pub mod qgram_index;
 pub mod rank_select;
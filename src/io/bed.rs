@@ -5,6 +5,11 @@
 
 //! BED format reading and writing.
 //!
+//! `track` and `browser` lines are available via [`Reader::header_lines`]. The blocks of BED12
+//! records can be accessed with [`Record::block_sizes`] and [`Record::block_starts`], or converted
+//! into a [`Spliced`](bio_types::annot::spliced::Spliced) annotation with `TryFrom`.
+//! See [`bedgraph`](crate::io::bedgraph) and [`peak`](crate::io::peak) for related formats.
+//!
 //! # Example
 //!
 //! ```
@@ -19,23 +24,189 @@
 //! }
 //! ```
 
-use std::convert::AsRef;
-use std::fmt::Write;
+use std::convert::{AsRef, TryFrom};
+use std::fmt::{self, Write as _};
 use std::fs;
 use std::io;
+use std::io::prelude::*;
 use std::marker::Copy;
 use std::ops::Deref;
 use std::path::Path;
+use std::str::FromStr;
 
 use anyhow::Context;
 use bio_types::annot;
 use bio_types::annot::loc::Loc;
+use bio_types::annot::spliced::SplicingError;
 use bio_types::strand;
+use thiserror::Error;
+
+use crate::io::compression::{Decoder, Encoder, Format};
+
+/// A `track` or `browser` line at the start of a BED-like file.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum HeaderLine {
+    /// A `track` line with its attributes (e.g. `name`, `type` and `description`), without
+    /// quotes.
+    Track(Vec<(String, String)>),
+    /// A `browser` line with the remainder of the line, e.g. `position chr7:127471196-127495720`.
+    Browser(String),
+}
+
+impl HeaderLine {
+    /// Return the value of the given attribute of a `track` line.
+    pub fn track_attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            HeaderLine::Track(attributes) => attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            HeaderLine::Browser(_) => None,
+        }
+    }
+
+    /// Return whether the given line is a `track` or `browser` line.
+    fn is_header_line(line: &[u8]) -> bool {
+        [&b"track"[..], b"browser"].iter().any(|prefix| {
+            line.starts_with(prefix)
+                && !matches!(line.get(prefix.len()), Some(c) if !c.is_ascii_whitespace())
+        })
+    }
+}
+
+impl FromStr for HeaderLine {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = line.strip_prefix("browser") {
+            return Ok(HeaderLine::Browser(rest.trim().to_owned()));
+        }
+        let mut rest = line
+            .strip_prefix("track")
+            .ok_or_else(|| format!("'{}' is neither a track nor a browser line.", line))?
+            .trim_start();
+        let mut attributes = Vec::new();
+        while !rest.is_empty() {
+            let (key, value) = rest
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid track attribute in '{}'.", line))?;
+            let (value, remainder) = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .split_once('"')
+                    .ok_or_else(|| format!("Unterminated quote in '{}'.", line))?,
+                None => value
+                    .split_once(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or((value, "")),
+            };
+            attributes.push((key.trim().to_owned(), value.to_owned()));
+            rest = remainder.trim_start();
+        }
+        Ok(HeaderLine::Track(attributes))
+    }
+}
+
+impl fmt::Display for HeaderLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderLine::Track(attributes) => {
+                f.write_str("track")?;
+                for (key, value) in attributes {
+                    if value.is_empty() || value.contains(|c: char| c.is_ascii_whitespace()) {
+                        write!(f, " {}=\"{}\"", key, value)?;
+                    } else {
+                        write!(f, " {}={}", key, value)?;
+                    }
+                }
+                Ok(())
+            }
+            HeaderLine::Browser(rest) => write!(f, "browser {}", rest),
+        }
+    }
+}
+
+/// Reader of the lines of BED-like files, skipping comments and collecting `track` and
+/// `browser` lines.
+#[derive(Debug)]
+pub(crate) struct LineReader<R: io::Read> {
+    inner: io::BufReader<R>,
+    line: String,
+    header_lines: Vec<HeaderLine>,
+}
+
+impl<R: io::Read> LineReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        LineReader {
+            inner: io::BufReader::new(reader),
+            line: String::new(),
+            header_lines: Vec::new(),
+        }
+    }
+
+    /// Return the header lines, after reading those at the start of the input.
+    pub(crate) fn header_lines(&mut self) -> csv::Result<&[HeaderLine]> {
+        loop {
+            let buf = self.inner.fill_buf()?;
+            if !(buf.starts_with(b"#") || HeaderLine::is_header_line(buf)) {
+                return Ok(&self.header_lines);
+            }
+            self.read_line()?;
+        }
+    }
+
+    /// Read the next data line, split into tab-separated fields.
+    pub(crate) fn next_fields(&mut self) -> Option<csv::Result<Vec<&str>>> {
+        loop {
+            match self.read_line() {
+                Ok(true) => break,
+                Ok(false) => {
+                    if self.line.is_empty() {
+                        return None;
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok(self
+            .line
+            .trim_end_matches(['\n', '\r'])
+            .split('\t')
+            .collect()))
+    }
+
+    /// Read a line into `self.line`, recording it if it is a header line.
+    /// Return whether it is a data line.
+    fn read_line(&mut self) -> csv::Result<bool> {
+        self.line.clear();
+        self.inner.read_line(&mut self.line)?;
+        let line = self.line.trim_end_matches(['\n', '\r']);
+        if HeaderLine::is_header_line(line.as_bytes()) {
+            let header_line = line.parse().map_err(invalid_data)?;
+            self.header_lines.push(header_line);
+            Ok(false)
+        } else {
+            Ok(!line.starts_with('#') && !line.trim().is_empty())
+        }
+    }
+}
+
+pub(crate) fn invalid_data(msg: String) -> csv::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+/// Parse the given field of a BED-like line.
+pub(crate) fn parse_field<T: FromStr>(fields: &[&str], i: usize, name: &str) -> csv::Result<T> {
+    let field = fields
+        .get(i)
+        .ok_or_else(|| invalid_data(format!("Missing {} in BED line.", name)))?;
+    field
+        .parse()
+        .map_err(|_| invalid_data(format!("Invalid {} '{}' in BED line.", name, field)))
+}
 
 /// A BED reader.
 #[derive(Debug)]
 pub struct Reader<R: io::Read> {
-    inner: csv::Reader<R>,
+    inner: LineReader<R>,
 }
 
 impl Reader<fs::File> {
@@ -47,43 +218,64 @@ impl Reader<fs::File> {
     }
 }
 
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read bed from {:#?}", path))
+    }
+}
+
 impl<R: io::Read> Reader<R> {
     /// Read from a given reader.
     pub fn new(reader: R) -> Self {
         Reader {
-            inner: csv::ReaderBuilder::new()
-                .delimiter(b'\t')
-                .has_headers(false)
-                .comment(Some(b'#'))
-                .from_reader(reader),
+            inner: LineReader::new(reader),
         }
     }
 
+    /// Return the `track` and `browser` lines read so far, after reading those at the start of
+    /// the file.
+    pub fn header_lines(&mut self) -> csv::Result<&[HeaderLine]> {
+        self.inner.header_lines()
+    }
+
     /// Iterate over all records.
     pub fn records(&mut self) -> Records<'_, R> {
         Records {
-            inner: self.inner.deserialize(),
+            inner: &mut self.inner,
         }
     }
 }
 
 /// An iterator over the records of a BED file.
 pub struct Records<'a, R: io::Read> {
-    inner: csv::DeserializeRecordsIter<'a, R, Record>,
+    inner: &'a mut LineReader<R>,
 }
 
 impl<'a, R: io::Read> Iterator for Records<'a, R> {
     type Item = csv::Result<Record>;
 
     fn next(&mut self) -> Option<csv::Result<Record>> {
-        self.inner.next()
+        self.inner.next_fields().map(|fields| {
+            let fields = fields?;
+            Ok(Record {
+                chrom: parse_field(&fields, 0, "chromosome")?,
+                start: parse_field(&fields, 1, "start")?,
+                end: parse_field(&fields, 2, "end")?,
+                aux: fields[3..].iter().map(|&field| field.to_owned()).collect(),
+            })
+        })
     }
 }
 
 /// A BED writer.
 #[derive(Debug)]
 pub struct Writer<W: io::Write> {
-    inner: csv::Writer<W>,
+    inner: io::BufWriter<W>,
 }
 
 impl Writer<fs::File> {
@@ -94,26 +286,49 @@ impl Writer<fs::File> {
     }
 }
 
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
 impl<W: io::Write> Writer<W> {
     /// Write to a given writer.
     pub fn new(writer: W) -> Self {
         Writer {
-            inner: csv::WriterBuilder::new()
-                .delimiter(b'\t')
-                .flexible(true)
-                .from_writer(writer),
+            inner: io::BufWriter::new(writer),
         }
     }
 
+    /// Write a given `track` or `browser` line.
+    pub fn write_header_line(&mut self, header_line: &HeaderLine) -> csv::Result<()> {
+        writeln!(self.inner, "{}", header_line)?;
+        Ok(())
+    }
+
     /// Write a given BED record.
     pub fn write(&mut self, record: &Record) -> csv::Result<()> {
-        if record.aux.is_empty() {
-            self.inner
-                .serialize((&record.chrom, record.start, record.end))
-        } else {
-            self.inner
-                .serialize((&record.chrom, record.start, record.end, &record.aux))
+        write!(
+            self.inner,
+            "{}\t{}\t{}",
+            record.chrom, record.start, record.end
+        )?;
+        for field in &record.aux {
+            write!(self.inner, "\t{}", field)?;
         }
+        writeln!(self.inner)?;
+        Ok(())
+    }
+
+    /// Flush the output stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
     }
 }
 
@@ -224,6 +439,117 @@ impl Record {
     pub fn push_aux(&mut self, field: &str) {
         self.aux.push(field.to_owned());
     }
+
+    /// Start of the thickly drawn region, e.g. the start codon (BED12 column 7).
+    pub fn thick_start(&self) -> Option<u64> {
+        self.aux(6).and_then(|field| field.parse().ok())
+    }
+
+    /// End of the thickly drawn region, e.g. the stop codon (BED12 column 8).
+    pub fn thick_end(&self) -> Option<u64> {
+        self.aux(7).and_then(|field| field.parse().ok())
+    }
+
+    /// RGB color of the feature (BED12 column 9). A value of `0` is interpreted as black.
+    pub fn item_rgb(&self) -> Option<[u8; 3]> {
+        let field = self.aux(8)?;
+        if field == "0" {
+            return Some([0; 3]);
+        }
+        let mut rgb = [0; 3];
+        let mut values = field.split(',');
+        for value in rgb.iter_mut() {
+            *value = values.next()?.trim().parse().ok()?;
+        }
+        match values.next() {
+            None => Some(rgb),
+            Some(_) => None,
+        }
+    }
+
+    /// Number of blocks, e.g. exons (BED12 column 10).
+    pub fn block_count(&self) -> Option<usize> {
+        self.aux(9).and_then(|field| field.parse().ok())
+    }
+
+    /// Sizes of the blocks (BED12 column 11).
+    pub fn block_sizes(&self) -> Option<Vec<u64>> {
+        self.aux(10).and_then(parse_list)
+    }
+
+    /// Starts of the blocks, relative to the start of the feature (BED12 column 12).
+    pub fn block_starts(&self) -> Option<Vec<u64>> {
+        self.aux(11).and_then(parse_list)
+    }
+}
+
+/// Parse a comma-separated list of numbers, allowing a trailing comma.
+fn parse_list(field: &str) -> Option<Vec<u64>> {
+    field
+        .strip_suffix(',')
+        .unwrap_or(field)
+        .split(',')
+        .map(|value| value.trim().parse().ok())
+        .collect()
+}
+
+/// Errors when converting a BED record into a spliced annotation.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid or inconsistent block columns in BED record")]
+    InvalidBlocks,
+    #[error(transparent)]
+    Splicing(#[from] SplicingError),
+}
+
+impl TryFrom<&Record> for annot::spliced::Spliced<String, strand::Strand> {
+    type Error = Error;
+
+    /// Returns a `Spliced` annotation for the BED record, with exons given by the blocks of
+    /// BED12 records. Records with less than 12 columns are treated as a single exon.
+    ///
+    /// ```
+    /// use bio::io::bed;
+    /// use bio_types::annot::spliced::Spliced;
+    /// use std::convert::TryFrom;
+    /// let example = b"chrXII\t765265\t766358\tYLR316C\t0\t-\t765265\t766358\t0\t3\t\
+    ///     808,52,109,\t0,864,984,";
+    /// let mut reader = bed::Reader::new(&example[..]);
+    /// let rec = reader.records().next().unwrap().unwrap();
+    /// let tad3 = Spliced::try_from(&rec).unwrap();
+    /// assert_eq!(
+    ///     tad3.to_string(),
+    ///     "chrXII:765265-766073;766129-766181;766249-766358(-)"
+    /// );
+    /// ```
+    fn try_from(rec: &Record) -> Result<Self, Self::Error> {
+        let strand = rec.strand().unwrap_or(strand::Strand::Unknown);
+        if rec.aux.len() < 9 {
+            return Ok(annot::spliced::Spliced::new(
+                rec.chrom.clone(),
+                rec.start as isize,
+                (rec.end - rec.start) as usize,
+                strand,
+            ));
+        }
+        let (count, sizes, starts) =
+            match (rec.block_count(), rec.block_sizes(), rec.block_starts()) {
+                (Some(count), Some(sizes), Some(starts)) => (count, sizes, starts),
+                _ => return Err(Error::InvalidBlocks),
+            };
+        if sizes.len() != count || starts.len() != count {
+            return Err(Error::InvalidBlocks);
+        }
+        let to_usize =
+            |values: Vec<u64>| values.into_iter().map(|v| v as usize).collect::<Vec<_>>();
+        Ok(annot::spliced::Spliced::with_lengths_starts(
+            rec.chrom.clone(),
+            rec.start as isize,
+            &to_usize(sizes),
+            &to_usize(starts),
+            strand,
+        )?)
+    }
 }
 
 impl<'a> From<&'a Record> for annot::contig::Contig<String, strand::Strand> {
@@ -573,4 +899,102 @@ mod tests {
         assert_eq!(record.score(), Some("0"));
         assert_eq!(record.strand(), Some(Strand::Reverse));
     }
+
+    #[test]
+    fn test_reader_header_lines() {
+        let bed = b"browser position chr7:127471196-127495720
+browser hide all
+track name=\"ItemRGBDemo\" description=\"Item RGB demonstration\" itemRgb=On
+chr7\t127471196\t127472363\tPos1\t0\t+\t127471196\t127472363\t255,0,0
+track name=second
+chr7\t127472363\t127473530\tPos2\t0\t+\t127472363\t127473530\t0
+";
+        let mut reader = Reader::new(&bed[..]);
+        let header_lines = reader.header_lines().unwrap().to_vec();
+        assert_eq!(header_lines.len(), 3);
+        assert_eq!(
+            header_lines[0],
+            HeaderLine::Browser("position chr7:127471196-127495720".to_owned())
+        );
+        assert_eq!(
+            header_lines[2],
+            HeaderLine::Track(vec![
+                ("name".to_owned(), "ItemRGBDemo".to_owned()),
+                (
+                    "description".to_owned(),
+                    "Item RGB demonstration".to_owned()
+                ),
+                ("itemRgb".to_owned(), "On".to_owned()),
+            ])
+        );
+
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].item_rgb(), Some([255, 0, 0]));
+        assert_eq!(records[1].item_rgb(), Some([0, 0, 0]));
+        assert_eq!(records[1].thick_start(), Some(127472363));
+        assert_eq!(records[1].block_count(), None);
+        assert_eq!(
+            reader.header_lines().unwrap()[3].track_attribute("name"),
+            Some("second")
+        );
+
+        let mut writer = Writer::new(vec![]);
+        for header_line in &header_lines {
+            writer.write_header_line(header_line).unwrap();
+        }
+        writer.write(&records[0]).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.inner.into_inner().unwrap()).unwrap(),
+            "browser position chr7:127471196-127495720
+browser hide all
+track name=ItemRGBDemo description=\"Item RGB demonstration\" itemRgb=On
+chr7\t127471196\t127472363\tPos1\t0\t+\t127471196\t127472363\t255,0,0
+"
+        );
+    }
+
+    #[test]
+    fn test_reader_invalid_record() {
+        let mut reader = Reader::new(&b"chr1\t5\n"[..]);
+        assert!(reader.records().next().unwrap().is_err());
+        let mut reader = Reader::new(&b"chr1\tfive\t10\n"[..]);
+        assert!(reader.records().next().unwrap().is_err());
+        let mut reader = Reader::new(&b"track name=\"unterminated\n"[..]);
+        assert!(reader.header_lines().is_err());
+    }
+
+    #[test]
+    fn test_bed12_to_spliced() {
+        let bed =
+            b"chrV\t166236\t166885\tYER007C-A\t0\t-\t166336\t166800\t0,0,255\t2\t535,11,\t0,638,
+chrXI\t334412\t334916\tYKL071W\t0\t+
+chrV\t166236\t166885\tbroken\t0\t-\t166336\t166800\t0\t3\t535,11,\t0,638,
+";
+        let mut reader = Reader::new(&bed[..]);
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+
+        let tma20 = &records[0];
+        assert_eq!(tma20.thick_start(), Some(166336));
+        assert_eq!(tma20.thick_end(), Some(166800));
+        assert_eq!(tma20.item_rgb(), Some([0, 0, 255]));
+        assert_eq!(tma20.block_count(), Some(2));
+        assert_eq!(tma20.block_sizes(), Some(vec![535, 11]));
+        assert_eq!(tma20.block_starts(), Some(vec![0, 638]));
+        let spliced = Spliced::<String, Strand>::try_from(tma20).unwrap();
+        assert_eq!(spliced.to_string(), "chrV:166236-166771;166874-166885(-)");
+        // writing the spliced annotation again yields the same blocks
+        let bed = Record::from(spliced);
+        assert_eq!(bed.block_sizes(), tma20.block_sizes());
+        assert_eq!(bed.block_starts(), tma20.block_starts());
+
+        let single = Spliced::<String, Strand>::try_from(&records[1]).unwrap();
+        assert_eq!(single.to_string(), "chrXI:334412-334916(+)");
+        assert_eq!(records[1].thick_start(), None);
+
+        assert!(matches!(
+            Spliced::<String, Strand>::try_from(&records[2]),
+            Err(Error::InvalidBlocks)
+        ));
+    }
 }
@@ -0,0 +1,290 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! [bedGraph](https://genome.ucsc.edu/goldenPath/help/bedgraph.html) format reading and
+//! writing. Each record assigns a value (e.g. a coverage or signal) to an interval.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::bedgraph;
+//! let example = b"track type=bedGraph name=coverage\nchr1\t0\t100\t2.5\nchr1\t100\t150\t3\n";
+//! let mut reader = bedgraph::Reader::new(&example[..]);
+//! assert_eq!(
+//!     reader.header_lines().unwrap()[0].track_attribute("type"),
+//!     Some("bedGraph")
+//! );
+//! let mut writer = bedgraph::Writer::new(vec![]);
+//! for record in reader.records() {
+//!     let rec = record.expect("Error reading record.");
+//!     println!("{}:{}-{} {}", rec.chrom(), rec.start(), rec.end(), rec.value());
+//!     writer.write(&rec).expect("Error writing record.");
+//! }
+//! ```
+
+use std::convert::AsRef;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::io::bed::{parse_field, HeaderLine, LineReader};
+use crate::io::compression::{Decoder, Encoder, Format};
+
+/// A bedGraph reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: LineReader<R>,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read bedGraph from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read bedGraph from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: LineReader::new(reader),
+        }
+    }
+
+    /// Return the `track` and `browser` lines read so far, after reading those at the start of
+    /// the file.
+    pub fn header_lines(&mut self) -> csv::Result<&[HeaderLine]> {
+        self.inner.header_lines()
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records {
+            inner: &mut self.inner,
+        }
+    }
+}
+
+/// An iterator over the records of a bedGraph file.
+pub struct Records<'a, R: io::Read> {
+    inner: &'a mut LineReader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = csv::Result<Record>;
+
+    fn next(&mut self) -> Option<csv::Result<Record>> {
+        self.inner.next_fields().map(|fields| {
+            let fields = fields?;
+            Ok(Record {
+                chrom: parse_field(&fields, 0, "chromosome")?,
+                start: parse_field(&fields, 1, "start")?,
+                end: parse_field(&fields, 2, "end")?,
+                value: parse_field(&fields, 3, "value")?,
+            })
+        })
+    }
+}
+
+/// A bedGraph writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write a given `track` or `browser` line.
+    pub fn write_header_line(&mut self, header_line: &HeaderLine) -> csv::Result<()> {
+        writeln!(self.inner, "{}", header_line)?;
+        Ok(())
+    }
+
+    /// Write a given bedGraph record.
+    pub fn write(&mut self, record: &Record) -> csv::Result<()> {
+        writeln!(
+            self.inner,
+            "{}\t{}\t{}\t{}",
+            record.chrom, record.start, record.end, record.value
+        )?;
+        Ok(())
+    }
+
+    /// Flush the output stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A bedGraph record.
+#[derive(Default, Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+pub struct Record {
+    chrom: String,
+    start: u64,
+    end: u64,
+    value: f64,
+}
+
+impl Record {
+    /// Create a new bedGraph record.
+    pub fn new() -> Self {
+        Record::default()
+    }
+
+    /// Chromosome of the interval.
+    pub fn chrom(&self) -> &str {
+        &self.chrom
+    }
+
+    /// Start position of the interval (0-based).
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// End position of the interval (0-based, not included).
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Value of the interval.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Set chromosome.
+    pub fn set_chrom(&mut self, chrom: &str) {
+        self.chrom = chrom.to_owned();
+    }
+
+    /// Set start of the interval.
+    pub fn set_start(&mut self, start: u64) {
+        self.start = start;
+    }
+
+    /// Set end of the interval.
+    pub fn set_end(&mut self, end: u64) {
+        self.end = end;
+    }
+
+    /// Set value of the interval.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEDGRAPH_FILE: &[u8] = b"browser position chr19:49302001-49304701
+track type=bedGraph name=\"BedGraph Format\" visibility=full color=200,100,0
+# a comment
+chr19\t49302000\t49302300\t-1
+chr19\t49302300\t49302600\t-0.75
+chr19\t49302600\t49302900\t0.5
+";
+
+    #[test]
+    fn test_reader() {
+        let mut reader = Reader::new(BEDGRAPH_FILE);
+        let header_lines = reader.header_lines().unwrap().to_vec();
+        assert_eq!(
+            header_lines[0],
+            HeaderLine::Browser("position chr19:49302001-49304701".to_owned())
+        );
+        assert_eq!(
+            header_lines[1].track_attribute("name"),
+            Some("BedGraph Format")
+        );
+        assert_eq!(header_lines[1].track_attribute("color"), Some("200,100,0"));
+
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].chrom(), "chr19");
+        assert_eq!(records[0].start(), 49302000);
+        assert_eq!(records[0].end(), 49302300);
+        assert_eq!(records[0].value(), -1.0);
+        assert_eq!(records[1].value(), -0.75);
+    }
+
+    #[test]
+    fn test_reader_invalid_value() {
+        let mut reader = Reader::new(&b"chr1\t0\t10\thigh\n"[..]);
+        assert!(reader.records().next().unwrap().is_err());
+        let mut reader = Reader::new(&b"chr1\t0\t10\n"[..]);
+        assert!(reader.records().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut reader = Reader::new(BEDGRAPH_FILE);
+        let mut writer = Writer::new(vec![]);
+        for header_line in reader.header_lines().unwrap().to_vec() {
+            writer.write_header_line(&header_line).unwrap();
+        }
+        for record in reader.records() {
+            writer.write(&record.unwrap()).unwrap();
+        }
+        let mut record = Record::new();
+        record.set_chrom("chr19");
+        record.set_start(49302900);
+        record.set_end(49303200);
+        record.set_value(1.25);
+        writer.write(&record).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.inner.into_inner().unwrap()).unwrap(),
+            "browser position chr19:49302001-49304701
+track type=bedGraph name=\"BedGraph Format\" visibility=full color=200,100,0
+chr19\t49302000\t49302300\t-1
+chr19\t49302300\t49302600\t-0.75
+chr19\t49302600\t49302900\t0.5
+chr19\t49302900\t49303200\t1.25
+"
+        );
+    }
+}
@@ -0,0 +1,597 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading and writing of the blocked gzip format (BGZF) as produced by `bgzip`,
+//! together with its `.gzi` index.
+//!
+//! BGZF files are valid gzip files that consist of independently compressed blocks of at
+//! most 64 kB. The `.gzi` index stores the compressed and uncompressed offset of each block,
+//! which allows [`Reader`] to seek to arbitrary uncompressed positions. This is used by
+//! [`fasta::IndexedReader`](crate::io::fasta::IndexedReader) to provide random access into
+//! compressed reference genomes as indexed by `samtools faidx`.
+//!
+//! This module requires the `gzip` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::bgzf;
+//! use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+//!
+//! let mut writer = bgzf::Writer::new(Vec::new());
+//! writer.write_all(b"ACGTACGTACGT").unwrap();
+//! let (compressed, gzi) = writer.finish().unwrap();
+//!
+//! let mut reader = bgzf::Reader::with_index(Cursor::new(compressed), gzi);
+//! reader.seek(SeekFrom::Start(4)).unwrap();
+//! let mut buf = [0; 4];
+//! reader.read_exact(&mut buf).unwrap();
+//! assert_eq!(&buf, b"ACGT");
+//! ```
+
+use std::convert::TryInto;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+use flate2::{Compress, Compression, Crc, Decompress, FlushCompress, FlushDecompress, Status};
+
+/// Maximum number of uncompressed bytes per block, as used by htslib.
+const BLOCK_SIZE: usize = 0xff00;
+/// Maximum size of a compressed block including header and footer.
+const MAX_BLOCK_SIZE: usize = 0x10000;
+/// Length of a BGZF block header without further extra subfields.
+const HEADER_LEN: usize = 18;
+/// Length of a BGZF block footer (CRC32 and ISIZE).
+const FOOTER_LEN: usize = 8;
+/// The empty block marking the end of a BGZF file.
+const EOF_BLOCK: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_owned())
+}
+
+/// A `.gzi` index, mapping the uncompressed start offset of each BGZF block to its
+/// compressed offset.
+#[derive(Default, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GziIndex {
+    /// Pairs of (compressed, uncompressed) block offsets, excluding the first block.
+    entries: Vec<(u64, u64)>,
+}
+
+impl GziIndex {
+    /// Read a `.gzi` index from a given `io::Read` instance.
+    pub fn new<R: io::Read>(mut gzi: R) -> io::Result<Self> {
+        let mut buf = [0; 8];
+        gzi.read_exact(&mut buf)?;
+        let n = u64::from_le_bytes(buf);
+        let mut entries = Vec::new();
+        for _ in 0..n {
+            gzi.read_exact(&mut buf)?;
+            let compressed = u64::from_le_bytes(buf);
+            gzi.read_exact(&mut buf)?;
+            let uncompressed = u64::from_le_bytes(buf);
+            entries.push((compressed, uncompressed));
+        }
+        Ok(GziIndex { entries })
+    }
+
+    /// Read a `.gzi` index from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        fs::File::open(path)
+            .and_then(|f| Self::new(io::BufReader::new(f)))
+            .with_context(|| format!("Failed to read gzi index from {:#?}", path))
+    }
+
+    /// Write the index in the binary `.gzi` format.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        for (compressed, uncompressed) in &self.entries {
+            writer.write_all(&compressed.to_le_bytes())?;
+            writer.write_all(&uncompressed.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Return the (compressed, uncompressed) offsets of the block containing the
+    /// given uncompressed position.
+    fn block_containing(&self, pos: u64) -> (u64, u64) {
+        let i = self.entries.partition_point(|&(_, u)| u <= pos);
+        if i == 0 {
+            (0, 0)
+        } else {
+            self.entries[i - 1]
+        }
+    }
+}
+
+/// A BGZF reader. Without a [`GziIndex`], the data can only be read sequentially.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: R,
+    index: Option<GziIndex>,
+    decompress: Decompress,
+    compressed: Vec<u8>,
+    block: Vec<u8>,
+    block_pos: usize,
+    /// Uncompressed offset of the start of the current block.
+    block_offset: u64,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path, using the index at `path.gzi`.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        let mut gzi_path = path.as_ref().as_os_str().to_owned();
+        gzi_path.push(".gzi");
+        let index = GziIndex::from_file(&gzi_path)?;
+        fs::File::open(path)
+            .map(|f| Reader::with_index(f, index))
+            .with_context(|| format!("Failed to read BGZF from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read sequentially from a given `io::Read`.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: reader,
+            index: None,
+            decompress: Decompress::new(false),
+            compressed: Vec::with_capacity(MAX_BLOCK_SIZE),
+            block: Vec::with_capacity(MAX_BLOCK_SIZE),
+            block_pos: 0,
+            block_offset: 0,
+        }
+    }
+
+    /// Read from a given `io::Read` with a `.gzi` index, allowing to seek if the
+    /// reader implements `io::Seek`.
+    pub fn with_index(reader: R, index: GziIndex) -> Self {
+        let mut reader = Reader::new(reader);
+        reader.index = Some(index);
+        reader
+    }
+
+    /// Read and decompress the next block. Returns `false` at the end of the data.
+    fn read_block(&mut self) -> io::Result<bool> {
+        self.block_offset += self.block.len() as u64;
+        self.block.clear();
+        self.block_pos = 0;
+
+        let mut header = [0; HEADER_LEN];
+        let mut n = 0;
+        while n < HEADER_LEN {
+            match self.inner.read(&mut header[n..]) {
+                Ok(0) if n == 0 => return Ok(false),
+                Ok(0) => return Err(invalid_data("BGZF block header is truncated.")),
+                Ok(k) => n += k,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        if header[..4] != [0x1f, 0x8b, 0x08, 0x04] {
+            return Err(invalid_data("Invalid BGZF block header."));
+        }
+        let xlen = u16::from_le_bytes([header[10], header[11]]) as usize;
+        if xlen < 6 {
+            return Err(invalid_data("Invalid BGZF block header."));
+        }
+        let mut extra = vec![0; xlen - 6];
+        let mut subfields = header[12..].to_vec();
+        self.inner.read_exact(&mut extra)?;
+        subfields.extend_from_slice(&extra);
+
+        let mut bsize = None;
+        let mut i = 0;
+        while i + 4 <= subfields.len() {
+            let slen = u16::from_le_bytes([subfields[i + 2], subfields[i + 3]]) as usize;
+            if subfields[i..i + 2] == *b"BC" && slen == 2 && i + 6 <= subfields.len() {
+                bsize = Some(u16::from_le_bytes([subfields[i + 4], subfields[i + 5]]) as usize);
+            }
+            i += 4 + slen;
+        }
+        let bsize = bsize.ok_or_else(|| invalid_data("BGZF block lacks the BC subfield."))? + 1;
+        let remaining = bsize
+            .checked_sub(12 + xlen)
+            .filter(|&r| r >= FOOTER_LEN)
+            .ok_or_else(|| invalid_data("Invalid BGZF block size."))?;
+
+        self.compressed.resize(remaining, 0);
+        self.inner.read_exact(&mut self.compressed)?;
+        let (cdata, footer) = self.compressed.split_at(remaining - FOOTER_LEN);
+        let crc = u32::from_le_bytes(footer[..4].try_into().unwrap());
+        let isize = u32::from_le_bytes(footer[4..].try_into().unwrap()) as usize;
+
+        self.block.reserve(isize);
+        self.decompress.reset(false);
+        self.decompress
+            .decompress_vec(cdata, &mut self.block, FlushDecompress::Finish)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if self.block.len() != isize {
+            return Err(invalid_data("BGZF block has an unexpected size."));
+        }
+        let mut checksum = Crc::new();
+        checksum.update(&self.block);
+        if checksum.sum() != crc {
+            return Err(invalid_data("BGZF block checksum mismatch."));
+        }
+        Ok(true)
+    }
+
+    /// The current uncompressed position.
+    fn position(&self) -> u64 {
+        self.block_offset + self.block_pos as u64
+    }
+}
+
+impl<R: io::Read> io::Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let mut available = self.fill_buf()?;
+            available.read(buf)?
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: io::Read> io::BufRead for Reader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // empty blocks, e.g. the EOF markers of concatenated files, are skipped
+        while self.block_pos >= self.block.len() {
+            if !self.read_block()? {
+                break;
+            }
+        }
+        Ok(&self.block[self.block_pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.block_pos = std::cmp::min(self.block_pos + amt, self.block.len());
+    }
+}
+
+impl<R: io::Read + io::Seek> io::Seek for Reader<R> {
+    /// Seek to an uncompressed position. This requires a [`GziIndex`].
+    /// Seeking relative to the end is not supported.
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            io::SeekFrom::Start(p) => p,
+            io::SeekFrom::Current(d) => self
+                .position()
+                .checked_add_signed(d)
+                .ok_or_else(|| invalid_data("Invalid seek to a negative position."))?,
+            io::SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Seeking relative to the end of BGZF data is not supported.",
+                ))
+            }
+        };
+        if target >= self.block_offset && target < self.block_offset + self.block.len() as u64 {
+            self.block_pos = (target - self.block_offset) as usize;
+            return Ok(target);
+        }
+
+        let (coffset, uoffset) = self
+            .index
+            .as_ref()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Seeking in BGZF data requires a .gzi index.",
+                )
+            })?
+            .block_containing(target);
+        self.inner.seek(io::SeekFrom::Start(coffset))?;
+        self.block.clear();
+        self.block_offset = uoffset;
+        self.block_pos = 0;
+        while self.read_block()? {
+            if target < self.block_offset + self.block.len() as u64 {
+                break;
+            }
+        }
+        if target > self.block_offset + self.block.len() as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Seek beyond the end of BGZF data.",
+            ));
+        }
+        self.block_pos = (target - self.block_offset) as usize;
+        Ok(target)
+    }
+}
+
+/// A BGZF writer. The data is buffered and written in full blocks, and the last block and
+/// the end-of-file marker are written by [`Writer::finish`], or when the writer is dropped.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: Option<W>,
+    compress: Compress,
+    buf: Vec<u8>,
+    compressed: Vec<u8>,
+    index: GziIndex,
+    compressed_offset: u64,
+    uncompressed_offset: u64,
+}
+
+impl Writer<fs::File> {
+    /// Write to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given `io::Write`, using the default compression level.
+    pub fn new(writer: W) -> Self {
+        Writer::with_compression(writer, Compression::default())
+    }
+
+    /// Write to a given `io::Write`, using the given compression level.
+    pub fn with_compression(writer: W, level: Compression) -> Self {
+        Writer {
+            inner: Some(writer),
+            compress: Compress::new(level, false),
+            buf: Vec::with_capacity(BLOCK_SIZE),
+            compressed: Vec::with_capacity(MAX_BLOCK_SIZE),
+            index: GziIndex::default(),
+            compressed_offset: 0,
+            uncompressed_offset: 0,
+        }
+    }
+
+    /// Return the `.gzi` index of the blocks written so far.
+    pub fn index(&self) -> &GziIndex {
+        &self.index
+    }
+
+    /// Return the number of uncompressed bytes written so far.
+    pub fn uncompressed_offset(&self) -> u64 {
+        self.uncompressed_offset + self.buf.len() as u64
+    }
+
+    /// Compress and write the buffered data as a block, even if it is not full.
+    pub(crate) fn write_block(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let writer = self.inner.as_mut().unwrap();
+        self.compressed.clear();
+        self.compress.reset();
+        let status = self
+            .compress
+            .compress_vec(&self.buf, &mut self.compressed, FlushCompress::Finish)
+            .map_err(io::Error::other)?;
+        let block_size = HEADER_LEN + self.compressed.len() + FOOTER_LEN;
+        if status != Status::StreamEnd || block_size > MAX_BLOCK_SIZE {
+            return Err(invalid_data(
+                "Compressed BGZF block exceeds the maximum size.",
+            ));
+        }
+        let mut crc = Crc::new();
+        crc.update(&self.buf);
+
+        let mut header = EOF_BLOCK[..HEADER_LEN].to_vec();
+        header[16..18].copy_from_slice(&((block_size - 1) as u16).to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&self.compressed)?;
+        writer.write_all(&crc.sum().to_le_bytes())?;
+        writer.write_all(&(self.buf.len() as u32).to_le_bytes())?;
+
+        if self.uncompressed_offset > 0 {
+            self.index
+                .entries
+                .push((self.compressed_offset, self.uncompressed_offset));
+        }
+        self.compressed_offset += block_size as u64;
+        self.uncompressed_offset += self.buf.len() as u64;
+        self.buf.clear();
+        Ok(())
+    }
+
+    /// Write the remaining data and the end-of-file marker, returning the underlying
+    /// writer and the `.gzi` index.
+    pub fn finish(mut self) -> io::Result<(W, GziIndex)> {
+        self.try_finish()?;
+        let index = std::mem::take(&mut self.index);
+        Ok((self.inner.take().unwrap(), index))
+    }
+
+    fn try_finish(&mut self) -> io::Result<()> {
+        if self.inner.is_some() {
+            self.write_block()?;
+            let writer = self.inner.as_mut().unwrap();
+            writer.write_all(&EOF_BLOCK)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: io::Write> io::Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = std::cmp::min(buf.len(), BLOCK_SIZE - self.buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        if self.buf.len() == BLOCK_SIZE {
+            self.write_block()?;
+        }
+        Ok(n)
+    }
+
+    /// Flush the underlying writer. Data that does not fill a block yet stays buffered, so
+    /// that flushing does not produce short blocks.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: io::Write> Drop for Writer<W> {
+    fn drop(&mut self) {
+        let _ = self.try_finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data(len: usize) -> Vec<u8> {
+        let mut seed = 42usize;
+        (0..len)
+            .map(|_| {
+                seed = ((seed ^ seed << 13) ^ seed >> 7) ^ seed << 17;
+                b"ACGT"[seed % 4]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_roundtrip_sequential() {
+        let data = test_data(200_000);
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data).unwrap();
+        let (compressed, index) = writer.finish().unwrap();
+        assert_eq!(&compressed[compressed.len() - 28..], &EOF_BLOCK);
+        assert_eq!(index.entries.len(), 200_000 / BLOCK_SIZE);
+
+        let mut decompressed = Vec::new();
+        Reader::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+
+        // BGZF is valid gzip
+        let mut decompressed = Vec::new();
+        flate2::read::MultiGzDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_seek() {
+        let data = test_data(150_000);
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data).unwrap();
+        let (compressed, index) = writer.finish().unwrap();
+
+        let mut reader = Reader::with_index(io::Cursor::new(compressed), index);
+        for &pos in &[
+            140_000u64,
+            0,
+            BLOCK_SIZE as u64 - 2,
+            BLOCK_SIZE as u64,
+            70_000,
+        ] {
+            reader.seek(io::SeekFrom::Start(pos)).unwrap();
+            let mut buf = [0; 10];
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, &data[pos as usize..pos as usize + 10]);
+        }
+        reader.seek(io::SeekFrom::Current(-20)).unwrap();
+        let mut buf = [0; 5];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, &data[69_990..69_995]);
+
+        assert!(reader.seek(io::SeekFrom::Start(150_001)).is_err());
+        assert!(reader.seek(io::SeekFrom::End(0)).is_err());
+    }
+
+    #[test]
+    fn test_seek_without_index_fails() {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&test_data(100_000)).unwrap();
+        let (compressed, _) = writer.finish().unwrap();
+        let mut reader = Reader::new(io::Cursor::new(compressed));
+        assert_eq!(
+            reader.seek(io::SeekFrom::Start(80_000)).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_gzi_roundtrip() {
+        let index = GziIndex {
+            entries: vec![(100, 65280), (200, 130560)],
+        };
+        let mut buf = Vec::new();
+        index.write(&mut buf).unwrap();
+        assert_eq!(buf.len(), 8 + 2 * 16);
+        assert_eq!(GziIndex::new(&buf[..]).unwrap(), index);
+        assert_eq!(index.block_containing(65279), (0, 0));
+        assert_eq!(index.block_containing(65280), (100, 65280));
+        assert_eq!(index.block_containing(200_000), (200, 130560));
+    }
+
+    #[test]
+    fn test_invalid_block() {
+        let mut reader =
+            Reader::new(&b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\xff\x00\x00ACGTAC"[..]);
+        let mut buf = Vec::new();
+        assert_eq!(
+            reader.read_to_end(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_flush_keeps_blocks_full() {
+        let data = test_data(100_000);
+        let mut writer = Writer::new(Vec::new());
+        for chunk in data.chunks(1000) {
+            writer.write_all(chunk).unwrap();
+            writer.flush().unwrap();
+        }
+        assert_eq!(writer.index().entries.len(), 0);
+        let (compressed, index) = writer.finish().unwrap();
+        assert_eq!(index.entries.len(), 1);
+        assert_eq!(index.entries[0].1, BLOCK_SIZE as u64);
+
+        let mut decompressed = Vec::new();
+        Reader::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_incompressible_block() {
+        let mut seed = 42u64;
+        let data: Vec<u8> = (0..BLOCK_SIZE)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 56) as u8
+            })
+            .collect();
+        let mut writer = Writer::with_compression(Vec::new(), Compression::none());
+        writer.write_all(&data).unwrap();
+        let (compressed, _) = writer.finish().unwrap();
+        let mut decompressed = Vec::new();
+        Reader::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_drop_writes_eof_marker() {
+        let mut compressed = Vec::new();
+        {
+            let mut writer = Writer::new(&mut compressed);
+            writer.write_all(b"ACGT").unwrap();
+        }
+        assert_eq!(&compressed[compressed.len() - 28..], &EOF_BLOCK);
+    }
+}
@@ -0,0 +1,459 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Transparent (de)compression of input and output streams.
+//!
+//! The compression [`Format`] of an input is detected by its magic bytes, so that
+//! [`Decoder`] can wrap any [`io::Read`] regardless of whether it is compressed or not.
+//! The `from_path_auto` constructors of the readers in this crate (e.g.
+//! `fasta::Reader::from_path_auto` or `bed::Reader::from_path_auto`) use it to open compressed
+//! files transparently. Likewise, [`Encoder`] compresses output in a given format, and the
+//! `to_path_auto` constructors of the writers choose the format from the file extension (see
+//! [`Format::from_path`]). The `from_file` and `to_file` constructors always read and write
+//! files as they are.
+//!
+//! Support for the individual formats has to be enabled with cargo features:
+//!
+//! * `gzip`: gzip and BGZF (bgzip), via [flate2](https://docs.rs/flate2),
+//! * `zstd`: Zstandard, via [zstd](https://docs.rs/zstd),
+//! * `bzip2`: bzip2, via [bzip2](https://docs.rs/bzip2).
+//!
+//! Uncompressed data is always supported. Compressed data of a format whose feature is
+//! disabled is detected nevertheless and results in an error.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::compression::{Decoder, Format};
+//! use bio::io::fasta;
+//!
+//! let data: &[u8] = b">id desc\nACGT\n";
+//! let decoder = Decoder::new(data).unwrap();
+//! assert_eq!(decoder.format(), Format::Plain);
+//! let records = fasta::Reader::new(decoder).records();
+//! assert_eq!(records.count(), 1);
+//! ```
+
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+/// Number of leading bytes inspected to determine the compression format.
+const MAGIC_LEN: usize = 18;
+
+/// A compression format.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Format {
+    /// Uncompressed data.
+    Plain,
+    /// Gzip compressed data (a single or multiple gzip members).
+    Gzip,
+    /// Blocked gzip (BGZF), as written by `bgzip`. BGZF is valid gzip.
+    Bgzf,
+    /// Zstandard compressed data.
+    Zstd,
+    /// Bzip2 compressed data.
+    Bzip2,
+}
+
+impl Format {
+    /// Determine the compression format from the leading bytes of a stream.
+    /// At least 14 bytes are needed to distinguish BGZF from plain gzip.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::io::compression::Format;
+    ///
+    /// assert_eq!(Format::detect(b"\x1f\x8b\x08\x00"), Format::Gzip);
+    /// assert_eq!(Format::detect(b"\x28\xb5\x2f\xfd"), Format::Zstd);
+    /// assert_eq!(Format::detect(b">id\nACGT"), Format::Plain);
+    /// ```
+    pub fn detect(magic: &[u8]) -> Self {
+        match magic {
+            [0x1f, 0x8b, 0x08, flags, _, _, _, _, _, _, _, _, b'B', b'C', ..]
+                if flags & 0x04 != 0 =>
+            {
+                Format::Bgzf
+            }
+            [0x1f, 0x8b, ..] => Format::Gzip,
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Format::Zstd,
+            [b'B', b'Z', b'h', b'1'..=b'9', ..] => Format::Bzip2,
+            _ => Format::Plain,
+        }
+    }
+
+    /// Determine the compression format from the extension of the given path
+    /// (`.gz`, `.bgz`, `.zst` and `.bz2`). Any other extension is considered uncompressed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::io::compression::Format;
+    ///
+    /// assert_eq!(Format::from_path("reads.fq.gz"), Format::Gzip);
+    /// assert_eq!(Format::from_path("reads.fq"), Format::Plain);
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("gz") | Some("gzip") => Format::Gzip,
+            Some("bgz") | Some("bgzf") => Format::Bgzf,
+            Some("zst") | Some("zstd") => Format::Zstd,
+            Some("bz2") => Format::Bzip2,
+            _ => Format::Plain,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Plain => "uncompressed",
+            Format::Gzip => "gzip",
+            Format::Bgzf => "BGZF",
+            Format::Zstd => "zstd",
+            Format::Bzip2 => "bzip2",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Error for formats whose support has not been compiled in.
+#[allow(dead_code)]
+fn unsupported(format: Format, feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{} compressed data requires the `{}` feature of rust-bio",
+            format, feature
+        ),
+    )
+}
+
+/// The input stream with the already inspected magic bytes prepended again.
+type Peeked<R> = io::Chain<io::Cursor<Vec<u8>>, R>;
+
+enum DecoderInner<R: io::Read> {
+    Plain(Peeked<R>),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::read::MultiGzDecoder<Peeked<R>>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<'static, io::BufReader<Peeked<R>>>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::read::MultiBzDecoder<Peeked<R>>),
+}
+
+/// A reader that transparently decompresses its input.
+pub struct Decoder<R: io::Read> {
+    inner: DecoderInner<R>,
+    format: Format,
+}
+
+impl<R: io::Read> Decoder<R> {
+    /// Create a new decoder, detecting the compression format of the given reader by
+    /// its magic bytes.
+    ///
+    /// # Errors
+    /// If reading the magic bytes fails, or if the input is compressed in a format whose
+    /// cargo feature is not enabled.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = Vec::with_capacity(MAGIC_LEN);
+        (&mut reader)
+            .take(MAGIC_LEN as u64)
+            .read_to_end(&mut magic)?;
+        let format = Format::detect(&magic);
+        let peeked = io::Cursor::new(magic).chain(reader);
+
+        let inner = match format {
+            Format::Plain => DecoderInner::Plain(peeked),
+            #[cfg(feature = "gzip")]
+            Format::Gzip | Format::Bgzf => {
+                DecoderInner::Gzip(flate2::read::MultiGzDecoder::new(peeked))
+            }
+            #[cfg(not(feature = "gzip"))]
+            Format::Gzip | Format::Bgzf => return Err(unsupported(format, "gzip")),
+            #[cfg(feature = "zstd")]
+            Format::Zstd => DecoderInner::Zstd(zstd::stream::read::Decoder::new(peeked)?),
+            #[cfg(not(feature = "zstd"))]
+            Format::Zstd => return Err(unsupported(format, "zstd")),
+            #[cfg(feature = "bzip2")]
+            Format::Bzip2 => DecoderInner::Bzip2(bzip2::read::MultiBzDecoder::new(peeked)),
+            #[cfg(not(feature = "bzip2"))]
+            Format::Bzip2 => return Err(unsupported(format, "bzip2")),
+        };
+
+        Ok(Decoder { inner, format })
+    }
+
+    /// Return the detected compression format.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+}
+
+impl<R: io::Read> io::Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            DecoderInner::Plain(r) => r.read(buf),
+            #[cfg(feature = "gzip")]
+            DecoderInner::Gzip(r) => r.read(buf),
+            #[cfg(feature = "zstd")]
+            DecoderInner::Zstd(r) => r.read(buf),
+            #[cfg(feature = "bzip2")]
+            DecoderInner::Bzip2(r) => r.read(buf),
+        }
+    }
+}
+
+impl<R: io::Read> fmt::Debug for Decoder<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+enum EncoderInner<W: io::Write> {
+    Plain(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "gzip")]
+    Bgzf(crate::io::bgzf::Writer<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+}
+
+/// A writer that compresses its output in a given format.
+///
+/// The compressed stream is finalized by [`Encoder::finish`], which reports any errors.
+/// Otherwise, it is finalized when the encoder is dropped, ignoring errors.
+pub struct Encoder<W: io::Write> {
+    inner: Option<EncoderInner<W>>,
+    format: Format,
+}
+
+impl<W: io::Write> Encoder<W> {
+    /// Create a new encoder writing in the given format, using the default compression level.
+    ///
+    /// # Errors
+    /// If the cargo feature of the requested format is not enabled.
+    pub fn new(writer: W, format: Format) -> io::Result<Self> {
+        let inner = match format {
+            Format::Plain => EncoderInner::Plain(writer),
+            #[cfg(feature = "gzip")]
+            Format::Gzip => EncoderInner::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            #[cfg(not(feature = "gzip"))]
+            Format::Gzip => return Err(unsupported(format, "gzip")),
+            #[cfg(feature = "gzip")]
+            Format::Bgzf => EncoderInner::Bgzf(crate::io::bgzf::Writer::new(writer)),
+            #[cfg(not(feature = "gzip"))]
+            Format::Bgzf => return Err(unsupported(format, "gzip")),
+            #[cfg(feature = "zstd")]
+            Format::Zstd => EncoderInner::Zstd(zstd::stream::write::Encoder::new(
+                writer,
+                zstd::DEFAULT_COMPRESSION_LEVEL,
+            )?),
+            #[cfg(not(feature = "zstd"))]
+            Format::Zstd => return Err(unsupported(format, "zstd")),
+            #[cfg(feature = "bzip2")]
+            Format::Bzip2 => EncoderInner::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::default(),
+            )),
+            #[cfg(not(feature = "bzip2"))]
+            Format::Bzip2 => return Err(unsupported(format, "bzip2")),
+        };
+
+        Ok(Encoder {
+            inner: Some(inner),
+            format,
+        })
+    }
+
+    /// Return the compression format.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Finalize the compressed stream and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.inner.take().unwrap() {
+            EncoderInner::Plain(mut w) => w.flush().map(|()| w),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Gzip(w) => w.finish(),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Bgzf(w) => w.finish().map(|(w, _)| w),
+            #[cfg(feature = "zstd")]
+            EncoderInner::Zstd(w) => w.finish(),
+            #[cfg(feature = "bzip2")]
+            EncoderInner::Bzip2(w) => w.finish(),
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut EncoderInner<W> {
+        self.inner.as_mut().unwrap()
+    }
+}
+
+impl<W: io::Write> io::Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.inner_mut() {
+            EncoderInner::Plain(w) => w.write(buf),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Gzip(w) => w.write(buf),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Bgzf(w) => w.write(buf),
+            #[cfg(feature = "zstd")]
+            EncoderInner::Zstd(w) => w.write(buf),
+            #[cfg(feature = "bzip2")]
+            EncoderInner::Bzip2(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.inner_mut() {
+            EncoderInner::Plain(w) => w.flush(),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Gzip(w) => w.flush(),
+            #[cfg(feature = "gzip")]
+            EncoderInner::Bgzf(w) => w.flush(),
+            #[cfg(feature = "zstd")]
+            EncoderInner::Zstd(w) => w.flush(),
+            #[cfg(feature = "bzip2")]
+            EncoderInner::Bzip2(w) => w.flush(),
+        }
+    }
+}
+
+impl<W: io::Write> Drop for Encoder<W> {
+    fn drop(&mut self) {
+        // the other encoders finalize their stream when dropped themselves
+        #[cfg(feature = "zstd")]
+        if let Some(EncoderInner::Zstd(w)) = &mut self.inner {
+            let _ = w.do_finish();
+        }
+    }
+}
+
+impl<W: io::Write> fmt::Debug for Encoder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encoder")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FASTA: &[u8] = b">id desc\nACCGTAGGCTGA\n>id2\nATTGTTGTTTTA\n";
+
+    fn roundtrip(format: Format) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new(), format).unwrap();
+        encoder.write_all(FASTA).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut dropped = Vec::new();
+        {
+            // the compressed stream is finalized on drop as well
+            let mut encoder = Encoder::new(&mut dropped, format).unwrap();
+            encoder.write_all(FASTA).unwrap();
+        }
+        assert_eq!(dropped, compressed);
+
+        let mut decoder = Decoder::new(&compressed[..]).unwrap();
+        assert_eq!(decoder.format(), format);
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed).unwrap();
+        decompressed
+    }
+
+    #[test]
+    fn test_detect() {
+        let bgzf_header = b"\x1f\x8b\x08\x04\x00\x00\x00\x00\x00\xff\x06\x00BC\x02\x00";
+        assert_eq!(Format::detect(bgzf_header), Format::Bgzf);
+        assert_eq!(Format::detect(&bgzf_header[..12]), Format::Gzip);
+        assert_eq!(Format::detect(b"BZh91AY&SY"), Format::Bzip2);
+        assert_eq!(Format::detect(b"BZh0"), Format::Plain);
+        assert_eq!(Format::detect(b""), Format::Plain);
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(Format::from_path("ref.fa.bgz"), Format::Bgzf);
+        assert_eq!(Format::from_path("reads.fq.zst"), Format::Zstd);
+        assert_eq!(Format::from_path("reads.fq.bz2"), Format::Bzip2);
+        assert_eq!(Format::from_path("reads"), Format::Plain);
+    }
+
+    #[test]
+    fn test_plain_roundtrip() {
+        assert_eq!(roundtrip(Format::Plain), FASTA);
+    }
+
+    #[test]
+    fn test_short_plain_input() {
+        let mut decoder = Decoder::new(&b">a"[..]).unwrap();
+        let mut data = Vec::new();
+        decoder.read_to_end(&mut data).unwrap();
+        assert_eq!(data, b">a");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_roundtrip() {
+        assert_eq!(roundtrip(Format::Gzip), FASTA);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_bgzf_roundtrip() {
+        assert_eq!(roundtrip(Format::Bgzf), FASTA);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_multiple_members() {
+        let mut data = Vec::new();
+        for chunk in FASTA.chunks(10) {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(chunk).unwrap();
+            data.extend(encoder.finish().unwrap());
+        }
+        let mut decompressed = Vec::new();
+        Decoder::new(&data[..])
+            .unwrap()
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, FASTA);
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_gzip_unsupported() {
+        let err = Decoder::new(&b"\x1f\x8b\x08\x00\x00\x00"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_roundtrip() {
+        assert_eq!(roundtrip(Format::Zstd), FASTA);
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn test_bzip2_roundtrip() {
+        assert_eq!(roundtrip(Format::Bzip2), FASTA);
+    }
+}
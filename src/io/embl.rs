@@ -0,0 +1,580 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! [EMBL](https://ftp.ebi.ac.uk/pub/databases/embl/doc/usrman.txt) flat file reading and
+//! writing.
+//!
+//! Records, features and locations are shared with the [GenBank](crate::io::genbank) module,
+//! so that the same code can handle both formats and records can be converted between them.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::{embl, genbank};
+//!
+//! let example = b"ID   TEST01; SV 1; linear; mRNA; STD; PLN; 20 BP.
+//! XX
+//! AC   TEST01;
+//! XX
+//! DE   A test sequence
+//! XX
+//! FH   Key             Location/Qualifiers
+//! FT   CDS             complement(1..18)
+//! FT                   /gene=\"abc\"
+//! SQ   Sequence 20 BP; 7 A; 3 C; 5 G; 5 T; 0 other;
+//!      atgaaacccg ggtttaatag                                                    20
+//! //
+//! ";
+//! let mut reader = embl::Reader::new(&example[..]);
+//! let record = reader.records().next().unwrap().unwrap();
+//! assert_eq!(record.version(), Some("TEST01.1"));
+//! assert_eq!(record.features()[0].location.to_string(), "complement(1..18)");
+//!
+//! // write the record as GenBank
+//! let mut writer = genbank::Writer::new(Vec::new());
+//! writer.write(&record).unwrap();
+//! ```
+
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::io::compression::{self, Decoder, Encoder};
+use crate::io::genbank::{
+    append, parse_features, split_list, wrap, write_features, LineReader, Locus, Record, Reference,
+    Result, LINE_WIDTH,
+};
+
+/// Width of the line type column, including the following spaces.
+const PREFIX_WIDTH: usize = 5;
+
+/// Split a line into the line type and the text after it.
+fn split_line(line: &str) -> (&str, &str) {
+    let code = line.get(..2).unwrap_or(line).trim();
+    let text = line.get(PREFIX_WIDTH..).unwrap_or("").trim_end();
+    (code, text)
+}
+
+/// An EMBL reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: LineReader<R>,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read EMBL from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read EMBL from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: LineReader::new(reader),
+        }
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self }
+    }
+
+    /// Read the next record, if any. Lines before the first ID line are skipped.
+    pub fn read(&mut self) -> Result<Option<Record>> {
+        let id = loop {
+            match self.inner.next_line()? {
+                Some(line) if line.starts_with("ID") => break line,
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        };
+        let mut record = Record::new();
+        let sv = self.parse_id(split_line(&id).1, record.locus_mut())?;
+
+        let mut description = String::new();
+        let mut keywords = String::new();
+        let mut taxonomy = String::new();
+        let mut comment: Vec<String> = Vec::new();
+        let mut features = Vec::new();
+        let mut seq = Vec::new();
+        let mut in_sequence = false;
+        let mut last_code = String::new();
+        loop {
+            let line = self.inner.record_line()?;
+            if line.starts_with("//") {
+                break;
+            }
+            if in_sequence {
+                seq.extend(line.bytes().filter(|c| c.is_ascii_alphabetic()));
+                continue;
+            }
+            let (code, text) = split_line(&line);
+            match code {
+                "XX" | "FH" => (),
+                "AC" => record.accessions_mut().extend(split_list(text)),
+                "DT" => {
+                    if let Some(date) = text.split_whitespace().next() {
+                        record.locus_mut().date = date.to_owned();
+                    }
+                }
+                "DE" => append(&mut description, text),
+                "KW" => append(&mut keywords, text),
+                "OS" => {
+                    let source = text.trim();
+                    record.set_source(source);
+                    let organism = match source.find(" (") {
+                        Some(pos) => &source[..pos],
+                        None => source,
+                    };
+                    record.set_organism(organism);
+                }
+                "OC" => append(&mut taxonomy, text),
+                "RN" => {
+                    let number = text
+                        .trim()
+                        .trim_matches(|c| c == '[' || c == ']')
+                        .parse()
+                        .map_err(|_| self.inner.error("invalid RN line"))?;
+                    record.references_mut().push(Reference {
+                        number,
+                        ..Default::default()
+                    });
+                }
+                "RP" | "RX" | "RA" | "RG" | "RT" | "RL" => {
+                    let reference = match record.references_mut().last_mut() {
+                        Some(reference) => reference,
+                        None => return Err(self.inner.error("reference line before RN line")),
+                    };
+                    match code {
+                        "RP" => reference.ranges.extend(text.split(',').filter_map(|range| {
+                            let (start, end) = range.trim().split_once('-')?;
+                            Some((
+                                start.parse::<u64>().ok()?.checked_sub(1)?,
+                                end.parse().ok()?,
+                            ))
+                        })),
+                        "RX" => {
+                            if let Some(id) = text.strip_prefix("PUBMED;") {
+                                reference.pubmed = Some(id.trim().trim_end_matches('.').to_owned());
+                            }
+                        }
+                        "RA" => append(&mut reference.authors, text),
+                        "RG" => append(&mut reference.consortium, text),
+                        "RT" => append(&mut reference.title, text),
+                        _ => append(&mut reference.journal, text),
+                    }
+                }
+                "CC" => comment.push(text.to_owned()),
+                "FT" => features.push(line.chars().skip(PREFIX_WIDTH).collect::<String>()),
+                "SQ" => in_sequence = true,
+                _ => {
+                    let other = record.other_mut();
+                    match other.last_mut() {
+                        Some((key, value)) if *key == code && last_code == code => {
+                            value.push('\n');
+                            value.push_str(text);
+                        }
+                        _ => other.push((code.to_owned(), text.to_owned())),
+                    }
+                }
+            }
+            last_code = code.to_owned();
+        }
+
+        if let Some(sv) = sv {
+            if let Some(accession) = record.accessions().first() {
+                let version = format!("{}.{}", accession, sv);
+                record.set_version(Some(&version));
+            }
+        }
+        record.set_definition(&description);
+        *record.keywords_mut() = split_list(&keywords);
+        *record.taxonomy_mut() = split_list(&taxonomy);
+        for reference in record.references_mut() {
+            for field in [&mut reference.authors, &mut reference.title] {
+                *field = field.trim_end_matches(';').to_owned();
+            }
+            reference.title = reference.title.trim_matches('"').to_owned();
+            reference.journal = reference.journal.trim_end_matches('.').to_owned();
+        }
+        record.set_comment(&comment.join("\n"));
+        *record.features_mut() = parse_features(&features, &self.inner)?;
+        let length = record.locus().length;
+        record.set_seq(&seq);
+        if seq.is_empty() {
+            record.locus_mut().length = length;
+        }
+        Ok(Some(record))
+    }
+
+    /// Parse the ID line, returning the sequence version if given.
+    fn parse_id(&self, text: &str, locus: &mut Locus) -> Result<Option<String>> {
+        let fields: Vec<&str> = text.split(';').map(|field| field.trim()).collect();
+        locus.name = fields[0].to_owned();
+        let mut version = None;
+        if fields.len() >= 7 {
+            // current format: name; SV version; topology; molecule; class; division; length BP.
+            version = fields[1].strip_prefix("SV").map(|sv| sv.trim().to_owned());
+            locus.circular = fields[2] == "circular";
+            locus.molecule_type = fields[3].to_owned();
+            locus.division = fields[5].to_owned();
+        }
+        let length = fields
+            .last()
+            .and_then(|field| field.split_whitespace().next())
+            .and_then(|length| length.parse().ok());
+        match (length, fields.len()) {
+            (Some(length), 2..) => {
+                locus.length = length;
+                Ok(version)
+            }
+            _ => Err(self.inner.error("invalid ID line")),
+        }
+    }
+}
+
+/// An iterator over the records of an EMBL file.
+pub struct Records<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        self.reader.read().transpose()
+    }
+}
+
+/// An EMBL writer.
+///
+/// Other header lines of a record (see [`Record::other`]) are only written if their key is
+/// a two letter EMBL line type.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`compression::Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = compression::Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write lines of the given type, wrapping the text.
+    fn write_lines(&mut self, code: &str, text: &str) -> io::Result<()> {
+        for line in text.split('\n') {
+            for wrapped in wrap(line, LINE_WIDTH - PREFIX_WIDTH, false) {
+                writeln!(
+                    self.inner,
+                    "{:<width$}{}",
+                    code,
+                    wrapped,
+                    width = PREFIX_WIDTH
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn spacer(&mut self) -> io::Result<()> {
+        writeln!(self.inner, "XX")
+    }
+
+    /// Write a record.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let locus = record.locus();
+        let sv = record
+            .version()
+            .and_then(|version| version.rsplit_once('.'))
+            .map_or("1", |(_, sv)| sv);
+        writeln!(
+            self.inner,
+            "ID   {}; SV {}; {}; {}; STD; {}; {} BP.",
+            locus.name,
+            sv,
+            if locus.circular { "circular" } else { "linear" },
+            locus.molecule_type,
+            locus.division,
+            record.seq().len()
+        )?;
+        self.spacer()?;
+        let accessions: Vec<String> = record
+            .accessions()
+            .iter()
+            .map(|accession| format!("{};", accession))
+            .collect();
+        self.write_lines("AC", &accessions.join(" "))?;
+        self.spacer()?;
+        if !locus.date.is_empty() {
+            self.write_lines("DT", &locus.date)?;
+            self.spacer()?;
+        }
+        self.write_lines("DE", record.definition())?;
+        self.spacer()?;
+        self.write_lines("KW", &format!("{}.", record.keywords().join("; ")))?;
+        self.spacer()?;
+        self.write_lines("OS", record.source())?;
+        if !record.taxonomy().is_empty() {
+            self.write_lines("OC", &format!("{}.", record.taxonomy().join("; ")))?;
+        }
+        self.spacer()?;
+        for reference in record.references() {
+            self.write_lines("RN", &format!("[{}]", reference.number))?;
+            if !reference.ranges.is_empty() {
+                let ranges: Vec<String> = reference
+                    .ranges
+                    .iter()
+                    .map(|(start, end)| format!("{}-{}", start + 1, end))
+                    .collect();
+                self.write_lines("RP", &ranges.join(", "))?;
+            }
+            if let Some(pubmed) = &reference.pubmed {
+                self.write_lines("RX", &format!("PUBMED; {}.", pubmed))?;
+            }
+            if !reference.consortium.is_empty() {
+                self.write_lines("RG", &reference.consortium)?;
+            }
+            self.write_lines("RA", &format!("{};", reference.authors))?;
+            let title = if reference.title.is_empty() {
+                ";".to_owned()
+            } else {
+                format!("\"{}\";", reference.title)
+            };
+            self.write_lines("RT", &title)?;
+            self.write_lines("RL", &format!("{}.", reference.journal))?;
+            self.spacer()?;
+        }
+        for (code, text) in record.other() {
+            if code.len() == 2 {
+                self.write_lines(code, text)?;
+                self.spacer()?;
+            }
+        }
+        if !record.comment().is_empty() {
+            self.write_lines("CC", record.comment())?;
+            self.spacer()?;
+        }
+        writeln!(self.inner, "FH   Key             Location/Qualifiers")?;
+        writeln!(self.inner, "FH")?;
+        write_features(&mut self.inner, "FT   ", record.features())?;
+        self.spacer()?;
+
+        let seq = record.seq();
+        let count = |bases: &[u8]| {
+            seq.iter()
+                .filter(|c| bases.contains(&c.to_ascii_uppercase()))
+                .count()
+        };
+        let (a, c, g, t) = (count(b"A"), count(b"C"), count(b"G"), count(b"T"));
+        writeln!(
+            self.inner,
+            "SQ   Sequence {} BP; {} A; {} C; {} G; {} T; {} other;",
+            seq.len(),
+            a,
+            c,
+            g,
+            t,
+            seq.len() - a - c - g - t
+        )?;
+        for (i, line) in seq.chunks(60).enumerate() {
+            let groups: Vec<&str> = line
+                .chunks(10)
+                .map(|group| std::str::from_utf8(group).unwrap_or(""))
+                .collect();
+            writeln!(
+                self.inner,
+                "     {:<65}{:>10}",
+                groups.join(" "),
+                i * 60 + line.len()
+            )?;
+        }
+        writeln!(self.inner, "//")
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::genbank;
+
+    const EMBL: &[u8] = b"ID   X56734; SV 1; linear; mRNA; STD; PLN; 70 BP.
+XX
+AC   X56734; S46826;
+XX
+DT   12-SEP-1991 (Rel. 29, Created)
+DT   25-NOV-2005 (Rel. 85, Last updated, Version 11)
+XX
+DE   Trifolium repens mRNA for non-cyanogenic beta-glucosidase
+XX
+KW   beta-glucosidase.
+XX
+OS   Trifolium repens (white clover)
+OC   Eukaryota; Viridiplantae; Streptophyta; Embryophyta; Tracheophyta;
+OC   Spermatophyta; Magnoliophyta; eudicotyledons; Trifolium.
+XX
+RN   [5]
+RP   1-70
+RX   PUBMED; 1907511.
+RA   Oxtoby E., Dunn M.A., Pancoro A., Hughes M.A.;
+RT   \"Nucleotide and derived amino acid sequence of the cyanogenic
+RT   beta-glucosidase (linamarase) from white clover\";
+RL   Plant Mol. Biol. 17(2):209-219(1991).
+XX
+DR   MD5; 1e51ca3a5450c43524b9185c236cc5cc.
+XX
+FH   Key             Location/Qualifiers
+FH
+FT   source          1..70
+FT                   /organism=\"Trifolium repens\"
+FT                   /mol_type=\"mRNA\"
+FT   CDS             14..>70
+FT                   /product=\"beta-glucosidase\"
+FT                   /translation=\"MDFIVAIFALFVISSFTITSTNAVEASTLLDIGNLSRS
+FT                   SFPRGFIFG\"
+XX
+SQ   Sequence 70 BP; 22 A; 14 C; 8 G; 26 T; 0 other;
+     aaacaaacca aatatggatt ttattgtagc catatttgct ctgtttgtta ttagctcatt        60
+     cacaactcca                                                               70
+//
+";
+
+    fn read(data: &[u8]) -> Vec<Record> {
+        Reader::new(data).records().collect::<Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_read() {
+        let records = read(EMBL);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        let locus = record.locus();
+        assert_eq!(locus.name, "X56734");
+        assert_eq!(locus.length, 70);
+        assert_eq!(locus.molecule_type, "mRNA");
+        assert_eq!(locus.division, "PLN");
+        assert_eq!(locus.date, "25-NOV-2005");
+        assert!(!locus.circular);
+        assert_eq!(record.accessions(), &["X56734", "S46826"]);
+        assert_eq!(record.version(), Some("X56734.1"));
+        assert_eq!(
+            record.definition(),
+            "Trifolium repens mRNA for non-cyanogenic beta-glucosidase"
+        );
+        assert_eq!(record.keywords(), &["beta-glucosidase"]);
+        assert_eq!(record.source(), "Trifolium repens (white clover)");
+        assert_eq!(record.organism(), "Trifolium repens");
+        assert_eq!(record.taxonomy().len(), 9);
+        let reference = &record.references()[0];
+        assert_eq!(reference.number, 5);
+        assert_eq!(reference.ranges, vec![(0, 70)]);
+        assert_eq!(reference.pubmed.as_deref(), Some("1907511"));
+        assert_eq!(
+            reference.authors,
+            "Oxtoby E., Dunn M.A., Pancoro A., Hughes M.A."
+        );
+        assert_eq!(
+            reference.title,
+            "Nucleotide and derived amino acid sequence of the cyanogenic beta-glucosidase \
+             (linamarase) from white clover"
+        );
+        assert_eq!(reference.journal, "Plant Mol. Biol. 17(2):209-219(1991)");
+        assert_eq!(
+            record.other(),
+            &[(
+                "DR".to_owned(),
+                "MD5; 1e51ca3a5450c43524b9185c236cc5cc.".to_owned()
+            )]
+        );
+        let cds = &record.features()[1];
+        assert_eq!(cds.location.to_string(), "14..>70");
+        assert_eq!(
+            cds.qualifier("translation"),
+            Some("MDFIVAIFALFVISSFTITSTNAVEASTLLDIGNLSRSSFPRGFIFG")
+        );
+        assert_eq!(record.seq().len(), 70);
+        assert!(record.seq().ends_with(b"ttagctcattcacaactcca"));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let record = &read(EMBL)[0];
+        let mut writer = Writer::new(Vec::new());
+        writer.write(record).unwrap();
+        let written = writer.inner.into_inner().unwrap();
+        let text = String::from_utf8(written.clone()).unwrap();
+        assert!(text.contains("SQ   Sequence 70 BP; 22 A; 14 C; 8 G; 26 T; 0 other;\n"));
+        assert!(text.contains(
+            "     cacaactcca                                                               70\n"
+        ));
+        assert_eq!(&read(&written)[0], record);
+
+        // convert to GenBank and back
+        let mut gb = Vec::new();
+        {
+            let mut writer = genbank::Writer::new(&mut gb);
+            writer.write(record).unwrap();
+            writer.flush().unwrap();
+        }
+        let converted = genbank::Reader::new(&gb[..]).read().unwrap().unwrap();
+        assert_eq!(&converted, record);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Reader::new(&b"ID   X56734\n//\n"[..]).read().is_err());
+        assert!(Reader::new(&b"ID   X; 10 BP.\nRP   1-10\n//\n"[..])
+            .read()
+            .is_err());
+        assert!(Reader::new(&b"ID   X; 10 BP.\nSQ   Sequence 10 BP;\n"[..])
+            .read()
+            .is_err());
+    }
+}
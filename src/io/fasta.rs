@@ -51,6 +51,26 @@
 //! println!("Number of bases: {}", nb_bases);
 //! ```
 //!
+//! If the records do not need to be kept, [`Reader::ref_records`] avoids the allocations of
+//! [`Record`]s by yielding [`RefRecord`]s, which point into the buffer the file is read into.
+//! The lines of wrapped sequences are joined within this buffer. Since each record is only
+//! valid until the next one is read, [`RefRecords`] is not an `Iterator`, but provides a
+//! similar `next` method.
+//!
+//! ```no_run
+//! use bio::io::fasta;
+//! use std::io;
+//! let mut records = fasta::Reader::new(io::stdin()).ref_records();
+//!
+//! let mut nb_bases = 0;
+//! while let Some(result) = records.next() {
+//!     let record = result.expect("Error during fasta record parsing");
+//!     nb_bases += record.seq().len();
+//! }
+//!
+//! println!("Number of bases: {}", nb_bases);
+//! ```
+//!
 //! ## Write
 //!
 //! In this example we generate 10 random sequences with length 100 and write them to stdout.
@@ -104,8 +124,8 @@
 //!
 //! ## Index
 //!
-//! Random access to FASTA files is facilitated by [`Index`] and [`IndexedReader`]. The FASTA files
-//! must already be indexed with [`samtools faidx`](https://www.htslib.org/doc/faidx.html).
+//! Random access to FASTA files is facilitated by [`Index`] and [`IndexedReader`]. The index is
+//! usually created with [`samtools faidx`](https://www.htslib.org/doc/faidx.html).
 //!
 //! In this example, we read in the first 10 bases of the sequence named "chr1".
 //!
@@ -129,6 +149,65 @@
 //! faidx.read(&mut seq).expect("Couldn't read the interval");
 //! assert_eq!(seq, b"GTAGGCTGAA");
 //! ```
+//!
+//! An index can also be built from a FASTA file with [`Index::build`] and written to disk in the
+//! `.fai` format with [`Index::write`]:
+//!
+//! ```rust
+//! use bio::io::fasta::Index;
+//!
+//! let index = Index::build(&b">chr1\nGTAGGCTGAAAA\nCCCC\n"[..]).unwrap();
+//! let mut fai = Vec::new();
+//! index.write(&mut fai).unwrap();
+//! assert_eq!(fai, b"chr1\t16\t6\t12\t13\n");
+//! ```
+//!
+//! With the `gzip` feature, FASTA files compressed with `bgzip` can be accessed in the same way,
+//! given their `.fai` and `.gzi` indexes as created by `samtools faidx`
+//! (see [`IndexedReader::from_bgzf_file`]). Such files, together with both indexes, can be
+//! created with [`IndexedWriter`].
+//!
+//! ```rust
+//! # #[cfg(feature = "gzip")]
+//! # {
+//! use bio::io::{bgzf, fasta};
+//! use std::io::Cursor;
+//!
+//! let mut writer = fasta::IndexedWriter::new(bgzf::Writer::new(Vec::new()));
+//! writer.write("chr1", None, b"GTAGGCTGAAAACCCC").unwrap();
+//! let (bgzf_writer, index) = writer.into_inner().unwrap();
+//! let (compressed, gzi) = bgzf_writer.finish().unwrap();
+//!
+//! let reader = bgzf::Reader::with_index(Cursor::new(compressed), gzi);
+//! let mut faidx = fasta::IndexedReader::with_index(reader, index);
+//! faidx.fetch("chr1", 4, 10).unwrap();
+//! let mut seq = Vec::new();
+//! faidx.read(&mut seq).unwrap();
+//! assert_eq!(seq, b"GCTGAA");
+//! # }
+//! ```
+//!
+//! ## Asynchronous I/O
+//!
+//! With the `async` feature, `AsyncReader` and `AsyncWriter` read and write FASTA records
+//! from and to any tokio `AsyncRead` or `AsyncWrite`, e.g. a network socket. Records are
+//! parsed with the same validation as [`Reader`] and returned as a `Stream`.
+//!
+//! ```rust
+//! # #[cfg(feature = "async")]
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! use bio::io::fasta;
+//! use futures_util::StreamExt;
+//!
+//! let mut records = fasta::AsyncReader::new(&b">id1\nACGT\n>id2\nGGTA\n"[..]).records();
+//! let mut writer = fasta::AsyncWriter::new(Vec::new());
+//! while let Some(result) = records.next().await {
+//!     let record = result.expect("Error during fasta record parsing");
+//!     writer.write_record(&record).await.expect("Error writing record");
+//! }
+//! assert_eq!(writer.into_inner().await.unwrap(), b">id1\nACGT\n>id2\nGGTA\n");
+//! # });
+//! ```
 
 use std::cmp::min;
 use std::collections;
@@ -136,11 +215,40 @@ use std::convert::AsRef;
 use std::fs;
 use std::io;
 use std::io::prelude::*;
-use std::path::Path;
-
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "gzip")]
+use crate::io::bgzf;
+use crate::io::compression::{Decoder, Encoder, Format};
+#[cfg(feature = "rayon")]
+use crate::io::parallel::{self, ChunkFormat, ParRecords};
+use crate::io::record_buffer::{join_lines, next_line, split_header, RecordBuffer};
 use crate::utils::{Text, TextSlice};
 use anyhow::Context;
 use std::fmt;
+use thiserror::Error;
+
+#[cfg(feature = "async")]
+pub(crate) mod asynchronous;
+#[cfg(feature = "async")]
+pub use self::asynchronous::{AsyncReader, AsyncRecords, AsyncWriter};
+
+/// Errors that can occur while building a FASTA index.
+#[derive(Error, Debug)]
+pub enum IndexError {
+    #[error("can't read input")]
+    ReadError(#[from] io::Error),
+
+    #[error("expected '>' at record start (line {line})")]
+    MissingHeader { line: u64 },
+
+    #[error("inconsistent line width in sequence {name} (line {line}). All sequence lines except the last must have the same length.")]
+    InconsistentLineWidth { name: String, line: u64 },
+
+    #[error("duplicate sequence name {name}")]
+    DuplicateName { name: String },
+}
 
 /// Maximum size of temporary buffer used for reading indexed FASTA files.
 const MAX_FASTA_BUFFER_SIZE: usize = 512;
@@ -176,6 +284,28 @@ impl Reader<io::BufReader<fs::File>> {
     }
 }
 
+impl Reader<io::BufReader<Decoder<fs::File>>> {
+    /// Read FASTA from given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read fasta from {:#?}", path))
+    }
+
+    /// Read FASTA from give file path and a capacity
+    pub fn from_path_auto_with_capacity<P: AsRef<Path> + std::fmt::Debug>(
+        capacity: usize,
+        path: P,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(|file| Reader::with_capacity(capacity, file))
+            .with_context(|| format!("Failed to read fasta from {:#?}", path))
+    }
+}
+
 impl<R> Reader<io::BufReader<R>>
 where
     R: io::Read,
@@ -272,6 +402,61 @@ where
             error_has_occured: false,
         }
     }
+
+    /// Return a streaming iterator over the records of this Fasta file, which yields
+    /// records pointing into its buffer instead of allocating a new record each time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bio::io::fasta::Reader;
+    /// # const fasta_file: &'static [u8] = b">id desc
+    /// # AAAA
+    /// # ";
+    /// let mut records = Reader::new(fasta_file).ref_records();
+    /// while let Some(record) = records.next() {
+    ///     let record = record.unwrap();
+    ///     assert_eq!(record.id(), "id");
+    ///     assert_eq!(record.desc().unwrap(), "desc");
+    ///     assert_eq!(record.seq(), b"AAAA");
+    /// }
+    /// ```
+    pub fn ref_records(self) -> RefRecords<B> {
+        RefRecords {
+            buffer: RecordBuffer::new(self.reader, self.line.as_bytes()),
+            error_has_occured: false,
+        }
+    }
+
+    /// Return a parallel iterator over the records of this Fasta file, which are parsed in
+    /// chunks on the rayon thread pool (see [`parallel`](crate::io::parallel)).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bio::io::fasta::Reader;
+    /// use rayon::prelude::*;
+    /// # const fasta_file: &'static [u8] = b">id1\nACGT\n>id2\nAAAA\n";
+    /// let lengths = Reader::new(fasta_file)
+    ///     .par_records()
+    ///     .preserve_order()
+    ///     .map(|record| record.unwrap().seq().len())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(lengths, [4, 4]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_records(self) -> ParRecords<Record, io::Error>
+    where
+        B: Send + 'static,
+    {
+        ParRecords::new(self.into_raw(), ChunkFormat::Fasta, |chunk: &[u8]| {
+            parallel::collect_until_err(Reader::new(chunk).records())
+        })
+    }
+
+    /// Return the underlying reader, including a header line that has already been read.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn into_raw(self) -> io::Chain<io::Cursor<Vec<u8>>, B> {
+        io::Cursor::new(self.line.into_bytes()).chain(self.reader)
+    }
 }
 
 impl<B> FastaRead for Reader<B>
@@ -386,6 +571,112 @@ impl Index {
         Self::from_file(&fai_path)
     }
 
+    /// Build the index of a FASTA file given as `io::Read`, in the same way as
+    /// `samtools faidx`.
+    ///
+    /// # Errors
+    /// If the lines of a sequence have different lengths (except for the last line), or if
+    /// sequence names are not unique.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bio::io::fasta::{Index, IndexError};
+    ///
+    /// let fasta = b">chr1\nACGT\nACGT\nAC\n>chr2\nACGT\nAC\nACGT\n";
+    /// match Index::build(&fasta[..]) {
+    ///     Err(IndexError::InconsistentLineWidth { name, line }) => {
+    ///         assert_eq!(name, "chr2");
+    ///         assert_eq!(line, 8);
+    ///     }
+    ///     _ => panic!("expected an inconsistent line width"),
+    /// }
+    /// ```
+    pub fn build<R: io::Read>(fasta: R) -> Result<Self, IndexError> {
+        let mut reader = io::BufReader::new(fasta);
+        let mut index = Index::default();
+        let mut record: Option<(IndexRecord, LineLayout)> = None;
+        let mut line = Vec::new();
+        let mut offset = 0;
+        let mut line_number = 0;
+        loop {
+            line.clear();
+            let bytes = reader.read_until(b'\n', &mut line)? as u64;
+            if bytes == 0 {
+                break;
+            }
+            offset += bytes;
+            line_number += 1;
+
+            if line[0] == b'>' {
+                if let Some((record, layout)) = record.take() {
+                    index.push(layout.finish(record))?;
+                }
+                let header = String::from_utf8_lossy(&line[1..]);
+                let name = header.split_whitespace().next().unwrap_or_default();
+                record = Some((
+                    IndexRecord {
+                        name: name.to_owned(),
+                        len: 0,
+                        offset,
+                        line_bases: 0,
+                        line_bytes: 0,
+                    },
+                    LineLayout::default(),
+                ));
+            } else {
+                let bases = line.trim_ascii_end().len() as u64;
+                match record.as_mut() {
+                    Some((record, layout)) => {
+                        if !layout.push(bases, bytes) {
+                            return Err(IndexError::InconsistentLineWidth {
+                                name: record.name.clone(),
+                                line: line_number,
+                            });
+                        }
+                    }
+                    None if bases == 0 => (),
+                    None => return Err(IndexError::MissingHeader { line: line_number }),
+                }
+            }
+        }
+        if let Some((record, layout)) = record {
+            index.push(layout.finish(record))?;
+        }
+        Ok(index)
+    }
+
+    /// Build the index of the FASTA file at the given path.
+    pub fn build_from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        fs::File::open(path)
+            .map_err(IndexError::from)
+            .and_then(Self::build)
+            .with_context(|| format!("Failed to index fasta {:#?}", path))
+    }
+
+    /// Add the index record of a sequence.
+    fn push(&mut self, record: IndexRecord) -> Result<(), IndexError> {
+        if self.name_to_rid.contains_key(&record.name) {
+            return Err(IndexError::DuplicateName { name: record.name });
+        }
+        self.name_to_rid
+            .insert(record.name.clone(), self.inner.len());
+        self.inner.push(record);
+        Ok(())
+    }
+
+    /// Write the index in the tab-separated 5-column `.fai` format of `samtools faidx`.
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        for record in &self.inner {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                record.name, record.len, record.offset, record.line_bases, record.line_bytes
+            )?;
+        }
+        writer.flush()
+    }
+
     /// Return a vector of sequences described in the index.
     pub fn sequences(&self) -> Vec<Sequence> {
         // sort kv pairs by rid to preserve order
@@ -421,6 +712,17 @@ impl IndexedReader<fs::File> {
     }
 }
 
+#[cfg(feature = "gzip")]
+impl IndexedReader<bgzf::Reader<fs::File>> {
+    /// Read from a given path to a FASTA file compressed with `bgzip`. This assumes
+    /// the indexes ref.fasta.gz.fai and ref.fasta.gz.gzi to be present for
+    /// ref.fasta.gz, as created by `samtools faidx`.
+    pub fn from_bgzf_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        let index = Index::with_fasta_file(path)?;
+        bgzf::Reader::from_file(path).map(|reader| Self::with_index(reader, index))
+    }
+}
+
 impl<R: io::Read + io::Seek> IndexedReader<R> {
     /// Read from a FASTA and its index, both given as `io::Read`. FASTA has to
     /// be `io::Seek` in addition.
@@ -654,6 +956,11 @@ impl<R: io::Read + io::Seek> IndexedReader<R> {
     /// of the cursor on the line that the seek ended on is returned.
     fn seek_to(&mut self, idx: &IndexRecord, start: u64) -> io::Result<u64> {
         assert!(start <= idx.len);
+        if idx.line_bases == 0 {
+            // empty sequence
+            self.reader.seek(io::SeekFrom::Start(idx.offset))?;
+            return Ok(0);
+        }
 
         let line_offset = start % idx.line_bases;
         let line_start = start / idx.line_bases * idx.line_bytes;
@@ -711,6 +1018,60 @@ impl<R: io::Read + io::Seek> IndexedReader<R> {
     }
 }
 
+/// The line layout of a sequence, as tracked while building an index.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct LineLayout {
+    len: u64,
+    line_bases: u64,
+    line_bytes: u64,
+    /// Whether a line shorter than the others has been seen, which must be the last one.
+    last_line: bool,
+}
+
+impl LineLayout {
+    /// Add a line with the given number of bases and bytes (including the line terminator).
+    /// Returns false if the line is inconsistent with the previous ones.
+    pub(crate) fn push(&mut self, bases: u64, bytes: u64) -> bool {
+        if bases == 0 {
+            // blank lines may only follow the sequence
+            self.last_line = true;
+            return true;
+        }
+        if self.last_line || (self.len > 0 && bases > self.line_bases) {
+            return false;
+        }
+        if self.len == 0 {
+            self.line_bases = bases;
+            self.line_bytes = bytes;
+        } else if bases < self.line_bases || bytes != self.line_bytes {
+            self.last_line = true;
+        }
+        self.len += bases;
+        true
+    }
+
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub(crate) fn line_bases(&self) -> u64 {
+        self.line_bases
+    }
+
+    pub(crate) fn line_bytes(&self) -> u64 {
+        self.line_bytes
+    }
+
+    fn finish(self, record: IndexRecord) -> IndexRecord {
+        IndexRecord {
+            len: self.len,
+            line_bases: self.line_bases,
+            line_bytes: self.line_bytes,
+            ..record
+        }
+    }
+}
+
 /// Record of a FASTA index.
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 struct IndexRecord {
@@ -810,6 +1171,29 @@ impl Writer<fs::File> {
     }
 }
 
+impl Writer<Encoder<fs::File>> {
+    /// Write to the given file path. The output is compressed according to the
+    /// file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+
+    /// Write to the given file path and a buffer capacity
+    pub fn to_path_auto_with_capacity<P: AsRef<Path>>(
+        capacity: usize,
+        path: P,
+    ) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(|file| Writer::with_capacity(capacity, file))
+    }
+}
+
 impl<W: io::Write> Writer<W> {
     /// Create a new Fasta writer.
     pub fn new(writer: W) -> Self {
@@ -938,6 +1322,159 @@ impl<W: io::Write> Writer<W> {
     }
 }
 
+/// A FASTA writer that builds the `.fai` index of the written records.
+///
+/// Sequences are wrapped at a fixed line width (60 by default), as required for indexing.
+///
+/// Writers created with [`to_file`](IndexedWriter::to_file) or `to_bgzf_file` write their
+/// indexes only when calling `finish`, which reports any errors. If such a writer is dropped
+/// instead, no index is written.
+#[derive(Debug)]
+pub struct IndexedWriter<W: io::Write> {
+    writer: io::BufWriter<W>,
+    linewrap: usize,
+    offset: u64,
+    index: Index,
+    path: Option<PathBuf>,
+}
+
+impl IndexedWriter<fs::File> {
+    /// Write to the given file path. The index is written to path.fai by
+    /// [`IndexedWriter::finish`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut writer = fs::File::create(&path).map(IndexedWriter::new)?;
+        writer.path = Some(path.as_ref().to_owned());
+        Ok(writer)
+    }
+
+    /// Flush the FASTA file and write its index.
+    pub fn finish(mut self) -> io::Result<()> {
+        let path = self.path.take();
+        let (_, index) = self.into_inner()?;
+        if let Some(path) = path {
+            write_index(&index, &path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl IndexedWriter<bgzf::Writer<fs::File>> {
+    /// Write a BGZF compressed FASTA to the given file path. The indexes are written
+    /// to path.fai and path.gzi by [`IndexedWriter::finish`], such that the file can
+    /// be read with [`IndexedReader::from_bgzf_file`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_bgzf_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut writer = bgzf::Writer::to_file(&path).map(IndexedWriter::new)?;
+        writer.path = Some(path.as_ref().to_owned());
+        Ok(writer)
+    }
+
+    /// Finish the BGZF FASTA file and write its `.fai` and `.gzi` indexes.
+    pub fn finish(mut self) -> io::Result<()> {
+        let path = self.path.take();
+        let (writer, index) = self.into_inner()?;
+        let (_, gzi) = writer.finish()?;
+        if let Some(path) = path {
+            write_index(&index, &path)?;
+            let mut gzi_path = path.into_os_string();
+            gzi_path.push(".gzi");
+            let mut gzi_writer = io::BufWriter::new(fs::File::create(gzi_path)?);
+            gzi.write(&mut gzi_writer)?;
+            gzi_writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the index of the FASTA file at `path` to path.fai.
+fn write_index(index: &Index, path: &Path) -> io::Result<()> {
+    let mut fai_path = path.as_os_str().to_owned();
+    fai_path.push(".fai");
+    index.write(fs::File::create(fai_path)?)
+}
+
+impl<W: io::Write> IndexedWriter<W> {
+    /// Create a new indexed FASTA writer.
+    pub fn new(writer: W) -> Self {
+        IndexedWriter {
+            writer: io::BufWriter::new(writer),
+            linewrap: 60,
+            offset: 0,
+            index: Index::default(),
+            path: None,
+        }
+    }
+
+    /// Set the line width of the written sequences.
+    ///
+    /// # Panics
+    /// If `linewrap` is zero.
+    pub fn set_linewrap(&mut self, linewrap: usize) {
+        assert!(linewrap > 0, "line width must be positive");
+        self.linewrap = linewrap;
+    }
+
+    /// Directly write a [`fasta::Record`](struct.Record.html).
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.write(record.id(), record.desc(), record.seq())
+    }
+
+    /// Write a Fasta record with given id, optional description and sequence.
+    ///
+    /// # Errors
+    /// If there is an issue writing to the underlying writer, or if a sequence with
+    /// the same id has already been written.
+    pub fn write(&mut self, id: &str, desc: Option<&str>, seq: TextSlice<'_>) -> io::Result<()> {
+        let header_len = 2 + id.len() + desc.map_or(0, |desc| desc.len() + 1);
+        let line_bases = min(seq.len(), self.linewrap) as u64;
+        self.index
+            .push(IndexRecord {
+                name: id.to_owned(),
+                len: seq.len() as u64,
+                offset: self.offset + header_len as u64,
+                line_bases,
+                line_bytes: if line_bases > 0 { line_bases + 1 } else { 0 },
+            })
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let writer = &mut self.writer;
+        writer.write_all(b">")?;
+        writer.write_all(id.as_bytes())?;
+        if let Some(desc) = desc {
+            writer.write_all(b" ")?;
+            writer.write_all(desc.as_bytes())?;
+        }
+        writer.write_all(b"\n")?;
+        for line in seq.chunks(self.linewrap) {
+            writer.write_all(line)?;
+            writer.write_all(b"\n")?;
+        }
+        let seq_lines = seq.len().div_ceil(self.linewrap);
+        self.offset += (header_len + seq.len() + seq_lines) as u64;
+
+        Ok(())
+    }
+
+    /// Return the index of the records written so far.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush the writer and return the underlying writer and the index.
+    /// No index file is written, even if the writer was created for a file path.
+    pub fn into_inner(self) -> io::Result<(W, Index)> {
+        let writer = self.writer.into_inner().map_err(|e| e.into_error())?;
+        Ok((writer, self.index))
+    }
+}
+
 /// A FASTA record.
 #[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub struct Record {
@@ -1074,6 +1611,16 @@ where
     error_has_occured: bool,
 }
 
+#[cfg(feature = "rayon")]
+impl<B> Records<B>
+where
+    B: io::BufRead,
+{
+    pub(crate) fn into_reader(self) -> Reader<B> {
+        self.reader
+    }
+}
+
 impl<B> Iterator for Records<B>
 where
     B: io::BufRead,
@@ -1097,6 +1644,145 @@ where
     }
 }
 
+/// A streaming iterator over the records of a Fasta file, yielding [`RefRecord`]s that
+/// point into its buffer. A record is only valid until `next` is called again.
+///
+/// After an error, no more records are returned.
+#[derive(Debug)]
+pub struct RefRecords<B> {
+    buffer: RecordBuffer<B>,
+    error_has_occured: bool,
+}
+
+impl<B> RefRecords<B>
+where
+    B: io::BufRead,
+{
+    /// Read the next record, returning `None` at the end of the file.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<io::Result<RefRecord<'_>>> {
+        if self.error_has_occured {
+            return None;
+        }
+        let (header, seq) = match self.read_next() {
+            Ok(Some(ranges)) => ranges,
+            Ok(None) => return None,
+            Err(err) => {
+                self.error_has_occured = true;
+                return Some(Err(err));
+            }
+        };
+        let seq = self.buffer.get(seq);
+        // like the owned records, header and sequence have to be valid UTF-8
+        match std::str::from_utf8(self.buffer.get(header))
+            .and_then(|header| std::str::from_utf8(seq).map(|_| header))
+        {
+            Ok(header) => {
+                let (id, desc) = split_header(header);
+                Some(Ok(RefRecord { id, desc, seq }))
+            }
+            Err(err) => {
+                self.error_has_occured = true;
+                Some(Err(io::Error::new(io::ErrorKind::InvalidData, err)))
+            }
+        }
+    }
+
+    /// Locate the next record in the buffer and join its sequence lines, returning the ranges
+    /// of its header and sequence, or `None` at the end of the file.
+    fn read_next(&mut self) -> io::Result<Option<(Range<usize>, Range<usize>)>> {
+        loop {
+            let eof = self.buffer.is_eof();
+            let data = self.buffer.data();
+            if data.is_empty() {
+                if eof {
+                    return Ok(None);
+                }
+                self.buffer.fill()?;
+                continue;
+            }
+            if data[0] != b'>' {
+                return Err(io::Error::other("Expected > at record start."));
+            }
+            // the record ends with the line before the next header
+            let mut end = None;
+            let mut from = 0;
+            while let Some((_, next)) = next_line(data, from, false) {
+                if data.get(next) == Some(&b'>') {
+                    end = Some(next);
+                    break;
+                }
+                from = next;
+            }
+            let end = match end {
+                Some(end) => end,
+                None if eof => data.len(),
+                None => {
+                    self.buffer.fill()?;
+                    continue;
+                }
+            };
+
+            let (header_end, seq_start) = next_line(data, 0, true).unwrap_or((end, end));
+            let header_end = 1 + data[1..header_end].trim_ascii_end().len();
+            let seq_end = join_lines(self.buffer.data_mut(), seq_start..end);
+            let offset = self.buffer.consume(end);
+            return Ok(Some((
+                offset + 1..offset + header_end,
+                offset + seq_start..offset + seq_end,
+            )));
+        }
+    }
+}
+
+/// A FASTA record borrowing its data from the buffers of a [`RefRecords`] iterator.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct RefRecord<'a> {
+    id: &'a str,
+    desc: Option<&'a str>,
+    seq: &'a [u8],
+}
+
+impl<'a> RefRecord<'a> {
+    /// Check if record is empty.
+    pub fn is_empty(&self) -> bool {
+        self.id.is_empty() && self.desc.is_none() && self.seq.is_empty()
+    }
+
+    /// Check validity of Fasta record.
+    pub fn check(&self) -> Result<(), &str> {
+        if self.id.is_empty() {
+            return Err("Expecting id for Fasta record.");
+        }
+        if !self.seq.is_ascii() {
+            return Err("Non-ascii character found in sequence.");
+        }
+
+        Ok(())
+    }
+
+    /// Return the id of the record.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// Return descriptions if present.
+    pub fn desc(&self) -> Option<&'a str> {
+        self.desc
+    }
+
+    /// Return the sequence of the record.
+    pub fn seq(&self) -> TextSlice<'a> {
+        self.seq
+    }
+
+    /// Copy the data into an owned [`Record`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_owned(&self) -> Record {
+        Record::with_attrs(self.id, self.desc, self.seq)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1835,6 +2521,41 @@ TTTA
         assert!(Writer::to_file_with_capacity(100, path).is_ok());
     }
 
+    #[test]
+    fn test_to_file_ignores_extension() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("test.fa.gz");
+        {
+            let mut writer = Writer::to_file(&path).unwrap();
+            writer.write("id", None, b"ACGT").unwrap();
+        }
+        assert_eq!(fs::read(&path).unwrap(), b">id\nACGT\n");
+        let mut records = Reader::from_file(&path).unwrap().records();
+        assert_eq!(records.next().unwrap().unwrap().seq(), b"ACGT");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_compressed_file_roundtrip() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("test.fa.gz");
+        {
+            let mut writer = Writer::to_path_auto(&path).unwrap();
+            writer.write("id", Some("desc"), b"ACCGTAGGCTGA").unwrap();
+            writer.write("id2", None, b"ATTGTTGTTTTA").unwrap();
+        }
+        assert_eq!(&fs::read(&path).unwrap()[..2], b"\x1f\x8b");
+
+        let records: Vec<Record> = Reader::from_path_auto(&path)
+            .unwrap()
+            .records()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].desc(), Some("desc"));
+        assert_eq!(records[1].seq(), b"ATTGTTGTTTTA");
+    }
+
     #[test]
     fn test_write_record() {
         let path = Path::new("test.fa");
@@ -1882,4 +2603,229 @@ TTTA
         writer.flush().unwrap();
         assert_eq!(writer.writer.get_ref(), &WRITE_FASTA_FILE_WIDTH);
     }
+
+    #[test]
+    fn test_indexed_writer() {
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.set_linewrap(12);
+        let reader = Reader::new(FASTA_FILE);
+        for record in reader.records() {
+            writer.write_record(&record.unwrap()).unwrap();
+        }
+        assert!(writer.write("id2", None, b"ACGT").is_err());
+        let (fasta, index) = writer.into_inner().unwrap();
+
+        assert_eq!(fasta, FASTA_FILE);
+        assert_eq!(index, Index::new(FAI_FILE).unwrap());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_indexed_reader_bgzf() {
+        // end a block after each line to spread the sequences over multiple blocks
+        let mut writer = bgzf::Writer::new(Vec::new());
+        for line in FASTA_FILE.split_inclusive(|&c| c == b'\n') {
+            writer.write_all(line).unwrap();
+            writer.write_block().unwrap();
+        }
+        let (compressed, gzi) = writer.finish().unwrap();
+        let bgzf_reader = bgzf::Reader::with_index(io::Cursor::new(compressed), gzi);
+        let mut reader = IndexedReader::new(bgzf_reader, FAI_FILE).unwrap();
+
+        let mut seq = Vec::new();
+        reader.fetch("id2", 10, 30).unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"TAATTGTTGTTTTAATTGTT");
+        reader.fetch("id", 1, 15).unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"CCGTAGGCTGACCG");
+        reader.fetch_all("id2").unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"ATTGTTGTTTTAATTGTTGTTTTAATTGTTGTTTTAGGGG");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_indexed_writer_bgzf_file() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("ref.fa.gz");
+        let mut writer = IndexedWriter::to_bgzf_file(&path).unwrap();
+        writer
+            .write("chr1", Some("desc"), b"GTAGGCTGAAAACCCC")
+            .unwrap();
+        writer.write("chr2", None, b"").unwrap();
+        writer.write("chr3", None, b"ACGT").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(
+            fs::read(dir.path().join("ref.fa.gz.fai")).unwrap(),
+            b"chr1\t16\t11\t16\t17\nchr2\t0\t34\t0\t0\nchr3\t4\t40\t4\t5\n"
+        );
+        assert!(dir.path().join("ref.fa.gz.gzi").exists());
+
+        let mut reader = IndexedReader::from_bgzf_file(&path).unwrap();
+        let mut seq = Vec::new();
+        reader.fetch("chr1", 4, 10).unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"GCTGAA");
+        reader.fetch_all("chr2").unwrap();
+        reader.read(&mut seq).unwrap();
+        assert!(seq.is_empty());
+        reader.fetch_all("chr3").unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"ACGT");
+
+        let mut records = Reader::from_path_auto(&path).unwrap().records();
+        assert_eq!(records.next().unwrap().unwrap().id(), "chr1");
+    }
+
+    #[test]
+    fn test_indexed_writer_file() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("ref.fa");
+        let mut writer = IndexedWriter::to_file(&path).unwrap();
+        writer.set_linewrap(4);
+        writer.write("chr1", None, b"GTAGGCTGAA").unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b">chr1\nGTAG\nGCTG\nAA\n");
+        let mut reader = IndexedReader::from_file(&path).unwrap();
+        let mut seq = Vec::new();
+        reader.fetch("chr1", 3, 9).unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"GGCTGA");
+
+        // the index is only written by finish
+        let path = dir.path().join("dropped.fa");
+        let mut writer = IndexedWriter::to_file(&path).unwrap();
+        writer.write("chr1", None, b"GTAGGCTGAA").unwrap();
+        drop(writer);
+        assert!(!dir.path().join("dropped.fa.fai").exists());
+    }
+
+    #[test]
+    fn test_index_build() {
+        for (fasta, fai) in [
+            (FASTA_FILE, FAI_FILE),
+            (FASTA_FILE_CRLF, FAI_FILE_CRLF),
+            (FASTA_FILE_NO_TRAILING_LF, FAI_FILE_NO_TRAILING_LF),
+        ] {
+            let index = Index::build(fasta).unwrap();
+            assert_eq!(index, Index::new(fai).unwrap());
+        }
+
+        let index = Index::build(&b">empty\n>blank\nACGT\n\n>id\nAC\n"[..]).unwrap();
+        let mut fai = Vec::new();
+        index.write(&mut fai).unwrap();
+        assert_eq!(
+            fai,
+            b"empty\t0\t7\t0\t0\nblank\t4\t14\t4\t5\nid\t2\t24\t2\t3\n"
+        );
+    }
+
+    #[test]
+    fn test_index_build_errors() {
+        let err = Index::build(&b">id\nACGT\nACGTA\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::InconsistentLineWidth { name, line: 3 } if name == "id"));
+        let err = Index::build(&b">id\nACGT\n\nACGT\n"[..]).unwrap_err();
+        assert!(matches!(
+            err,
+            IndexError::InconsistentLineWidth { line: 4, .. }
+        ));
+        let err = Index::build(&b">id\nACGT\n>id desc\nACGT\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::DuplicateName { name } if name == "id"));
+        let err = Index::build(&b"\nACGT\n>id\nACGT\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::MissingHeader { line: 2 }));
+    }
+
+    #[test]
+    fn test_index_build_from_file() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("ref.fa");
+        fs::write(&path, FASTA_FILE).unwrap();
+        let index = Index::build_from_file(&path).unwrap();
+        index
+            .write(fs::File::create(dir.path().join("ref.fa.fai")).unwrap())
+            .unwrap();
+
+        let mut reader = IndexedReader::from_file(&path).unwrap();
+        let mut seq = Vec::new();
+        reader.fetch("id2", 10, 14).unwrap();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"TAAT");
+    }
+
+    #[test]
+    fn test_ref_records() {
+        for fasta in [FASTA_FILE, FASTA_FILE_CRLF, FASTA_FILE_NO_TRAILING_LF] {
+            let expected = Reader::new(fasta)
+                .records()
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            let mut records = Reader::new(fasta).ref_records();
+            let mut i = 0;
+            while let Some(record) = records.next() {
+                let record = record.unwrap();
+                assert_eq!(record.check(), Ok(()));
+                assert_eq!(record.id(), expected[i].id());
+                assert_eq!(record.desc(), expected[i].desc());
+                assert_eq!(record.seq(), expected[i].seq());
+                assert_eq!(record.to_owned(), expected[i]);
+                i += 1;
+            }
+            assert_eq!(i, expected.len());
+        }
+    }
+
+    #[test]
+    fn test_ref_records_large() {
+        // records larger than the initial buffer, and many records crossing its boundaries
+        let mut fasta = Vec::new();
+        for i in 0..2000 {
+            let len = if i % 500 == 0 { 100_000 } else { 150 };
+            writeln!(fasta, ">id{}\tdesc {}", i, i).unwrap();
+            for line in vec![b'A' + (i % 4) as u8; len].chunks(60) {
+                fasta.extend_from_slice(line);
+                fasta.push(b'\n');
+            }
+        }
+        let expected = Reader::new(&fasta[..])
+            .records()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        let mut records = Reader::new(&fasta[..]).ref_records();
+        let mut i = 0;
+        while let Some(record) = records.next() {
+            assert_eq!(record.unwrap().to_owned(), expected[i]);
+            i += 1;
+        }
+        assert_eq!(i, 2000);
+        assert_eq!(expected[0].id(), "id0");
+        assert_eq!(expected[0].desc(), Some("desc 0"));
+    }
+
+    #[test]
+    fn test_ref_records_after_read() {
+        let mut reader = Reader::new(FASTA_FILE);
+        let mut record = Record::new();
+        reader.read(&mut record).unwrap();
+        assert_eq!(record.id(), "id");
+
+        let mut records = reader.ref_records();
+        assert_eq!(records.next().unwrap().unwrap().id(), "id2");
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn test_ref_records_errors() {
+        let mut records = Reader::new(&b"ACGT\n>id\nACGT\n"[..]).ref_records();
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+
+        let mut records = Reader::new(&b">id\nAC\xffGT\n"[..]).ref_records();
+        assert_eq!(
+            records.next().unwrap().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
 }
@@ -0,0 +1,398 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Non-blocking FASTA reading and writing on top of tokio's `AsyncRead` and `AsyncWrite`.
+
+use std::future::poll_fn;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
+
+use super::Record;
+use crate::utils::TextSlice;
+
+/// Append the next line of the reader to `line`, including the trailing newline.
+///
+/// Bytes are moved into `line` as soon as they are available, so that polling again after
+/// `Poll::Pending` continues the same line. Nothing is appended at the end of the input.
+pub(crate) fn poll_read_line<B>(
+    reader: &mut B,
+    cx: &mut Context<'_>,
+    line: &mut Vec<u8>,
+) -> Poll<io::Result<()>>
+where
+    B: AsyncBufRead + Unpin,
+{
+    loop {
+        let buf = ready!(Pin::new(&mut *reader).poll_fill_buf(cx))?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(()));
+        }
+        let (len, done) = match buf.iter().position(|&c| c == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (buf.len(), false),
+        };
+        line.extend_from_slice(&buf[..len]);
+        Pin::new(&mut *reader).consume(len);
+        if done {
+            return Poll::Ready(Ok(()));
+        }
+    }
+}
+
+/// Interpret a line as UTF-8, like `BufRead::read_line`.
+pub(crate) fn line_str(line: &[u8]) -> io::Result<&str> {
+    std::str::from_utf8(line).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })
+}
+
+/// An asynchronous FASTA reader, the non-blocking counterpart of [`Reader`](super::Reader).
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fasta::AsyncReader;
+/// use futures_util::StreamExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let fasta: &[u8] = b">id1 desc\nACGT\nACGT\n>id2\nAAAA\n";
+/// let mut records = AsyncReader::new(fasta).records();
+/// let record = records.next().await.unwrap().unwrap();
+/// assert_eq!(record.id(), "id1");
+/// assert_eq!(record.seq(), b"ACGTACGT");
+/// assert_eq!(records.next().await.unwrap().unwrap().id(), "id2");
+/// assert!(records.next().await.is_none());
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncReader<B> {
+    reader: B,
+    line: Vec<u8>,
+    line_complete: bool,
+    record: Record,
+    in_record: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncReader<BufReader<R>> {
+    /// Create a new FASTA reader from a given `AsyncRead`, e.g. a `tokio::fs::File` or a
+    /// network stream.
+    pub fn new(reader: R) -> Self {
+        Self::from_bufread(BufReader::new(reader))
+    }
+
+    /// Create a new FASTA reader with the given capacity of the read buffer.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self::from_bufread(BufReader::with_capacity(capacity, reader))
+    }
+}
+
+impl<B: AsyncBufRead + Unpin> AsyncReader<B> {
+    /// Create a new FASTA reader from a given `AsyncBufRead`.
+    pub fn from_bufread(bufreader: B) -> Self {
+        AsyncReader {
+            reader: bufreader,
+            line: Vec::new(),
+            line_complete: false,
+            record: Record::new(),
+            in_record: false,
+        }
+    }
+
+    /// Read the next FASTA record into the given `Record`.
+    /// An empty record indicates that no more records can be read.
+    ///
+    /// # Errors
+    ///
+    /// Like [`FastaRead::read`](super::FastaRead::read), this function will return an error
+    /// if a record does not start with `>` or any form of I/O error is encountered.
+    pub async fn read(&mut self, record: &mut Record) -> io::Result<()> {
+        match poll_fn(|cx| self.poll_record(cx)).await? {
+            Some(next) => *record = next,
+            None => record.clear(),
+        }
+        Ok(())
+    }
+
+    /// Return a `Stream` over the records of this FASTA file, which ends after the first
+    /// error.
+    pub fn records(self) -> AsyncRecords<B> {
+        AsyncRecords {
+            reader: self,
+            error_has_occured: false,
+        }
+    }
+
+    /// Poll the next line into the line buffer, unless the current one is not consumed yet.
+    fn poll_line(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.line_complete {
+            ready!(poll_read_line(&mut self.reader, cx, &mut self.line))?;
+            self.line_complete = true;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn consume_line(&mut self) {
+        self.line.clear();
+        self.line_complete = false;
+    }
+
+    fn poll_record(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Option<Record>>> {
+        loop {
+            ready!(self.poll_line(cx))?;
+            let line = line_str(&self.line)?;
+            if !self.in_record {
+                if line.is_empty() {
+                    return Poll::Ready(Ok(None));
+                }
+                if !line.starts_with('>') {
+                    return Poll::Ready(Err(io::Error::other("Expected > at record start.")));
+                }
+                let mut header_fields = line[1..].trim_end().splitn(2, char::is_whitespace);
+                self.record.id = header_fields.next().map(|s| s.to_owned()).unwrap();
+                self.record.desc = header_fields.next().map(|s| s.to_owned());
+                self.in_record = true;
+            } else if line.is_empty() || line.starts_with('>') {
+                // keep the header of the next record in the line buffer
+                self.in_record = false;
+                return Poll::Ready(Ok(Some(mem::take(&mut self.record))));
+            } else {
+                self.record.seq.push_str(line.trim_end());
+            }
+            self.consume_line();
+        }
+    }
+}
+
+/// A `Stream` over the records of a FASTA file.
+#[derive(Debug)]
+pub struct AsyncRecords<B> {
+    reader: AsyncReader<B>,
+    error_has_occured: bool,
+}
+
+impl<B: AsyncBufRead + Unpin> Stream for AsyncRecords<B> {
+    type Item = io::Result<Record>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.error_has_occured {
+            return Poll::Ready(None);
+        }
+        match ready!(this.reader.poll_record(cx)) {
+            Ok(record) => Poll::Ready(record.map(Ok)),
+            Err(err) => {
+                this.error_has_occured = true;
+                Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+}
+
+/// An asynchronous FASTA writer, the non-blocking counterpart of [`Writer`](super::Writer).
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fasta::{AsyncWriter, Record};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncWriter::new(Vec::new());
+/// writer.set_linewrap(Some(4));
+/// let record = Record::with_attrs("id", Some("desc"), b"ACGTACGT");
+/// writer.write_record(&record).await.unwrap();
+/// let fasta = writer.into_inner().await.unwrap();
+/// assert_eq!(fasta, b">id desc\nACGT\nACGT\n");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncWriter<W: AsyncWrite + Unpin> {
+    writer: BufWriter<W>,
+    linewrap: Option<usize>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWriter<W> {
+    /// Create a new FASTA writer.
+    pub fn new(writer: W) -> Self {
+        Self::from_bufwriter(BufWriter::new(writer))
+    }
+
+    /// Create a new FASTA writer with the given capacity of the write buffer.
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self::from_bufwriter(BufWriter::with_capacity(capacity, writer))
+    }
+
+    /// Create a new FASTA writer with a given `BufWriter`.
+    pub fn from_bufwriter(bufwriter: BufWriter<W>) -> Self {
+        AsyncWriter {
+            writer: bufwriter,
+            linewrap: None,
+        }
+    }
+
+    /// Set the line width of sequences, or `None` to write each sequence on a single line.
+    pub fn set_linewrap(&mut self, linewrap: Option<usize>) {
+        self.linewrap = linewrap
+    }
+
+    /// Write a [`Record`].
+    pub async fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.write(record.id(), record.desc(), record.seq()).await
+    }
+
+    /// Write a FASTA record with given id, optional description and sequence.
+    pub async fn write(
+        &mut self,
+        id: &str,
+        desc: Option<&str>,
+        seq: TextSlice<'_>,
+    ) -> io::Result<()> {
+        self.writer.write_all(b">").await?;
+        self.writer.write_all(id.as_bytes()).await?;
+        if let Some(desc) = desc {
+            self.writer.write_all(b" ").await?;
+            self.writer.write_all(desc.as_bytes()).await?;
+        }
+        self.writer.write_all(b"\n").await?;
+        match self.linewrap {
+            None => {
+                self.writer.write_all(seq).await?;
+                self.writer.write_all(b"\n").await?;
+            }
+            Some(linewrap) => {
+                for chunk in seq.chunks(linewrap) {
+                    self.writer.write_all(chunk).await?;
+                    self.writer.write_all(b"\n").await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush().await
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub async fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush().await?;
+        Ok(self.writer.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::fasta::{FastaRead, Reader};
+    use futures_util::StreamExt;
+
+    const FASTA_FILE: &[u8] = b">id desc
+ACCGTAGGCTGA
+CCGTAGGCTGAA
+CGTAGGCTGAAA
+GTAGGCTGAAAA
+CCCC
+>id2
+ATTGTTGTTTTA
+ATTGTTGTTTTA
+ATTGTTGTTTTA
+GGGG
+";
+
+    /// A reader that returns its data in small pieces, each preceded by `Poll::Pending`.
+    struct Trickle {
+        data: &'static [u8],
+        pending: bool,
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let len = self.data.len().min(5).min(buf.remaining());
+            buf.put_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_match_sync_reader() {
+        let expected: Vec<Record> = Reader::new(FASTA_FILE)
+            .records()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let records: Vec<Record> = AsyncReader::new(FASTA_FILE)
+            .records()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(records, expected);
+
+        let trickle = Trickle {
+            data: FASTA_FILE,
+            pending: false,
+        };
+        let records: Vec<Record> = AsyncReader::with_capacity(3, trickle)
+            .records()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(records, expected);
+
+        let mut reader = AsyncReader::new(FASTA_FILE);
+        let mut sync_reader = Reader::new(FASTA_FILE);
+        let (mut record, mut sync_record) = (Record::new(), Record::new());
+        loop {
+            reader.read(&mut record).await.unwrap();
+            sync_reader.read(&mut sync_record).unwrap();
+            assert_eq!(record, sync_record);
+            if record.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let mut records = AsyncReader::new(&b"ACGT\n>id\nACGT\n"[..]).records();
+        let err = records.next().await.unwrap().unwrap_err();
+        assert_eq!(err.to_string(), "Expected > at record start.");
+        assert!(records.next().await.is_none());
+
+        let mut records = AsyncReader::new(&b">id\nAC\xffGT\n"[..]).records();
+        let err = records.next().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn test_writer() {
+        let records: Vec<Record> = Reader::new(FASTA_FILE)
+            .records()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        let mut writer = AsyncWriter::new(Vec::new());
+        writer.set_linewrap(Some(12));
+        for record in &records {
+            writer.write_record(record).await.unwrap();
+        }
+        assert_eq!(writer.into_inner().await.unwrap(), FASTA_FILE);
+    }
+}
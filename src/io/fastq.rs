@@ -48,6 +48,23 @@
 //! println!("Number of bases: {}", nb_bases);
 //! ```
 //!
+//! To avoid allocating a new [`Record`] for each read, [`Reader::ref_records`] yields
+//! [`RefRecord`]s, which point into the buffer the file is read into:
+//!
+//! ```no_run
+//! use bio::io::fastq;
+//! use std::io;
+//! let mut records = fastq::Reader::new(io::stdin()).ref_records();
+//!
+//! let mut nb_bases = 0;
+//! while let Some(result) = records.next() {
+//!     let record = result.expect("Error during fastq record parsing");
+//!     nb_bases += record.seq().len();
+//! }
+//!
+//! println!("Number of bases: {}", nb_bases);
+//! ```
+//!
 //! ## Write
 //!
 //! In this example we generate 10 random sequences with length 100 and write them to stdout.
@@ -100,16 +117,114 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Quality encodings
+//!
+//! Base qualities are returned as raw bytes by [`Record::qual`]. Their [`QualityEncoding`]
+//! (Phred+33, Phred+64 or Solexa) can be guessed from a sample of records, and they can be
+//! converted between encodings or into [`PHREDProb`]s and [`LogProb`]s for use with the
+//! [`stats`](crate::stats) module.
+//!
+//! ```rust
+//! use bio::io::fastq::{self, QualityEncoding};
+//!
+//! let fq: &[u8] = b"@id\nACGT\n+\nhhBT\n";
+//! let mut records = fastq::Reader::new(fq)
+//!     .records()
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! let encoding = QualityEncoding::detect(records.iter().map(|record| record.qual())).unwrap();
+//! assert_eq!(encoding, QualityEncoding::Phred64);
+//!
+//! for record in &mut records {
+//!     record.convert_qual(encoding, QualityEncoding::Phred33);
+//! }
+//! assert_eq!(records[0].qual(), b"II#5");
+//! ```
+//!
+//! ## Paired-end reads
+//!
+//! Paired-end reads stored in two files, or interleaved in a single file, are read as pairs of
+//! records with [`PairedReader`]. The names of the mates are validated, such that a loss of
+//! synchronization is reported as [`Error::MateMismatch`] or [`Error::MissingMate`], and
+//! swapped or duplicated inputs as [`Error::ReadNumberMismatch`].
+//!
+//! ```rust
+//! use bio::io::fastq;
+//!
+//! const READS_1: &[u8] = b"@read1/1\nACGT\n+\nIIII\n@read2/1\nGGTA\n+\nJJJJ\n";
+//! const READS_2: &[u8] = b"@read1/2\nTTGA\n+\nIIII\n@read2/2\nCCAT\n+\nJJJJ\n";
+//!
+//! let reader = fastq::PairedReader::new(fastq::Reader::new(READS_1), fastq::Reader::new(READS_2));
+//! let mut writer = fastq::PairedWriter::interleaved(fastq::Writer::new(Vec::new()));
+//! for result in reader.records() {
+//!     let (r1, r2) = result.expect("Error during paired fastq parsing");
+//!     writer.write_pair(&r1, &r2).expect("Error writing pair");
+//! }
+//! ```
+//!
+//! ## Index
+//!
+//! Random access to records of a FASTQ file by their name is facilitated by [`Index`] and
+//! [`IndexedReader`]. The index can be created with
+//! [`samtools fqidx`](https://www.htslib.org/doc/samtools-fqidx.html) or [`Index::build`].
+//!
+//! ```rust
+//! use bio::io::fastq::{Index, IndexedReader};
+//! use std::io::Cursor;
+//!
+//! const FASTQ_FILE: &[u8] = b"@read1 desc\nACGT\n+\nIIII\n@read2\nGGTA\n+\nJJJJ\n";
+//!
+//! let index = Index::build(FASTQ_FILE).unwrap();
+//! let mut reader = IndexedReader::with_index(Cursor::new(FASTQ_FILE), index);
+//! let record = reader.fetch("read2").unwrap();
+//! assert_eq!(record.seq(), b"GGTA");
+//! assert_eq!(record.qual(), b"JJJJ");
+//! ```
+//!
+//! ## Asynchronous I/O
+//!
+//! With the `async` feature, `AsyncReader` and `AsyncWriter` read and write FastQ records
+//! from and to any tokio `AsyncRead` or `AsyncWrite`, e.g. a network socket. Records are
+//! parsed with the same validation and [`Error`]s as [`Reader`] and returned as a `Stream`.
+//!
+//! ```rust
+//! # #[cfg(feature = "async")]
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! use bio::io::fastq;
+//! use futures_util::StreamExt;
+//!
+//! let fq: &[u8] = b"@id1\nACGT\n+\nIIII\n@id2\nGGTA\n+\nJJJJ\n";
+//! let mut records = fastq::AsyncReader::new(fq).records();
+//! let mut nb_bases = 0;
+//! while let Some(result) = records.next().await {
+//!     let record = result.expect("Error during fastq record parsing");
+//!     nb_bases += record.seq().len();
+//! }
+//! assert_eq!(nb_bases, 8);
+//! # });
+//! ```
 
 use anyhow::Context;
+use std::collections;
 use std::convert::AsRef;
 use std::fmt;
 use std::fs;
 use std::io;
 use std::io::prelude::*;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "async")]
+pub use self::asynchronous::{AsyncReader, AsyncRecords, AsyncWriter};
+
+#[cfg(feature = "gzip")]
+use crate::io::bgzf;
+
+/// Errors of reading and pairing FastQ records.
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("expected '@' at record start")]
@@ -123,11 +238,54 @@ pub enum Error {
 
     #[error("Incomplete record. Each FastQ record has to consist of 4 lines: header, sequence, separator and qualities.")]
     IncompleteRecord,
+
+    #[error("unknown record name {name}")]
+    UnknownName { name: String },
+
+    #[error("invalid record index {rid}")]
+    InvalidRecordIndex { rid: usize },
+
+    #[error("mate names do not match: {id1} and {id2}")]
+    MateMismatch { id1: String, id2: String },
+
+    #[error("missing mate of record {id}")]
+    MissingMate { id: String },
+
+    #[error("record {id} has read number {found}, but is expected to be mate {mate}")]
+    ReadNumberMismatch { id: String, mate: u8, found: u8 },
 }
+/// Errors that can occur while building a FastQ index.
+#[derive(Error, Debug)]
+pub enum IndexError {
+    #[error("can't read input")]
+    ReadError(#[from] io::Error),
+
+    #[error("expected '@' at record start (line {line})")]
+    MissingAt { line: u64 },
+
+    #[error("incomplete record {name}")]
+    IncompleteRecord { name: String },
+
+    #[error("inconsistent line width in record {name} (line {line}). All sequence and quality lines except the last must have the same length.")]
+    InconsistentLineWidth { name: String, line: u64 },
+
+    #[error("sequence and qualities of record {name} differ in length")]
+    QualityLengthMismatch { name: String },
+
+    #[error("duplicate record name {name}")]
+    DuplicateName { name: String },
+}
+
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 use bio_types::sequence::SequenceRead;
 
+use crate::io::compression::{Decoder, Encoder, Format};
+use crate::io::fasta::{LineLayout, Sequence};
+#[cfg(feature = "rayon")]
+use crate::io::parallel::{self, ChunkFormat, ParRecords};
+use crate::io::record_buffer::{join_lines, next_line, split_header, RecordBuffer};
+use crate::stats::{LogProb, PHREDProb};
 use crate::utils::TextSlice;
 
 /// Trait for FastQ readers.
@@ -155,6 +313,21 @@ impl Reader<io::BufReader<fs::File>> {
     }
 }
 
+impl Reader<io::BufReader<Decoder<fs::File>>> {
+    /// Read from a given file.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(path.as_ref())
+            .and_then(Decoder::new)
+            .map_err(|e| Error::FileOpen {
+                path: path.as_ref().to_owned(),
+                source: e,
+            })
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read fastq from {:#?}", path))
+    }
+}
+
 impl<R: io::Read> Reader<io::BufReader<R>> {
     /// Read from a given [`io::Read`](https://doc.rust-lang.org/std/io/trait.Read.html).
     pub fn new(reader: R) -> Self {
@@ -208,6 +381,62 @@ where
     pub fn records(self) -> Records<B> {
         Records { reader: self }
     }
+
+    /// Return a streaming iterator over the records of this FastQ file, which yields
+    /// records pointing into its buffer instead of allocating a new record each time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bio::io::fastq::Reader;
+    /// # const FASTQ_FILE: &'static [u8] = b"@id desc
+    /// # AAAA
+    /// # +
+    /// # IIII
+    /// # ";
+    /// let mut records = Reader::new(FASTQ_FILE).ref_records();
+    /// while let Some(record) = records.next() {
+    ///     let record = record.unwrap();
+    ///     assert_eq!(record.id(), "id");
+    ///     assert_eq!(record.seq(), b"AAAA");
+    ///     assert_eq!(record.qual(), b"IIII");
+    /// }
+    /// ```
+    pub fn ref_records(self) -> RefRecords<B> {
+        RefRecords {
+            buffer: RecordBuffer::new(self.reader, &[]),
+            error_has_occured: false,
+        }
+    }
+
+    /// Return a parallel iterator over the records of this FastQ file, which are parsed in
+    /// chunks on the rayon thread pool (see [`parallel`](crate::io::parallel)).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use bio::io::fastq::Reader;
+    /// use rayon::prelude::*;
+    /// # const FASTQ_FILE: &'static [u8] = b"@id1\nACGT\n+\nIIII\n@id2\nAA\n+\nII\n";
+    /// let bases: usize = Reader::new(FASTQ_FILE)
+    ///     .par_records()
+    ///     .map(|record| record.unwrap().seq().len())
+    ///     .sum();
+    /// assert_eq!(bases, 6);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_records(self) -> ParRecords<Record, Error>
+    where
+        B: Send + 'static,
+    {
+        ParRecords::new(self.reader, ChunkFormat::Fastq, |chunk: &[u8]| {
+            parallel::collect_until_err(Reader::new(chunk).records())
+        })
+    }
+
+    /// Return the underlying reader.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn into_raw(self) -> B {
+        self.reader
+    }
 }
 
 impl<B> FastqRead for Reader<B>
@@ -294,6 +523,134 @@ where
     }
 }
 
+/// The encoding of base qualities as ASCII characters.
+#[derive(
+    Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize,
+)]
+pub enum QualityEncoding {
+    /// PHRED scores with an offset of 33 (Sanger, Illumina 1.8+).
+    #[default]
+    Phred33,
+    /// PHRED scores with an offset of 64 (Illumina 1.3 to 1.7).
+    Phred64,
+    /// Solexa scores `-10 log10(p / (1 - p))` with an offset of 64 (Solexa, Illumina 1.0).
+    Solexa,
+}
+
+impl QualityEncoding {
+    /// Return the ASCII offset of the scores.
+    pub fn offset(self) -> u8 {
+        match self {
+            QualityEncoding::Phred33 => 33,
+            QualityEncoding::Phred64 | QualityEncoding::Solexa => 64,
+        }
+    }
+
+    /// Guess the encoding from the range of the given base qualities, e.g. of the first
+    /// thousand records of a file.
+    /// Returns `None` if there are no qualities or they are outside of the printable ASCII range.
+    ///
+    /// Characters below `;` can only occur with Phred+33. Otherwise, characters above `J` imply
+    /// Phred+64, or Solexa if there are characters below `@`. As modern data is usually
+    /// Phred+33, this is chosen for high qualities that fit all encodings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio::io::fastq::{QualityEncoding, Reader};
+    ///
+    /// let fq: &[u8] = b"@id1\nACGT\n+\nhhBh\n@id2\nACGT\n+\nhh^h\n";
+    /// let records = Reader::new(fq)
+    ///     .records()
+    ///     .take(1000)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// let encoding = QualityEncoding::detect(records.iter().map(|record| record.qual()));
+    /// assert_eq!(encoding, Some(QualityEncoding::Phred64));
+    /// ```
+    pub fn detect<I, Q>(quals: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: AsRef<[u8]>,
+    {
+        let (mut min, mut max) = (u8::MAX, u8::MIN);
+        for qual in quals {
+            for &q in qual.as_ref() {
+                min = min.min(q);
+                max = max.max(q);
+            }
+        }
+        if min > max || min < b'!' || max > b'~' {
+            None
+        } else if min < b';' || max <= b'J' {
+            Some(QualityEncoding::Phred33)
+        } else if min < b'@' {
+            Some(QualityEncoding::Solexa)
+        } else {
+            Some(QualityEncoding::Phred64)
+        }
+    }
+
+    /// Return the score encoded by the given character, without any range checks.
+    fn score(self, q: u8) -> i32 {
+        i32::from(q) - i32::from(self.offset())
+    }
+
+    /// Return the PHRED score of the given quality character.
+    /// Solexa scores are converted exactly, and thus may be fractional.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio::io::fastq::QualityEncoding;
+    /// use bio::stats::PHREDProb;
+    ///
+    /// assert_eq!(QualityEncoding::Phred33.phred(b'5'), PHREDProb(20.0));
+    /// assert_eq!(QualityEncoding::Phred64.phred(b'T'), PHREDProb(20.0));
+    /// assert!((*QualityEncoding::Solexa.phred(b'@') - 3.0103).abs() < 1e-4);
+    /// ```
+    pub fn phred(self, q: u8) -> PHREDProb {
+        let score = f64::from(self.score(q));
+        match self {
+            QualityEncoding::Solexa => PHREDProb(10.0 * (10.0f64.powf(score / 10.0) + 1.0).log10()),
+            _ => PHREDProb(score.max(0.0)),
+        }
+    }
+
+    /// Return the log-scaled probability of a base call error for the given quality character.
+    pub fn log_prob(self, q: u8) -> LogProb {
+        LogProb::from(self.phred(q))
+    }
+
+    /// Convert a quality character of this encoding into the given encoding.
+    /// Scores that are out of range for the target encoding are clamped, and conversions
+    /// between PHRED and Solexa scores are rounded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio::io::fastq::QualityEncoding;
+    ///
+    /// assert_eq!(QualityEncoding::Phred64.convert(b'h', QualityEncoding::Phred33), b'I');
+    /// assert_eq!(QualityEncoding::Solexa.convert(b';', QualityEncoding::Phred33), b'"');
+    /// assert_eq!(QualityEncoding::Phred33.convert(b'!', QualityEncoding::Solexa), b';');
+    /// ```
+    pub fn convert(self, q: u8, to: QualityEncoding) -> u8 {
+        let score = match (self, to) {
+            (QualityEncoding::Solexa, QualityEncoding::Solexa) => self.score(q),
+            (QualityEncoding::Solexa, _) => self.phred(q).round() as i32,
+            (_, QualityEncoding::Solexa) => {
+                let phred = f64::from(self.score(q));
+                (10.0 * (10.0f64.powf(phred / 10.0) - 1.0).log10())
+                    .round()
+                    .max(-5.0) as i32
+            }
+            _ => self.score(q).max(0),
+        };
+        (score + i32::from(to.offset())).clamp(i32::from(b'!'), i32::from(b'~')) as u8
+    }
+}
+
 /// A FastQ record.
 #[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub struct Record {
@@ -415,6 +772,53 @@ impl Record {
         self.qual.trim_end().as_bytes()
     }
 
+    /// Return the base qualities of the record as PHRED scores, given their encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio::io::fastq::{QualityEncoding, Record};
+    /// use bio::stats::PHREDProb;
+    ///
+    /// let record = Record::with_attrs("id", None, b"ACG", b"+5?");
+    /// let phred = record.qual_phred(QualityEncoding::Phred33);
+    /// assert_eq!(phred, [PHREDProb(10.0), PHREDProb(20.0), PHREDProb(30.0)]);
+    ///
+    /// let log_probs = record.qual_log_probs(QualityEncoding::Phred33);
+    /// assert!((log_probs[0].exp() - 0.1).abs() < 1e-10);
+    /// ```
+    pub fn qual_phred(&self, encoding: QualityEncoding) -> Vec<PHREDProb> {
+        self.qual().iter().map(|&q| encoding.phred(q)).collect()
+    }
+
+    /// Return the base qualities of the record as log-scaled error probabilities, given their
+    /// encoding.
+    pub fn qual_log_probs(&self, encoding: QualityEncoding) -> Vec<LogProb> {
+        self.qual().iter().map(|&q| encoding.log_prob(q)).collect()
+    }
+
+    /// Convert the base qualities of the record from one encoding into another
+    /// (see [`QualityEncoding::convert`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio::io::fastq::{QualityEncoding, Record};
+    ///
+    /// let mut record = Record::with_attrs("id", None, b"ACGT", b"hhBT");
+    /// record.convert_qual(QualityEncoding::Phred64, QualityEncoding::Phred33);
+    /// assert_eq!(record.qual(), b"II#5");
+    /// ```
+    pub fn convert_qual(&mut self, from: QualityEncoding, to: QualityEncoding) {
+        if from != to {
+            self.qual = self
+                .qual()
+                .iter()
+                .map(|&q| char::from(from.convert(q, to)))
+                .collect();
+        }
+    }
+
     /// Clear the record.
     fn clear(&mut self) {
         self.id.clear();
@@ -492,6 +896,13 @@ pub struct Records<R: io::Read> {
     reader: Reader<R>,
 }
 
+#[cfg(feature = "rayon")]
+impl<R: io::Read> Records<R> {
+    pub(crate) fn into_reader(self) -> Reader<R> {
+        self.reader
+    }
+}
+
 impl<B> Iterator for Records<B>
 where
     B: io::BufRead,
@@ -508,6 +919,388 @@ where
     }
 }
 
+/// A streaming iterator over the records of a FastQ file, yielding [`RefRecord`]s that
+/// point into its buffer. A record is only valid until `next` is called again.
+///
+/// Like in FASTA files, the header is split into id and description at the first whitespace
+/// character, whereas [`Reader::read`](FastqRead::read) only splits at a space.
+/// After an error, no more records are returned.
+#[derive(Debug)]
+pub struct RefRecords<B> {
+    buffer: RecordBuffer<B>,
+    error_has_occured: bool,
+}
+
+impl<B> RefRecords<B>
+where
+    B: io::BufRead,
+{
+    /// Read the next record, returning `None` at the end of the file.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<RefRecord<'_>>> {
+        if self.error_has_occured {
+            return None;
+        }
+        let (header, seq, qual) = match self.read_next() {
+            Ok(Some(ranges)) => ranges,
+            Ok(None) => return None,
+            Err(err) => {
+                self.error_has_occured = true;
+                return Some(Err(err));
+            }
+        };
+        let seq = self.buffer.get(seq);
+        let qual = self.buffer.get(qual);
+        // like the owned records, header, sequence and qualities have to be valid UTF-8
+        match std::str::from_utf8(self.buffer.get(header)).and_then(|header| {
+            std::str::from_utf8(seq)?;
+            std::str::from_utf8(qual)?;
+            Ok(header)
+        }) {
+            Ok(header) => {
+                let (id, desc) = split_header(header);
+                Some(Ok(RefRecord {
+                    id,
+                    desc,
+                    seq,
+                    qual,
+                }))
+            }
+            Err(err) => {
+                self.error_has_occured = true;
+                Some(Err(io::Error::new(io::ErrorKind::InvalidData, err).into()))
+            }
+        }
+    }
+
+    /// Locate the next record in the buffer and join its sequence and quality lines,
+    /// returning the ranges of its header, sequence and qualities, or `None` at the end of
+    /// the file.
+    #[allow(clippy::type_complexity)]
+    fn read_next(&mut self) -> Result<Option<(Range<usize>, Range<usize>, Range<usize>)>> {
+        loop {
+            let eof = self.buffer.is_eof();
+            let data = self.buffer.data();
+            if data.is_empty() {
+                if eof {
+                    return Ok(None);
+                }
+                self.buffer.fill()?;
+                continue;
+            }
+            if data[0] != b'@' {
+                return Err(Error::MissingAt);
+            }
+            let Some(layout) = FastqLayout::locate(data, eof) else {
+                self.buffer.fill()?;
+                continue;
+            };
+
+            let data = self.buffer.data_mut();
+            let seq_end = join_lines(data, layout.seq.clone());
+            let qual_end = join_lines(data, layout.qual.clone());
+            let offset = self.buffer.consume(layout.end);
+            if qual_end == layout.qual.start {
+                return Err(Error::IncompleteRecord);
+            }
+            return Ok(Some((
+                offset + 1..offset + layout.header_end,
+                offset + layout.seq.start..offset + seq_end,
+                offset + layout.qual.start..offset + qual_end,
+            )));
+        }
+    }
+}
+
+/// The positions of the parts of a FastQ record, relative to its start.
+struct FastqLayout {
+    /// End of the header, without trailing whitespace.
+    header_end: usize,
+    /// The sequence lines.
+    seq: Range<usize>,
+    /// The quality lines, as many as there are sequence lines.
+    qual: Range<usize>,
+    end: usize,
+}
+
+impl FastqLayout {
+    /// Locate the record at the start of the data, in the same way as
+    /// [`Reader::read`](FastqRead::read). Returns `None` if it is not complete.
+    fn locate(data: &[u8], eof: bool) -> Option<Self> {
+        let (header_end, seq_start) = next_line(data, 0, eof)?;
+        let header_end = 1 + data[1..header_end].trim_ascii_end().len();
+
+        let mut from = seq_start;
+        let mut seq_end = seq_start;
+        let mut lines = 0;
+        loop {
+            match next_line(data, from, eof) {
+                Some((_, next)) if data[from] == b'+' => {
+                    from = next;
+                    break;
+                }
+                Some((_, next)) => {
+                    from = next;
+                    seq_end = next;
+                    lines += 1;
+                }
+                None if eof => break,
+                None => return None,
+            }
+        }
+
+        let qual_start = from;
+        for _ in 0..lines {
+            match next_line(data, from, eof) {
+                Some((_, next)) => from = next,
+                None if eof => break,
+                None => return None,
+            }
+        }
+        Some(FastqLayout {
+            header_end,
+            seq: seq_start..seq_end,
+            qual: qual_start..from,
+            end: from,
+        })
+    }
+}
+
+/// A FastQ record borrowing its data from the buffers of a [`RefRecords`] iterator.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct RefRecord<'a> {
+    id: &'a str,
+    desc: Option<&'a str>,
+    seq: &'a [u8],
+    qual: &'a [u8],
+}
+
+impl<'a> RefRecord<'a> {
+    /// Check if a record is empty.
+    pub fn is_empty(&self) -> bool {
+        self.id.is_empty() && self.desc.is_none() && self.seq.is_empty() && self.qual.is_empty()
+    }
+
+    /// Check the validity of a FastQ record, see [`Record::check`].
+    pub fn check(&self) -> Result<(), &str> {
+        if self.id.is_empty() {
+            return Err("Expecting id for FastQ record.");
+        }
+        if !self.seq.is_ascii() {
+            return Err("Non-ascii character found in sequence.");
+        }
+        if !self.qual.is_ascii() {
+            return Err("Non-ascii character found in qualities.");
+        }
+        if self.seq.len() != self.qual.len() {
+            return Err("Unequal length of sequence an qualities.");
+        }
+
+        Ok(())
+    }
+
+    /// Return the id of the record.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// Return descriptions if present.
+    pub fn desc(&self) -> Option<&'a str> {
+        self.desc
+    }
+
+    /// Return the sequence of the record.
+    pub fn seq(&self) -> TextSlice<'a> {
+        self.seq
+    }
+
+    /// Return the base qualities of the record.
+    pub fn qual(&self) -> &'a [u8] {
+        self.qual
+    }
+
+    /// Copy the data into an owned [`Record`].
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_owned(&self) -> Record {
+        Record::with_attrs(self.id, self.desc, self.seq, self.qual)
+    }
+}
+
+/// A reader for paired-end reads, either from two files or from a single interleaved file.
+///
+/// The names of the mates have to match, ignoring `/1` and `/2` suffixes. The read number of
+/// each mate, given either by such a suffix or by the comment of Illumina reads
+/// (e.g. `1:N:0:ATCACG`), has to be 1 for the first and 2 for the second mate, such that
+/// swapped or duplicated inputs are detected.
+#[derive(Debug)]
+pub struct PairedReader<B> {
+    reader1: Reader<B>,
+    reader2: Option<Reader<B>>,
+}
+
+impl PairedReader<io::BufReader<fs::File>> {
+    /// Read the first and second mates from the given files.
+    pub fn from_files<P: AsRef<Path> + std::fmt::Debug>(
+        path1: P,
+        path2: P,
+    ) -> anyhow::Result<Self> {
+        Ok(PairedReader::new(
+            Reader::from_file(path1)?,
+            Reader::from_file(path2)?,
+        ))
+    }
+
+    /// Read pairs from a given interleaved file.
+    pub fn from_interleaved_file<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+    ) -> anyhow::Result<Self> {
+        Reader::from_file(path).map(PairedReader::interleaved)
+    }
+}
+
+impl PairedReader<io::BufReader<Decoder<fs::File>>> {
+    /// Read the first and second mates from the given files.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_paths_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path1: P,
+        path2: P,
+    ) -> anyhow::Result<Self> {
+        Ok(PairedReader::new(
+            Reader::from_path_auto(path1)?,
+            Reader::from_path_auto(path2)?,
+        ))
+    }
+
+    /// Read pairs from a given interleaved file.
+    pub fn from_interleaved_path_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+    ) -> anyhow::Result<Self> {
+        Reader::from_path_auto(path).map(PairedReader::interleaved)
+    }
+}
+
+impl<B> PairedReader<B>
+where
+    B: io::BufRead,
+{
+    /// Read the first and second mates from the given readers.
+    pub fn new(reader1: Reader<B>, reader2: Reader<B>) -> Self {
+        PairedReader {
+            reader1,
+            reader2: Some(reader2),
+        }
+    }
+
+    /// Read pairs from a single reader, where the second mate directly follows the first.
+    pub fn interleaved(reader: Reader<B>) -> Self {
+        PairedReader {
+            reader1: reader,
+            reader2: None,
+        }
+    }
+
+    /// Read the next pair into the given records.
+    /// Empty records indicate that no more pairs can be read.
+    ///
+    /// # Errors
+    /// In addition to the errors of [`Reader::read`](FastqRead::read), if the names of the
+    /// mates do not match, if their read numbers are not 1 and 2, or if one of the inputs ends
+    /// before the other.
+    pub fn read(&mut self, record1: &mut Record, record2: &mut Record) -> Result<()> {
+        self.reader1.read(record1)?;
+        match self.reader2.as_mut() {
+            Some(reader2) => reader2.read(record2)?,
+            None => self.reader1.read(record2)?,
+        }
+
+        match (record1.is_empty(), record2.is_empty()) {
+            (true, true) => Ok(()),
+            (false, true) => Err(Error::MissingMate {
+                id: record1.id().to_owned(),
+            }),
+            (true, false) => Err(Error::MissingMate {
+                id: record2.id().to_owned(),
+            }),
+            (false, false) => check_mates(record1, record2),
+        }
+    }
+
+    /// Return an iterator over the pairs of records.
+    pub fn records(self) -> PairedRecords<B> {
+        PairedRecords { reader: self }
+    }
+}
+
+/// Check whether the given records are the first and second mate of a pair.
+fn check_mates(record1: &Record, record2: &Record) -> Result<()> {
+    let (name1, number1) = mate_name(record1);
+    let (name2, number2) = mate_name(record2);
+    if name1 != name2 {
+        return Err(Error::MateMismatch {
+            id1: record1.id().to_owned(),
+            id2: record2.id().to_owned(),
+        });
+    }
+    for (record, mate, number) in [(record1, 1, number1), (record2, 2, number2)] {
+        match number {
+            Some(found) if found != mate => {
+                return Err(Error::ReadNumberMismatch {
+                    id: record.id().to_owned(),
+                    mate,
+                    found,
+                })
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Return the name of a mate without `/1` or `/2` suffix, and its read number if given by
+/// such a suffix or an Illumina comment (e.g. `1:N:0:ATCACG`).
+fn mate_name(record: &Record) -> (&str, Option<u8>) {
+    let id = record.id();
+    for (suffix, number) in [("/1", 1), ("/2", 2)] {
+        if let Some(name) = id.strip_suffix(suffix) {
+            return (name, Some(number));
+        }
+    }
+    let number = record
+        .desc()
+        .and_then(|desc| desc.split_whitespace().next())
+        .and_then(|comment| {
+            let fields: Vec<&str> = comment.split(':').collect();
+            match fields.as_slice() {
+                [number, "Y" | "N", _, _] => number.parse().ok(),
+                _ => None,
+            }
+        });
+    (id, number)
+}
+
+/// An iterator over the pairs of records of paired-end FastQ files.
+#[derive(Debug)]
+pub struct PairedRecords<B> {
+    reader: PairedReader<B>,
+}
+
+impl<B> Iterator for PairedRecords<B>
+where
+    B: io::BufRead,
+{
+    type Item = Result<(Record, Record)>;
+
+    fn next(&mut self) -> Option<Result<(Record, Record)>> {
+        let mut record1 = Record::new();
+        let mut record2 = Record::new();
+        match self.reader.read(&mut record1, &mut record2) {
+            Ok(()) if record1.is_empty() => None,
+            Ok(()) => Some(Ok((record1, record2))),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
 /// A FastQ writer.
 #[derive(Debug)]
 pub struct Writer<W: io::Write> {
@@ -527,6 +1320,29 @@ impl Writer<fs::File> {
     }
 }
 
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+
+    /// Write to the given file path and a buffer capacity
+    pub fn to_path_auto_with_capacity<P: AsRef<Path>>(
+        capacity: usize,
+        path: P,
+    ) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(|file| Writer::with_capacity(capacity, file))
+    }
+}
+
 impl<W: io::Write> Writer<W> {
     /// Write to a given `io::Write`.
     pub fn new(writer: W) -> Self {
@@ -581,6 +1397,435 @@ impl<W: io::Write> Writer<W> {
     }
 }
 
+/// A writer for paired-end reads, either into two files or into a single interleaved file.
+#[derive(Debug)]
+pub struct PairedWriter<W: io::Write> {
+    writer1: Writer<W>,
+    writer2: Option<Writer<W>>,
+}
+
+impl PairedWriter<fs::File> {
+    /// Write the first and second mates to the given file paths.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_files<P: AsRef<Path>>(path1: P, path2: P) -> io::Result<Self> {
+        Ok(PairedWriter::new(
+            Writer::to_file(path1)?,
+            Writer::to_file(path2)?,
+        ))
+    }
+
+    /// Write interleaved pairs to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_interleaved_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Writer::to_file(path).map(PairedWriter::interleaved)
+    }
+}
+
+impl PairedWriter<Encoder<fs::File>> {
+    /// Write the first and second mates to the given file paths. The output is compressed
+    /// according to the file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_paths_auto<P: AsRef<Path>>(path1: P, path2: P) -> io::Result<Self> {
+        Ok(PairedWriter::new(
+            Writer::to_path_auto(path1)?,
+            Writer::to_path_auto(path2)?,
+        ))
+    }
+
+    /// Write interleaved pairs to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_interleaved_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Writer::to_path_auto(path).map(PairedWriter::interleaved)
+    }
+}
+
+impl<W: io::Write> PairedWriter<W> {
+    /// Write the first and second mates to the given writers.
+    pub fn new(writer1: Writer<W>, writer2: Writer<W>) -> Self {
+        PairedWriter {
+            writer1,
+            writer2: Some(writer2),
+        }
+    }
+
+    /// Write pairs to a single writer, the second mate directly following the first.
+    pub fn interleaved(writer: Writer<W>) -> Self {
+        PairedWriter {
+            writer1: writer,
+            writer2: None,
+        }
+    }
+
+    /// Write a pair of records.
+    pub fn write_pair(&mut self, record1: &Record, record2: &Record) -> io::Result<()> {
+        self.writer1.write_record(record1)?;
+        self.writer2
+            .as_mut()
+            .unwrap_or(&mut self.writer1)
+            .write_record(record2)
+    }
+
+    /// Flush the writers, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer1.flush()?;
+        if let Some(writer2) = self.writer2.as_mut() {
+            writer2.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// A FASTQ index as created by `samtools fqidx` (.fai). In addition to the columns of a
+/// FASTA index, it stores the offset of the qualities of each record.
+///
+/// # Example
+///
+/// ```rust
+/// use bio::io::fastq::Index;
+///
+/// let index = Index::build(&b"@read1\nACGT\n+\nIIII\n@read2\nGG\n+\nII\n"[..]).unwrap();
+/// let mut fai = Vec::new();
+/// index.write(&mut fai).unwrap();
+/// assert_eq!(fai, b"read1\t4\t7\t4\t5\t14\nread2\t2\t26\t2\t3\t31\n");
+/// ```
+#[derive(Default, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Index {
+    inner: Vec<IndexRecord>,
+    name_to_rid: collections::HashMap<String, usize>,
+}
+
+impl Index {
+    /// Open a FASTQ index from a given `io::Read` instance.
+    pub fn new<R: io::Read>(fai: R) -> csv::Result<Self> {
+        let mut index = Index::default();
+        let mut fai_reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .has_headers(false)
+            .from_reader(fai);
+        for row in fai_reader.deserialize() {
+            let record: IndexRecord = row?;
+            index
+                .name_to_rid
+                .insert(record.name.clone(), index.inner.len());
+            index.inner.push(record);
+        }
+        Ok(index)
+    }
+
+    /// Open a FASTQ index from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        fs::File::open(path)
+            .map_err(csv::Error::from)
+            .and_then(Self::new)
+            .with_context(|| format!("Failed to read fastq index from {:#?}", path))
+    }
+
+    /// Open a FASTQ index given the corresponding FASTQ file path.
+    /// That is, for reads.fastq we expect reads.fastq.fai.
+    pub fn with_fastq_file<P: AsRef<Path>>(fastq_path: &P) -> anyhow::Result<Self> {
+        let mut fai_path = fastq_path.as_ref().as_os_str().to_owned();
+        fai_path.push(".fai");
+
+        Self::from_file(&fai_path)
+    }
+
+    /// Build the index of a FASTQ file given as `io::Read`, in the same way as
+    /// `samtools fqidx`. Sequences and qualities may span multiple lines.
+    ///
+    /// # Errors
+    /// If a record is incomplete, the lines of a record have different lengths (except for
+    /// the last line), or if record names are not unique.
+    pub fn build<R: io::Read>(fastq: R) -> Result<Self, IndexError> {
+        let mut lines = IndexLines {
+            reader: io::BufReader::new(fastq),
+            line: Vec::new(),
+            offset: 0,
+            line_number: 0,
+        };
+        let mut index = Index::default();
+        let inconsistent =
+            |name: String, line: u64| IndexError::InconsistentLineWidth { name, line };
+
+        loop {
+            if lines.read_line()? == 0 {
+                break;
+            }
+            let bases = lines.bases();
+            if bases == 0 {
+                continue;
+            }
+            if lines.line[0] != b'@' {
+                return Err(IndexError::MissingAt {
+                    line: lines.line_number,
+                });
+            }
+            let header = String::from_utf8_lossy(&lines.line[1..bases as usize]).into_owned();
+            let name = header
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_owned();
+
+            let mut record = IndexRecord {
+                offset: lines.offset,
+                ..IndexRecord::default()
+            };
+            let mut seq = LineLayout::default();
+            loop {
+                let bytes = lines.read_line()?;
+                if bytes == 0 {
+                    return Err(IndexError::IncompleteRecord { name });
+                }
+                if lines.line[0] == b'+' {
+                    break;
+                }
+                if !seq.push(lines.bases(), bytes) {
+                    return Err(inconsistent(name, lines.line_number));
+                }
+            }
+
+            record.qual_offset = lines.offset;
+            let mut qual = LineLayout::default();
+            while qual.len() < seq.len() {
+                let bytes = lines.read_line()?;
+                if bytes == 0 {
+                    return Err(IndexError::IncompleteRecord { name });
+                }
+                if !qual.push(lines.bases(), bytes) {
+                    return Err(inconsistent(name, lines.line_number));
+                }
+            }
+            if qual.len() != seq.len() {
+                return Err(IndexError::QualityLengthMismatch { name });
+            }
+            if qual.line_bases() != seq.line_bases() {
+                return Err(inconsistent(name, lines.line_number));
+            }
+
+            record.len = seq.len();
+            record.line_bases = seq.line_bases();
+            record.line_bytes = seq.line_bytes();
+            if index.name_to_rid.contains_key(&name) {
+                return Err(IndexError::DuplicateName { name });
+            }
+            index.name_to_rid.insert(name.clone(), index.inner.len());
+            record.name = name;
+            index.inner.push(record);
+        }
+        Ok(index)
+    }
+
+    /// Build the index of the FASTQ file at the given path.
+    pub fn build_from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        fs::File::open(path)
+            .map_err(IndexError::from)
+            .and_then(Self::build)
+            .with_context(|| format!("Failed to index fastq {:#?}", path))
+    }
+
+    /// Write the index in the tab-separated 6-column `.fai` format of `samtools fqidx`.
+    pub fn write<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        for record in &self.inner {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                record.name,
+                record.len,
+                record.offset,
+                record.line_bases,
+                record.line_bytes,
+                record.qual_offset
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Return a vector of the records described in the index.
+    pub fn sequences(&self) -> Vec<Sequence> {
+        self.inner
+            .iter()
+            .map(|record| Sequence {
+                name: record.name.clone(),
+                len: record.len,
+            })
+            .collect()
+    }
+}
+
+/// A FASTQ reader with an index as created by `samtools fqidx` (.fai).
+#[derive(Debug)]
+pub struct IndexedReader<R: io::Read + io::Seek> {
+    reader: io::BufReader<R>,
+    pub index: Index,
+    line: Vec<u8>,
+}
+
+impl IndexedReader<fs::File> {
+    /// Read from a given file path. This assumes the index reads.fastq.fai to be
+    /// present for FASTQ reads.fastq.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        let index = Index::with_fastq_file(path)?;
+        fs::File::open(path)
+            .map(|f| Self::with_index(f, index))
+            .map_err(csv::Error::from)
+            .with_context(|| format!("Failed to read fastq from {:#?}", path))
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl IndexedReader<bgzf::Reader<fs::File>> {
+    /// Read from a given path to a FASTQ file compressed with `bgzip`. This assumes
+    /// the indexes reads.fastq.gz.fai and reads.fastq.gz.gzi to be present for
+    /// reads.fastq.gz, as created by `samtools fqidx`.
+    pub fn from_bgzf_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        let index = Index::with_fastq_file(path)?;
+        bgzf::Reader::from_file(path).map(|reader| Self::with_index(reader, index))
+    }
+}
+
+impl<R: io::Read + io::Seek> IndexedReader<R> {
+    /// Read from a FASTQ and its index, both given as `io::Read`. FASTQ has to
+    /// be `io::Seek` in addition.
+    pub fn new<I: io::Read>(fastq: R, fai: I) -> csv::Result<Self> {
+        let index = Index::new(fai)?;
+        Ok(Self::with_index(fastq, index))
+    }
+
+    /// Read from a FASTQ and its index, the first given as `io::Read`, the
+    /// second given as index object.
+    pub fn with_index(fastq: R, index: Index) -> Self {
+        IndexedReader {
+            reader: io::BufReader::new(fastq),
+            index,
+            line: Vec::new(),
+        }
+    }
+
+    /// Fetch the record with the given name.
+    ///
+    /// # Errors
+    /// If the name is not contained in the index, or if the record in the FASTQ file
+    /// does not match the index.
+    pub fn fetch(&mut self, name: &str) -> Result<Record> {
+        match self.index.name_to_rid.get(name) {
+            Some(&rid) => self.fetch_by_rid(rid),
+            None => Err(Error::UnknownName {
+                name: name.to_owned(),
+            }),
+        }
+    }
+
+    /// Fetch the record with the given index, i.e. the `rid`-th record of the file.
+    pub fn fetch_by_rid(&mut self, rid: usize) -> Result<Record> {
+        let idx = match self.index.inner.get(rid) {
+            Some(idx) => idx.clone(),
+            None => return Err(Error::InvalidRecordIndex { rid }),
+        };
+
+        let header = utf8(self.read_header(&idx)?)?;
+        let mut header_fields = header.trim_end().splitn(2, ' ');
+        let id = header_fields.next().unwrap_or_default().to_owned();
+        let desc = header_fields.next().map(|desc| desc.to_owned());
+
+        let seq = utf8(self.read_lines(idx.offset, idx.len)?)?;
+        let qual = utf8(self.read_lines(idx.qual_offset, idx.len)?)?;
+
+        Ok(Record {
+            id,
+            desc,
+            seq,
+            qual,
+        })
+    }
+
+    /// Read the header line preceding the sequence, without the leading '@'.
+    fn read_header(&mut self, idx: &IndexRecord) -> Result<Vec<u8>> {
+        // The header ends right before the sequence. Search backwards for its start,
+        // doubling the window until the previous line break is found.
+        let end = idx.offset.saturating_sub(1);
+        let mut window = 128;
+        loop {
+            let start = end.saturating_sub(window);
+            let mut buf = vec![0; (end - start) as usize];
+            self.reader.seek(io::SeekFrom::Start(start))?;
+            self.reader.read_exact(&mut buf)?;
+            let line_start = match buf.iter().rposition(|&c| c == b'\n') {
+                Some(pos) => pos + 1,
+                None if start == 0 => 0,
+                None => {
+                    window *= 2;
+                    continue;
+                }
+            };
+            return match buf[line_start..].split_first() {
+                Some((b'@', header)) => Ok(header.to_vec()),
+                _ => Err(Error::MissingAt),
+            };
+        }
+    }
+
+    /// Read `len` bases or qualities, starting at `offset` and skipping line breaks.
+    fn read_lines(&mut self, offset: u64, len: u64) -> Result<Vec<u8>> {
+        // the length is taken from the index, so it is not used to preallocate
+        let mut text = Vec::new();
+        self.reader.seek(io::SeekFrom::Start(offset))?;
+        while (text.len() as u64) < len {
+            self.line.clear();
+            if self.reader.read_until(b'\n', &mut self.line)? == 0 {
+                return Err(Error::IncompleteRecord);
+            }
+            text.extend_from_slice(self.line.trim_ascii_end());
+        }
+        if text.len() as u64 != len {
+            return Err(Error::IncompleteRecord);
+        }
+        Ok(text)
+    }
+}
+
+/// Convert text read from the file into a `String`, reporting invalid UTF-8 like
+/// [`Reader::read`](FastqRead::read) does.
+fn utf8(text: Vec<u8>) -> Result<String> {
+    String::from_utf8(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+}
+
+/// Reads the lines of a FASTQ file while keeping track of the offset.
+struct IndexLines<R> {
+    reader: io::BufReader<R>,
+    line: Vec<u8>,
+    /// Offset after the current line.
+    offset: u64,
+    line_number: u64,
+}
+
+impl<R: io::Read> IndexLines<R> {
+    /// Read the next line, returning its length in bytes (0 at the end of the file).
+    fn read_line(&mut self) -> io::Result<u64> {
+        self.line.clear();
+        let bytes = self.reader.read_until(b'\n', &mut self.line)? as u64;
+        self.offset += bytes;
+        self.line_number += 1;
+        Ok(bytes)
+    }
+
+    /// The length of the current line without the line terminator.
+    fn bases(&self) -> u64 {
+        self.line.trim_ascii_end().len() as u64
+    }
+}
+
+/// Record of a FASTQ index.
+#[derive(Default, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+struct IndexRecord {
+    name: String,
+    len: u64,
+    offset: u64,
+    line_bases: u64,
+    line_bytes: u64,
+    qual_offset: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -593,6 +1838,84 @@ ACCGTAGGCTGA
 IIIIIIJJJJJJ
 ";
 
+    #[test]
+    fn test_quality_encoding_detect() {
+        let detect = |quals: &[&[u8]]| QualityEncoding::detect(quals);
+        assert_eq!(detect(&[b"II5#", b"!!"]), Some(QualityEncoding::Phred33));
+        assert_eq!(detect(&[b"@@@@", b"JJ"]), Some(QualityEncoding::Phred33));
+        assert_eq!(detect(&[b"hh", b"BT"]), Some(QualityEncoding::Phred64));
+        assert_eq!(detect(&[b"hh", b";T"]), Some(QualityEncoding::Solexa));
+        assert_eq!(detect(&[]), None);
+        assert_eq!(detect(&[b"II ", b"II"]), None);
+    }
+
+    #[test]
+    fn test_quality_encoding_convert() {
+        for q in b'@'..=b'h' {
+            let phred33 = QualityEncoding::Phred64.convert(q, QualityEncoding::Phred33);
+            assert_eq!(phred33, q - 31);
+            assert_eq!(
+                QualityEncoding::Phred33.convert(phred33, QualityEncoding::Phred64),
+                q
+            );
+        }
+        // Solexa and PHRED scores agree for high qualities
+        assert_eq!(
+            QualityEncoding::Solexa.convert(b'h', QualityEncoding::Phred33),
+            b'I'
+        );
+        assert_eq!(
+            QualityEncoding::Phred64.convert(b'h', QualityEncoding::Solexa),
+            b'h'
+        );
+        assert_eq!(
+            QualityEncoding::Solexa.convert(b'@', QualityEncoding::Phred64),
+            b'C'
+        );
+        assert_eq!(
+            QualityEncoding::Phred64.convert(b'C', QualityEncoding::Solexa),
+            b'@'
+        );
+        // out of range scores are clamped
+        assert_eq!(
+            QualityEncoding::Phred33.convert(b'~', QualityEncoding::Phred64),
+            b'~'
+        );
+        assert_eq!(
+            QualityEncoding::Phred64.convert(b';', QualityEncoding::Phred33),
+            b'!'
+        );
+    }
+
+    #[test]
+    fn test_record_qual_probs() {
+        let mut record = Record::with_attrs("id", None, b"ACGT", b"hhBT");
+        assert_eq!(
+            record.qual_phred(QualityEncoding::Phred64),
+            [
+                PHREDProb(40.0),
+                PHREDProb(40.0),
+                PHREDProb(2.0),
+                PHREDProb(20.0)
+            ]
+        );
+        let log_probs = record.qual_log_probs(QualityEncoding::Phred64);
+        assert_relative_eq!(*log_probs[3], 0.01f64.ln(), epsilon = 1e-10);
+
+        record.convert_qual(QualityEncoding::Phred64, QualityEncoding::Phred33);
+        assert_eq!(record.qual(), b"II#5");
+        assert_eq!(
+            record.qual_phred(QualityEncoding::Phred33),
+            [
+                PHREDProb(40.0),
+                PHREDProb(40.0),
+                PHREDProb(2.0),
+                PHREDProb(20.0)
+            ]
+        );
+        assert!(record.check().is_ok());
+    }
+
     #[test]
     fn test_reader() {
         let reader = Reader::new(FASTQ_FILE);
@@ -846,6 +2169,24 @@ IIIIIIJJJJJJ
         assert_eq!(&error, "Failed to read fastq from \"/I/dont/exist.fq\"")
     }
 
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compressed_file_roundtrip() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("reads.fq.zst");
+        {
+            let mut writer = Writer::to_file(&path).unwrap();
+            writer
+                .write("id", Some("desc"), b"ACCGTAGGCTGA", b"IIIIIIJJJJJJ")
+                .unwrap();
+        }
+        let mut records = Reader::from_file(&path).unwrap().records();
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.id(), "id");
+        assert_eq!(record.qual(), b"IIIIIIJJJJJJ");
+        assert!(records.next().is_none());
+    }
+
     #[test]
     fn test_reader_from_file_path_exists_returns_ok() {
         let path = Path::new("Cargo.toml");
@@ -977,4 +2318,341 @@ IIIIIIJJJJJJ
         assert!(fs::remove_file(path).is_ok());
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn test_index_build() {
+        let fastq = b"@id desc\nACCGTAGGCTGA\n+\nIIIIIIJJJJJJ\n@id2\nACCG\nTA\n+id2\nIIII\nJJ\n";
+        let index = Index::build(&fastq[..]).unwrap();
+        let mut fai = Vec::new();
+        index.write(&mut fai).unwrap();
+        assert_eq!(fai, b"id\t12\t9\t12\t13\t24\nid2\t6\t42\t4\t5\t55\n");
+        assert_eq!(Index::new(&fai[..]).unwrap(), index);
+        assert_eq!(
+            index.sequences().iter().map(|s| s.len).collect::<Vec<_>>(),
+            [12, 6]
+        );
+    }
+
+    #[test]
+    fn test_index_build_errors() {
+        let err = Index::build(&b"@id\nACGT\n+\nIII\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::IncompleteRecord { name } if name == "id"));
+        let err = Index::build(&b"@id\nACGT\n+\nIIIII\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::QualityLengthMismatch { name } if name == "id"));
+        let err = Index::build(&b"@id\nAC\nGTA\n+\nIIIII\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::InconsistentLineWidth { name, line: 3 } if name == "id"));
+        let err = Index::build(&b"@id\nACGT\n+\nIIII\n@id\nA\n+\nI\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::DuplicateName { name } if name == "id"));
+        let err = Index::build(&b">id\nACGT\n"[..]).unwrap_err();
+        assert!(matches!(err, IndexError::MissingAt { line: 1 }));
+    }
+
+    const INDEXED_FASTQ_FILE: &[u8] = b"@id desc
+ACCGTAGGCTGA
++
+IIIIIIJJJJJJ
+@id2
+ACCG
+TA
++id2
+IIII
+JJ
+@id3\r
+ACGT\r
++\r
+IIII\r
+";
+
+    #[test]
+    fn test_indexed_reader() {
+        let index = Index::build(INDEXED_FASTQ_FILE).unwrap();
+        let mut reader = IndexedReader::with_index(io::Cursor::new(INDEXED_FASTQ_FILE), index);
+
+        let record = reader.fetch("id2").unwrap();
+        assert_eq!(
+            record,
+            Record::with_attrs("id2", None, b"ACCGTA", b"IIIIJJ")
+        );
+        let record = reader.fetch("id").unwrap();
+        assert_eq!(
+            record,
+            Record::with_attrs("id", Some("desc"), b"ACCGTAGGCTGA", b"IIIIIIJJJJJJ")
+        );
+        let record = reader.fetch_by_rid(2).unwrap();
+        assert_eq!(record, Record::with_attrs("id3", None, b"ACGT", b"IIII"));
+
+        assert!(matches!(
+            reader.fetch("id4"),
+            Err(Error::UnknownName { name }) if name == "id4"
+        ));
+        assert!(matches!(
+            reader.fetch_by_rid(3),
+            Err(Error::InvalidRecordIndex { rid: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_indexed_reader_invalid_utf8() {
+        let mut reader = IndexedReader::new(
+            io::Cursor::new(&b"@id\nAC\xffT\n+\nIIII\n"[..]),
+            &b"id\t4\t4\t4\t5\t11\n"[..],
+        )
+        .unwrap();
+        assert!(matches!(
+            reader.fetch("id"),
+            Err(Error::ReadError(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_indexed_reader_long_header() {
+        let desc = "x".repeat(1000);
+        let mut fastq = Vec::new();
+        let mut writer = Writer::new(&mut fastq);
+        writer.write("id", Some(&desc), b"ACGT", b"IIII").unwrap();
+        writer.write("id2", Some(&desc), b"GG", b"II").unwrap();
+        drop(writer);
+
+        let mut reader = IndexedReader::new(
+            io::Cursor::new(&fastq),
+            &b"id\t4\t1005\t4\t5\t1012\nid2\t2\t2023\t2\t3\t2028\n"[..],
+        )
+        .unwrap();
+        let record = reader.fetch("id2").unwrap();
+        assert_eq!(record.desc(), Some(desc.as_str()));
+        assert_eq!(record.seq(), b"GG");
+        let record = reader.fetch("id").unwrap();
+        assert_eq!(record.desc(), Some(desc.as_str()));
+        assert_eq!(record.qual(), b"IIII");
+    }
+
+    #[test]
+    fn test_indexed_reader_from_file() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("reads.fq");
+        fs::write(&path, INDEXED_FASTQ_FILE).unwrap();
+        Index::build_from_file(&path)
+            .unwrap()
+            .write(fs::File::create(dir.path().join("reads.fq.fai")).unwrap())
+            .unwrap();
+
+        let mut reader = IndexedReader::from_file(&path).unwrap();
+        assert_eq!(reader.fetch("id2").unwrap().seq(), b"ACCGTA");
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_indexed_reader_bgzf() {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        for line in INDEXED_FASTQ_FILE.split_inclusive(|&c| c == b'\n') {
+            writer.write_all(line).unwrap();
+            writer.write_block().unwrap();
+        }
+        let (compressed, gzi) = writer.finish().unwrap();
+        let index = Index::build(INDEXED_FASTQ_FILE).unwrap();
+        let bgzf_reader = bgzf::Reader::with_index(io::Cursor::new(compressed), gzi);
+        let mut reader = IndexedReader::with_index(bgzf_reader, index);
+
+        let record = reader.fetch("id2").unwrap();
+        assert_eq!(
+            record,
+            Record::with_attrs("id2", None, b"ACCGTA", b"IIIIJJ")
+        );
+        let record = reader.fetch("id").unwrap();
+        assert_eq!(record.desc(), Some("desc"));
+    }
+
+    const READS_1: &[u8] = b"@read1/1
+ACGT
++
+IIII
+@read2 1:N:0:ATCACG
+GGTA
++
+JJJJ
+";
+    const READS_2: &[u8] = b"@read1/2
+TTGA
++
+IIII
+@read2 2:N:0:ATCACG
+CCAT
++
+JJJJ
+";
+    const INTERLEAVED: &[u8] = b"@read1/1
+ACGT
++
+IIII
+@read1/2
+TTGA
++
+IIII
+@read2 1:N:0:ATCACG
+GGTA
++
+JJJJ
+@read2 2:N:0:ATCACG
+CCAT
++
+JJJJ
+";
+
+    #[test]
+    fn test_paired_reader() {
+        let reader = PairedReader::new(Reader::new(READS_1), Reader::new(READS_2));
+        let pairs = reader.records().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].0.seq(), b"ACGT");
+        assert_eq!(pairs[0].1.seq(), b"TTGA");
+        assert_eq!(pairs[1].0.desc(), Some("1:N:0:ATCACG"));
+        assert_eq!(pairs[1].1.desc(), Some("2:N:0:ATCACG"));
+
+        let reader = PairedReader::interleaved(Reader::new(INTERLEAVED));
+        let interleaved = reader.records().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(interleaved, pairs);
+    }
+
+    #[test]
+    fn test_paired_reader_desync() {
+        // the second file lacks the first read
+        let reader = PairedReader::new(Reader::new(READS_1), Reader::new(&READS_2[21..]));
+        let mut records = reader.records();
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::MateMismatch { id1, id2 })) if id1 == "read1/1" && id2 == "read2"
+        ));
+
+        // the second file lacks the last read
+        let reader = PairedReader::new(Reader::new(READS_1), Reader::new(&READS_2[..21]));
+        let mut records = reader.records();
+        assert!(records.next().unwrap().is_ok());
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::MissingMate { id })) if id == "read2"
+        ));
+
+        // swapped mates
+        let reader = PairedReader::new(Reader::new(READS_2), Reader::new(READS_1));
+        let mut records = reader.records();
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::ReadNumberMismatch { id, mate: 1, found: 2 })) if id == "read1/2"
+        ));
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::ReadNumberMismatch { id, mate: 1, found: 2 })) if id == "read2"
+        ));
+
+        // the same reads twice
+        let reader = PairedReader::new(Reader::new(READS_1), Reader::new(READS_1));
+        let mut records = reader.records();
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::ReadNumberMismatch { id, mate: 2, found: 1 })) if id == "read1/1"
+        ));
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::ReadNumberMismatch { id, mate: 2, found: 1 })) if id == "read2"
+        ));
+
+        // odd number of records
+        let reader = PairedReader::interleaved(Reader::new(&INTERLEAVED[..74]));
+        let mut records = reader.records();
+        assert!(records.next().unwrap().is_ok());
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::MissingMate { id })) if id == "read2"
+        ));
+    }
+
+    #[test]
+    fn test_paired_writer() {
+        let pairs = PairedReader::new(Reader::new(READS_1), Reader::new(READS_2))
+            .records()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        let mut writer = PairedWriter::interleaved(Writer::new(Vec::new()));
+        for (r1, r2) in &pairs {
+            writer.write_pair(r1, r2).unwrap();
+        }
+        writer.flush().unwrap();
+        assert_eq!(writer.writer1.writer.get_ref(), &INTERLEAVED);
+
+        let mut writer = PairedWriter::new(Writer::new(Vec::new()), Writer::new(Vec::new()));
+        for (r1, r2) in &pairs {
+            writer.write_pair(r1, r2).unwrap();
+        }
+        writer.flush().unwrap();
+        assert_eq!(writer.writer1.writer.get_ref(), &READS_1);
+        assert_eq!(writer.writer2.unwrap().writer.get_ref(), &READS_2);
+    }
+
+    #[test]
+    fn test_ref_records() {
+        let fastq =
+            b"@id desc\nACCG\nTA\n+\nIIII\nJJ\n@id2\r\nGGTA\r\n+id2\r\nJJJJ\r\n@id3\nA\n+\nI";
+        let expected = Reader::new(&fastq[..])
+            .records()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(expected.len(), 3);
+        let mut records = Reader::new(&fastq[..]).ref_records();
+        let mut i = 0;
+        while let Some(record) = records.next() {
+            let record = record.unwrap();
+            assert_eq!(record.check(), Ok(()));
+            assert_eq!(record.id(), expected[i].id());
+            assert_eq!(record.desc(), expected[i].desc());
+            assert_eq!(record.seq(), expected[i].seq());
+            assert_eq!(record.qual(), expected[i].qual());
+            assert_eq!(record.to_owned(), expected[i]);
+            i += 1;
+        }
+        assert_eq!(i, expected.len());
+    }
+
+    #[test]
+    fn test_ref_records_large() {
+        // records larger than the initial buffer, and many records crossing its boundaries
+        let mut fastq = Vec::new();
+        for i in 0..2000 {
+            let len = if i % 500 == 0 { 100_000 } else { 150 };
+            writeln!(fastq, "@id{} desc {}", i, i).unwrap();
+            fastq.extend(vec![b'A' + (i % 4) as u8; len]);
+            fastq.extend_from_slice(b"\n+\n");
+            fastq.extend(vec![b'@' + (i % 4) as u8; len]);
+            fastq.push(b'\n');
+        }
+        let expected = Reader::new(&fastq[..])
+            .records()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let mut records = Reader::new(&fastq[..]).ref_records();
+        let mut i = 0;
+        while let Some(record) = records.next() {
+            assert_eq!(record.unwrap().to_owned(), expected[i]);
+            i += 1;
+        }
+        assert_eq!(i, 2000);
+    }
+
+    #[test]
+    fn test_ref_records_header() {
+        let mut records = Reader::new(&b"@id\tdesc\nACGT\n+\nIIII\n"[..]).ref_records();
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.id(), "id");
+        assert_eq!(record.desc(), Some("desc"));
+    }
+
+    #[test]
+    fn test_ref_records_errors() {
+        let mut records = Reader::new(&b">id\nACGT\n+\nIIII\n@id2\nA\n+\nI\n"[..]).ref_records();
+        assert!(matches!(records.next(), Some(Err(Error::MissingAt))));
+        assert!(records.next().is_none());
+
+        let mut records = Reader::new(&b"@id\nACGT\n"[..]).ref_records();
+        assert!(matches!(records.next(), Some(Err(Error::IncompleteRecord))));
+    }
 }
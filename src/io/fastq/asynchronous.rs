@@ -0,0 +1,344 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Non-blocking FASTQ reading and writing on top of tokio's `AsyncRead` and `AsyncWrite`.
+
+use std::future::poll_fn;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
+
+use super::{Error, Record, Result};
+use crate::io::fasta::asynchronous::{line_str, poll_read_line};
+use crate::utils::TextSlice;
+
+/// The part of a record that is parsed next.
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Header,
+    /// Sequence lines, with the number of lines read so far.
+    Seq(usize),
+    /// Quality lines, with the number of lines still to be read.
+    Qual(usize),
+}
+
+/// An asynchronous FastQ reader, the non-blocking counterpart of [`Reader`](super::Reader).
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fastq::AsyncReader;
+/// use futures_util::StreamExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let fastq: &[u8] = b"@id1 desc\nACGT\n+\nIIII\n@id2\nAA\n+\nJJ\n";
+/// let mut records = AsyncReader::new(fastq).records();
+/// let record = records.next().await.unwrap().unwrap();
+/// assert_eq!(record.id(), "id1");
+/// assert_eq!(record.qual(), b"IIII");
+/// assert_eq!(records.next().await.unwrap().unwrap().id(), "id2");
+/// assert!(records.next().await.is_none());
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncReader<B> {
+    reader: B,
+    line: Vec<u8>,
+    line_complete: bool,
+    record: Record,
+    state: State,
+}
+
+impl<R: AsyncRead + Unpin> AsyncReader<BufReader<R>> {
+    /// Create a new FastQ reader from a given `AsyncRead`, e.g. a `tokio::fs::File` or a
+    /// network stream.
+    pub fn new(reader: R) -> Self {
+        Self::from_bufread(BufReader::new(reader))
+    }
+
+    /// Create a new FastQ reader with the given capacity of the read buffer.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self::from_bufread(BufReader::with_capacity(capacity, reader))
+    }
+}
+
+impl<B: AsyncBufRead + Unpin> AsyncReader<B> {
+    /// Create a new FastQ reader from a given `AsyncBufRead`.
+    pub fn from_bufread(bufreader: B) -> Self {
+        AsyncReader {
+            reader: bufreader,
+            line: Vec::new(),
+            line_complete: false,
+            record: Record::new(),
+            state: State::Header,
+        }
+    }
+
+    /// Read the next FastQ record into the given `Record`.
+    /// An empty record indicates that no more records can be read.
+    ///
+    /// # Errors
+    ///
+    /// Like [`FastqRead::read`](super::FastqRead::read), this function will return an error
+    /// if the record is incomplete, syntax is violated or any form of I/O error is
+    /// encountered.
+    pub async fn read(&mut self, record: &mut Record) -> Result<()> {
+        match poll_fn(|cx| self.poll_record(cx)).await? {
+            Some(next) => *record = next,
+            None => record.clear(),
+        }
+        Ok(())
+    }
+
+    /// Return a `Stream` over the records of this FastQ file.
+    pub fn records(self) -> AsyncRecords<B> {
+        AsyncRecords { reader: self }
+    }
+
+    /// Poll the next line into the line buffer, unless the current one is not consumed yet.
+    fn poll_line(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.line_complete {
+            ready!(poll_read_line(&mut self.reader, cx, &mut self.line))?;
+            self.line_complete = true;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn consume_line(&mut self) {
+        self.line.clear();
+        self.line_complete = false;
+    }
+
+    fn poll_record(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Record>>> {
+        let result = ready!(self.poll_record_inner(cx));
+        if result.is_err() {
+            // like the synchronous reader, continue with the next line after an error
+            self.consume_line();
+            self.record.clear();
+            self.state = State::Header;
+        }
+        Poll::Ready(result)
+    }
+
+    fn poll_record_inner(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<Record>>> {
+        loop {
+            if let State::Qual(0) = self.state {
+                self.state = State::Header;
+                if self.record.qual.is_empty() {
+                    return Poll::Ready(Err(Error::IncompleteRecord));
+                }
+                return Poll::Ready(Ok(Some(mem::take(&mut self.record))));
+            }
+            ready!(self.poll_line(cx))?;
+            let line = line_str(&self.line)?;
+            match self.state {
+                State::Header => {
+                    if line.is_empty() {
+                        return Poll::Ready(Ok(None));
+                    }
+                    if !line.starts_with('@') {
+                        return Poll::Ready(Err(Error::MissingAt));
+                    }
+                    let mut header_fields = line[1..].trim_end().splitn(2, ' ');
+                    self.record.id = header_fields.next().unwrap_or_default().to_owned();
+                    self.record.desc = header_fields.next().map(|s| s.to_owned());
+                    self.state = State::Seq(0);
+                }
+                State::Seq(lines_read) => {
+                    if line.is_empty() || line.starts_with('+') {
+                        self.state = State::Qual(lines_read);
+                    } else {
+                        self.record.seq.push_str(line.trim_end());
+                        self.state = State::Seq(lines_read + 1);
+                    }
+                }
+                State::Qual(remaining) => {
+                    self.record.qual.push_str(line.trim_end());
+                    self.state = State::Qual(remaining - 1);
+                }
+            }
+            self.consume_line();
+        }
+    }
+}
+
+/// A `Stream` over the records of a FastQ file.
+#[derive(Debug)]
+pub struct AsyncRecords<B> {
+    reader: AsyncReader<B>,
+}
+
+impl<B: AsyncBufRead + Unpin> Stream for AsyncRecords<B> {
+    type Item = Result<Record>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().reader.poll_record(cx).map(Result::transpose)
+    }
+}
+
+/// An asynchronous FastQ writer, the non-blocking counterpart of [`Writer`](super::Writer).
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fastq::{AsyncWriter, Record};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncWriter::new(Vec::new());
+/// let record = Record::with_attrs("id", Some("desc"), b"ACGT", b"IIII");
+/// writer.write_record(&record).await.unwrap();
+/// let fastq = writer.into_inner().await.unwrap();
+/// assert_eq!(fastq, b"@id desc\nACGT\n+\nIIII\n");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncWriter<W: AsyncWrite + Unpin> {
+    writer: BufWriter<W>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWriter<W> {
+    /// Create a new FastQ writer.
+    pub fn new(writer: W) -> Self {
+        Self::from_bufwriter(BufWriter::new(writer))
+    }
+
+    /// Create a new FastQ writer with the given capacity of the write buffer.
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self::from_bufwriter(BufWriter::with_capacity(capacity, writer))
+    }
+
+    /// Create a new FastQ writer with a given `BufWriter`.
+    pub fn from_bufwriter(bufwriter: BufWriter<W>) -> Self {
+        AsyncWriter { writer: bufwriter }
+    }
+
+    /// Write a [`Record`].
+    pub async fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.write(record.id(), record.desc(), record.seq(), record.qual())
+            .await
+    }
+
+    /// Write a FastQ record with given id, optional description, sequence and qualities.
+    pub async fn write(
+        &mut self,
+        id: &str,
+        desc: Option<&str>,
+        seq: TextSlice<'_>,
+        qual: &[u8],
+    ) -> io::Result<()> {
+        self.writer.write_all(b"@").await?;
+        self.writer.write_all(id.as_bytes()).await?;
+        if let Some(desc) = desc {
+            self.writer.write_all(b" ").await?;
+            self.writer.write_all(desc.as_bytes()).await?;
+        }
+        self.writer.write_all(b"\n").await?;
+        self.writer.write_all(seq).await?;
+        self.writer.write_all(b"\n+\n").await?;
+        self.writer.write_all(qual).await?;
+        self.writer.write_all(b"\n").await?;
+
+        Ok(())
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush().await
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub async fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush().await?;
+        Ok(self.writer.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::fastq::{FastqRead, Reader};
+    use futures_util::StreamExt;
+
+    const FASTQ_FILE: &[u8] = b"@id desc
+ACCGTAGGCTGA
++
+IIIIIIJJJJJJ
+@id2
+ACCGTAGG
+CTGA
++
+IIIIIIJJ
+JJJJ
+@id3 a b
+AC
++
+II
+";
+
+    #[tokio::test]
+    async fn test_records_match_sync_reader() {
+        let expected: Vec<Record> = Reader::new(FASTQ_FILE)
+            .records()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(expected.len(), 3);
+        let records: Vec<Record> = AsyncReader::with_capacity(4, FASTQ_FILE)
+            .records()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(records, expected);
+
+        let mut reader = AsyncReader::new(FASTQ_FILE);
+        let mut sync_reader = Reader::new(FASTQ_FILE);
+        let (mut record, mut sync_record) = (Record::new(), Record::new());
+        loop {
+            reader.read(&mut record).await.unwrap();
+            sync_reader.read(&mut sync_record).unwrap();
+            assert_eq!(record, sync_record);
+            if record.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let mut records = AsyncReader::new(&b"id\nACGT\n+\nIIII\n"[..]).records();
+        assert!(matches!(
+            records.next().await.unwrap(),
+            Err(Error::MissingAt)
+        ));
+
+        let mut records = AsyncReader::new(&b"@id\nACGT\n"[..]).records();
+        assert!(matches!(
+            records.next().await.unwrap(),
+            Err(Error::IncompleteRecord)
+        ));
+        assert!(records.next().await.is_none());
+
+        let mut records = AsyncReader::new(&b"@id\nAC\xffGT\n+\nIIII\n"[..]).records();
+        assert!(matches!(
+            records.next().await.unwrap(),
+            Err(Error::ReadError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_writer() {
+        let record = Record::with_attrs("id", Some("desc"), b"ACCGTAGGCTGA", b"IIIIIIJJJJJJ");
+        let mut writer = AsyncWriter::new(Vec::new());
+        writer.write_record(&record).await.unwrap();
+        writer.write("id2", None, b"AC", b"II").await.unwrap();
+        assert_eq!(
+            writer.into_inner().await.unwrap(),
+            b"@id desc\nACCGTAGGCTGA\n+\nIIIIIIJJJJJJ\n@id2\nAC\n+\nII\n"
+        );
+    }
+}
@@ -179,6 +179,9 @@ use std::mem;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::io::compression::Decoder;
+#[cfg(feature = "rayon")]
+use crate::io::parallel::{self, ChunkFormat, ParRecords};
 use crate::io::{fasta, fastq};
 use crate::utils::TextSlice;
 
@@ -243,6 +246,38 @@ impl Record for super::fastq::Record {
     }
 }
 
+impl Record for fasta::RefRecord<'_> {
+    passthrough!(is_empty, bool);
+    passthrough!(check, Result<(), &str>);
+    passthrough!(id, &str);
+    passthrough!(desc, Option<&str>);
+    passthrough!(seq, TextSlice<'_>);
+
+    fn qual(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::FASTA
+    }
+}
+
+impl Record for fastq::RefRecord<'_> {
+    passthrough!(is_empty, bool);
+    passthrough!(check, Result<(), &str>);
+    passthrough!(id, &str);
+    passthrough!(desc, Option<&str>);
+    passthrough!(seq, TextSlice<'_>);
+
+    fn qual(&self) -> Option<&[u8]> {
+        Some(self.qual())
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::FASTQ
+    }
+}
+
 #[derive(Clone, Display, Debug, Serialize, Deserialize)]
 pub enum EitherRecord {
     FASTA(fasta::Record),
@@ -333,6 +368,18 @@ impl EitherRecords<BufReader<fs::File>> {
     }
 }
 
+impl EitherRecords<BufReader<Decoder<fs::File>>> {
+    /// Read from a given file.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(path.as_ref())
+            .and_then(Decoder::new)
+            .map(BufReader::new)
+            .map(EitherRecords::from)
+            .with_context(|| format!("Failed to read fastq from {:#?}", path))
+    }
+}
+
 impl<R: BufRead> EitherRecords<R> {
     pub fn new(reader: R) -> Self {
         EitherRecords::from(reader)
@@ -350,6 +397,45 @@ impl<R: BufRead> EitherRecords<R> {
         }
     }
 
+    /// Return a parallel iterator over the records, which are parsed in chunks on the rayon
+    /// thread pool (see [`parallel`](crate::io::parallel)).
+    #[cfg(feature = "rayon")]
+    pub fn par_records(self) -> ParRecords<EitherRecord, Error>
+    where
+        R: Send + 'static,
+    {
+        match (self.reader, self.records) {
+            (Some(reader), _) => ParRecords::new(reader, ChunkFormat::Either, |chunk: &[u8]| {
+                parallel::collect_until_err(EitherRecords::new(chunk))
+            }),
+            (None, Some(EitherRecordsInner::FASTA(records))) => ParRecords::new(
+                records.into_reader().into_raw(),
+                ChunkFormat::Fasta,
+                |chunk: &[u8]| {
+                    parallel::collect_until_err(
+                        fasta::Reader::new(chunk)
+                            .records()
+                            .map(|record| record.map(EitherRecord::FASTA).map_err(Error::IO)),
+                    )
+                },
+            ),
+            (None, Some(EitherRecordsInner::FASTQ(records))) => ParRecords::new(
+                records.into_reader().into_raw(),
+                ChunkFormat::Fastq,
+                |chunk: &[u8]| {
+                    parallel::collect_until_err(
+                        fastq::Reader::new(chunk)
+                            .records()
+                            .map(|record| record.map(EitherRecord::FASTQ).map_err(Error::FASTQ)),
+                    )
+                },
+            ),
+            (None, None) => {
+                ParRecords::new(io::empty(), ChunkFormat::Either, |_: &[u8]| Vec::new())
+            }
+        }
+    }
+
     fn initialize(&mut self) -> io::Result<()> {
         if let Some(reader) = mem::replace(&mut self.reader, None) {
             match get_kind(reader) {
@@ -566,8 +652,12 @@ pub fn get_kind_seek<R: Read + io::Seek>(reader: &mut R) -> io::Result<Kind> {
 }
 
 /// Determine whether a file is a FASTA or FASTQ.
+/// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
 pub fn get_kind_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> io::Result<Kind> {
-    fs::File::open(&path).and_then(|mut f| get_kind_seek(&mut f))
+    fs::File::open(&path)
+        .and_then(Decoder::new)
+        .and_then(get_kind)
+        .map(|(_, kind)| kind)
 }
 
 impl std::fmt::Display for Kind {
@@ -879,4 +969,28 @@ ACCGTAGGCTGA
         let from_fastq = EitherRecord::from(fastq::Record::with_attrs("asd", None, &[], &[]));
         assert_eq!(from_fastq.id(), "asd");
     }
+
+    #[test]
+    fn test_ref_records_generic() {
+        fn count_bases<T: Record>(record: &T) -> usize {
+            record.seq().len()
+        }
+
+        let mut nb_bases = 0;
+        let mut records = fasta::Reader::new(FASTA_FILE).ref_records();
+        while let Some(record) = records.next() {
+            let record = record.unwrap();
+            assert_eq!(record.kind(), Kind::FASTA);
+            assert!(Record::qual(&record).is_none());
+            nb_bases += count_bases(&record);
+        }
+        assert_eq!(nb_bases, 92);
+
+        let mut records = fastq::Reader::new(FASTQ_FILE).ref_records();
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.kind(), Kind::FASTQ);
+        assert_eq!(Record::qual(&record), Some(&b"IIIIIIJJJJJJ"[..]));
+        assert_eq!(Record::id(&record), "id");
+        assert_eq!(count_bases(&record), 12);
+    }
 }
@@ -0,0 +1,1611 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! [GenBank](https://www.ncbi.nlm.nih.gov/genbank/samplerecord/) flat file reading and writing.
+//!
+//! A `Record` holds the header metadata (LOCUS, DEFINITION, ACCESSION, VERSION, KEYWORDS,
+//! organism and taxonomy, references), the feature table and the sequence. The same types are
+//! used for [EMBL](crate::io::embl) files, so records can be converted between both formats.
+//!
+//! Feature locations support the full INSDC location syntax (`join`, `order`, `complement`,
+//! fuzzy `<`/`>` ends, `(a.b)` ranges, `a^b` sites and remote entries) and can be mapped to
+//! `bio_types::annot` locations. Coordinates of a `Location` are 0-based and half-open, as
+//! everywhere in Rust-Bio; they are converted from and to the 1-based coordinates of the file.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::genbank;
+//!
+//! let example = b"LOCUS       TEST                      20 bp    DNA     linear   SYN 01-JAN-2025
+//! DEFINITION  A test sequence.
+//! ACCESSION   TEST01
+//! VERSION     TEST01.1
+//! FEATURES             Location/Qualifiers
+//!      CDS             join(1..6,13..>20)
+//!                      /gene=\"abc\"
+//! ORIGIN
+//!         1 atgaaacccg ggtttaatag
+//! //
+//! ";
+//! let mut reader = genbank::Reader::new(&example[..]);
+//! let record = reader.records().next().unwrap().unwrap();
+//! assert_eq!(record.definition(), "A test sequence");
+//! assert_eq!(record.seq(), b"atgaaacccgggtttaatag");
+//!
+//! let cds = &record.features()[0];
+//! assert_eq!(cds.qualifier("gene"), Some("abc"));
+//! let spliced = cds.location.to_spliced(record.locus().name.as_str()).unwrap();
+//! assert_eq!(spliced.exon_starts(), vec![0, 12]);
+//! assert_eq!(spliced.exon_lengths(), vec![6, 8]);
+//!
+//! let fasta = record.fasta_record();
+//! assert_eq!(fasta.id(), "TEST01.1");
+//! ```
+
+use std::cmp::{max, min};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use bio_types::annot::contig::Contig;
+use bio_types::annot::spliced::{Spliced, SplicingError};
+use bio_types::strand::ReqStrand;
+use thiserror::Error;
+
+use crate::io::compression::{self, Decoder, Encoder};
+use crate::io::fasta;
+
+/// Width of the key column of GenBank header lines.
+const KEY_WIDTH: usize = 12;
+/// Width of the key column of feature table lines, after the 5 character prefix.
+const FEATURE_KEY_WIDTH: usize = 16;
+/// Maximum line length of written files.
+pub(crate) const LINE_WIDTH: usize = 79;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read or write record")]
+    Io(#[from] io::Error),
+
+    #[error("invalid record in line {line}: {msg}")]
+    InvalidRecord { line: u64, msg: String },
+
+    #[error("invalid location {location:?}: {msg}")]
+    InvalidLocation { location: String, msg: &'static str },
+
+    #[error("location {location} can't be converted: {msg}")]
+    UnsupportedLocation { location: String, msg: &'static str },
+
+    #[error("invalid exon structure")]
+    Splicing(#[from] SplicingError),
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A position of a location, which may be fuzzy.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Position {
+    /// An exact position.
+    Exact(u64),
+    /// A position at or before the given one (`<`).
+    Before(u64),
+    /// A position at or after the given one (`>`).
+    After(u64),
+    /// A single position somewhere within the given bounds (`(a.b)`).
+    Within(u64, u64),
+}
+
+impl Position {
+    /// The lowest coordinate the position may take.
+    pub fn lower(&self) -> u64 {
+        match *self {
+            Position::Exact(pos) | Position::Before(pos) | Position::After(pos) => pos,
+            Position::Within(lower, _) => lower,
+        }
+    }
+
+    /// The highest coordinate the position may take.
+    pub fn upper(&self) -> u64 {
+        match *self {
+            Position::Exact(pos) | Position::Before(pos) | Position::After(pos) => pos,
+            Position::Within(_, upper) => upper,
+        }
+    }
+
+    fn map<F: Fn(u64) -> u64>(self, f: F) -> Self {
+        match self {
+            Position::Exact(pos) => Position::Exact(f(pos)),
+            Position::Before(pos) => Position::Before(f(pos)),
+            Position::After(pos) => Position::After(f(pos)),
+            Position::Within(lower, upper) => Position::Within(f(lower), f(upper)),
+        }
+    }
+
+    fn try_map<F: Fn(u64) -> Option<u64>>(self, f: F) -> Option<Self> {
+        Some(match self {
+            Position::Exact(pos) => Position::Exact(f(pos)?),
+            Position::Before(pos) => Position::Before(f(pos)?),
+            Position::After(pos) => Position::After(f(pos)?),
+            Position::Within(lower, upper) => Position::Within(f(lower)?, f(upper)?),
+        })
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Position::Exact(pos) => write!(f, "{}", pos),
+            Position::Before(pos) => write!(f, "<{}", pos),
+            Position::After(pos) => write!(f, ">{}", pos),
+            Position::Within(lower, upper) => write!(f, "({}.{})", lower, upper),
+        }
+    }
+}
+
+/// The location of a feature.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Location {
+    /// A range of bases with 0-based start and exclusive end (`12..30` in the file becomes
+    /// `11..30`). A single base is a range of length one.
+    Range { start: Position, end: Position },
+    /// The site between two bases (`12^13`), given as the empty range of the 0-based offsets
+    /// before and after it, i.e. `start == end` unless the site spans the origin of a circular
+    /// sequence.
+    Between { start: u64, end: u64 },
+    /// The location on the reverse strand.
+    Complement(Box<Location>),
+    /// Locations joined to a contiguous sequence.
+    Join(Vec<Location>),
+    /// Locations in the given order, without implying that they are joined.
+    Order(Vec<Location>),
+    /// A location on another sequence, given by its accession.
+    Remote {
+        accession: String,
+        location: Box<Location>,
+    },
+}
+
+impl Location {
+    /// A range of bases with exact, 0-based, half-open coordinates.
+    pub fn range(start: u64, end: u64) -> Self {
+        Location::Range {
+            start: Position::Exact(start),
+            end: Position::Exact(end),
+        }
+    }
+
+    /// The leftmost 0-based coordinate of the location on this sequence.
+    pub fn start(&self) -> Option<u64> {
+        self.bounds().map(|(start, _)| start)
+    }
+
+    /// The rightmost 0-based, exclusive coordinate of the location on this sequence.
+    pub fn end(&self) -> Option<u64> {
+        self.bounds().map(|(_, end)| end)
+    }
+
+    fn bounds(&self) -> Option<(u64, u64)> {
+        match self {
+            Location::Range { start, end } => Some((start.lower(), end.upper())),
+            Location::Between { start, end } => Some((min(*start, *end), max(*start, *end))),
+            Location::Complement(location) => location.bounds(),
+            Location::Join(locations) | Location::Order(locations) => locations
+                .iter()
+                .filter_map(|location| location.bounds())
+                .reduce(|(s1, e1), (s2, e2)| (min(s1, s2), max(e1, e2))),
+            Location::Remote { .. } => None,
+        }
+    }
+
+    /// The ranges making up this location as 0-based start, end and strand.
+    fn exons(&self, strand: ReqStrand) -> Result<Vec<(u64, u64, ReqStrand)>> {
+        match self {
+            Location::Range { start, end } => Ok(vec![(start.lower(), end.upper(), strand)]),
+            Location::Complement(location) => location.exons(-strand),
+            Location::Join(locations) | Location::Order(locations) => {
+                let mut exons = Vec::new();
+                for location in locations {
+                    exons.extend(location.exons(strand)?);
+                }
+                Ok(exons)
+            }
+            Location::Between { .. } => Err(self.unsupported("sites between bases have no extent")),
+            Location::Remote { .. } => Err(self.unsupported("location is on another sequence")),
+        }
+    }
+
+    fn unsupported(&self, msg: &'static str) -> Error {
+        Error::UnsupportedLocation {
+            location: self.to_string(),
+            msg,
+        }
+    }
+
+    /// The strand-specific exons of the location, sorted by position.
+    fn stranded_exons(&self) -> Result<(Vec<(u64, u64)>, ReqStrand)> {
+        let exons = self.exons(ReqStrand::Forward)?;
+        let strand = exons
+            .first()
+            .ok_or_else(|| self.unsupported("location is empty"))?
+            .2;
+        if exons.iter().any(|exon| exon.2 != strand) {
+            return Err(self.unsupported("location spans both strands"));
+        }
+        let mut exons: Vec<_> = exons.into_iter().map(|(s, e, _)| (s, e)).collect();
+        exons.sort_unstable();
+        Ok((exons, strand))
+    }
+
+    /// The strand of the location, or `None` if it is ambiguous, e.g. for remote locations or
+    /// locations spanning both strands.
+    pub fn strand(&self) -> Option<ReqStrand> {
+        self.stranded_exons().ok().map(|(_, strand)| strand)
+    }
+
+    /// The contiguous region covered by this location on the sequence `refid`.
+    pub fn to_contig(&self, refid: &str) -> Result<Contig<String, ReqStrand>> {
+        let (exons, strand) = self.stranded_exons()?;
+        let start = exons.iter().map(|exon| exon.0).min().unwrap_or(0);
+        let end = exons.iter().map(|exon| exon.1).max().unwrap_or(0);
+        Ok(Contig::new(
+            refid.to_owned(),
+            start as isize,
+            end.saturating_sub(start) as usize,
+            strand,
+        ))
+    }
+
+    /// The exons of this location as a spliced location on the sequence `refid`. The location
+    /// has to lie on a single strand, and its parts must neither overlap nor abut.
+    pub fn to_spliced(&self, refid: &str) -> Result<Spliced<String, ReqStrand>> {
+        let (exons, strand) = self.stranded_exons()?;
+        let start = exons[0].0;
+        let lengths: Vec<usize> = exons
+            .iter()
+            .map(|(s, e)| e.saturating_sub(*s) as usize)
+            .collect();
+        let starts: Vec<usize> = exons.iter().map(|(s, _)| (s - start) as usize).collect();
+        Ok(Spliced::with_lengths_starts(
+            refid.to_owned(),
+            start as isize,
+            &lengths,
+            &starts,
+            strand,
+        )?)
+    }
+}
+
+impl FromStr for Location {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut parser = LocationParser {
+            s: &compact,
+            pos: 0,
+        };
+        let location = parser.location()?;
+        if parser.pos != compact.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(location)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list(f: &mut fmt::Formatter<'_>, name: &str, locations: &[Location]) -> fmt::Result {
+            write!(f, "{}(", name)?;
+            for (i, location) in locations.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}", location)?;
+            }
+            f.write_str(")")
+        }
+
+        match self {
+            Location::Range { start, end } => {
+                if start.map(|pos| pos + 1) == *end {
+                    write!(f, "{}", end)
+                } else {
+                    write!(f, "{}..{}", start.map(|pos| pos + 1), end)
+                }
+            }
+            Location::Between { start, end } => write!(f, "{}^{}", start, end + 1),
+            Location::Complement(location) => write!(f, "complement({})", location),
+            Location::Join(locations) => list(f, "join", locations),
+            Location::Order(locations) => list(f, "order", locations),
+            Location::Remote {
+                accession,
+                location,
+            } => write!(f, "{}:{}", accession, location),
+        }
+    }
+}
+
+/// A recursive descent parser for INSDC feature locations.
+struct LocationParser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> LocationParser<'a> {
+    fn error(&self, msg: &'static str) -> Error {
+        Error::InvalidLocation {
+            location: self.s.to_owned(),
+            msg,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+
+    fn location(&mut self) -> Result<Location> {
+        if self.eat("complement(") {
+            let location = self.location()?;
+            self.expect(")")?;
+            Ok(Location::Complement(Box::new(location)))
+        } else if self.eat("join(") {
+            Ok(Location::Join(self.list()?))
+        } else if self.eat("order(") {
+            Ok(Location::Order(self.list()?))
+        } else {
+            let rest = self.rest();
+            if let Some(colon) = rest.find(':') {
+                let accession = &rest[..colon];
+                if !accession.is_empty()
+                    && !accession.contains("..")
+                    && accession
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+                {
+                    self.pos += colon + 1;
+                    return Ok(Location::Remote {
+                        accession: accession.to_owned(),
+                        location: Box::new(self.location()?),
+                    });
+                }
+            }
+            self.simple()
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<Location>> {
+        let mut locations = vec![self.location()?];
+        while self.eat(",") {
+            locations.push(self.location()?);
+        }
+        self.expect(")")?;
+        Ok(locations)
+    }
+
+    fn simple(&mut self) -> Result<Location> {
+        let first = self.position()?;
+        let start = first
+            .try_map(|pos| pos.checked_sub(1))
+            .ok_or_else(|| self.error("positions are 1-based"))?;
+        if self.eat("..") {
+            let end = self.position()?;
+            Ok(Location::Range { start, end })
+        } else if self.eat("^") {
+            let end = self.number()?;
+            match first {
+                Position::Exact(start) if end > 0 => Ok(Location::Between {
+                    start,
+                    end: end - 1,
+                }),
+                _ => Err(self.error("invalid site between bases")),
+            }
+        } else {
+            Ok(Location::Range { start, end: first })
+        }
+    }
+
+    fn position(&mut self) -> Result<Position> {
+        if self.eat("<") {
+            Ok(Position::Before(self.number()?))
+        } else if self.eat(">") {
+            Ok(Position::After(self.number()?))
+        } else if self.eat("(") {
+            let lower = self.number()?;
+            self.expect(".")?;
+            let upper = self.number()?;
+            self.expect(")")?;
+            Ok(Position::Within(lower, upper))
+        } else {
+            let pos = self.number()?;
+            let rest = self.rest();
+            if rest.starts_with('.') && rest[1..].starts_with(|c: char| c.is_ascii_digit()) {
+                self.pos += 1;
+                Ok(Position::Within(pos, self.number()?))
+            } else {
+                Ok(Position::Exact(pos))
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<u64> {
+        let digits = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| self.rest().len());
+        let number = self.rest()[..digits]
+            .parse()
+            .map_err(|_| self.error("expected number"))?;
+        self.pos += digits;
+        Ok(number)
+    }
+}
+
+/// A feature of the feature table.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Feature {
+    /// The feature key, e.g. `gene` or `CDS`.
+    pub kind: String,
+    pub location: Location,
+    /// Qualifiers as key and value, which is `None` for qualifiers without value such as
+    /// `/pseudo`.
+    pub qualifiers: Vec<(String, Option<String>)>,
+}
+
+impl Feature {
+    /// Create a new feature without qualifiers.
+    pub fn new(kind: &str, location: Location) -> Self {
+        Feature {
+            kind: kind.to_owned(),
+            location,
+            qualifiers: Vec::new(),
+        }
+    }
+
+    /// Value of the first qualifier with the given key.
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Values of all qualifiers with the given key.
+    pub fn qualifier_values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.qualifiers
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .filter_map(|(_, value)| value.as_deref())
+    }
+
+    /// Add a qualifier.
+    pub fn push_qualifier(&mut self, key: &str, value: Option<&str>) {
+        self.qualifiers
+            .push((key.to_owned(), value.map(|value| value.to_owned())));
+    }
+}
+
+/// The LOCUS line of a GenBank record, or the ID line of an EMBL record.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Locus {
+    pub name: String,
+    /// Sequence length.
+    pub length: u64,
+    /// Molecule type, e.g. `DNA` or `mRNA`.
+    pub molecule_type: String,
+    pub circular: bool,
+    /// Three letter division code, e.g. `PLN`.
+    pub division: String,
+    /// Date of the last modification, e.g. `21-JUN-1999`.
+    pub date: String,
+}
+
+/// A literature reference.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Reference {
+    pub number: usize,
+    /// The ranges of the sequence the reference applies to, with 0-based, half-open
+    /// coordinates.
+    pub ranges: Vec<(u64, u64)>,
+    pub authors: String,
+    pub consortium: String,
+    pub title: String,
+    pub journal: String,
+    pub pubmed: Option<String>,
+}
+
+/// A GenBank or EMBL record.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Record {
+    locus: Locus,
+    definition: String,
+    accessions: Vec<String>,
+    version: Option<String>,
+    keywords: Vec<String>,
+    source: String,
+    organism: String,
+    taxonomy: Vec<String>,
+    references: Vec<Reference>,
+    comment: String,
+    other: Vec<(String, String)>,
+    features: Vec<Feature>,
+    seq: Vec<u8>,
+}
+
+impl Record {
+    /// Create a new, empty record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The LOCUS line.
+    pub fn locus(&self) -> &Locus {
+        &self.locus
+    }
+
+    /// Mutable access to the LOCUS line.
+    pub fn locus_mut(&mut self) -> &mut Locus {
+        &mut self.locus
+    }
+
+    /// Description of the sequence, without the trailing period of GenBank files.
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+
+    /// Set the description of the sequence.
+    pub fn set_definition(&mut self, definition: &str) {
+        self.definition = definition.to_owned();
+    }
+
+    /// Accessions, the primary accession first.
+    pub fn accessions(&self) -> &[String] {
+        &self.accessions
+    }
+
+    /// Mutable access to the accessions.
+    pub fn accessions_mut(&mut self) -> &mut Vec<String> {
+        &mut self.accessions
+    }
+
+    /// Versioned accession, e.g. `U49845.1`.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Set the versioned accession.
+    pub fn set_version(&mut self, version: Option<&str>) {
+        self.version = version.map(|version| version.to_owned());
+    }
+
+    /// Keywords.
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    /// Mutable access to the keywords.
+    pub fn keywords_mut(&mut self) -> &mut Vec<String> {
+        &mut self.keywords
+    }
+
+    /// Free text description of the source organism (SOURCE or OS line).
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Set the description of the source organism.
+    pub fn set_source(&mut self, source: &str) {
+        self.source = source.to_owned();
+    }
+
+    /// Scientific name of the source organism.
+    pub fn organism(&self) -> &str {
+        &self.organism
+    }
+
+    /// Set the scientific name of the source organism.
+    pub fn set_organism(&mut self, organism: &str) {
+        self.organism = organism.to_owned();
+    }
+
+    /// Taxonomic lineage of the source organism, from the root.
+    pub fn taxonomy(&self) -> &[String] {
+        &self.taxonomy
+    }
+
+    /// Mutable access to the taxonomic lineage.
+    pub fn taxonomy_mut(&mut self) -> &mut Vec<String> {
+        &mut self.taxonomy
+    }
+
+    /// Literature references.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Mutable access to the literature references.
+    pub fn references_mut(&mut self) -> &mut Vec<Reference> {
+        &mut self.references
+    }
+
+    /// Free text comment, with lines separated by newlines.
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Set the comment.
+    pub fn set_comment(&mut self, comment: &str) {
+        self.comment = comment.to_owned();
+    }
+
+    /// Other header lines as key and text, with lines separated by newlines. They are written
+    /// back unchanged.
+    pub fn other(&self) -> &[(String, String)] {
+        &self.other
+    }
+
+    /// Mutable access to other header lines.
+    pub fn other_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.other
+    }
+
+    /// The feature table.
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Mutable access to the feature table.
+    pub fn features_mut(&mut self) -> &mut Vec<Feature> {
+        &mut self.features
+    }
+
+    /// The sequence.
+    pub fn seq(&self) -> &[u8] {
+        &self.seq
+    }
+
+    /// Set the sequence. This updates the length given in the LOCUS line.
+    pub fn set_seq(&mut self, seq: &[u8]) {
+        self.seq = seq.to_owned();
+        self.locus.length = seq.len() as u64;
+    }
+
+    /// The sequence as FASTA record. The identifier is the versioned accession if present,
+    /// else the primary accession or the locus name, and the description is the definition.
+    pub fn fasta_record(&self) -> fasta::Record {
+        let id = self
+            .version
+            .as_deref()
+            .or_else(|| self.accessions.first().map(|acc| acc.as_str()))
+            .unwrap_or(&self.locus.name);
+        let desc = if self.definition.is_empty() {
+            None
+        } else {
+            Some(self.definition.as_str())
+        };
+        fasta::Record::with_attrs(id, desc, &self.seq)
+    }
+}
+
+/// Line-based reading with one line of lookahead, shared with the EMBL reader.
+#[derive(Debug)]
+pub(crate) struct LineReader<R: io::Read> {
+    inner: io::BufReader<R>,
+    pending: Option<String>,
+    line: u64,
+}
+
+impl<R: io::Read> LineReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        LineReader {
+            inner: io::BufReader::new(reader),
+            pending: None,
+            line: 0,
+        }
+    }
+
+    /// Return the next line without line terminator.
+    pub(crate) fn next_line(&mut self) -> Result<Option<String>> {
+        if let Some(line) = self.pending.take() {
+            return Ok(Some(line));
+        }
+        let mut line = String::new();
+        if self.inner.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        let len = line.trim_end_matches(&['\n', '\r'][..]).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+
+    /// Return the next line of a record, failing at the end of input.
+    pub(crate) fn record_line(&mut self) -> Result<String> {
+        match self.next_line()? {
+            Some(line) => Ok(line),
+            None => Err(self.error("unexpected end of file, missing '//'")),
+        }
+    }
+
+    /// Put back a line to be returned by the next call to `next_line`.
+    pub(crate) fn unread(&mut self, line: String) {
+        self.pending = Some(line);
+    }
+
+    pub(crate) fn error(&self, msg: &str) -> Error {
+        Error::InvalidRecord {
+            line: self.line,
+            msg: msg.to_owned(),
+        }
+    }
+}
+
+/// Split a header line into the (trimmed) key column and the text after it.
+fn split_key(line: &str, width: usize) -> (&str, &str) {
+    match line.char_indices().nth(width) {
+        Some((i, _)) => (line[..i].trim(), line[i..].trim_end()),
+        None => (line.trim(), ""),
+    }
+}
+
+/// Split a string at `;` into trimmed, non-empty items, ignoring a trailing period.
+pub(crate) fn split_list(text: &str) -> Vec<String> {
+    text.trim()
+        .trim_end_matches('.')
+        .split(';')
+        .map(|item| item.trim().to_owned())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Parse the lines of a feature table, with the 5 character line prefix removed.
+pub(crate) fn parse_features<R: io::Read>(
+    lines: &[String],
+    reader: &LineReader<R>,
+) -> Result<Vec<Feature>> {
+    struct Pending {
+        kind: String,
+        location: String,
+        qualifiers: Vec<(String, Option<String>)>,
+    }
+
+    fn finish(pending: Pending) -> Result<Feature> {
+        let qualifiers = pending
+            .qualifiers
+            .into_iter()
+            .map(|(key, value)| {
+                let value = value.map(|value| match value.strip_prefix('"') {
+                    Some(quoted) => quoted
+                        .strip_suffix('"')
+                        .unwrap_or(quoted)
+                        .replace("\"\"", "\""),
+                    None => value,
+                });
+                (key, value)
+            })
+            .collect();
+        Ok(Feature {
+            kind: pending.kind,
+            location: pending.location.parse()?,
+            qualifiers,
+        })
+    }
+
+    fn is_open(value: &Option<String>) -> bool {
+        matches!(value, Some(value) if value.starts_with('"')
+            && (value.len() == 1 || !value.ends_with('"') || value.matches('"').count() % 2 == 1))
+    }
+
+    let mut features = Vec::new();
+    let mut pending: Option<Pending> = None;
+    for line in lines {
+        let (key, text) = split_key(line, FEATURE_KEY_WIDTH);
+        let text = text.trim();
+        if !key.is_empty() {
+            if let Some(pending) = pending.take() {
+                features.push(finish(pending)?);
+            }
+            pending = Some(Pending {
+                kind: key.to_owned(),
+                location: text.to_owned(),
+                qualifiers: Vec::new(),
+            });
+            continue;
+        }
+        let current = match pending.as_mut() {
+            Some(current) => current,
+            None => return Err(reader.error("feature table line without feature key")),
+        };
+        let open = current.qualifiers.last().is_some_and(|(_, v)| is_open(v));
+        if let (Some(qualifier), false) = (text.strip_prefix('/'), open) {
+            let (key, value) = match qualifier.split_once('=') {
+                Some((key, value)) => (key, Some(value.to_owned())),
+                None => (qualifier, None),
+            };
+            current.qualifiers.push((key.to_owned(), value));
+        } else if let Some((key, value)) = current.qualifiers.last_mut() {
+            let value = value.get_or_insert_with(String::new);
+            if key != "translation" {
+                value.push(' ');
+            }
+            value.push_str(text);
+        } else {
+            current.location.push_str(text);
+        }
+    }
+    if let Some(pending) = pending {
+        features.push(finish(pending)?);
+    }
+    Ok(features)
+}
+
+/// Wrap text at spaces into lines of at most `width` characters. Words longer than `width`,
+/// or all text if `anywhere` is true, are broken at arbitrary positions.
+pub(crate) fn wrap(text: &str, width: usize, anywhere: bool) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let push_chunks = |current: &mut String, lines: &mut Vec<String>, word: &str| {
+        let chars: Vec<char> = word.chars().collect();
+        for chunk in chars.chunks(width) {
+            if current.chars().count() == width {
+                lines.push(std::mem::take(current));
+            }
+            current.extend(chunk);
+        }
+    };
+    if anywhere {
+        push_chunks(&mut current, &mut lines, text);
+    } else {
+        for word in text.split(' ') {
+            let len = current.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut current));
+            } else if len > 0 {
+                current.push(' ');
+            }
+            if word.chars().count() > width {
+                push_chunks(&mut current, &mut lines, word);
+            } else {
+                current.push_str(word);
+            }
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+/// Wrap a location after commas into lines of at most `width` characters.
+fn wrap_location(location: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for part in location.split_inclusive(',') {
+        for chunk in wrap(part, width, true) {
+            let current = lines.last().unwrap();
+            if !current.is_empty() && current.len() + chunk.len() > width {
+                lines.push(String::new());
+            }
+            lines.last_mut().unwrap().push_str(&chunk);
+        }
+    }
+    lines
+}
+
+/// Write a feature table. Every line starts with `prefix`, which is 5 characters wide.
+pub(crate) fn write_features<W: io::Write>(
+    writer: &mut W,
+    prefix: &str,
+    features: &[Feature],
+) -> io::Result<()> {
+    let width = LINE_WIDTH - prefix.len() - FEATURE_KEY_WIDTH;
+    let indent = format!("{}{:width$}", prefix, "", width = FEATURE_KEY_WIDTH);
+    for feature in features {
+        for (i, line) in wrap_location(&feature.location.to_string(), width)
+            .iter()
+            .enumerate()
+        {
+            if i == 0 {
+                writeln!(
+                    writer,
+                    "{}{:width$}{}",
+                    prefix,
+                    feature.kind,
+                    line,
+                    width = FEATURE_KEY_WIDTH
+                )?;
+            } else {
+                writeln!(writer, "{}{}", indent, line)?;
+            }
+        }
+        for (key, value) in &feature.qualifiers {
+            let text = match value {
+                None => format!("/{}", key),
+                Some(value)
+                    if !value.is_empty()
+                        && (value.bytes().all(|c| c.is_ascii_digit())
+                            || (value.starts_with('(') && !value.contains(' '))) =>
+                {
+                    format!("/{}={}", key, value)
+                }
+                Some(value) => format!("/{}=\"{}\"", key, value.replace('"', "\"\"")),
+            };
+            for line in wrap(&text, width, key == "translation") {
+                writeln!(writer, "{}{}", indent, line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse reference ranges like `(bases 1 to 5028; 6000 to 7000)` into 0-based ranges.
+/// Ranges that cannot be parsed are skipped.
+fn parse_reference_ranges(text: &str) -> Vec<(u64, u64)> {
+    text.trim_matches(|c| c == '(' || c == ')')
+        .trim_start_matches("bases")
+        .split(';')
+        .filter_map(|range| {
+            let (start, end) = range.split_once("to")?;
+            Some((
+                start.trim().parse::<u64>().ok()?.checked_sub(1)?,
+                end.trim().parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Append a continuation line to a free text field.
+pub(crate) fn append(field: &mut String, text: &str) {
+    if !field.is_empty() {
+        field.push(' ');
+    }
+    field.push_str(text.trim());
+}
+
+/// A GenBank reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: LineReader<R>,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read GenBank from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read GenBank from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: LineReader::new(reader),
+        }
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self }
+    }
+
+    /// Read the next record, if any. Lines before the first LOCUS line are skipped.
+    pub fn read(&mut self) -> Result<Option<Record>> {
+        let locus = loop {
+            match self.inner.next_line()? {
+                Some(line) if line.starts_with("LOCUS") => break line,
+                Some(_) => continue,
+                None => return Ok(None),
+            }
+        };
+        let mut record = Record {
+            locus: self.parse_locus(&locus)?,
+            ..Default::default()
+        };
+
+        loop {
+            let first = self.inner.record_line()?;
+            if first.starts_with("//") {
+                break;
+            }
+            let mut block = vec![first];
+            loop {
+                let line = self.inner.record_line()?;
+                if line.starts_with(' ') || line.is_empty() {
+                    block.push(line);
+                } else {
+                    self.inner.unread(line);
+                    break;
+                }
+            }
+            self.parse_block(&mut record, &block)?;
+        }
+        Ok(Some(record))
+    }
+
+    fn parse_locus(&self, line: &str) -> Result<Locus> {
+        let fields: Vec<&str> = line.split_whitespace().skip(1).collect();
+        if fields.len() < 3 {
+            return Err(self.inner.error("incomplete LOCUS line"));
+        }
+        let length = fields[1]
+            .parse()
+            .map_err(|_| self.inner.error("invalid sequence length in LOCUS line"))?;
+        let mut rest = &fields[3..];
+        let mut locus = Locus {
+            name: fields[0].to_owned(),
+            length,
+            ..Default::default()
+        };
+        if let Some((date, init)) = rest.split_last() {
+            if date.contains('-') {
+                locus.date = (*date).to_owned();
+                rest = init;
+            }
+        }
+        if let Some((division, init)) = rest.split_last() {
+            if division.len() == 3 && division.bytes().all(|c| c.is_ascii_uppercase()) {
+                locus.division = (*division).to_owned();
+                rest = init;
+            }
+        }
+        let mut molecule = Vec::new();
+        for field in rest {
+            match *field {
+                "linear" => locus.circular = false,
+                "circular" => locus.circular = true,
+                _ => molecule.push(*field),
+            }
+        }
+        locus.molecule_type = molecule.join(" ");
+        Ok(locus)
+    }
+
+    fn parse_block(&self, record: &mut Record, block: &[String]) -> Result<()> {
+        let (key, text) = split_key(&block[0], KEY_WIDTH);
+        let continuation = || {
+            block[1..]
+                .iter()
+                .map(|line| split_key(line, KEY_WIDTH).1.trim())
+        };
+        match key {
+            "DEFINITION" | "KEYWORDS" | "ACCESSION" => {
+                let mut joined = text.trim().to_owned();
+                continuation().for_each(|line| append(&mut joined, line));
+                match key {
+                    "DEFINITION" => {
+                        record.definition = joined.trim_end_matches('.').to_owned();
+                    }
+                    "KEYWORDS" => record.keywords = split_list(&joined),
+                    _ => {
+                        record.accessions =
+                            joined.split_whitespace().map(|a| a.to_owned()).collect()
+                    }
+                }
+            }
+            "VERSION" => {
+                record.version = text.split_whitespace().next().map(|v| v.to_owned());
+            }
+            "SOURCE" => {
+                record.source = text.trim().to_owned();
+                let mut taxonomy = String::new();
+                let mut in_organism = false;
+                for line in &block[1..] {
+                    let (subkey, text) = split_key(line, KEY_WIDTH);
+                    match subkey {
+                        "ORGANISM" => {
+                            record.organism = text.trim().to_owned();
+                            in_organism = true;
+                        }
+                        "" if in_organism => append(&mut taxonomy, text),
+                        "" => append(&mut record.source, text),
+                        _ => in_organism = false,
+                    }
+                }
+                record.taxonomy = split_list(&taxonomy);
+            }
+            "REFERENCE" => {
+                let mut reference = Reference::default();
+                let mut fields = text.trim().splitn(2, char::is_whitespace);
+                reference.number = fields
+                    .next()
+                    .and_then(|number| number.parse().ok())
+                    .ok_or_else(|| self.inner.error("invalid REFERENCE number"))?;
+                reference.ranges = parse_reference_ranges(fields.next().unwrap_or("").trim());
+                let mut current: Option<&mut String> = None;
+                for line in &block[1..] {
+                    let (subkey, text) = split_key(line, KEY_WIDTH);
+                    current = match subkey {
+                        "AUTHORS" => Some(&mut reference.authors),
+                        "CONSRTM" => Some(&mut reference.consortium),
+                        "TITLE" => Some(&mut reference.title),
+                        "JOURNAL" => Some(&mut reference.journal),
+                        "PUBMED" => {
+                            reference.pubmed = Some(text.trim().to_owned());
+                            None
+                        }
+                        "" => current,
+                        _ => None,
+                    };
+                    if let Some(field) = current.as_mut() {
+                        append(field, text);
+                    }
+                }
+                record.references.push(reference);
+            }
+            "COMMENT" => {
+                let lines: Vec<&str> = std::iter::once(text.trim_end())
+                    .chain(block[1..].iter().map(|line| split_key(line, KEY_WIDTH).1))
+                    .collect();
+                record.comment = lines.join("\n");
+            }
+            "FEATURES" => {
+                let lines: Vec<String> = block[1..]
+                    .iter()
+                    .map(|line| line.chars().skip(5).collect())
+                    .collect();
+                record.features = parse_features(&lines, &self.inner)?;
+            }
+            "ORIGIN" => {
+                record.seq = block[1..]
+                    .iter()
+                    .flat_map(|line| line.bytes())
+                    .filter(|c| c.is_ascii_alphabetic() || *c == b'*' || *c == b'-')
+                    .collect();
+            }
+            _ => {
+                let lines: Vec<&str> = std::iter::once(text.trim_end())
+                    .chain(block[1..].iter().map(|line| split_key(line, KEY_WIDTH).1))
+                    .collect();
+                record.other.push((key.to_owned(), lines.join("\n")));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the records of a GenBank file.
+pub struct Records<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        self.reader.read().transpose()
+    }
+}
+
+/// A GenBank writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`compression::Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = compression::Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write a header field, wrapping its text.
+    fn write_field(&mut self, key: &str, text: &str) -> io::Result<()> {
+        for (i, line) in text.split('\n').enumerate() {
+            for (j, wrapped) in wrap(line, LINE_WIDTH - KEY_WIDTH, false).iter().enumerate() {
+                let key = if i == 0 && j == 0 { key } else { "" };
+                writeln!(self.inner, "{:width$}{}", key, wrapped, width = KEY_WIDTH)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a record.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let locus = &record.locus;
+        writeln!(
+            self.inner,
+            "LOCUS       {:<16} {:>11} bp    {:<7} {:<8} {} {}",
+            locus.name,
+            record.seq.len(),
+            locus.molecule_type,
+            if locus.circular { "circular" } else { "linear" },
+            locus.division,
+            locus.date
+        )?;
+        self.write_field("DEFINITION", &format!("{}.", record.definition))?;
+        self.write_field("ACCESSION", &record.accessions.join(" "))?;
+        if let Some(version) = &record.version {
+            self.write_field("VERSION", version)?;
+        }
+        self.write_field("KEYWORDS", &format!("{}.", record.keywords.join("; ")))?;
+        self.write_field("SOURCE", &record.source)?;
+        self.write_field("  ORGANISM", &record.organism)?;
+        if !record.taxonomy.is_empty() {
+            self.write_field("", &format!("{}.", record.taxonomy.join("; ")))?;
+        }
+        for reference in &record.references {
+            let ranges: Vec<String> = reference
+                .ranges
+                .iter()
+                .map(|(start, end)| format!("{} to {}", start + 1, end))
+                .collect();
+            if ranges.is_empty() {
+                self.write_field("REFERENCE", &reference.number.to_string())?;
+            } else {
+                self.write_field(
+                    "REFERENCE",
+                    &format!("{}  (bases {})", reference.number, ranges.join("; ")),
+                )?;
+            }
+            for (key, text) in &[
+                ("  AUTHORS", &reference.authors),
+                ("  CONSRTM", &reference.consortium),
+                ("  TITLE", &reference.title),
+                ("  JOURNAL", &reference.journal),
+            ] {
+                if !text.is_empty() {
+                    self.write_field(key, text)?;
+                }
+            }
+            if let Some(pubmed) = &reference.pubmed {
+                self.write_field("   PUBMED", pubmed)?;
+            }
+        }
+        if !record.comment.is_empty() {
+            self.write_field("COMMENT", &record.comment)?;
+        }
+        for (key, text) in &record.other {
+            self.write_field(key, text)?;
+        }
+        writeln!(self.inner, "FEATURES             Location/Qualifiers")?;
+        write_features(&mut self.inner, "     ", &record.features)?;
+        writeln!(self.inner, "ORIGIN")?;
+        for (i, line) in record.seq.chunks(60).enumerate() {
+            write!(self.inner, "{:>9}", i * 60 + 1)?;
+            for group in line.chunks(10) {
+                self.inner.write_all(b" ")?;
+                self.inner.write_all(group)?;
+            }
+            writeln!(self.inner)?;
+        }
+        writeln!(self.inner, "//")
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENBANK: &[u8] =
+        b"LOCUS       SCU49845                 150 bp    DNA     linear   PLN 21-JUN-1999
+DEFINITION  Saccharomyces cerevisiae TCP1-beta gene, partial cds; and Axl2p
+            (AXL2) and Rev7p (REV7) genes, complete cds.
+ACCESSION   U49845
+VERSION     U49845.1  GI:1293613
+KEYWORDS    .
+SOURCE      Saccharomyces cerevisiae (baker's yeast)
+  ORGANISM  Saccharomyces cerevisiae
+            Eukaryota; Fungi; Dikarya; Ascomycota; Saccharomycotina;
+            Saccharomycetes; Saccharomycetales; Saccharomycetaceae; Saccharomyces.
+REFERENCE   1  (bases 1 to 150)
+  AUTHORS   Roemer,T., Madden,K., Chang,J. and Snyder,M.
+  TITLE     Selection of axial growth sites in yeast requires Axl2p, a novel
+            plasma membrane glycoprotein
+  JOURNAL   Genes Dev. 10 (7), 777-793 (1996)
+   PUBMED   8846915
+DBLINK      BioProject: PRJNA1
+FEATURES             Location/Qualifiers
+     source          1..150
+                     /organism=\"Saccharomyces cerevisiae\"
+                     /db_xref=\"taxon:4932\"
+     mRNA            <1..>150
+                     /product=\"TCP1-beta\"
+     CDS             join(<1..20,31..90,
+                     101..>150)
+                     /codon_start=2
+                     /product=\"TCP1-beta\"
+                     /note=\"a \"\"quoted\"\" note spanning
+                     two lines\"
+                     /translation=\"SSIYNGISTSGLDLNNGTIADMRQLGIVESYKLKRAVVSSASEA
+                     AEVLLRVDNIIRARPRTANRQHM\"
+     misc_feature    complement(order(10^11,40..50))
+                     /pseudo
+ORIGIN
+        1 gatcctccat atacaacggt atctccacct caggtttaga tctcaacaac ggaaccattg
+       61 ccgacatgag acagttaggt atcgtcgaga gttacaagct aaaacgagca gtagtcagct
+      121 ctgcatctga agccgctgaa gtactactaa
+//
+";
+
+    fn read(data: &[u8]) -> Vec<Record> {
+        Reader::new(data).records().collect::<Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_location_syntax() {
+        for (location, expected) in &[
+            ("467", Location::range(466, 467)),
+            ("340..565", Location::range(339, 565)),
+            (
+                "<345..>500",
+                Location::Range {
+                    start: Position::Before(344),
+                    end: Position::After(500),
+                },
+            ),
+            (
+                "(102.110)..500",
+                Location::Range {
+                    start: Position::Within(101, 109),
+                    end: Position::Exact(500),
+                },
+            ),
+            (
+                "123^124",
+                Location::Between {
+                    start: 123,
+                    end: 123,
+                },
+            ),
+            (
+                "complement(join(2691..4571,4918..5163))",
+                Location::Complement(Box::new(Location::Join(vec![
+                    Location::range(2690, 4571),
+                    Location::range(4917, 5163),
+                ]))),
+            ),
+            (
+                "order(1..3,J00194.1:100..202)",
+                Location::Order(vec![
+                    Location::range(0, 3),
+                    Location::Remote {
+                        accession: "J00194.1".to_owned(),
+                        location: Box::new(Location::range(99, 202)),
+                    },
+                ]),
+            ),
+        ] {
+            let parsed: Location = location.parse().unwrap();
+            assert_eq!(&parsed, expected);
+            assert_eq!(&parsed.to_string(), location);
+        }
+        assert_eq!(
+            "join(1..10, 20..30)"
+                .parse::<Location>()
+                .unwrap()
+                .to_string(),
+            "join(1..10,20..30)"
+        );
+        for invalid in &[
+            "",
+            "0..5",
+            "(5.0)",
+            "(0.5)..10",
+            "join(1..2",
+            "1..2)",
+            "complement(x)",
+            "5^",
+        ] {
+            assert!(invalid.parse::<Location>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_location_annotations() {
+        let location: Location = "complement(join(2691..4571,4918..5163))".parse().unwrap();
+        assert_eq!(location.strand(), Some(ReqStrand::Reverse));
+        assert_eq!((location.start(), location.end()), (Some(2690), Some(5163)));
+        let contig = location.to_contig("chr").unwrap();
+        assert_eq!(contig.to_string(), "chr:2690-5163(-)");
+        let spliced = location.to_spliced("chr").unwrap();
+        assert_eq!(spliced.exon_starts(), vec![0, 4917 - 2690]);
+        assert_eq!(spliced.exon_lengths(), vec![4571 - 2690, 5163 - 4917]);
+
+        let location: Location = "join(complement(20..30),complement(1..10))"
+            .parse()
+            .unwrap();
+        assert_eq!(location.strand(), Some(ReqStrand::Reverse));
+        assert_eq!(
+            location.to_spliced("chr").unwrap().exon_starts(),
+            vec![0, 19]
+        );
+
+        let mixed: Location = "join(1..10,complement(20..30))".parse().unwrap();
+        assert_eq!(mixed.strand(), None);
+        assert!(mixed.to_contig("chr").is_err());
+        let abutting: Location = "join(1..10,11..30)".parse().unwrap();
+        assert!(matches!(
+            abutting.to_spliced("chr"),
+            Err(Error::Splicing(_))
+        ));
+        let remote: Location = "J00194.1:100..202".parse().unwrap();
+        assert!(remote.to_contig("chr").is_err());
+        for empty in [Location::Join(vec![]), Location::Order(vec![])] {
+            assert_eq!(empty.strand(), None);
+            assert!(matches!(
+                empty.to_contig("chr"),
+                Err(Error::UnsupportedLocation { .. })
+            ));
+            assert!(empty.to_spliced("chr").is_err());
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let records = read(GENBANK);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(
+            record.locus(),
+            &Locus {
+                name: "SCU49845".to_owned(),
+                length: 150,
+                molecule_type: "DNA".to_owned(),
+                circular: false,
+                division: "PLN".to_owned(),
+                date: "21-JUN-1999".to_owned(),
+            }
+        );
+        assert_eq!(
+            record.definition(),
+            "Saccharomyces cerevisiae TCP1-beta gene, partial cds; and Axl2p (AXL2) and Rev7p \
+             (REV7) genes, complete cds"
+        );
+        assert_eq!(record.accessions(), &["U49845".to_owned()]);
+        assert_eq!(record.version(), Some("U49845.1"));
+        assert!(record.keywords().is_empty());
+        assert_eq!(record.source(), "Saccharomyces cerevisiae (baker's yeast)");
+        assert_eq!(record.organism(), "Saccharomyces cerevisiae");
+        assert_eq!(record.taxonomy().len(), 9);
+        assert_eq!(record.taxonomy()[8], "Saccharomyces");
+        let reference = &record.references()[0];
+        assert_eq!(reference.number, 1);
+        assert_eq!(reference.ranges, vec![(0, 150)]);
+        assert_eq!(
+            reference.title,
+            "Selection of axial growth sites in yeast requires Axl2p, a novel plasma membrane \
+             glycoprotein"
+        );
+        assert_eq!(reference.pubmed.as_deref(), Some("8846915"));
+        assert_eq!(
+            record.other(),
+            &[("DBLINK".to_owned(), "BioProject: PRJNA1".to_owned())]
+        );
+
+        let features = record.features();
+        assert_eq!(features.len(), 4);
+        assert_eq!(features[0].qualifier("db_xref"), Some("taxon:4932"));
+        let cds = &features[2];
+        assert_eq!(cds.kind, "CDS");
+        assert_eq!(cds.location.to_string(), "join(<1..20,31..90,101..>150)");
+        assert_eq!(cds.qualifier("codon_start"), Some("2"));
+        assert_eq!(
+            cds.qualifier("note"),
+            Some("a \"quoted\" note spanning two lines")
+        );
+        assert_eq!(
+            cds.qualifier("translation"),
+            Some("SSIYNGISTSGLDLNNGTIADMRQLGIVESYKLKRAVVSSASEAAEVLLRVDNIIRARPRTANRQHM")
+        );
+        assert_eq!(features[3].qualifiers, vec![("pseudo".to_owned(), None)]);
+
+        assert_eq!(record.seq().len(), 150);
+        assert!(record.seq().starts_with(b"gatcctccatatacaacggt"));
+        let fasta = record.fasta_record();
+        assert_eq!(fasta.id(), "U49845.1");
+        assert_eq!(fasta.seq().len(), 150);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let record = &read(GENBANK)[0];
+        let mut writer = Writer::new(Vec::new());
+        writer.write(record).unwrap();
+        writer.write(record).unwrap();
+        let written = writer.inner.into_inner().unwrap();
+        let text = String::from_utf8(written.clone()).unwrap();
+        assert!(text.lines().all(|line| line.len() <= LINE_WIDTH));
+        assert!(text.contains("     CDS             join(<1..20,31..90,101..>150)\n"));
+        assert!(text.contains("                     /codon_start=2\n"));
+        assert!(text.contains("        1 gatcctccat atacaacggt"));
+        let records = read(&written);
+        assert_eq!(records.len(), 2);
+        assert_eq!(&records[1], record);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Reader::new(&b"LOCUS       X 10 bp DNA\nORIGIN\n"[..])
+            .read()
+            .is_err());
+        assert!(Reader::new(&b"LOCUS       X\n//\n"[..]).read().is_err());
+        let invalid_location = b"LOCUS       X 1 bp
+FEATURES             Location/Qualifiers
+     gene            1..x
+//
+";
+        assert!(Reader::new(&invalid_location[..]).read().is_err());
+    }
+
+    #[test]
+    fn test_reference_ranges() {
+        assert_eq!(
+            parse_reference_ranges("(bases 1 to 5028; 6000 to 7000)"),
+            vec![(0, 5028), (5999, 7000)]
+        );
+        assert_eq!(
+            parse_reference_ranges("(bases 0 to 10; 20 to 30)"),
+            vec![(19, 30)]
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("aaa bbb ccc", 7, false), vec!["aaa bbb", "ccc"]);
+        assert_eq!(wrap("abcdefghij", 4, true), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("ab cdefghij", 4, false), vec!["ab", "cdef", "ghij"]);
+        assert_eq!(
+            wrap_location("join(1..10,20..30,40..50)", 12),
+            vec!["join(1..10,", "20..30,", "40..50)"]
+        );
+    }
+}
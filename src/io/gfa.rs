@@ -0,0 +1,744 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading and writing of sequence graphs in
+//! [GFA 1.0](https://gfa-spec.github.io/GFA-spec/GFA1.html) format.
+//!
+//! A [`Gfa`] holds the segments (`S`), links (`L`) and paths (`P`) of a graph; other record
+//! types are skipped when reading. Graphs of a partial order alignment (see
+//! [`poa`](crate::alignment::poa)) can be exported with [`Gfa::from_poa_graph`], which
+//! compacts linear chains of single-base nodes into segments and writes each aligned sequence
+//! as a path. [`Gfa::to_poa`] loads such a graph back, so that further sequences can be
+//! aligned to it.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::alignment::pairwise::Scoring;
+//! use bio::alignment::poa::Aligner;
+//! use bio::io::gfa;
+//!
+//! let seqs: Vec<&[u8]> = vec![b"ACGTACGT", b"ACGAACGT"];
+//! let scoring = Scoring::new(-1, 0, |a: u8, b: u8| if a == b { 1i32 } else { -1i32 });
+//! let mut aligner = Aligner::new(scoring, seqs[0]);
+//! aligner.global(seqs[1]).add_to_graph();
+//!
+//! let graph = gfa::Gfa::from_poa_graph(aligner.graph(), vec![("a", seqs[0]), ("b", seqs[1])])
+//!     .unwrap();
+//! assert_eq!(graph.segments.len(), 4);
+//! assert_eq!(graph.paths[1].name, "b");
+//!
+//! let mut writer = gfa::Writer::new(Vec::new());
+//! writer.write(&graph).unwrap();
+//! let data = writer.into_inner().unwrap();
+//!
+//! let graph = gfa::Reader::new(&data[..]).read().unwrap();
+//! let scoring = Scoring::new(-1, 0, |a: u8, b: u8| if a == b { 1i32 } else { -1i32 });
+//! let mut aligner = Aligner::from_poa(graph.to_poa(scoring).unwrap());
+//! aligner.global(b"ACGAACGT").add_to_graph();
+//! assert_eq!(aligner.consensus(), b"ACGAACGT");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path as FsPath;
+
+use anyhow::Context;
+use bio_types::strand::ReqStrand;
+use petgraph::algo::is_cyclic_directed;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::Topo;
+use petgraph::{Incoming, Outgoing};
+use thiserror::Error;
+
+use crate::alignment::pairwise::{MatchFunc, Scoring};
+use crate::alignment::poa::{POAGraph, Poa};
+use crate::io::compression::{self, Decoder, Encoder};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read or write GFA file")]
+    Io(#[from] io::Error),
+
+    #[error("invalid GFA record in line {line}: {msg}")]
+    InvalidRecord { line: u64, msg: String },
+
+    #[error("unknown segment {name}")]
+    UnknownSegment { name: String },
+
+    #[error("sequence {name} is not spelled by a path of the graph")]
+    PathNotFound { name: String },
+
+    #[error("graph can't be converted to a partial order alignment: {msg}")]
+    Unsupported { msg: &'static str },
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A segment (`S` line).
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Segment {
+    pub name: String,
+    pub seq: Vec<u8>,
+    /// Optional fields like `LN:i:10`.
+    pub tags: Vec<String>,
+}
+
+/// A link between two oriented segments (`L` line).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Link {
+    pub from: String,
+    pub from_orient: ReqStrand,
+    pub to: String,
+    pub to_orient: ReqStrand,
+    /// The overlap as CIGAR string, e.g. `0M`, or `*` if unspecified.
+    pub overlap: String,
+    /// Optional fields like `RC:i:3`.
+    pub tags: Vec<String>,
+}
+
+/// A path through oriented segments (`P` line).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Path {
+    pub name: String,
+    pub segments: Vec<(String, ReqStrand)>,
+    /// The overlaps between consecutive segments as CIGAR strings, empty if unspecified.
+    pub overlaps: Vec<String>,
+}
+
+/// Return the value of the optional field with the given tag, e.g. `"3"` for `RC` in
+/// `RC:i:3`.
+fn tag_value<'a>(tags: &'a [String], tag: &str) -> Option<&'a str> {
+    tags.iter().find_map(|field| {
+        let mut parts = field.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(t), Some(_), Some(value)) if t == tag => Some(value),
+            _ => None,
+        }
+    })
+}
+
+impl Segment {
+    /// Return the value of the optional field with the given tag.
+    pub fn tag(&self, tag: &str) -> Option<&str> {
+        tag_value(&self.tags, tag)
+    }
+}
+
+impl Link {
+    /// Return the value of the optional field with the given tag.
+    pub fn tag(&self, tag: &str) -> Option<&str> {
+        tag_value(&self.tags, tag)
+    }
+}
+
+/// A sequence graph consisting of segments, links and paths.
+#[derive(Default, Clone, Eq, PartialEq, Debug)]
+pub struct Gfa {
+    pub segments: Vec<Segment>,
+    pub links: Vec<Link>,
+    pub paths: Vec<Path>,
+}
+
+impl Gfa {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export the graph of a partial order alignment, e.g. `poa::Aligner::graph`, together
+    /// with the sequences aligned into it, given as name and sequence.
+    ///
+    /// Linear chains of nodes are compacted into segments, named by consecutive numbers in
+    /// topological order. Chains are split where a sequence starts or ends, so that each
+    /// sequence can be written as a path of whole segments. Edge weights are stored in the
+    /// `RC:i` field of the links.
+    ///
+    /// # Errors
+    /// If a sequence is not spelled by any path of the graph.
+    pub fn from_poa_graph<I, S, T>(graph: &POAGraph, sequences: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: AsRef<str>,
+        T: AsRef<[u8]>,
+    {
+        let mut node_paths = Vec::new();
+        let mut starts = HashSet::new();
+        let mut ends = HashSet::new();
+        for (name, seq) in sequences {
+            let nodes = find_path(graph, seq.as_ref()).ok_or_else(|| Error::PathNotFound {
+                name: name.as_ref().to_owned(),
+            })?;
+            starts.insert(nodes[0]);
+            ends.insert(nodes[nodes.len() - 1]);
+            node_paths.push((name.as_ref().to_owned(), nodes));
+        }
+
+        // the single successor of a node if both can be joined into one segment
+        let chain_next = |node: NodeIndex<usize>| {
+            let mut successors = graph.neighbors_directed(node, Outgoing);
+            match (successors.next(), successors.next()) {
+                (Some(next), None)
+                    if graph.neighbors_directed(next, Incoming).count() == 1
+                        && !ends.contains(&node)
+                        && !starts.contains(&next) =>
+                {
+                    Some(next)
+                }
+                _ => None,
+            }
+        };
+        let is_chain_start = |node: NodeIndex<usize>| {
+            let mut predecessors = graph.neighbors_directed(node, Incoming);
+            match (predecessors.next(), predecessors.next()) {
+                (Some(prev), None) => chain_next(prev) != Some(node),
+                _ => true,
+            }
+        };
+
+        let mut gfa = Gfa::new();
+        let mut segment_of = HashMap::new();
+        let mut topo = Topo::new(graph);
+        while let Some(start) = topo.next(graph) {
+            if !is_chain_start(start) {
+                continue;
+            }
+            let name = (gfa.segments.len() + 1).to_string();
+            let mut seq = Vec::new();
+            let mut node = Some(start);
+            while let Some(current) = node {
+                seq.push(graph[current]);
+                segment_of.insert(current, gfa.segments.len());
+                node = chain_next(current);
+            }
+            gfa.segments.push(Segment {
+                name,
+                seq,
+                tags: Vec::new(),
+            });
+        }
+
+        let mut link_weights: HashMap<(usize, usize), i32> = HashMap::new();
+        let mut link_order = Vec::new();
+        for edge in graph.raw_edges() {
+            let (from, to) = (edge.source(), edge.target());
+            if chain_next(from) == Some(to) {
+                continue;
+            }
+            let key = (segment_of[&from], segment_of[&to]);
+            let weight = link_weights.entry(key).or_insert_with(|| {
+                link_order.push(key);
+                0
+            });
+            *weight += edge.weight;
+        }
+        link_order.sort_unstable();
+        for key in link_order {
+            gfa.links.push(Link {
+                from: gfa.segments[key.0].name.clone(),
+                from_orient: ReqStrand::Forward,
+                to: gfa.segments[key.1].name.clone(),
+                to_orient: ReqStrand::Forward,
+                overlap: "0M".to_owned(),
+                tags: vec![format!("RC:i:{}", link_weights[&key])],
+            });
+        }
+
+        for (name, nodes) in node_paths {
+            let mut segments: Vec<usize> = nodes.iter().map(|node| segment_of[node]).collect();
+            segments.dedup();
+            gfa.paths.push(Path {
+                name,
+                overlaps: vec!["0M".to_owned(); segments.len() - 1],
+                segments: segments
+                    .into_iter()
+                    .map(|segment| (gfa.segments[segment].name.clone(), ReqStrand::Forward))
+                    .collect(),
+            });
+        }
+        Ok(gfa)
+    }
+
+    /// Build a partial order alignment graph with one node per base of each segment.
+    ///
+    /// Edge weights are the number of paths traversing an edge. Edges that are not part of
+    /// any path have the weight given in the `RC:i` field of their link, or 1.
+    ///
+    /// # Errors
+    /// If the graph contains reverse complemented segments in links or paths, links with
+    /// overlaps, references to unknown segments or cycles.
+    pub fn to_poa_graph(&self) -> Result<POAGraph> {
+        let mut graph = POAGraph::default();
+        let mut segment_nodes: HashMap<&str, Vec<NodeIndex<usize>>> = HashMap::new();
+        for segment in &self.segments {
+            let mut nodes: Vec<NodeIndex<usize>> = Vec::with_capacity(segment.seq.len());
+            for &base in &segment.seq {
+                let node = graph.add_node(base);
+                if let Some(&prev) = nodes.last() {
+                    graph.add_edge(prev, node, 1);
+                }
+                nodes.push(node);
+            }
+            if nodes.is_empty() {
+                return Err(Error::Unsupported {
+                    msg: "segments without sequence",
+                });
+            }
+            segment_nodes.insert(&segment.name, nodes);
+        }
+        let nodes = |name: &str| {
+            segment_nodes
+                .get(name)
+                .ok_or_else(|| Error::UnknownSegment {
+                    name: name.to_owned(),
+                })
+        };
+        let forward = |orient: ReqStrand| {
+            if orient == ReqStrand::Forward {
+                Ok(())
+            } else {
+                Err(Error::Unsupported {
+                    msg: "reverse complemented segments",
+                })
+            }
+        };
+
+        for link in &self.links {
+            forward(link.from_orient)?;
+            forward(link.to_orient)?;
+            if !matches!(link.overlap.as_str(), "*" | "0M" | "") {
+                return Err(Error::Unsupported {
+                    msg: "overlapping segments",
+                });
+            }
+            let from = *nodes(&link.from)?.last().unwrap();
+            let to = nodes(&link.to)?[0];
+            let weight = link
+                .tag("RC")
+                .and_then(|weight| weight.parse().ok())
+                .unwrap_or(1);
+            graph.update_edge(from, to, weight);
+        }
+        if is_cyclic_directed(&graph) {
+            return Err(Error::Unsupported { msg: "cycles" });
+        }
+
+        let mut traversals: HashMap<(NodeIndex<usize>, NodeIndex<usize>), i32> = HashMap::new();
+        for path in &self.paths {
+            let mut prev: Option<NodeIndex<usize>> = None;
+            for (name, orient) in &path.segments {
+                forward(*orient)?;
+                for &node in nodes(name)? {
+                    if let Some(prev) = prev {
+                        *traversals.entry((prev, node)).or_insert(0) += 1;
+                    }
+                    prev = Some(node);
+                }
+            }
+        }
+        for ((from, to), count) in traversals {
+            match graph.find_edge(from, to) {
+                Some(edge) => graph[edge] = count,
+                None => {
+                    return Err(Error::Unsupported {
+                        msg: "paths along missing links",
+                    })
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Build a partial order alignment from this graph (see `to_poa_graph`), e.g. to continue
+    /// aligning further sequences with `poa::Aligner::from_poa`.
+    pub fn to_poa<F: MatchFunc>(&self, scoring: Scoring<F>) -> Result<Poa<F>> {
+        Ok(Poa::new(scoring, self.to_poa_graph()?))
+    }
+}
+
+/// Find a path of nodes in the graph whose bases spell the given sequence.
+fn find_path(graph: &POAGraph, seq: &[u8]) -> Option<Vec<NodeIndex<usize>>> {
+    let first = *seq.first()?;
+    // (node, position in the sequence) pairs from which the rest can't be spelled
+    let mut failed = HashSet::new();
+    for start in graph.node_indices().filter(|&node| graph[node] == first) {
+        let mut path = vec![start];
+        let mut candidates: Vec<Vec<NodeIndex<usize>>> =
+            vec![graph.neighbors_directed(start, Outgoing).collect()];
+        while let Some(next) = candidates.last_mut() {
+            if path.len() == seq.len() {
+                return Some(path);
+            }
+            let pos = path.len();
+            match next.pop() {
+                Some(node) => {
+                    if graph[node] == seq[pos] && !failed.contains(&(node, pos)) {
+                        path.push(node);
+                        candidates.push(graph.neighbors_directed(node, Outgoing).collect());
+                    }
+                }
+                None => {
+                    failed.insert((path.pop().unwrap(), pos - 1));
+                    candidates.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Parse the orientation of a segment.
+fn orientation(s: &str) -> Result<ReqStrand, String> {
+    match s {
+        "+" => Ok(ReqStrand::Forward),
+        "-" => Ok(ReqStrand::Reverse),
+        _ => Err(format!("invalid orientation '{}'", s)),
+    }
+}
+
+/// Parse a single GFA line into the graph.
+fn parse_line(gfa: &mut Gfa, line: &str) -> Result<(), String> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let require = |n: usize| {
+        if fields.len() < n {
+            Err(format!(
+                "expected at least {} columns, found {}",
+                n,
+                fields.len()
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    let tags = |start: usize| fields.iter().skip(start).map(|t| t.to_string()).collect();
+    match fields[0] {
+        "S" => {
+            require(3)?;
+            gfa.segments.push(Segment {
+                name: fields[1].to_owned(),
+                seq: if fields[2] == "*" {
+                    Vec::new()
+                } else {
+                    fields[2].as_bytes().to_owned()
+                },
+                tags: tags(3),
+            });
+        }
+        "L" => {
+            require(6)?;
+            gfa.links.push(Link {
+                from: fields[1].to_owned(),
+                from_orient: orientation(fields[2])?,
+                to: fields[3].to_owned(),
+                to_orient: orientation(fields[4])?,
+                overlap: fields[5].to_owned(),
+                tags: tags(6),
+            });
+        }
+        "P" => {
+            require(3)?;
+            let segments = fields[2]
+                .split(',')
+                .map(|segment| {
+                    let split = segment.len().saturating_sub(1);
+                    let (name, orient) = segment.split_at(split);
+                    Ok((name.to_owned(), orientation(orient)?))
+                })
+                .collect::<Result<_, String>>()?;
+            let overlaps = match fields.get(3) {
+                Some(&"*") | None => Vec::new(),
+                Some(overlaps) => overlaps.split(',').map(|o| o.to_owned()).collect(),
+            };
+            gfa.paths.push(Path {
+                name: fields[1].to_owned(),
+                segments,
+                overlaps,
+            });
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// A GFA reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: io::BufReader<R>,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<FsPath> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read GFA from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<FsPath> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read GFA from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: io::BufReader::new(reader),
+        }
+    }
+
+    /// Read the whole graph. Record types other than segments, links and paths, as well as
+    /// comments, are skipped.
+    pub fn read(&mut self) -> Result<Gfa> {
+        let mut gfa = Gfa::new();
+        for (i, line) in (&mut self.inner).lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            parse_line(&mut gfa, line).map_err(|msg| Error::InvalidRecord {
+                line: i as u64 + 1,
+                msg,
+            })?;
+        }
+        Ok(gfa)
+    }
+}
+
+/// A GFA writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<FsPath>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`compression::Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<FsPath>>(path: P) -> io::Result<Self> {
+        let format = compression::Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write a graph, preceded by a header line with the GFA version.
+    pub fn write(&mut self, gfa: &Gfa) -> io::Result<()> {
+        let tags =
+            |tags: &[String]| -> String { tags.iter().map(|t| format!("\t{}", t)).collect() };
+        writeln!(self.inner, "H\tVN:Z:1.0")?;
+        for segment in &gfa.segments {
+            let seq = if segment.seq.is_empty() {
+                "*"
+            } else {
+                std::str::from_utf8(&segment.seq)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            };
+            writeln!(
+                self.inner,
+                "S\t{}\t{}{}",
+                segment.name,
+                seq,
+                tags(&segment.tags)
+            )?;
+        }
+        for link in &gfa.links {
+            writeln!(
+                self.inner,
+                "L\t{}\t{}\t{}\t{}\t{}{}",
+                link.from,
+                link.from_orient.strand_symbol(),
+                link.to,
+                link.to_orient.strand_symbol(),
+                link.overlap,
+                tags(&link.tags)
+            )?;
+        }
+        for path in &gfa.paths {
+            let segments: Vec<String> = path
+                .segments
+                .iter()
+                .map(|(name, orient)| format!("{}{}", name, orient.strand_symbol()))
+                .collect();
+            let overlaps = if path.overlaps.is_empty() {
+                "*".to_owned()
+            } else {
+                path.overlaps.join(",")
+            };
+            writeln!(
+                self.inner,
+                "P\t{}\t{}\t{}",
+                path.name,
+                segments.join(","),
+                overlaps
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Flush the writer and return the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.inner.into_inner().map_err(|e| e.into_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::poa::Aligner;
+
+    fn scoring() -> Scoring<impl Fn(u8, u8) -> i32> {
+        Scoring::new(-1, 0, |a: u8, b: u8| if a == b { 1i32 } else { -1i32 })
+    }
+
+    #[test]
+    fn test_poa_export() {
+        let seqs: Vec<&[u8]> = vec![b"AAAAAAA", b"AABBBAA", b"AABCBAA", b"AAAAA"];
+        let mut aligner = Aligner::new(scoring(), seqs[0]);
+        for seq in &seqs[1..] {
+            aligner.global(seq).add_to_graph();
+        }
+        let consensus = aligner.consensus();
+        let graph = aligner.graph();
+        let gfa = Gfa::from_poa_graph(
+            graph,
+            seqs.iter()
+                .enumerate()
+                .map(|(i, seq)| (format!("seq{}", i), seq)),
+        )
+        .unwrap();
+
+        let total: usize = gfa.segments.iter().map(|s| s.seq.len()).sum();
+        assert_eq!(total, graph.node_count());
+        assert!(gfa.segments.len() < graph.node_count());
+        let segments: HashMap<&str, &[u8]> = gfa
+            .segments
+            .iter()
+            .map(|s| (s.name.as_str(), s.seq.as_slice()))
+            .collect();
+        for (path, seq) in gfa.paths.iter().zip(&seqs) {
+            let spelled: Vec<u8> = path
+                .segments
+                .iter()
+                .flat_map(|(name, _)| segments[name.as_str()].iter().copied())
+                .collect();
+            assert_eq!(&spelled, seq);
+            assert_eq!(path.overlaps.len(), path.segments.len() - 1);
+        }
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write(&gfa).unwrap();
+        let data = writer.into_inner().unwrap();
+        let text = String::from_utf8(data.clone()).unwrap();
+        assert!(text.starts_with("H\tVN:Z:1.0\nS\t1\tAA\n"));
+        let read = Reader::new(&data[..]).read().unwrap();
+        assert_eq!(read, gfa);
+
+        // the reconstructed graph is isomorphic to the original one
+        let roundtrip = read.to_poa_graph().unwrap();
+        assert_eq!(roundtrip.node_count(), graph.node_count());
+        assert_eq!(roundtrip.edge_count(), graph.edge_count());
+        let mut weights: Vec<i32> = graph.raw_edges().iter().map(|e| e.weight).collect();
+        let mut roundtrip_weights: Vec<i32> =
+            roundtrip.raw_edges().iter().map(|e| e.weight).collect();
+        weights.sort_unstable();
+        roundtrip_weights.sort_unstable();
+        assert_eq!(weights, roundtrip_weights);
+
+        let mut aligner = Aligner::from_poa(read.to_poa(scoring()).unwrap());
+        assert_eq!(aligner.consensus(), consensus);
+        let nodes = aligner.graph().node_count();
+        aligner.global(b"AABBBBAA").add_to_graph();
+        assert_eq!(aligner.graph().node_count(), nodes + 1);
+    }
+
+    #[test]
+    fn test_path_not_found() {
+        let aligner = Aligner::new(scoring(), b"ACGT");
+        assert!(matches!(
+            Gfa::from_poa_graph(aligner.graph(), vec![("x", b"ACGA")]),
+            Err(Error::PathNotFound { .. })
+        ));
+        // a sequence starting within a chain splits the chain
+        let gfa = Gfa::from_poa_graph(aligner.graph(), vec![("x", b"CG")]).unwrap();
+        let seqs: Vec<&[u8]> = gfa.segments.iter().map(|s| s.seq.as_slice()).collect();
+        assert_eq!(seqs, vec![&b"A"[..], b"CG", b"T"]);
+        assert_eq!(
+            gfa.paths[0].segments,
+            vec![("2".to_owned(), ReqStrand::Forward)]
+        );
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let gfa = b"H\tVN:Z:1.0
+# comment
+S\t11\tACCTT
+S\t12\tTCAAGG
+S\t13\t*\tLN:i:3
+L\t11\t+\t12\t-\t4M
+P\t14\t11+,12-\t4M
+W\tsample\t1\tchr1\t0\t11\t>11
+";
+        let read = Reader::new(&gfa[..]).read().unwrap();
+        assert_eq!(read.segments.len(), 3);
+        assert_eq!(read.segments[2].tag("LN"), Some("3"));
+        assert!(read.segments[2].seq.is_empty());
+        assert_eq!(read.links[0].to_orient, ReqStrand::Reverse);
+        assert_eq!(read.paths[0].overlaps, vec!["4M"]);
+        assert!(matches!(
+            read.to_poa_graph(),
+            Err(Error::Unsupported { .. })
+        ));
+
+        for invalid in &[&b"S\t1\n"[..], b"L\t1\t+\t2\tx\t0M\n", b"P\t1\t2\n"] {
+            assert!(matches!(
+                Reader::new(*invalid).read(),
+                Err(Error::InvalidRecord { line: 1, .. })
+            ));
+        }
+        let unknown = b"S\t1\tA\nL\t1\t+\t2\t+\t0M\n";
+        assert!(matches!(
+            Reader::new(&unknown[..]).read().unwrap().to_poa_graph(),
+            Err(Error::UnknownSegment { .. })
+        ));
+        let cyclic = b"S\t1\tA\nS\t2\tC\nL\t1\t+\t2\t+\t0M\nL\t2\t+\t1\t+\t0M\n";
+        assert!(matches!(
+            Reader::new(&cyclic[..]).read().unwrap().to_poa_graph(),
+            Err(Error::Unsupported { msg: "cycles" })
+        ));
+    }
+}
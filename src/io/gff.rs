@@ -5,6 +5,13 @@
 
 //! [GFF3] format reading and writing. [GFF2] is currently not supported.
 //!
+//! For GFF3, directives (e.g. `##sequence-region`) are available via [`Reader::directives`],
+//! the sequences of a trailing `##FASTA` section via [`Reader::sequences`], and
+//! percent-encoded characters are decoded when reading and encoded when writing.
+//!
+//! Gene models can be reconstructed from the parent-child relations between records with
+//! [`FeatureGraph`].
+//!
 //! [GFF2]: http://gmod.org/wiki/GFF2 (not supported)
 //! [GTF2]: http://mblab.wustl.edu/GTF2.html (not supported)
 //! [GFF3]: http://gmod.org/wiki/GFF3#GFF3_Format
@@ -28,13 +35,22 @@ use anyhow::Context;
 use itertools::Itertools;
 use multimap::MultiMap;
 use regex::Regex;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::convert::{AsRef, TryInto};
+use std::fmt;
 use std::fs;
 use std::io;
+use std::io::prelude::*;
 use std::path::Path;
 use std::str::FromStr;
 
+use bio_types::annot::spliced::{Spliced, SplicingError};
 use bio_types::strand::Strand;
+use thiserror::Error;
+
+use crate::io::compression::{Decoder, Encoder, Format};
+use crate::io::fasta;
 use serde::{Deserialize, Deserializer, Serialize};
 
 /// `GffType`
@@ -92,11 +108,194 @@ impl GffType {
     }
 }
 
+/// A GFF3 directive, i.e. a line starting with `##`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Directive {
+    /// Version of the format, e.g. `3` or `3.1.26` (`##gff-version`).
+    GffVersion(String),
+    /// Boundaries of a sequence, with 1-based inclusive coordinates (`##sequence-region`).
+    SequenceRegion { seqid: String, start: u64, end: u64 },
+    /// NCBI taxonomy URL of the species (`##species`).
+    Species(String),
+    /// Source and name of the genome assembly (`##genome-build`).
+    GenomeBuild { source: String, name: String },
+    /// All forward references to features have been resolved (`###`).
+    ForwardReferencesResolved,
+    /// Any other directive, e.g. `##feature-ontology`, with the remainder of the line as value.
+    Other { name: String, value: String },
+}
+
+impl FromStr for Directive {
+    type Err = String;
+
+    /// Parse a directive line, including the leading `##`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if line.trim_end() == "###" {
+            return Ok(Directive::ForwardReferencesResolved);
+        }
+        let line = line
+            .strip_prefix("##")
+            .ok_or_else(|| format!("Directive '{}' does not start with ##.", line))?;
+        let (name, value) = line
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((line, ""));
+        let value = value.trim();
+        let fields = value.split_ascii_whitespace().collect::<Vec<_>>();
+        let invalid = || format!("Invalid {} directive '{}'.", name, value);
+        match name {
+            "gff-version" => Ok(Directive::GffVersion(value.to_owned())),
+            "sequence-region" => match fields[..] {
+                [seqid, start, end] => Ok(Directive::SequenceRegion {
+                    seqid: percent_decode(seqid),
+                    start: start.parse().map_err(|_| invalid())?,
+                    end: end.parse().map_err(|_| invalid())?,
+                }),
+                _ => Err(invalid()),
+            },
+            "species" => Ok(Directive::Species(value.to_owned())),
+            "genome-build" => match fields[..] {
+                [source, name] => Ok(Directive::GenomeBuild {
+                    source: source.to_owned(),
+                    name: name.to_owned(),
+                }),
+                _ => Err(invalid()),
+            },
+            _ => Ok(Directive::Other {
+                name: name.to_owned(),
+                value: value.to_owned(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Directive::GffVersion(version) => write!(f, "##gff-version {}", version),
+            Directive::SequenceRegion { seqid, start, end } => write!(
+                f,
+                "##sequence-region {} {} {}",
+                percent_encode_seqid(seqid),
+                start,
+                end
+            ),
+            Directive::Species(species) => write!(f, "##species {}", species),
+            Directive::GenomeBuild { source, name } => {
+                write!(f, "##genome-build {} {}", source, name)
+            }
+            Directive::ForwardReferencesResolved => f.write_str("###"),
+            Directive::Other { name, value } if value.is_empty() => write!(f, "##{}", name),
+            Directive::Other { name, value } => write!(f, "##{} {}", name, value),
+        }
+    }
+}
+
+/// Characters that have to be percent-encoded in GFF3 attribute tags and values, in addition
+/// to `%` and control characters.
+const ATTRIBUTE_RESERVED: &str = ";=&,";
+
+/// Characters besides ASCII letters and digits that may appear unescaped in a GFF3 seqid.
+const SEQID_ALLOWED: &str = ".:^*$@!+_?-|";
+
+/// Percent-encode `%`, control characters and the given reserved characters.
+fn percent_encode<'a>(s: &'a str, reserved: &str) -> Cow<'a, str> {
+    percent_encode_if(s, |c| {
+        c == '%' || c.is_ascii_control() || reserved.contains(c)
+    })
+}
+
+/// Percent-encode a seqid, i.e. all characters except ASCII letters, digits and
+/// [`SEQID_ALLOWED`].
+fn percent_encode_seqid(s: &str) -> Cow<'_, str> {
+    percent_encode_if(s, |c| {
+        !(c.is_ascii_alphanumeric() || SEQID_ALLOWED.contains(c))
+    })
+}
+
+/// Percent-encode the UTF-8 bytes of all characters for which `needs_encoding` is true.
+fn percent_encode_if<F: Fn(char) -> bool>(s: &str, needs_encoding: F) -> Cow<'_, str> {
+    if !s.contains(&needs_encoding) {
+        return Cow::Borrowed(s);
+    }
+    let mut encoded = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        if needs_encoding(c) {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+/// Decode percent-encoded characters. Invalid escapes are kept as they are.
+fn percent_decode(s: &str) -> String {
+    if !s.contains('%') {
+        return s.to_owned();
+    }
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+fn invalid_data(msg: String) -> csv::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+/// Errors of reading and writing GFF directives and FASTA sections.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read or write GFF")]
+    Io(#[from] io::Error),
+
+    #[error("invalid GFF3 directive {0:?}")]
+    InvalidDirective(String),
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl From<Error> for csv::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e.into(),
+            e => io::Error::new(io::ErrorKind::InvalidData, e).into(),
+        }
+    }
+}
+
 /// A GFF reader.
+///
+/// Besides feature records, GFF3 files may contain directives (see [`Reader::directives`]) and
+/// a trailing FASTA section (see [`Reader::sequences`]). For GFF3, percent-encoded characters
+/// are decoded.
 #[derive(Debug)]
 pub struct Reader<R: io::Read> {
-    inner: csv::Reader<R>,
+    inner: io::BufReader<R>,
     gff_type: GffType,
+    directives: Vec<Directive>,
+    line: String,
+    fasta: bool,
 }
 
 impl Reader<fs::File> {
@@ -111,16 +310,29 @@ impl Reader<fs::File> {
     }
 }
 
+impl Reader<Decoder<fs::File>> {
+    /// Read GFF from given file path in given format.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        fileformat: GffType,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(|f| Reader::new(f, fileformat))
+            .with_context(|| format!("Failed to read GFF from {:#?}", path))
+    }
+}
+
 impl<R: io::Read> Reader<R> {
     /// Create a new GFF reader given an instance of `io::Read`, in given format.
     pub fn new(reader: R, fileformat: GffType) -> Self {
         Reader {
-            inner: csv::ReaderBuilder::new()
-                .delimiter(b'\t')
-                .has_headers(false)
-                .comment(Some(b'#'))
-                .from_reader(reader),
+            inner: io::BufReader::new(reader),
             gff_type: fileformat,
+            directives: Vec::new(),
+            line: String::new(),
+            fasta: false,
         }
     }
 
@@ -134,24 +346,66 @@ impl<R: io::Read> Reader<R> {
         );
         let attribute_re = Regex::new(&r).unwrap();
         Records {
-            inner: self.inner.deserialize(),
+            reader: self,
             attribute_re,
             value_delim: vdelim as char,
         }
     }
-}
 
-type GffRecordInner = (
-    String,
-    String,
-    String,
-    u64,
-    u64,
-    String,
-    String,
-    Phase,
-    String,
-);
+    /// Return the directives read so far, after reading those at the start of the file
+    /// (e.g. `##gff-version` and `##sequence-region`). Directives between records are added
+    /// while iterating over the records. In formats other than GFF3, lines starting with `##`
+    /// are comments, so no directives are returned.
+    pub fn directives(&mut self) -> Result<&[Directive]> {
+        while !self.fasta && self.inner.fill_buf()?.first() == Some(&b'#') {
+            self.read_any_line()?;
+        }
+        Ok(&self.directives)
+    }
+
+    /// Iterate over the sequences of the FASTA section at the end of a GFF3 file, skipping
+    /// any remaining records. The section starts with a `##FASTA` directive or the first
+    /// FASTA header. Other formats have no FASTA section.
+    pub fn sequences(mut self) -> Result<fasta::Records<io::BufReader<R>>> {
+        while self.read_line()? {}
+        Ok(fasta::Reader::from_bufread(self.inner).records())
+    }
+
+    /// Read the next record line of the GFF part into `self.line`, recording any directives.
+    /// Return `false` at the end of the GFF part.
+    fn read_line(&mut self) -> Result<bool> {
+        while !self.fasta && !self.inner.fill_buf()?.is_empty() {
+            if self.read_any_line()? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Read a single line into `self.line`, recording it if it is a GFF3 directive.
+    /// Return whether it is a record line.
+    fn read_any_line(&mut self) -> Result<bool> {
+        let gff3 = self.gff_type == GffType::GFF3;
+        if gff3 && self.inner.fill_buf()?.first() == Some(&b'>') {
+            self.fasta = true;
+            return Ok(false);
+        }
+        self.line.clear();
+        self.inner.read_line(&mut self.line)?;
+        let line = self.line.trim_end_matches(['\n', '\r']);
+        if gff3 && line == "##FASTA" {
+            self.fasta = true;
+        } else if gff3 && line.starts_with("##") {
+            let directive = line
+                .parse()
+                .map_err(|_| Error::InvalidDirective(line.to_owned()))?;
+            self.directives.push(directive);
+        } else if !line.starts_with('#') && !line.trim().is_empty() {
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Phase(Option<u8>);
@@ -274,58 +528,99 @@ impl Serialize for Phase {
 
 /// An iterator over the records of a GFF file.
 pub struct Records<'a, R: io::Read> {
-    inner: csv::DeserializeRecordsIter<'a, R, GffRecordInner>,
+    reader: &'a mut Reader<R>,
     attribute_re: Regex,
     value_delim: char,
 }
 
+impl<'a, R: io::Read> Records<'a, R> {
+    fn parse(&self, line: &str) -> csv::Result<Record> {
+        let gff3 = self.reader.gff_type == GffType::GFF3;
+        let decode = |s: &str| {
+            if gff3 {
+                percent_decode(s)
+            } else {
+                s.to_owned()
+            }
+        };
+        let fields = line.splitn(9, '\t').collect::<Vec<_>>();
+        if fields.len() < 9 {
+            return Err(invalid_data(format!(
+                "Expected 9 tab-separated fields in GFF line '{}'.",
+                line
+            )));
+        }
+        let position = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| invalid_data(format!("Invalid position '{}' in GFF line.", s)))
+        };
+        let score = match fields[5] {
+            "." => ".",
+            s if s.parse::<f64>().is_ok() => s,
+            s => return Err(invalid_data(format!("Invalid score '{}' in GFF line.", s))),
+        };
+        let phase = match fields[7] {
+            "." => Phase(None),
+            p => Phase(Phase::validate(u8::from_str(p).map_err(|_| {
+                invalid_data("Phase must be \".\", 0, 1, or 2".to_owned())
+            })?)),
+        };
+
+        let raw_attributes = fields[8];
+        let mut attributes = MultiMap::new();
+        if gff3 {
+            for attribute in raw_attributes.split(';') {
+                if let Some((key, values)) = attribute.trim_start().split_once('=') {
+                    for value in values.split(',') {
+                        attributes.insert(percent_decode(key), percent_decode(value));
+                    }
+                }
+            }
+        } else {
+            let trim_quotes = |s: &str| s.trim_matches('\'').trim_matches('"').to_owned();
+            for caps in self.attribute_re.captures_iter(raw_attributes) {
+                for value in caps["value"].split(self.value_delim) {
+                    attributes.insert(trim_quotes(&caps["key"]), trim_quotes(value));
+                }
+            }
+        }
+
+        Ok(Record {
+            seqname: decode(fields[0]),
+            source: decode(fields[1]),
+            feature_type: decode(fields[2]),
+            start: position(fields[3])?,
+            end: position(fields[4])?,
+            score: score.to_owned(),
+            strand: fields[6].to_owned(),
+            phase,
+            attributes,
+        })
+    }
+}
+
 impl<'a, R: io::Read> Iterator for Records<'a, R> {
     type Item = csv::Result<Record>;
 
     fn next(&mut self) -> Option<csv::Result<Record>> {
-        self.inner.next().map(|res| {
-            res.map(
-                |(
-                    seqname,
-                    source,
-                    feature_type,
-                    start,
-                    end,
-                    score,
-                    strand,
-                    phase,
-                    raw_attributes,
-                )| {
-                    let trim_quotes = |s: &str| s.trim_matches('\'').trim_matches('"').to_owned();
-                    let mut attributes = MultiMap::new();
-                    for caps in self.attribute_re.captures_iter(&raw_attributes) {
-                        for value in caps["value"].split(self.value_delim) {
-                            attributes.insert(trim_quotes(&caps["key"]), trim_quotes(value));
-                        }
-                    }
-                    Record {
-                        seqname,
-                        source,
-                        feature_type,
-                        start,
-                        end,
-                        score,
-                        strand,
-                        phase,
-                        attributes,
-                    }
-                },
-            )
-        })
+        match self.reader.read_line() {
+            Ok(true) => {
+                let line = self.reader.line.trim_end_matches(['\n', '\r']);
+                Some(self.parse(line))
+            }
+            Ok(false) => None,
+            Err(e) => Some(Err(e.into())),
+        }
     }
 }
 
 /// A GFF writer.
+///
+/// For GFF3, reserved characters are percent-encoded.
 #[derive(Debug)]
 pub struct Writer<W: io::Write> {
-    inner: csv::Writer<W>,
-    delimiter: char,
-    terminator: String,
+    inner: io::BufWriter<W>,
+    gff_type: GffType,
 }
 
 impl Writer<fs::File> {
@@ -336,44 +631,102 @@ impl Writer<fs::File> {
     }
 }
 
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path in given format. The output is compressed according
+    /// to the file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P, fileformat: GffType) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|f| Encoder::new(f, format))
+            .map(|f| Writer::new(f, fileformat))
+    }
+}
+
 impl<W: io::Write> Writer<W> {
     /// Write to a given writer.
     pub fn new(writer: W, fileformat: GffType) -> Self {
-        let (delim, termi, _) = fileformat.separator();
-
         Writer {
-            inner: csv::WriterBuilder::new()
-                .delimiter(b'\t')
-                .flexible(true)
-                .from_writer(writer),
-            delimiter: delim as char,
-            terminator: String::from_utf8(vec![termi]).unwrap(),
+            inner: io::BufWriter::new(writer),
+            gff_type: fileformat,
         }
     }
 
     /// Write a given GFF record.
     pub fn write(&mut self, record: &Record) -> csv::Result<()> {
-        let attributes = if !record.attributes.is_empty() {
-            record
-                .attributes
-                .iter()
-                .map(|(a, b)| format!("{}{}{}", a, self.delimiter, b))
-                .join(&self.terminator)
+        let (delim, term, vdelim) = self.gff_type.separator();
+        let gff3 = self.gff_type == GffType::GFF3;
+        let encode = |s: &'_ str, reserved| {
+            if gff3 {
+                percent_encode(s, reserved).into_owned()
+            } else {
+                s.to_owned()
+            }
+        };
+
+        let mut attributes = Vec::new();
+        for (key, values) in record.attributes.iter_all() {
+            let key = encode(key, ATTRIBUTE_RESERVED);
+            let mut values = values.iter().map(|value| encode(value, ATTRIBUTE_RESERVED));
+            if vdelim == 0 {
+                attributes.extend(values.map(|value| format!("{}{}{}", key, delim as char, value)));
+            } else {
+                attributes.push(format!(
+                    "{}{}{}",
+                    key,
+                    delim as char,
+                    values.join(&(vdelim as char).to_string())
+                ));
+            }
+        }
+
+        let seqname = if gff3 {
+            percent_encode_seqid(&record.seqname).into_owned()
         } else {
-            "".to_owned()
+            record.seqname.clone()
         };
+        let fields = [
+            seqname,
+            encode(&record.source, ""),
+            encode(&record.feature_type, ""),
+            record.start.to_string(),
+            record.end.to_string(),
+            record.score.clone(),
+            record.strand.clone(),
+            record
+                .phase
+                .0
+                .map_or_else(|| ".".to_owned(), |p| p.to_string()),
+            attributes.join(&(term as char).to_string()),
+        ];
+        writeln!(self.inner, "{}", fields.join("\t"))?;
+        Ok(())
+    }
 
-        self.inner.serialize((
-            &record.seqname,
-            &record.source,
-            &record.feature_type,
-            record.start,
-            record.end,
-            &record.score,
-            &record.strand,
-            &record.phase,
-            attributes,
-        ))
+    /// Write a given directive.
+    pub fn write_directive(&mut self, directive: &Directive) -> Result<()> {
+        writeln!(self.inner, "{}", directive)?;
+        Ok(())
+    }
+
+    /// Write the given sequences as FASTA section, starting with a `##FASTA` directive.
+    /// No records may be written afterwards.
+    pub fn write_sequences<'a, I>(&mut self, sequences: I) -> Result<()>
+    where
+        I: IntoIterator<Item = &'a fasta::Record>,
+    {
+        writeln!(self.inner, "##FASTA")?;
+        let mut writer = fasta::Writer::new(&mut self.inner);
+        for sequence in sequences {
+            writer.write_record(sequence)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Flush the output stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
     }
 }
 
@@ -433,10 +786,10 @@ impl Record {
     }
 
     /// Score of feature
-    pub fn score(&self) -> Option<u64> {
+    pub fn score(&self) -> Option<f64> {
         match self.score.as_ref() {
             "." => None,
-            _ => self.score.parse::<u64>().ok(),
+            _ => self.score.parse::<f64>().ok(),
         }
     }
 
@@ -505,6 +858,476 @@ impl Record {
     }
 }
 
+/// Errors when building a [`FeatureGraph`] or converting its transcripts.
+#[derive(Error, Debug)]
+pub enum FeatureError {
+    #[error("parent {parent} of feature {feature} not found")]
+    MissingParent { feature: String, parent: String },
+
+    #[error("parts of feature {feature} are located on different sequences")]
+    InconsistentSeqname { feature: String },
+
+    #[error("feature {feature} has no exons")]
+    NoExons { feature: String },
+
+    #[error("a record of feature {feature} ends before it starts")]
+    InvalidInterval { feature: String },
+
+    #[error("invalid exon structure of feature {feature}")]
+    Splicing {
+        feature: String,
+        #[source]
+        source: SplicingError,
+    },
+}
+
+/// A feature of a [`FeatureGraph`].
+///
+/// A feature consists of one or more records, since discontinuous features (e.g. a CDS spanning
+/// several exons) are given as multiple records sharing the same ID.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Feature {
+    id: Option<String>,
+    records: Vec<Record>,
+    parents: Vec<usize>,
+    children: Vec<usize>,
+}
+
+impl Feature {
+    fn new(id: Option<String>, record: Record) -> Self {
+        Feature {
+            id,
+            records: vec![record],
+            parents: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// ID of the feature. For GTF, genes and transcripts are identified by their `gene_id`
+    /// and `transcript_id`.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Records that make up the feature, in order of appearance.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Type of the feature.
+    pub fn feature_type(&self) -> &str {
+        self.records[0].feature_type()
+    }
+
+    /// Sequence name of the feature.
+    pub fn seqname(&self) -> &str {
+        self.records[0].seqname()
+    }
+
+    /// Strand of the feature.
+    pub fn strand(&self) -> Option<Strand> {
+        self.records[0].strand()
+    }
+
+    /// Start position of the feature (1-based), i.e. the minimum start of its records.
+    pub fn start(&self) -> u64 {
+        self.records.iter().map(|r| r.start).min().unwrap()
+    }
+
+    /// End position of the feature (1-based, inclusive), i.e. the maximum end of its records.
+    pub fn end(&self) -> u64 {
+        self.records.iter().map(|r| r.end).max().unwrap()
+    }
+
+    /// Indices of the parents of the feature in the [`FeatureGraph`].
+    pub fn parents(&self) -> &[usize] {
+        &self.parents
+    }
+
+    /// Indices of the children of the feature in the [`FeatureGraph`].
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// Name of the feature in error messages.
+    fn name(&self) -> String {
+        match &self.id {
+            Some(id) => id.clone(),
+            None => format!("{}:{}-{}", self.seqname(), self.start(), self.end()),
+        }
+    }
+}
+
+/// A transcript, given by its exons and, for coding transcripts, its CDS.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Transcript {
+    id: Option<String>,
+    exons: Spliced<String, Strand>,
+    cds: Option<Spliced<String, Strand>>,
+    cds_phases: Vec<Phase>,
+}
+
+impl Transcript {
+    /// ID of the transcript.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Exons of the transcript (0-based coordinates).
+    pub fn exons(&self) -> &Spliced<String, Strand> {
+        &self.exons
+    }
+
+    /// Coding segments of the transcript (0-based coordinates), `None` if non-coding.
+    pub fn cds(&self) -> Option<&Spliced<String, Strand>> {
+        self.cds.as_ref()
+    }
+
+    /// Phases of the coding segments, in the order of [`Spliced::exon_starts`], i.e. sorted by
+    /// position regardless of the strand.
+    pub fn cds_phases(&self) -> &[Phase] {
+        &self.cds_phases
+    }
+}
+
+/// Parent-child relations between the features of a GFF or GTF file, e.g. genes, transcripts
+/// and exons.
+///
+/// For GFF3, features are linked via their `ID` and `Parent` attributes. Features may have
+/// several parents, and records sharing the same `ID` are combined into one feature.
+/// For GTF, records are grouped by their `gene_id` and `transcript_id` attributes. Genes and
+/// transcripts without a record of their own are inferred from the records referring to them.
+///
+/// # Example
+///
+/// ```
+/// use bio::io::gff;
+///
+/// let gff = b"chr1\t.\tgene\t1000\t9000\t.\t+\t.\tID=gene1
+/// chr1\t.\tmRNA\t1050\t9000\t.\t+\t.\tID=mRNA1;Parent=gene1
+/// chr1\t.\texon\t1050\t1500\t.\t+\t.\tParent=mRNA1
+/// chr1\t.\texon\t7000\t9000\t.\t+\t.\tParent=mRNA1
+/// chr1\t.\tCDS\t1201\t1500\t.\t+\t0\tID=cds1;Parent=mRNA1
+/// chr1\t.\tCDS\t7000\t7600\t.\t+\t0\tID=cds1;Parent=mRNA1
+/// ";
+/// let mut reader = gff::Reader::new(&gff[..], gff::GffType::GFF3);
+/// let records = reader.records().collect::<Result<Vec<_>, _>>().unwrap();
+/// let graph = gff::FeatureGraph::from_records(records, gff::GffType::GFF3).unwrap();
+///
+/// let gene = graph.feature("gene", "gene1").unwrap();
+/// let mrna = &graph.features()[gene.children()[0]];
+/// assert_eq!(mrna.id(), Some("mRNA1"));
+///
+/// let transcript = graph.transcript(mrna).unwrap();
+/// assert_eq!(transcript.exons().to_string(), "chr1:1049-1500;6999-9000(+)");
+/// assert_eq!(transcript.cds().unwrap().to_string(), "chr1:1200-1500;6999-7600(+)");
+/// ```
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct FeatureGraph {
+    features: Vec<Feature>,
+    /// Features with an ID, keyed by their type and ID.
+    ids: HashMap<(String, String), usize>,
+}
+
+impl FeatureGraph {
+    /// Build the graph from the given records. GFF2 and GTF2 records are grouped by their
+    /// `gene_id` and `transcript_id`, all others are linked via `ID` and `Parent`.
+    pub fn from_records<I>(records: I, gff_type: GffType) -> Result<Self, FeatureError>
+    where
+        I: IntoIterator<Item = Record>,
+    {
+        match gff_type {
+            GffType::GFF2 | GffType::GTF2 => Ok(Self::from_gtf_records(records)),
+            _ => Self::from_gff3_records(records),
+        }
+    }
+
+    fn from_gff3_records<I: IntoIterator<Item = Record>>(records: I) -> Result<Self, FeatureError> {
+        let mut graph = FeatureGraph::default();
+        // IDs are unique regardless of the feature type in GFF3
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut parent_ids: Vec<Vec<String>> = Vec::new();
+        for record in records {
+            let id = record.attributes.get("ID").cloned();
+            let parents = record.attributes.get_vec("Parent").cloned();
+            let index = match id.as_ref().and_then(|id| ids.get(id)) {
+                Some(&index) => {
+                    graph.features[index].records.push(record);
+                    index
+                }
+                None => {
+                    parent_ids.push(Vec::new());
+                    let index = graph.push(Feature::new(id.clone(), record));
+                    if let Some(id) = id {
+                        ids.insert(id, index);
+                    }
+                    index
+                }
+            };
+            for parent in parents.into_iter().flatten() {
+                if !parent_ids[index].contains(&parent) {
+                    parent_ids[index].push(parent);
+                }
+            }
+        }
+
+        for (index, parents) in parent_ids.into_iter().enumerate() {
+            for parent in parents {
+                let parent_index =
+                    *ids.get(&parent)
+                        .ok_or_else(|| FeatureError::MissingParent {
+                            feature: graph.features[index].name(),
+                            parent,
+                        })?;
+                graph.link(parent_index, index);
+            }
+        }
+        Ok(graph)
+    }
+
+    fn from_gtf_records<I: IntoIterator<Item = Record>>(records: I) -> Self {
+        let mut graph = FeatureGraph::default();
+        let (mut genes, mut transcripts) = (HashMap::new(), HashMap::new());
+        let mut inferred = HashSet::new();
+        for record in records {
+            let gene_id = record.attributes.get("gene_id").cloned();
+            let transcript_id = record.attributes.get("transcript_id").cloned();
+            let (index, is_node) = match (record.feature_type(), &gene_id, &transcript_id) {
+                ("gene", Some(id), _) => (
+                    graph.gtf_feature(&mut genes, &mut inferred, id, record.clone()),
+                    true,
+                ),
+                ("transcript", _, Some(id)) => (
+                    graph.gtf_feature(&mut transcripts, &mut inferred, id, record.clone()),
+                    true,
+                ),
+                _ => (graph.push(Feature::new(None, record.clone())), false),
+            };
+            let mut child = index;
+            if let (Some(id), false) = (&transcript_id, is_node) {
+                let transcript =
+                    graph.gtf_node(&mut transcripts, &mut inferred, "transcript", id, &record);
+                graph.link(transcript, child);
+                child = transcript;
+            }
+            if let (Some(id), false) = (&gene_id, record.feature_type() == "gene") {
+                let gene = graph.gtf_node(&mut genes, &mut inferred, "gene", id, &record);
+                graph.link(gene, child);
+            }
+        }
+        graph
+    }
+
+    /// Add a GTF gene or transcript record, replacing a previously inferred one.
+    fn gtf_feature(
+        &mut self,
+        ids: &mut HashMap<String, usize>,
+        inferred: &mut HashSet<usize>,
+        id: &str,
+        record: Record,
+    ) -> usize {
+        match ids.get(id) {
+            Some(&index) => {
+                if inferred.remove(&index) {
+                    self.features[index].records[0] = record;
+                } else {
+                    self.features[index].records.push(record);
+                }
+                index
+            }
+            None => {
+                let index = self.push(Feature::new(Some(id.to_owned()), record));
+                ids.insert(id.to_owned(), index);
+                index
+            }
+        }
+    }
+
+    /// Return the GTF gene or transcript with the given ID, inferring it from the given
+    /// record if necessary.
+    fn gtf_node(
+        &mut self,
+        ids: &mut HashMap<String, usize>,
+        inferred: &mut HashSet<usize>,
+        feature_type: &str,
+        id: &str,
+        record: &Record,
+    ) -> usize {
+        if let Some(&index) = ids.get(id) {
+            if inferred.contains(&index) {
+                let node = &mut self.features[index].records[0];
+                node.start = node.start.min(record.start);
+                node.end = node.end.max(record.end);
+            }
+            return index;
+        }
+        let mut node = Record::new();
+        node.seqname.clone_from(&record.seqname);
+        node.source.clone_from(&record.source);
+        node.feature_type = feature_type.to_owned();
+        node.start = record.start;
+        node.end = record.end;
+        node.strand.clone_from(&record.strand);
+        let keys: &[&str] = match feature_type {
+            "gene" => &["gene_id"],
+            _ => &["gene_id", "transcript_id"],
+        };
+        for &key in keys {
+            if let Some(value) = record.attributes.get(key) {
+                node.attributes.insert(key.to_owned(), value.clone());
+            }
+        }
+        let index = self.gtf_feature(ids, inferred, id, node);
+        inferred.insert(index);
+        index
+    }
+
+    fn push(&mut self, feature: Feature) -> usize {
+        let index = self.features.len();
+        if let Some(id) = &feature.id {
+            self.ids
+                .entry((feature.feature_type().to_owned(), id.clone()))
+                .or_insert(index);
+        }
+        self.features.push(feature);
+        index
+    }
+
+    fn link(&mut self, parent: usize, child: usize) {
+        if !self.features[parent].children.contains(&child) {
+            self.features[parent].children.push(child);
+            self.features[child].parents.push(parent);
+        }
+    }
+
+    /// All features, in order of their first record. Inferred GTF genes and transcripts are
+    /// placed at their first reference.
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Return the feature of the given type with the given ID, e.g. `("gene", "gene1")`.
+    /// For GTF, genes and transcripts are found by their `gene_id` and `transcript_id` and the
+    /// types `gene` and `transcript`, which may share the same ID.
+    pub fn feature(&self, feature_type: &str, id: &str) -> Option<&Feature> {
+        self.ids
+            .get(&(feature_type.to_owned(), id.to_owned()))
+            .map(|&index| &self.features[index])
+    }
+
+    /// Iterate over the features without parents, e.g. genes.
+    pub fn roots(&self) -> impl Iterator<Item = &Feature> {
+        self.features.iter().filter(|f| f.parents.is_empty())
+    }
+
+    /// Iterate over the children of the given feature.
+    pub fn children<'a>(&'a self, feature: &'a Feature) -> impl Iterator<Item = &'a Feature> {
+        feature
+            .children
+            .iter()
+            .map(move |&index| &self.features[index])
+    }
+
+    /// Iterate over the parents of the given feature.
+    pub fn parents<'a>(&'a self, feature: &'a Feature) -> impl Iterator<Item = &'a Feature> {
+        feature
+            .parents
+            .iter()
+            .map(move |&index| &self.features[index])
+    }
+
+    /// Iterate over all transcripts, i.e. features with `exon` or `CDS` children.
+    pub fn transcripts(&self) -> impl Iterator<Item = Result<Transcript, FeatureError>> + '_ {
+        self.features
+            .iter()
+            .filter(move |feature| {
+                self.children(feature)
+                    .any(|child| matches!(child.feature_type(), "exon" | "CDS"))
+            })
+            .map(move |feature| self.transcript(feature))
+    }
+
+    /// Convert the given feature into a transcript, using its `exon` and `CDS` children.
+    /// If there are no exons, they are inferred from the CDS and UTR children.
+    pub fn transcript(&self, feature: &Feature) -> Result<Transcript, FeatureError> {
+        let (mut exons, mut coding, mut utrs) = (Vec::new(), Vec::new(), Vec::new());
+        for record in self.children(feature).flat_map(|child| &child.records) {
+            if record.seqname != feature.seqname() {
+                return Err(FeatureError::InconsistentSeqname {
+                    feature: feature.name(),
+                });
+            }
+            if record.end < record.start {
+                return Err(FeatureError::InvalidInterval {
+                    feature: feature.name(),
+                });
+            }
+            match record.feature_type() {
+                "exon" => exons.push(record),
+                "CDS" => coding.push(record),
+                t if t.to_ascii_lowercase().contains("utr") => utrs.push(record),
+                _ => (),
+            }
+        }
+        if exons.is_empty() {
+            exons = coding.iter().chain(&utrs).copied().collect();
+        }
+        exons.sort_by_key(|record| record.start);
+        coding.sort_by_key(|record| record.start);
+
+        let mut exon_segments: Vec<(u64, u64)> = Vec::new();
+        for record in exons {
+            match exon_segments.last_mut() {
+                Some(last) if record.start <= last.1 + 1 => last.1 = last.1.max(record.end),
+                _ => exon_segments.push((record.start, record.end)),
+            }
+        }
+        if exon_segments.is_empty() {
+            return Err(FeatureError::NoExons {
+                feature: feature.name(),
+            });
+        }
+        let coding_segments = coding
+            .iter()
+            .map(|record| (record.start, record.end))
+            .collect::<Vec<_>>();
+
+        let spliced = |segments: &[(u64, u64)]| {
+            let start = segments[0].0.saturating_sub(1);
+            let lengths = segments
+                .iter()
+                .map(|&(s, e)| (e + 1 - s) as usize)
+                .collect::<Vec<_>>();
+            let starts = segments
+                .iter()
+                .map(|&(s, _)| (s.saturating_sub(1) - start) as usize)
+                .collect::<Vec<_>>();
+            Spliced::with_lengths_starts(
+                feature.seqname().to_owned(),
+                start as isize,
+                &lengths,
+                &starts,
+                feature.strand().unwrap_or(Strand::Unknown),
+            )
+            .map_err(|source| FeatureError::Splicing {
+                feature: feature.name(),
+                source,
+            })
+        };
+
+        Ok(Transcript {
+            id: feature.id.clone(),
+            exons: spliced(&exon_segments)?,
+            cds: if coding.is_empty() {
+                None
+            } else {
+                Some(spliced(&coding_segments)?)
+            },
+            cds_phases: coding.iter().map(|record| record.phase.clone()).collect(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -559,7 +1382,7 @@ P0A7B8\tUniProtKB\tChain\t2\t176\t50\t+\t.\tID PRO_0000148105
         let feature_type = ["Initiator methionine", "Chain"];
         let starts = [1, 2];
         let ends = [1, 176];
-        let scores = [None, Some(50)];
+        let scores = [None, Some(50.0)];
         let strand = [None, Some(Strand::Forward)];
         let phase = [Phase(None), Phase(None)];
         let mut attributes = [MultiMap::new(), MultiMap::new()];
@@ -637,7 +1460,7 @@ P0A7B8\tUniProtKB\tChain\t2\t176\t50\t+\t.\tID PRO_0000148105
         let feature_type = ["Initiator methionine", "Chain"];
         let starts = [1, 2];
         let ends = [1, 176];
-        let scores = [None, Some(50)];
+        let scores = [None, Some(50.0)];
         let strand = [None, Some(Strand::Forward)];
         let phase = [Phase(None), Phase(None)];
         let mut attributes = [MultiMap::new(), MultiMap::new()];
@@ -775,4 +1598,362 @@ P0A7B8\tUniProtKB\tChain\t2\t176\t50\t+\t.\tID PRO_0000148105
         let result: Result<u8, ()> = phase.try_into();
         assert_eq!(result, Err(()));
     }
+
+    const GFF_FILE_GENE: &[u8] = b"chr1\tsrc\tgene\t1000\t9000\t.\t+\t.\tID=gene1
+chr1\tsrc\tmRNA\t1050\t9000\t.\t+\t.\tID=mRNA1;Parent=gene1
+chr1\tsrc\tmRNA\t1050\t9000\t.\t+\t.\tID=mRNA2;Parent=gene1
+chr1\tsrc\texon\t1050\t1500\t.\t+\t.\tParent=mRNA1,mRNA2
+chr1\tsrc\texon\t3000\t3902\t.\t+\t.\tParent=mRNA1
+chr1\tsrc\texon\t5000\t5500\t.\t+\t.\tParent=mRNA1,mRNA2
+chr1\tsrc\texon\t7000\t9000\t.\t+\t.\tParent=mRNA1,mRNA2
+chr1\tsrc\tCDS\t1201\t1500\t.\t+\t0\tID=cds1;Parent=mRNA1
+chr1\tsrc\tCDS\t3000\t3901\t.\t+\t0\tID=cds1;Parent=mRNA1
+chr1\tsrc\tCDS\t5000\t5500\t.\t+\t1\tID=cds1;Parent=mRNA1
+chr1\tsrc\tCDS\t7000\t7600\t.\t+\t1\tID=cds1;Parent=mRNA1
+";
+
+    const GTF_FILE_GENE: &[u8] =
+        b"chr2\tsrc\texon\t100\t200\t.\t-\t.\tgene_id \"g1\"; transcript_id \"t1\";
+chr2\tsrc\tCDS\t150\t200\t.\t-\t0\tgene_id \"g1\"; transcript_id \"t1\";
+chr2\tsrc\texon\t300\t400\t.\t-\t.\tgene_id \"g1\"; transcript_id \"t1\";
+chr2\tsrc\tCDS\t300\t350\t.\t-\t2\tgene_id \"g1\"; transcript_id \"t1\";
+chr2\tsrc\texon\t300\t420\t.\t-\t.\tgene_id \"g1\"; transcript_id \"t2\";
+chr2\tsrc\tgene\t100\t500\t.\t-\t.\tgene_id \"g1\";
+";
+
+    fn feature_graph(gff: &[u8], gff_type: GffType) -> Result<FeatureGraph, FeatureError> {
+        let mut reader = Reader::new(gff, gff_type);
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        FeatureGraph::from_records(records, gff_type)
+    }
+
+    #[test]
+    fn test_feature_graph_gff3() {
+        let graph = feature_graph(GFF_FILE_GENE, GffType::GFF3).unwrap();
+        // gene, 2 mRNAs, 4 exons and a CDS made of 4 records
+        assert_eq!(graph.features().len(), 8);
+        let roots = graph.roots().collect::<Vec<_>>();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].id(), Some("gene1"));
+        let mrnas = graph
+            .children(roots[0])
+            .filter_map(|f| f.id())
+            .collect::<Vec<_>>();
+        assert_eq!(mrnas, ["mRNA1", "mRNA2"]);
+
+        let cds = graph.feature("CDS", "cds1").unwrap();
+        assert_eq!(cds.records().len(), 4);
+        assert_eq!((cds.start(), cds.end()), (1201, 7600));
+        assert_eq!(cds.strand(), Some(Strand::Forward));
+
+        let mrna2 = graph.feature("mRNA", "mRNA2").unwrap();
+        assert_eq!(mrna2.children().len(), 3);
+        let first_exon = graph.children(mrna2).next().unwrap();
+        let parents = graph
+            .parents(first_exon)
+            .filter_map(|f| f.id())
+            .collect::<Vec<_>>();
+        assert_eq!(parents, ["mRNA1", "mRNA2"]);
+
+        let transcripts = graph.transcripts().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(transcripts.len(), 2);
+        let mrna1 = &transcripts[0];
+        assert_eq!(mrna1.id(), Some("mRNA1"));
+        assert_eq!(mrna1.exons().exon_starts(), [0, 1950, 3950, 5950]);
+        assert_eq!(mrna1.exons().exon_lengths(), [451, 903, 501, 2001]);
+        let cds = mrna1.cds().unwrap();
+        assert_eq!(
+            cds.to_string(),
+            "chr1:1200-1500;2999-3901;4999-5500;6999-7600(+)"
+        );
+        assert_eq!(
+            mrna1.cds_phases(),
+            [
+                Phase::from(0),
+                Phase::from(0),
+                Phase::from(1),
+                Phase::from(1)
+            ]
+        );
+        let mrna2 = &transcripts[1];
+        assert_eq!(
+            mrna2.exons().to_string(),
+            "chr1:1049-1500;4999-5500;6999-9000(+)"
+        );
+        assert_eq!(mrna2.cds(), None);
+        assert!(mrna2.cds_phases().is_empty());
+    }
+
+    #[test]
+    fn test_feature_graph_gtf() {
+        let graph = feature_graph(GTF_FILE_GENE, GffType::GTF2).unwrap();
+        // 6 records and an inferred transcript for each transcript_id
+        assert_eq!(graph.features().len(), 8);
+        let gene = graph.feature("gene", "g1").unwrap();
+        assert_eq!(gene.feature_type(), "gene");
+        assert_eq!(gene.records().len(), 1);
+        assert_eq!((gene.start(), gene.end()), (100, 500));
+        let transcripts = graph
+            .children(gene)
+            .filter_map(|f| f.id())
+            .collect::<Vec<_>>();
+        assert_eq!(transcripts, ["t1", "t2"]);
+
+        let t1 = graph.feature("transcript", "t1").unwrap();
+        assert_eq!(t1.feature_type(), "transcript");
+        assert_eq!((t1.start(), t1.end()), (100, 400));
+        assert_eq!(t1.strand(), Some(Strand::Reverse));
+        assert_eq!(t1.children().len(), 4);
+        assert_eq!(
+            t1.records()[0].attributes().get("gene_id"),
+            Some(&"g1".to_owned())
+        );
+
+        let transcript = graph.transcript(t1).unwrap();
+        assert_eq!(transcript.exons().to_string(), "chr2:99-200;299-400(-)");
+        assert_eq!(
+            transcript.cds().unwrap().to_string(),
+            "chr2:149-200;299-350(-)"
+        );
+        assert_eq!(transcript.cds_phases(), [Phase::from(0), Phase::from(2)]);
+        let transcripts = graph.transcripts().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(transcripts.len(), 2);
+        assert_eq!(transcripts[1].exons().to_string(), "chr2:299-420(-)");
+    }
+
+    #[test]
+    fn test_feature_graph_exons_from_cds() {
+        let gff = b"chr1\tsrc\tmRNA\t1\t100\t.\t-\t.\tID=tx
+chr1\tsrc\tfive_prime_UTR\t81\t100\t.\t-\t.\tParent=tx
+chr1\tsrc\tCDS\t41\t80\t.\t-\t0\tParent=tx
+chr1\tsrc\tCDS\t1\t20\t.\t-\t2\tParent=tx
+";
+        let graph = feature_graph(gff, GffType::GFF3).unwrap();
+        let transcript = graph
+            .transcript(graph.feature("mRNA", "tx").unwrap())
+            .unwrap();
+        assert_eq!(transcript.exons().to_string(), "chr1:0-20;40-100(-)");
+        assert_eq!(transcript.cds().unwrap().to_string(), "chr1:0-20;40-80(-)");
+        assert_eq!(transcript.cds_phases(), [Phase::from(2), Phase::from(0)]);
+    }
+
+    #[test]
+    fn test_feature_graph_errors() {
+        let gff = b"chr1\tsrc\texon\t1\t100\t.\t+\t.\tParent=tx";
+        assert!(matches!(
+            feature_graph(gff, GffType::GFF3),
+            Err(FeatureError::MissingParent { parent, .. }) if parent == "tx"
+        ));
+
+        let gff = b"chr1\tsrc\tmRNA\t1\t100\t.\t+\t.\tID=tx
+chr2\tsrc\texon\t1\t100\t.\t+\t.\tParent=tx
+";
+        let graph = feature_graph(gff, GffType::GFF3).unwrap();
+        assert!(matches!(
+            graph.transcript(graph.feature("mRNA", "tx").unwrap()),
+            Err(FeatureError::InconsistentSeqname { .. })
+        ));
+
+        let gff = b"chr1\tsrc\tgene\t1\t100\t.\t+\t.\tID=gene";
+        let graph = feature_graph(gff, GffType::GFF3).unwrap();
+        assert!(matches!(
+            graph.transcript(graph.feature("gene", "gene").unwrap()),
+            Err(FeatureError::NoExons { .. })
+        ));
+        assert_eq!(graph.transcripts().count(), 0);
+
+        let gff = b"chr1\tsrc\tmRNA\t1\t100\t.\t+\t.\tID=tx
+chr1\tsrc\texon\t50\t10\t.\t+\t.\tParent=tx
+";
+        let graph = feature_graph(gff, GffType::GFF3).unwrap();
+        assert!(matches!(
+            graph.transcript(graph.feature("mRNA", "tx").unwrap()),
+            Err(FeatureError::InvalidInterval { .. })
+        ));
+    }
+
+    #[test]
+    fn test_feature_graph_gtf_shared_id() {
+        let gtf = b"chr1\tsrc\texon\t1\t100\t.\t+\t.\tgene_id \"x\"; transcript_id \"x\";
+chr1\tsrc\tgene\t1\t200\t.\t+\t.\tgene_id \"x\";
+";
+        let graph = feature_graph(gtf, GffType::GTF2).unwrap();
+        let gene = graph.feature("gene", "x").unwrap();
+        let transcript = graph.feature("transcript", "x").unwrap();
+        assert_eq!((gene.start(), gene.end()), (1, 200));
+        assert_eq!((transcript.start(), transcript.end()), (1, 100));
+        assert_eq!(graph.children(gene).next(), Some(transcript));
+    }
+
+    const GFF3_FILE_FULL: &[u8] = b"##gff-version 3.1.26
+##sequence-region ctg%20123 1 1497228
+##species https://www.ncbi.nlm.nih.gov/Taxonomy/Browser/wwwtax.cgi?id=9606
+# a comment
+ctg%20123\t.\tgene\t1000\t9000\t0.5\t+\t.\tID=gene%3B1;Note=a%2Cb%3Dc,second
+###
+ctg%20123\t.\tCDS\t1201\t1500\t.\t+\t2\tID=cds1;Parent=gene%3B1
+##FASTA
+>ctg 123
+ACGT
+ACGT
+";
+
+    #[test]
+    fn test_reader_gff3_directives_and_fasta() {
+        let mut reader = Reader::new(GFF3_FILE_FULL, GffType::GFF3);
+        assert_eq!(
+            reader.directives().unwrap(),
+            [
+                Directive::GffVersion("3.1.26".to_owned()),
+                Directive::SequenceRegion {
+                    seqid: "ctg 123".to_owned(),
+                    start: 1,
+                    end: 1497228
+                },
+                Directive::Species(
+                    "https://www.ncbi.nlm.nih.gov/Taxonomy/Browser/wwwtax.cgi?id=9606".to_owned()
+                ),
+            ]
+        );
+
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].seqname(), "ctg 123");
+        assert_eq!(records[0].score(), Some(0.5));
+        assert_eq!(
+            records[0].attributes().get("ID"),
+            Some(&"gene;1".to_owned())
+        );
+        assert_eq!(
+            records[0].attributes().get_vec("Note"),
+            Some(&vec!["a,b=c".to_owned(), "second".to_owned()])
+        );
+        assert_eq!(records[1].score(), None);
+        assert_eq!(*records[1].phase(), Phase::from(2));
+        assert_eq!(
+            records[1].attributes().get("Parent"),
+            Some(&"gene;1".to_owned())
+        );
+        assert_eq!(
+            reader.directives().unwrap()[3],
+            Directive::ForwardReferencesResolved
+        );
+
+        let sequences = reader
+            .sequences()
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(sequences.len(), 1);
+        assert_eq!(sequences[0].id(), "ctg");
+        assert_eq!(sequences[0].seq(), b"ACGTACGT");
+    }
+
+    #[test]
+    fn test_reader_gff3_fasta_without_directive() {
+        let gff = b"ctg1\t.\tgene\t1\t4\t.\t+\t.\tID=gene1\n>ctg1\nACGT\n";
+        let reader = Reader::new(&gff[..], GffType::GFF3);
+        // remaining records are skipped
+        let sequences = reader
+            .sequences()
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(sequences.len(), 1);
+        assert_eq!(sequences[0].seq(), b"ACGT");
+    }
+
+    #[test]
+    fn test_reader_invalid_lines() {
+        let gff = b"ctg1\t.\tgene\t1\n";
+        let mut reader = Reader::new(&gff[..], GffType::GFF3);
+        assert!(reader.records().next().unwrap().is_err());
+
+        let gff = b"ctg1\t.\tgene\tx\t4\t.\t+\t.\tID=gene1\n";
+        let mut reader = Reader::new(&gff[..], GffType::GFF3);
+        assert!(reader.records().next().unwrap().is_err());
+
+        let gff = b"ctg1\t.\tgene\t1\t4\thigh\t+\t.\tID=gene1\n";
+        let mut reader = Reader::new(&gff[..], GffType::GFF3);
+        assert!(reader.records().next().unwrap().is_err());
+
+        let gff = b"##sequence-region ctg1 1\n";
+        let mut reader = Reader::new(&gff[..], GffType::GFF3);
+        assert!(matches!(
+            reader.directives(),
+            Err(Error::InvalidDirective(line)) if line == "##sequence-region ctg1 1"
+        ));
+        let mut reader = Reader::new(&gff[..], GffType::GFF3);
+        assert!(reader.records().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_writer_gff3_roundtrip() {
+        let mut reader = Reader::new(GFF3_FILE_FULL, GffType::GFF3);
+        let directives = reader.directives().unwrap().to_vec();
+        let mut records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        // only one attribute per record, since the order of attributes is not preserved
+        records[0].attributes_mut().remove("ID");
+        records[1].attributes_mut().remove("ID");
+        let sequences = reader
+            .sequences()
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        let mut writer = Writer::new(vec![], GffType::GFF3);
+        for directive in &directives {
+            writer.write_directive(directive).unwrap();
+        }
+        writer.write(&records[0]).unwrap();
+        writer
+            .write_directive(&Directive::ForwardReferencesResolved)
+            .unwrap();
+        writer.write(&records[1]).unwrap();
+        writer.write_sequences(&sequences).unwrap();
+        let written = writer.inner.into_inner().unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "##gff-version 3.1.26
+##sequence-region ctg%20123 1 1497228
+##species https://www.ncbi.nlm.nih.gov/Taxonomy/Browser/wwwtax.cgi?id=9606
+ctg%20123\t.\tgene\t1000\t9000\t0.5\t+\t.\tNote=a%2Cb%3Dc,second
+###
+ctg%20123\t.\tCDS\t1201\t1500\t.\t+\t2\tParent=gene%3B1
+##FASTA
+>ctg 123
+ACGTACGT
+"
+        );
+    }
+
+    #[test]
+    fn test_reader_gtf2_no_directives() {
+        let gtf = b"##FASTA\n##description: test\n\
+            chr1\tsrc\texon\t1\t10\t.\t+\t.\tgene_id \"g1\";\n";
+        let mut reader = Reader::new(&gtf[..], GffType::GTF2);
+        assert!(reader.directives().unwrap().is_empty());
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].attributes()["gene_id"], "g1");
+
+        let mut reader = Reader::new(&b"chr1\tsrc\texon\t1\t10\t.\t+\t.\n"[..], GffType::GFF3);
+        let err = reader.records().next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("Expected 9 tab-separated fields"));
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!(percent_encode("a b", ""), "a b");
+        assert_eq!(
+            percent_encode("50%\ta;b", ATTRIBUTE_RESERVED),
+            "50%25%09a%3Bb"
+        );
+        assert_eq!(percent_decode("50%25%09a%3Bb"), "50%\ta;b");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%2c"), "%zz,");
+        assert_eq!(percent_encode_seqid("chr1|a.b:c"), "chr1|a.b:c");
+        assert_eq!(percent_encode_seqid("ctg 1;2/é"), "ctg%201%3B2%2F%C3%A9");
+        assert_eq!(percent_decode("ctg%201%3B2%2F%C3%A9"), "ctg 1;2/é");
+    }
 }
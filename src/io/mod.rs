@@ -1,9 +1,25 @@
 //! Readers and writers for common bioinformatics file formats.
 
 pub mod bed;
+pub mod bedgraph;
+#[cfg(feature = "gzip")]
+pub mod bgzf;
+pub mod compression;
+pub mod embl;
 pub mod fasta;
 pub mod fastq;
 pub mod fastx;
+pub mod genbank;
+pub mod gfa;
 pub mod gff;
+pub mod motif;
+pub mod msa;
 #[cfg(feature = "phylogeny")]
 pub mod newick;
+pub mod paf;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod peak;
+mod record_buffer;
+pub mod twobit;
+pub mod vcf;
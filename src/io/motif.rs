@@ -0,0 +1,1172 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Readers and writers for position frequency matrices of sequence motifs, as distributed by
+//! motif databases:
+//!
+//! * [JASPAR](https://jaspar.elixir.no/docs/), both the `.jaspar` format with labeled rows
+//!   and the plain `.pfm` format,
+//! * [MEME minimal motif format](https://meme-suite.org/meme/doc/meme-format.html),
+//! * [TRANSFAC](https://en.wikipedia.org/wiki/TRANSFAC) matrices.
+//!
+//! Each [`Record`] holds the count matrix of a motif together with its identifier, name and
+//! further annotations. Records can be converted into position-specific scoring matrices
+//! ([`DNAMotif`](crate::pattern_matching::pssm::DNAMotif) or
+//! [`ProtMotif`](crate::pattern_matching::pssm::ProtMotif)) for scanning sequences.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::motif::{Format, Reader, Writer};
+//! use bio::pattern_matching::pssm::Motif;
+//!
+//! let jaspar = b">MA0004.1\tArnt
+//! A  [ 4 19  0  0  0  0 ]
+//! C  [16  0 20  0  0  0 ]
+//! G  [ 0  1  0 20  0 20 ]
+//! T  [ 0  0  0  0 20  0 ]
+//! ";
+//! let mut reader = Reader::new(&jaspar[..], Format::Jaspar);
+//! let record = reader.records().next().unwrap().unwrap();
+//! assert_eq!(record.id(), "MA0004.1");
+//! assert_eq!(record.name(), Some("Arnt"));
+//! assert_eq!(record.len(), 6);
+//! assert_eq!(record.counts()[[1, 0]], 19.0);
+//!
+//! let motif = record.dna_motif(None).unwrap();
+//! assert_eq!(motif.degenerate_consensus(), b"CACGTG");
+//! assert_eq!(motif.score(b"TTCACGTGAA").unwrap().loc, 2);
+//!
+//! let mut writer = Writer::new(Vec::new(), Format::Transfac);
+//! writer.write(&record).unwrap();
+//! let transfac = writer.into_inner().unwrap();
+//! assert!(transfac.starts_with(b"ID  MA0004.1\nXX\nNA  Arnt\n"));
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+use ndarray::prelude::*;
+use thiserror::Error;
+
+use crate::io::compression::{self, Decoder, Encoder};
+use crate::pattern_matching::pssm::{self, DNAMotif, Motif, ProtMotif, DEF_PSEUDO};
+
+/// Alphabet of JASPAR `.pfm` files, whose rows are not labeled.
+const PFM_ALPHABET: &[u8] = b"ACGT";
+/// Number of sites MEME assumes if a letter-probability matrix doesn't specify it.
+const MEME_DEFAULT_NSITES: f32 = 20.0;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read or write motif file")]
+    Io(#[from] io::Error),
+
+    #[error("invalid {format} motif in line {line}: {msg}")]
+    Parse {
+        format: Format,
+        line: u64,
+        msg: String,
+    },
+
+    #[error("motif {id} can't be written in {format} format: {msg}")]
+    InvalidRecord {
+        format: Format,
+        id: String,
+        msg: &'static str,
+    },
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A supported motif file format.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Format {
+    /// JASPAR format with one labeled row per letter, e.g. `A [ 4 19 0 ]`.
+    Jaspar,
+    /// JASPAR `.pfm` format with unlabeled rows for `A`, `C`, `G` and `T`.
+    Pfm,
+    /// MEME minimal motif format with letter-probability matrices.
+    Meme,
+    /// TRANSFAC matrix format.
+    Transfac,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Jaspar => "JASPAR",
+            Format::Pfm => "PFM",
+            Format::Meme => "MEME",
+            Format::Transfac => "TRANSFAC",
+        })
+    }
+}
+
+/// A motif given as position frequency matrix.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Record {
+    id: String,
+    name: Option<String>,
+    alphabet: Vec<u8>,
+    counts: Array2<f32>,
+    annotations: Vec<(String, String)>,
+}
+
+impl Record {
+    /// Create a new record from a count matrix indexed by `[position, letter]`, with columns
+    /// in the order of the given alphabet.
+    ///
+    /// # Panics
+    ///
+    /// If the number of columns differs from the size of the alphabet.
+    pub fn new(id: &str, alphabet: &[u8], counts: Array2<f32>) -> Self {
+        assert_eq!(
+            counts.ncols(),
+            alphabet.len(),
+            "count matrix must have one column per letter"
+        );
+        Record {
+            id: id.to_owned(),
+            name: None,
+            alphabet: alphabet.to_owned(),
+            counts,
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Create a record from the weights of a position-specific scoring matrix. Columns are
+    /// sorted alphabetically, e.g. `A`, `C`, `G`, `T` for DNA.
+    pub fn from_motif<M: Motif>(id: &str, motif: &M) -> Self {
+        let mut letters: Vec<(u8, usize)> = M::MONOS
+            .iter()
+            .enumerate()
+            .map(|(i, &letter)| (letter, i))
+            .collect();
+        letters.sort_unstable();
+        let scores = motif.get_scores();
+        let columns: Vec<usize> = letters.iter().map(|&(_, i)| i).collect();
+        let alphabet: Vec<u8> = letters.iter().map(|&(letter, _)| letter).collect();
+        Record::new(id, &alphabet, scores.select(Axis(1), &columns))
+    }
+
+    /// Return the identifier, e.g. the JASPAR matrix ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Return the name, e.g. of the transcription factor.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Set the name.
+    pub fn set_name(&mut self, name: Option<&str>) {
+        self.name = name.map(|name| name.to_owned());
+    }
+
+    /// Return the letters corresponding to the columns of the count matrix.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    /// Return the count matrix, indexed by `[position, letter]`. For MEME motifs, these are
+    /// the letter probabilities multiplied with the number of sites.
+    pub fn counts(&self) -> &Array2<f32> {
+        &self.counts
+    }
+
+    /// Return the count of a letter at the given position, or `None` if the letter is not
+    /// part of the alphabet.
+    pub fn count(&self, pos: usize, letter: u8) -> Option<f32> {
+        self.alphabet
+            .iter()
+            .position(|&l| l.eq_ignore_ascii_case(&letter))
+            .map(|i| self.counts[[pos, i]])
+    }
+
+    /// Return the width of the motif.
+    pub fn len(&self) -> usize {
+        self.counts.nrows()
+    }
+
+    /// Return true if the motif has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the number of sites, i.e. the total count of the first position.
+    pub fn nsites(&self) -> f32 {
+        if self.is_empty() {
+            0.0
+        } else {
+            self.counts.row(0).sum()
+        }
+    }
+
+    /// Return all annotations as pairs of tag and text, e.g. the `DE` lines of TRANSFAC or
+    /// the `E` value and `URL` of MEME motifs.
+    pub fn annotations(&self) -> &[(String, String)] {
+        &self.annotations
+    }
+
+    /// Return the first annotation with the given tag.
+    pub fn annotation(&self, tag: &str) -> Option<&str> {
+        self.annotations
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map(|(_, text)| text.as_str())
+    }
+
+    /// Add an annotation.
+    pub fn push_annotation(&mut self, tag: &str, text: &str) {
+        self.annotations.push((tag.to_owned(), text.to_owned()));
+    }
+
+    /// Return a position-specific scoring matrix for DNA.
+    ///
+    /// # Arguments
+    /// * `pseudos` - array slice with a pseudocount for each monomer in the order of
+    ///   `DNAMotif::MONOS`; defaults to `pssm::DEF_PSEUDO` for all if None is supplied
+    ///
+    /// # Errors
+    /// * `pssm::Error::InvalidMonomer` - the alphabet contains a letter other than `ACGT`
+    /// * `pssm::Error::InvalidPseudos` - `pseudos` has the wrong length
+    pub fn dna_motif(&self, pseudos: Option<&[f32]>) -> pssm::Result<DNAMotif> {
+        self.weights::<DNAMotif>(pseudos).map(DNAMotif::from)
+    }
+
+    /// Return a position-specific scoring matrix for proteins.
+    ///
+    /// # Arguments
+    /// * `pseudos` - array slice with a pseudocount for each monomer in the order of
+    ///   `ProtMotif::MONOS`; defaults to `pssm::DEF_PSEUDO` for all if None is supplied
+    ///
+    /// # Errors
+    /// * `pssm::Error::InvalidMonomer` - the alphabet contains a letter other than the 20
+    ///   standard amino acids
+    /// * `pssm::Error::InvalidPseudos` - `pseudos` has the wrong length
+    pub fn prot_motif(&self, pseudos: Option<&[f32]>) -> pssm::Result<ProtMotif> {
+        self.weights::<ProtMotif>(pseudos).map(ProtMotif::from)
+    }
+
+    /// Return the counts plus pseudocounts with columns in the order of `M::MONOS`.
+    fn weights<M: Motif>(&self, pseudos: Option<&[f32]>) -> pssm::Result<Array2<f32>> {
+        let pseudos = match pseudos {
+            Some(pseudos) if pseudos.len() != M::MONO_CT => {
+                return Err(pssm::Error::InvalidPseudos {
+                    expected: M::MONO_CT as u8,
+                    received: pseudos.len() as u8,
+                })
+            }
+            Some(pseudos) => Array1::from_vec(pseudos.to_vec()),
+            None => Array1::from_elem(M::MONO_CT, DEF_PSEUDO),
+        };
+        let mut weights = Array2::zeros((self.len(), M::MONO_CT));
+        for mut row in weights.rows_mut() {
+            row += &pseudos;
+        }
+        for (i, &letter) in self.alphabet.iter().enumerate() {
+            let mut column = weights.column_mut(M::lookup(letter)?);
+            column += &self.counts.column(i);
+        }
+        Ok(weights)
+    }
+}
+
+/// Split off the first whitespace-delimited word of a line.
+fn split_word(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.find(char::is_whitespace) {
+        Some(i) => (&line[..i], line[i..].trim_start()),
+        None => (line, ""),
+    }
+}
+
+/// Return `None` for empty strings.
+fn non_empty(s: &str) -> Option<&str> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// Parse whitespace-delimited numbers.
+fn parse_numbers(line: &str) -> Option<Vec<f32>> {
+    line.split_whitespace()
+        .map(|field| field.parse().ok())
+        .collect()
+}
+
+/// Build a matrix from rows of equal length.
+fn from_rows(rows: &[Vec<f32>]) -> Option<Array2<f32>> {
+    let ncols = rows.first().map_or(0, |row| row.len());
+    if rows.iter().any(|row| row.len() != ncols) {
+        return None;
+    }
+    Array2::from_shape_vec((rows.len(), ncols), rows.concat()).ok()
+}
+
+/// A reader for motif files.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: io::BufReader<R>,
+    format: Format,
+    pending: Option<String>,
+    line: u64,
+    alphabet: Option<Vec<u8>>,
+    background: Option<Vec<(u8, f32)>>,
+    header_read: bool,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path in the given format.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        format: Format,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(|reader| Reader::new(reader, format))
+            .with_context(|| format!("Failed to read {} motifs from {:#?}", format, path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path in the given format.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        format: Format,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(|reader| Reader::new(reader, format))
+            .with_context(|| format!("Failed to read {} motifs from {:#?}", format, path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader in the given format.
+    pub fn new(reader: R, format: Format) -> Self {
+        Reader {
+            inner: io::BufReader::new(reader),
+            format,
+            pending: None,
+            line: 0,
+            alphabet: None,
+            background: None,
+            header_read: false,
+        }
+    }
+
+    /// Iterate over all motifs.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self }
+    }
+
+    /// Read the next motif, if any.
+    pub fn read(&mut self) -> Result<Option<Record>> {
+        match self.format {
+            Format::Jaspar | Format::Pfm => self.read_jaspar(),
+            Format::Meme => self.read_meme(),
+            Format::Transfac => self.read_transfac(),
+        }
+    }
+
+    /// Return the background letter frequencies of a MEME file. They are available after
+    /// the first motif has been read.
+    pub fn background(&self) -> Option<&[(u8, f32)]> {
+        self.background.as_deref()
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>> {
+        if let Some(line) = self.pending.take() {
+            return Ok(Some(line));
+        }
+        let mut line = String::new();
+        if self.inner.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        let len = line.trim_end_matches(&['\n', '\r'][..]).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+
+    /// Return the next line that is not blank.
+    fn next_nonblank_line(&mut self) -> Result<Option<String>> {
+        while let Some(line) = self.next_line()? {
+            if !line.trim().is_empty() {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    fn error<T>(&self, msg: &str) -> Result<T> {
+        Err(Error::Parse {
+            format: self.format,
+            line: self.line,
+            msg: msg.to_owned(),
+        })
+    }
+
+    fn read_jaspar(&mut self) -> Result<Option<Record>> {
+        let header = match self.next_nonblank_line()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let (id, name) = match header.strip_prefix('>') {
+            Some(header) => split_word(header),
+            None => return self.error("expected '>' at record start"),
+        };
+        let (id, name) = (id.to_owned(), name.to_owned());
+
+        let mut alphabet = Vec::new();
+        let mut rows = Vec::new();
+        while let Some(line) = self.next_line()? {
+            if line.starts_with('>') {
+                self.pending = Some(line);
+                break;
+            } else if line.trim().is_empty() {
+                continue;
+            }
+            let mut values = line.trim();
+            if values.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                let (letter, rest) = split_word(values);
+                if letter.len() != 1 {
+                    return self.error("expected a single letter as row label");
+                }
+                alphabet.push(letter.as_bytes()[0]);
+                values = rest;
+            }
+            let values = values.trim_start_matches('[').trim_end_matches(']');
+            match parse_numbers(values) {
+                Some(row) => rows.push(row),
+                None => return self.error("expected counts"),
+            }
+        }
+
+        if alphabet.is_empty() {
+            if rows.len() != PFM_ALPHABET.len() {
+                return self.error("expected four rows for A, C, G and T");
+            }
+            alphabet = PFM_ALPHABET.to_owned();
+        } else if alphabet.len() != rows.len() {
+            return self.error("expected a label for every row");
+        }
+        let counts = match from_rows(&rows) {
+            Some(counts) => counts.reversed_axes(),
+            None => return self.error("rows have different lengths"),
+        };
+        let mut record = Record::new(&id, &alphabet, counts);
+        record.set_name(non_empty(&name));
+        Ok(Some(record))
+    }
+
+    fn read_meme_header(&mut self) -> Result<()> {
+        match self.next_nonblank_line()? {
+            Some(line) if line.starts_with("MEME version") => (),
+            Some(_) => return self.error("expected 'MEME version' header"),
+            None => return Ok(()),
+        }
+        while let Some(line) = self.next_line()? {
+            if line.starts_with("MOTIF") {
+                self.pending = Some(line);
+                break;
+            } else if let Some(alphabet) = line.strip_prefix("ALPHABET=") {
+                self.alphabet = Some(alphabet.trim().bytes().collect());
+            } else if line.starts_with("ALPHABET") {
+                self.read_meme_alphabet()?;
+            } else if line.starts_with("Background letter frequencies") {
+                let mut background = Vec::new();
+                while let Some(line) = self.next_line()? {
+                    if line.trim().is_empty() || line.starts_with("MOTIF") {
+                        self.pending = Some(line);
+                        break;
+                    }
+                    let fields: Vec<&str> = line.split_whitespace().collect();
+                    for pair in fields.chunks(2) {
+                        match (pair[0].as_bytes(), pair.get(1).map(|f| f.parse())) {
+                            ([letter], Some(Ok(freq))) => background.push((*letter, freq)),
+                            _ => return self.error("expected letters and frequencies"),
+                        }
+                    }
+                }
+                self.background = Some(background);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the core symbols of a custom alphabet definition, which are sorted
+    /// alphabetically to give the column order of the matrices.
+    fn read_meme_alphabet(&mut self) -> Result<()> {
+        let mut alphabet = Vec::new();
+        loop {
+            let line = match self.next_line()? {
+                Some(line) => line,
+                None => return self.error("missing 'END ALPHABET'"),
+            };
+            if line.starts_with("END ALPHABET") {
+                break;
+            } else if line.trim().is_empty() || line.contains('=') {
+                // ambiguous symbols are not part of the matrices
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            alphabet.push(fields.next().unwrap().as_bytes()[0]);
+            if fields.any(|field| field == "~") {
+                match fields.next() {
+                    Some(complement) => alphabet.push(complement.as_bytes()[0]),
+                    None => return self.error("expected complementary symbol"),
+                }
+            }
+        }
+        alphabet.sort_unstable();
+        self.alphabet = Some(alphabet);
+        Ok(())
+    }
+
+    fn read_meme(&mut self) -> Result<Option<Record>> {
+        if !self.header_read {
+            self.header_read = true;
+            self.read_meme_header()?;
+        }
+        let header = match self.next_nonblank_line()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let (id, name) = match header.strip_prefix("MOTIF") {
+            Some(header) => split_word(header),
+            None => return self.error("expected 'MOTIF' at record start"),
+        };
+        if id.is_empty() {
+            return self.error("missing motif identifier");
+        }
+        let (id, name) = (id.to_owned(), name.to_owned());
+
+        let mut annotations = Vec::new();
+        let mut matrix = None;
+        while let Some(line) = self.next_line()? {
+            if line.starts_with("MOTIF") {
+                self.pending = Some(line);
+                break;
+            } else if let Some(url) = line.strip_prefix("URL") {
+                annotations.push(("URL".to_owned(), url.trim().to_owned()));
+            } else if let Some(rest) = line.strip_prefix("letter-probability matrix:") {
+                let mut nsites = MEME_DEFAULT_NSITES;
+                let mut width = None;
+                let fields: Vec<&str> = rest.split_whitespace().collect();
+                for pair in fields.chunks(2) {
+                    let value = pair.get(1).copied().unwrap_or_default();
+                    match pair[0] {
+                        "w=" => width = value.parse().ok(),
+                        "nsites=" => nsites = value.parse().unwrap_or(nsites),
+                        "E=" => annotations.push(("E".to_owned(), value.to_owned())),
+                        _ => (),
+                    }
+                }
+                let rows = self.read_meme_rows()?;
+                if width.is_some_and(|width: usize| width != rows.len()) {
+                    return self.error("number of rows differs from motif width");
+                }
+                match from_rows(&rows) {
+                    Some(probs) => matrix = Some(probs * nsites),
+                    None => return self.error("rows have different lengths"),
+                }
+            } else if line.starts_with("log-odds matrix:") {
+                self.read_meme_rows()?;
+            }
+        }
+
+        let counts = match matrix {
+            Some(counts) => counts,
+            None => return self.error("missing letter-probability matrix"),
+        };
+        let alphabet = match &self.alphabet {
+            Some(alphabet) => alphabet.clone(),
+            // MEME versions before 4.4 have no alphabet line
+            None if counts.ncols() == 4 => PFM_ALPHABET.to_owned(),
+            None if counts.ncols() == 20 => b"ACDEFGHIKLMNPQRSTVWY".to_vec(),
+            None => return self.error("missing alphabet"),
+        };
+        if alphabet.len() != counts.ncols() {
+            return self.error("number of columns differs from alphabet size");
+        }
+        let mut record = Record::new(&id, &alphabet, counts);
+        record.set_name(non_empty(&name));
+        record.annotations = annotations;
+        Ok(Some(record))
+    }
+
+    /// Read the numeric rows following a MEME matrix header.
+    fn read_meme_rows(&mut self) -> Result<Vec<Vec<f32>>> {
+        let mut rows = Vec::new();
+        while let Some(line) = self.next_line()? {
+            if line.trim().is_empty() && rows.is_empty() {
+                continue;
+            }
+            match parse_numbers(&line) {
+                Some(row) if !row.is_empty() => rows.push(row),
+                _ => {
+                    self.pending = Some(line);
+                    break;
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    fn read_transfac(&mut self) -> Result<Option<Record>> {
+        let mut id = None;
+        let mut accession = None;
+        let mut name = None;
+        let mut annotations = Vec::new();
+        let mut alphabet = None;
+        let mut rows = Vec::new();
+        loop {
+            let line = match self.next_line()? {
+                Some(line) => line,
+                None if id.is_none() && accession.is_none() && alphabet.is_none() => {
+                    return Ok(None)
+                }
+                None => return self.error("missing '//' terminator"),
+            };
+            let (key, text) = split_word(&line);
+            match key {
+                "//" => {
+                    if id.is_none() && accession.is_none() && alphabet.is_none() {
+                        // e.g. the end of the VV header of a TRANSFAC file
+                        annotations.clear();
+                        continue;
+                    }
+                    break;
+                }
+                "" | "XX" | "VV" => (),
+                "ID" => id = Some(text.to_owned()),
+                "NA" => name = Some(text.to_owned()),
+                "P0" | "PO" => {
+                    alphabet = Some(
+                        text.split_whitespace()
+                            .map(|letter| letter.as_bytes()[0])
+                            .collect::<Vec<u8>>(),
+                    )
+                }
+                _ if key.bytes().all(|c| c.is_ascii_digit()) => {
+                    let ncols = match &alphabet {
+                        Some(alphabet) => alphabet.len(),
+                        None => return self.error("matrix row before 'P0' header"),
+                    };
+                    // an optional consensus letter may follow the counts
+                    let values: Vec<&str> = text.split_whitespace().take(ncols).collect();
+                    match parse_numbers(&values.join(" ")) {
+                        Some(row) if row.len() == ncols => rows.push(row),
+                        _ => return self.error("expected counts"),
+                    }
+                }
+                _ => {
+                    if key == "AC" && accession.is_none() {
+                        accession = Some(text.to_owned());
+                    }
+                    annotations.push((key.to_owned(), text.to_owned()));
+                }
+            }
+        }
+
+        let alphabet = match alphabet {
+            Some(alphabet) => alphabet,
+            None => return self.error("missing 'P0' matrix"),
+        };
+        let counts = if rows.is_empty() {
+            Array2::zeros((0, alphabet.len()))
+        } else {
+            match from_rows(&rows) {
+                Some(counts) if counts.ncols() == alphabet.len() => counts,
+                _ => return self.error("rows have different lengths"),
+            }
+        };
+        let id = match id.or(accession) {
+            Some(id) => id,
+            None => return self.error("missing 'ID' or 'AC' line"),
+        };
+        let mut record = Record::new(&id, &alphabet, counts);
+        record.name = name;
+        record.annotations = annotations;
+        Ok(Some(record))
+    }
+}
+
+/// An iterator over the motifs of a file.
+pub struct Records<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        self.reader.read().transpose()
+    }
+}
+
+/// A writer for motif files.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+    format: Format,
+    alphabet: Option<Vec<u8>>,
+    background: Option<Vec<(u8, f32)>>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path in the given format.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        fs::File::create(path).map(|writer| Writer::new(writer, format))
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path in the given format. The output is compressed according
+    /// to the file extension (see [`compression::Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        let compression = compression::Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, compression))
+            .map(|writer| Writer::new(writer, format))
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer in the given format.
+    pub fn new(writer: W, format: Format) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+            format,
+            alphabet: None,
+            background: None,
+        }
+    }
+
+    /// Set the background letter frequencies written to the header of MEME files. Defaults
+    /// to a uniform distribution. Must be called before the first motif is written.
+    pub fn set_background(&mut self, background: &[(u8, f32)]) {
+        self.background = Some(background.to_owned());
+    }
+
+    /// Write a motif. All motifs of a MEME file must have the same alphabet.
+    pub fn write(&mut self, record: &Record) -> Result<()> {
+        if record.id.is_empty() || record.id.contains(char::is_whitespace) {
+            return self.invalid(record, "identifier must be a non-empty word");
+        }
+        match self.format {
+            Format::Jaspar => self.write_jaspar(record),
+            Format::Pfm => self.write_pfm(record),
+            Format::Meme => self.write_meme(record),
+            Format::Transfac => self.write_transfac(record),
+        }
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.inner.into_inner().map_err(|e| e.into_error())
+    }
+
+    fn invalid<T>(&self, record: &Record, msg: &'static str) -> Result<T> {
+        Err(Error::InvalidRecord {
+            format: self.format,
+            id: record.id.clone(),
+            msg,
+        })
+    }
+
+    fn write_header(&mut self, record: &Record) -> io::Result<()> {
+        write!(self.inner, ">{}", record.id)?;
+        if let Some(name) = &record.name {
+            write!(self.inner, "\t{}", name)?;
+        }
+        writeln!(self.inner)
+    }
+
+    fn write_jaspar(&mut self, record: &Record) -> Result<()> {
+        self.write_header(record)?;
+        for (letter, column) in record.alphabet.iter().zip(record.counts.columns()) {
+            write!(self.inner, "{}  [", char::from(*letter))?;
+            for count in column {
+                write!(self.inner, " {:>6}", count)?;
+            }
+            writeln!(self.inner, " ]")?;
+        }
+        Ok(())
+    }
+
+    fn write_pfm(&mut self, record: &Record) -> Result<()> {
+        let mut letters = record.alphabet.to_ascii_uppercase();
+        letters.sort_unstable();
+        if letters != PFM_ALPHABET {
+            return self.invalid(record, "alphabet must consist of A, C, G and T");
+        }
+        self.write_header(record)?;
+        for letter in PFM_ALPHABET {
+            let i = record
+                .alphabet
+                .iter()
+                .position(|l| l.eq_ignore_ascii_case(letter))
+                .unwrap();
+            let row: Vec<String> = record.counts.column(i).iter().map(f32::to_string).collect();
+            writeln!(self.inner, "{}", row.join("\t"))?;
+        }
+        Ok(())
+    }
+
+    fn write_meme(&mut self, record: &Record) -> Result<()> {
+        let mut columns: Vec<usize> = (0..record.alphabet.len()).collect();
+        columns.sort_unstable_by_key(|&i| record.alphabet[i]);
+        let alphabet: Vec<u8> = columns.iter().map(|&i| record.alphabet[i]).collect();
+        match &self.alphabet {
+            Some(expected) if *expected != alphabet => {
+                return self.invalid(record, "alphabet differs from previous motifs");
+            }
+            Some(_) => (),
+            None => {
+                writeln!(self.inner, "MEME version 4\n")?;
+                writeln!(
+                    self.inner,
+                    "ALPHABET= {}\n",
+                    String::from_utf8_lossy(&alphabet)
+                )?;
+                if alphabet == PFM_ALPHABET {
+                    writeln!(self.inner, "strands: + -\n")?;
+                }
+                writeln!(self.inner, "Background letter frequencies")?;
+                let uniform = 1.0 / alphabet.len() as f32;
+                let background: Vec<String> = match &self.background {
+                    Some(background) => background
+                        .iter()
+                        .map(|(letter, freq)| format!("{} {}", char::from(*letter), freq))
+                        .collect(),
+                    None => alphabet
+                        .iter()
+                        .map(|letter| format!("{} {}", char::from(*letter), uniform))
+                        .collect(),
+                };
+                writeln!(self.inner, "{}\n", background.join(" "))?;
+                self.alphabet = Some(alphabet);
+            }
+        }
+
+        write!(self.inner, "MOTIF {}", record.id)?;
+        if let Some(name) = &record.name {
+            write!(self.inner, " {}", name)?;
+        }
+        writeln!(self.inner, "\n")?;
+        write!(
+            self.inner,
+            "letter-probability matrix: alength= {} w= {} nsites= {}",
+            columns.len(),
+            record.len(),
+            record.nsites().round()
+        )?;
+        if let Some(evalue) = record.annotation("E") {
+            write!(self.inner, " E= {}", evalue)?;
+        }
+        writeln!(self.inner)?;
+        for row in record.counts.rows() {
+            let total = row.sum();
+            for &i in &columns {
+                let prob = if total > 0.0 { row[i] / total } else { 0.0 };
+                write!(self.inner, " {:.6}", prob)?;
+            }
+            writeln!(self.inner)?;
+        }
+        writeln!(self.inner)?;
+        if let Some(url) = record.annotation("URL") {
+            writeln!(self.inner, "URL {}\n", url)?;
+        }
+        Ok(())
+    }
+
+    fn write_transfac(&mut self, record: &Record) -> Result<()> {
+        let (accessions, others): (Vec<_>, Vec<_>) =
+            record.annotations.iter().partition(|(tag, _)| tag == "AC");
+        for (tag, text) in accessions {
+            writeln!(self.inner, "{}  {}", tag, text)?;
+            writeln!(self.inner, "XX")?;
+        }
+        writeln!(self.inner, "ID  {}", record.id)?;
+        writeln!(self.inner, "XX")?;
+        if let Some(name) = &record.name {
+            writeln!(self.inner, "NA  {}", name)?;
+            writeln!(self.inner, "XX")?;
+        }
+        for (i, (tag, text)) in others.iter().enumerate() {
+            writeln!(self.inner, "{}  {}", tag, text)?;
+            if others.get(i + 1).map(|(next, _)| next) != Some(tag) {
+                writeln!(self.inner, "XX")?;
+            }
+        }
+        write!(self.inner, "P0")?;
+        for letter in &record.alphabet {
+            write!(self.inner, " {:>6}", char::from(*letter))?;
+        }
+        writeln!(self.inner)?;
+        for (pos, row) in record.counts.rows().into_iter().enumerate() {
+            write!(self.inner, "{:02}", pos + 1)?;
+            for count in row {
+                write!(self.inner, " {:>6}", count)?;
+            }
+            writeln!(self.inner)?;
+        }
+        writeln!(self.inner, "XX\n//")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JASPAR: &[u8] = b">MA0004.1\tArnt
+A  [ 4 19  0  0  0  0 ]
+C  [16  0 20  0  0  0 ]
+G  [ 0  1  0 20  0 20 ]
+T  [ 0  0  0  0 20  0 ]
+>MA0006.1 Ahr::Arnt
+A  [ 3  0  0  0  0  0 ]
+C  [ 8  0 23  0  0  0 ]
+G  [ 2 23  0 23  0 24 ]
+T  [11  1  1  1 24  0 ]
+";
+
+    const MEME: &[u8] = b"MEME version 4
+
+ALPHABET= ACGT
+
+strands: + -
+
+Background letter frequencies (from uniform background):
+A 0.30000 C 0.20000 G 0.20000 T 0.30000
+
+MOTIF crp alt_crp
+letter-probability matrix: alength= 4 w= 3 nsites= 10 E= 4.1e-009
+ 0.100000  0.200000  0.300000  0.400000
+ 0.000000  1.000000  0.000000  0.000000
+ 0.500000  0.000000  0.500000  0.000000
+
+log-odds matrix: alength= 4 w= 3 E= 4.1e-009
+ -1.0 1.0 0.5 0.2
+ -1.0 1.0 0.5 0.2
+ -1.0 1.0 0.5 0.2
+
+URL http://example.org/crp
+
+MOTIF lexA
+letter-probability matrix: alength= 4 w= 2
+ 0.250000  0.250000  0.250000  0.250000
+ 1.000000  0.000000  0.000000  0.000000
+";
+
+    const TRANSFAC: &[u8] = b"VV  TRANSFAC MATRIX TABLE, Rel.3.2 26-06-1997
+XX
+//
+AC  M00001
+XX
+ID  V$MYOD_01
+XX
+NA  MyoD
+XX
+DE  myoblast determination gene product
+XX
+BF  T00526; MyoD; Species: mouse, Mus musculus.
+BF  T01130; MyoD; Species: human, Homo sapiens.
+XX
+P0      A      C      G      T
+01      1      2      2      0      S
+02      2      1      2      0      R
+03      3      0      1      1      A
+XX
+//
+";
+
+    fn read_all(data: &[u8], format: Format) -> Vec<Record> {
+        Reader::new(data, format)
+            .records()
+            .collect::<Result<_>>()
+            .unwrap()
+    }
+
+    fn roundtrip(records: &[Record], format: Format) -> Vec<Record> {
+        let mut writer = Writer::new(Vec::new(), format);
+        for record in records {
+            writer.write(record).unwrap();
+        }
+        read_all(&writer.into_inner().unwrap(), format)
+    }
+
+    #[test]
+    fn test_jaspar() {
+        let records = read_all(JASPAR, Format::Jaspar);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].id(), "MA0006.1");
+        assert_eq!(records[1].name(), Some("Ahr::Arnt"));
+        assert_eq!(records[1].alphabet(), b"ACGT");
+        assert_eq!(records[1].counts().dim(), (6, 4));
+        assert_eq!(records[1].count(0, b'T'), Some(11.0));
+        assert_eq!(records[1].nsites(), 24.0);
+        assert_eq!(roundtrip(&records, Format::Jaspar), records);
+        assert_eq!(roundtrip(&records, Format::Pfm), records);
+
+        let mut writer = Writer::new(Vec::new(), Format::Pfm);
+        writer.write(&records[0]).unwrap();
+        assert_eq!(
+            writer.into_inner().unwrap(),
+            b">MA0004.1\tArnt\n4\t19\t0\t0\t0\t0\n16\t0\t20\t0\t0\t0\n\
+              0\t1\t0\t20\t0\t20\n0\t0\t0\t0\t20\t0\n"
+        );
+
+        let pfm = b">MA0004.1 Arnt\n4 19 0\n16 0 20\n0 1 0\n";
+        assert!(matches!(
+            Reader::new(&pfm[..], Format::Pfm).read(),
+            Err(Error::Parse { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_meme() {
+        let mut reader = Reader::new(MEME, Format::Meme);
+        let records: Vec<Record> = reader.records().collect::<Result<_>>().unwrap();
+        assert_eq!(
+            reader.background(),
+            Some(&[(b'A', 0.3), (b'C', 0.2), (b'G', 0.2), (b'T', 0.3)][..])
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id(), "crp");
+        assert_eq!(records[0].name(), Some("alt_crp"));
+        assert_eq!(records[0].len(), 3);
+        assert_relative_eq!(records[0].count(0, b'T').unwrap(), 4.0);
+        assert_eq!(records[0].annotation("E"), Some("4.1e-009"));
+        assert_eq!(records[0].annotation("URL"), Some("http://example.org/crp"));
+        // nsites defaults to 20
+        assert_eq!(records[1].name(), None);
+        assert_relative_eq!(records[1].nsites(), 20.0);
+        assert_eq!(records[1].count(1, b'A'), Some(20.0));
+
+        let written = roundtrip(&records, Format::Meme);
+        assert_eq!(written.len(), 2);
+        for (written, record) in written.iter().zip(&records) {
+            assert_eq!(written.id(), record.id());
+            assert_eq!(written.annotations(), record.annotations());
+            assert!((written.counts() - record.counts())
+                .iter()
+                .all(|d| d.abs() < 1e-4));
+        }
+
+        let protein = Record::new("p", b"AC", Array2::ones((1, 2)));
+        let mut writer = Writer::new(Vec::new(), Format::Meme);
+        writer.write(&records[0]).unwrap();
+        assert!(matches!(
+            writer.write(&protein),
+            Err(Error::InvalidRecord { .. })
+        ));
+    }
+
+    #[test]
+    fn test_meme_custom_alphabet() {
+        let meme = b"MEME version 5
+
+ALPHABET \"RNA\" RNA-LIKE
+A \"Adenine\" CC0000
+C \"Cytosine\" 0000CC
+G \"Guanine\" FFB300
+U \"Uracil\" 008000
+N = ACGU
+END ALPHABET
+
+MOTIF m1
+letter-probability matrix: alength= 4 w= 1 nsites= 4
+ 0.25 0.25 0.25 0.25
+";
+        let records = read_all(meme, Format::Meme);
+        assert_eq!(records[0].alphabet(), b"ACGU");
+    }
+
+    #[test]
+    fn test_transfac() {
+        let records = read_all(TRANSFAC, Format::Transfac);
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.id(), "V$MYOD_01");
+        assert_eq!(record.name(), Some("MyoD"));
+        assert_eq!(record.annotation("AC"), Some("M00001"));
+        assert_eq!(
+            record.annotation("DE"),
+            Some("myoblast determination gene product")
+        );
+        assert_eq!(record.annotations().len(), 4);
+        assert_eq!(
+            record.counts(),
+            &array![[1., 2., 2., 0.], [2., 1., 2., 0.], [3., 0., 1., 1.]]
+        );
+        assert_eq!(roundtrip(&records, Format::Transfac), records);
+
+        // JASPAR records convert to TRANSFAC and back
+        let jaspar = read_all(JASPAR, Format::Jaspar);
+        assert_eq!(roundtrip(&jaspar, Format::Transfac), jaspar);
+
+        // a second matrix header changes the number of columns
+        for transfac in [
+            &b"ID  m\nP0 A C G T\n01 1 2 2 0\nP0 A C G\n02 1 2 2\n//\n"[..],
+            b"ID  m\nP0 A C G T\n01 1 2 2 0\nP0 A C G\n//\n",
+        ] {
+            assert!(matches!(
+                Reader::new(transfac, Format::Transfac).read(),
+                Err(Error::Parse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_motif_conversion() {
+        let record = &read_all(TRANSFAC, Format::Transfac)[0];
+        let motif = record.dna_motif(Some(&[0.0; 4])).unwrap();
+        // DNAMotif columns are ordered A, T, G, C
+        assert_relative_eq!(motif.scores[[2, 0]], 0.6);
+        assert_relative_eq!(motif.scores[[2, 1]], 0.2);
+        assert_eq!(
+            record.dna_motif(Some(&[0.0; 3])),
+            Err(pssm::Error::InvalidPseudos {
+                expected: 4,
+                received: 3
+            })
+        );
+
+        let back = Record::from_motif("m", &motif);
+        assert_eq!(back.alphabet(), b"ACGT");
+        assert_relative_eq!(back.count(2, b'A').unwrap(), 0.6);
+
+        let protein = ProtMotif::from_seqs(&[b"ARND".to_vec(), b"ARNE".to_vec()], None).unwrap();
+        let record = Record::from_motif("prot", &protein);
+        assert_eq!(record.alphabet(), b"ACDEFGHIKLMNPQRSTVWY");
+        assert_eq!(
+            record.dna_motif(None).map(|_| ()),
+            Err(pssm::Error::InvalidMonomer { mono: b'D' })
+        );
+        let converted = record.prot_motif(Some(&[0.0; 20])).unwrap();
+        assert!((converted.scores - protein.scores)
+            .iter()
+            .all(|d| d.abs() < 1e-6));
+    }
+}
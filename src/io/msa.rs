@@ -0,0 +1,1315 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multiple sequence alignment (MSA) container and file formats.
+//!
+//! An [`Alignment`] holds gapped sequences of equal length and offers row- and column-wise
+//! access. It can be read from and written to
+//!
+//! * [Clustal](http://www.clustal.org),
+//! * [Stockholm](https://en.wikipedia.org/wiki/Stockholm_format), including `#=GF`, `#=GC`,
+//!   `#=GS` and `#=GR` annotations,
+//! * strict and relaxed
+//!   [PHYLIP](https://evolution.genetics.washington.edu/phylip/doc/sequence.html),
+//!   in sequential or interleaved layout,
+//! * gapped (aligned) FASTA.
+//!
+//! Both `-` and `.` are treated as gaps. Alignments can also be derived from and converted to
+//! the graph of a partial order alignment (see [`poa`](crate::alignment::poa)).
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::msa::{Format, Reader, Writer};
+//!
+//! let stockholm = b"# STOCKHOLM 1.0
+//! #=GF ID example
+//! seq1 AC-GT
+//! seq2 ACAGT
+//! #=GC SS_cons <<.>>
+//! //
+//! ";
+//! let mut reader = Reader::new(&stockholm[..], Format::Stockholm);
+//! let alignment = reader.alignments().next().unwrap().unwrap();
+//! assert_eq!(alignment.len(), 2);
+//! assert_eq!(alignment.ncols(), 5);
+//! assert_eq!(alignment.column(2), Some(b"-A".to_vec()));
+//! assert_eq!(alignment.column_annotation("SS_cons"), Some(&b"<<.>>"[..]));
+//! assert_eq!(alignment.records()[0].ungapped(), b"ACGT");
+//!
+//! let mut writer = Writer::new(Vec::new(), Format::Fasta);
+//! writer.write(&alignment).unwrap();
+//! assert_eq!(
+//!     writer.into_inner().unwrap(),
+//!     b">seq1\nAC-GT\n>seq2\nACAGT\n"
+//! );
+//! ```
+
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::Topo;
+use thiserror::Error;
+
+use crate::alignment::pairwise::{MatchFunc, Scoring};
+use crate::alignment::poa::{POAGraph, Poa};
+use crate::io::compression::{self, Decoder, Encoder};
+
+/// Width of sequence names in strict PHYLIP.
+const PHYLIP_NAME_WIDTH: usize = 10;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read or write alignment")]
+    Io(#[from] io::Error),
+
+    #[error("invalid {format} alignment in line {line}: {msg}")]
+    Parse {
+        format: Format,
+        line: u64,
+        msg: String,
+    },
+
+    #[error("{what} of {id} has length {len}, expected {expected}")]
+    UnequalLength {
+        what: &'static str,
+        id: String,
+        len: usize,
+        expected: usize,
+    },
+
+    #[error("sequence name {id:?} can't be written in {format} format")]
+    InvalidName { format: Format, id: String },
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Return true if the given symbol is a gap (`-` or `.`).
+pub fn is_gap(symbol: u8) -> bool {
+    symbol == b'-' || symbol == b'.'
+}
+
+/// A supported multiple sequence alignment file format.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Format {
+    /// Clustal `.aln` format.
+    Clustal,
+    /// Stockholm format, as used by Pfam and Rfam.
+    Stockholm,
+    /// Strict PHYLIP, with sequence names padded or truncated to 10 characters.
+    Phylip,
+    /// Relaxed PHYLIP, with sequence names separated from the sequence by whitespace.
+    RelaxedPhylip,
+    /// FASTA with gapped sequences of equal length.
+    Fasta,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Clustal => "Clustal",
+            Format::Stockholm => "Stockholm",
+            Format::Phylip => "PHYLIP",
+            Format::RelaxedPhylip => "relaxed PHYLIP",
+            Format::Fasta => "FASTA",
+        })
+    }
+}
+
+/// A gapped sequence of a multiple sequence alignment.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Record {
+    id: String,
+    desc: Option<String>,
+    seq: Vec<u8>,
+    annotations: Vec<(String, String)>,
+    residue_annotations: Vec<(String, Vec<u8>)>,
+}
+
+impl Record {
+    /// Create a new record from an identifier, an optional description and a gapped sequence.
+    pub fn new(id: &str, desc: Option<&str>, seq: &[u8]) -> Self {
+        Record {
+            id: id.to_owned(),
+            desc: desc.map(|desc| desc.to_owned()),
+            seq: seq.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Identifier of the sequence.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Description of the sequence (FASTA only).
+    pub fn desc(&self) -> Option<&str> {
+        self.desc.as_deref()
+    }
+
+    /// Gapped sequence.
+    pub fn seq(&self) -> &[u8] {
+        &self.seq
+    }
+
+    /// Sequence with all gaps removed.
+    pub fn ungapped(&self) -> Vec<u8> {
+        self.seq.iter().copied().filter(|&c| !is_gap(c)).collect()
+    }
+
+    /// Per-sequence annotations (Stockholm `#=GS` lines) as tag and text.
+    pub fn annotations(&self) -> &[(String, String)] {
+        &self.annotations
+    }
+
+    /// Text of the first per-sequence annotation with the given tag.
+    pub fn annotation(&self, tag: &str) -> Option<&str> {
+        self.annotations
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map(|(_, text)| text.as_str())
+    }
+
+    /// Add a per-sequence annotation.
+    pub fn push_annotation(&mut self, tag: &str, text: &str) {
+        self.annotations.push((tag.to_owned(), text.to_owned()));
+    }
+
+    /// Per-residue annotations (Stockholm `#=GR` lines) as tag and one symbol per column.
+    pub fn residue_annotations(&self) -> &[(String, Vec<u8>)] {
+        &self.residue_annotations
+    }
+
+    /// Per-residue annotation with the given tag.
+    pub fn residue_annotation(&self, tag: &str) -> Option<&[u8]> {
+        find_annotation(&self.residue_annotations, tag)
+    }
+
+    /// Set a per-residue annotation, which has to have the same length as the sequence.
+    pub fn set_residue_annotation(&mut self, tag: &str, annotation: &[u8]) -> Result<()> {
+        check_length(
+            "residue annotation",
+            &self.id,
+            annotation.len(),
+            self.seq.len(),
+        )?;
+        set_annotation(&mut self.residue_annotations, tag, annotation);
+        Ok(())
+    }
+}
+
+/// A multiple sequence alignment: gapped sequences of equal length plus alignment-wide
+/// annotations.
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Alignment {
+    records: Vec<Record>,
+    file_annotations: Vec<(String, String)>,
+    column_annotations: Vec<(String, Vec<u8>)>,
+}
+
+impl Alignment {
+    /// Create an empty alignment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an alignment from records, which all have to be of the same length.
+    pub fn from_records(records: Vec<Record>) -> Result<Self> {
+        let mut alignment = Alignment::new();
+        for record in records {
+            alignment.push(record)?;
+        }
+        Ok(alignment)
+    }
+
+    /// Add a record, which has to be of the same length as the records already present.
+    pub fn push(&mut self, record: Record) -> Result<()> {
+        if let Some(first) = self.records.first() {
+            check_length("sequence", &record.id, record.seq.len(), first.seq.len())?;
+        }
+        for (_, annotation) in &record.residue_annotations {
+            check_length(
+                "residue annotation",
+                &record.id,
+                annotation.len(),
+                record.seq.len(),
+            )?;
+        }
+        self.records.push(record);
+        Ok(())
+    }
+
+    /// The records of the alignment.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// The record with the given identifier.
+    pub fn record(&self, id: &str) -> Option<&Record> {
+        self.records.iter().find(|record| record.id == id)
+    }
+
+    /// Number of sequences.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Return true if the alignment has no sequences.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Number of columns.
+    pub fn ncols(&self) -> usize {
+        self.records.first().map_or(0, |record| record.seq.len())
+    }
+
+    /// The symbols of all sequences at the given column.
+    pub fn column(&self, i: usize) -> Option<Vec<u8>> {
+        if i < self.ncols() {
+            Some(self.records.iter().map(|record| record.seq[i]).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over all columns.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        (0..self.ncols()).map(move |i| self.records.iter().map(|record| record.seq[i]).collect())
+    }
+
+    /// Annotations of the whole alignment (Stockholm `#=GF` lines) as tag and text.
+    pub fn file_annotations(&self) -> &[(String, String)] {
+        &self.file_annotations
+    }
+
+    /// Add an annotation of the whole alignment.
+    pub fn push_file_annotation(&mut self, tag: &str, text: &str) {
+        self.file_annotations
+            .push((tag.to_owned(), text.to_owned()));
+    }
+
+    /// Per-column annotations (Stockholm `#=GC` lines) as tag and one symbol per column.
+    pub fn column_annotations(&self) -> &[(String, Vec<u8>)] {
+        &self.column_annotations
+    }
+
+    /// Per-column annotation with the given tag, e.g. `SS_cons`.
+    pub fn column_annotation(&self, tag: &str) -> Option<&[u8]> {
+        find_annotation(&self.column_annotations, tag)
+    }
+
+    /// Set a per-column annotation, which has to have one symbol per column.
+    pub fn set_column_annotation(&mut self, tag: &str, annotation: &[u8]) -> Result<()> {
+        if !self.is_empty() {
+            check_length("column annotation", tag, annotation.len(), self.ncols())?;
+        }
+        set_annotation(&mut self.column_annotations, tag, annotation);
+        Ok(())
+    }
+
+    /// Derive an alignment from a partial order alignment graph and the sequences that were
+    /// aligned into it, e.g. via `poa::Aligner::graph`.
+    ///
+    /// Like `poa::Alignment::pretty`, nodes are visited in topological order and each sequence
+    /// consumes a node if it matches its next residue. Residues that could not be placed this
+    /// way are appended in additional columns.
+    pub fn from_poa_graph<I, S, T>(graph: &POAGraph, sequences: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: AsRef<str>,
+        T: AsRef<[u8]>,
+    {
+        let (ids, seqs): (Vec<S>, Vec<T>) = sequences.into_iter().unzip();
+        let mut positions = vec![0; seqs.len()];
+        let mut rows = vec![Vec::new(); seqs.len()];
+        let mut topo = Topo::new(graph);
+        while let Some(node) = topo.next(graph) {
+            let base = graph[node];
+            let mut consumed = false;
+            for ((seq, pos), row) in seqs.iter().zip(&mut positions).zip(&mut rows) {
+                if seq.as_ref().get(*pos) == Some(&base) {
+                    row.push(base);
+                    *pos += 1;
+                    consumed = true;
+                } else {
+                    row.push(b'-');
+                }
+            }
+            if !consumed {
+                rows.iter_mut().for_each(|row| {
+                    row.pop();
+                });
+            }
+        }
+        for (i, seq) in seqs.iter().enumerate() {
+            for &base in &seq.as_ref()[positions[i]..] {
+                for (j, row) in rows.iter_mut().enumerate() {
+                    row.push(if i == j { base } else { b'-' });
+                }
+            }
+        }
+
+        Alignment {
+            records: ids
+                .iter()
+                .zip(rows)
+                .map(|(id, row)| Record::new(id.as_ref(), None, &row))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Build a partial order alignment graph with one node per distinct residue of each
+    /// column. Edge weights count the sequences passing from one node to the next.
+    pub fn to_poa_graph(&self) -> POAGraph {
+        let mut graph = POAGraph::default();
+        let mut column_nodes: Vec<HashMap<u8, NodeIndex<usize>>> =
+            vec![HashMap::new(); self.ncols()];
+        let mut prev: Vec<Option<NodeIndex<usize>>> = vec![None; self.len()];
+        for (col, nodes) in column_nodes.iter_mut().enumerate() {
+            for (record, prev) in self.records.iter().zip(&mut prev) {
+                let base = record.seq[col];
+                if is_gap(base) {
+                    continue;
+                }
+                let node = *nodes.entry(base).or_insert_with(|| graph.add_node(base));
+                if let Some(prev) = *prev {
+                    match graph.find_edge(prev, node) {
+                        Some(edge) => graph[edge] += 1,
+                        None => {
+                            graph.add_edge(prev, node, 1);
+                        }
+                    }
+                }
+                *prev = Some(node);
+            }
+        }
+        graph
+    }
+
+    /// Build a partial order alignment from this alignment (see `to_poa_graph`), e.g. to
+    /// continue aligning further sequences with `poa::Aligner::from_poa`.
+    pub fn to_poa<F: MatchFunc>(&self, scoring: Scoring<F>) -> Poa<F> {
+        Poa::new(scoring, self.to_poa_graph())
+    }
+}
+
+fn find_annotation<'a>(annotations: &'a [(String, Vec<u8>)], tag: &str) -> Option<&'a [u8]> {
+    annotations
+        .iter()
+        .find(|(t, _)| t == tag)
+        .map(|(_, annotation)| annotation.as_slice())
+}
+
+fn set_annotation(annotations: &mut Vec<(String, Vec<u8>)>, tag: &str, annotation: &[u8]) {
+    match annotations.iter_mut().find(|(t, _)| t == tag) {
+        Some((_, existing)) => *existing = annotation.to_owned(),
+        None => annotations.push((tag.to_owned(), annotation.to_owned())),
+    }
+}
+
+fn check_length(what: &'static str, id: &str, len: usize, expected: usize) -> Result<()> {
+    if len == expected {
+        Ok(())
+    } else {
+        Err(Error::UnequalLength {
+            what,
+            id: id.to_owned(),
+            len,
+            expected,
+        })
+    }
+}
+
+/// Split off the first whitespace-delimited word of a line.
+fn split_word(line: &str) -> (&str, &str) {
+    let line = line.trim_start();
+    match line.find(char::is_whitespace) {
+        Some(i) => (&line[..i], line[i..].trim()),
+        None => (line, ""),
+    }
+}
+
+/// Remove all whitespace from a sequence line.
+fn residues(line: &str) -> impl Iterator<Item = u8> + '_ {
+    line.bytes().filter(|c| !c.is_ascii_whitespace())
+}
+
+/// Records under construction, looked up by identifier.
+#[derive(Default)]
+struct RecordBuilder {
+    records: Vec<Record>,
+    index: HashMap<String, usize>,
+}
+
+impl RecordBuilder {
+    fn get(&mut self, id: &str) -> &mut Record {
+        let records = &mut self.records;
+        let i = *self.index.entry(id.to_owned()).or_insert_with(|| {
+            records.push(Record::new(id, None, b""));
+            records.len() - 1
+        });
+        &mut self.records[i]
+    }
+}
+
+/// A reader for multiple sequence alignments.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: io::BufReader<R>,
+    format: Format,
+    /// Lines that were read ahead, in reverse order.
+    pending: Vec<String>,
+    line: u64,
+    done: bool,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path in the given format.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        format: Format,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(|reader| Reader::new(reader, format))
+            .with_context(|| format!("Failed to read {} alignment from {:#?}", format, path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path in the given format.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        format: Format,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(|reader| Reader::new(reader, format))
+            .with_context(|| format!("Failed to read {} alignment from {:#?}", format, path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader in the given format.
+    pub fn new(reader: R, format: Format) -> Self {
+        Reader {
+            inner: io::BufReader::new(reader),
+            format,
+            pending: Vec::new(),
+            line: 0,
+            done: false,
+        }
+    }
+
+    /// Iterate over all alignments. Stockholm and PHYLIP files may contain several
+    /// alignments, Clustal and FASTA files contain one.
+    pub fn alignments(&mut self) -> Alignments<'_, R> {
+        Alignments { reader: self }
+    }
+
+    /// Read the next alignment, if any.
+    pub fn read(&mut self) -> Result<Option<Alignment>> {
+        if self.done {
+            return Ok(None);
+        }
+        let alignment = match self.format {
+            Format::Clustal => self.read_clustal(),
+            Format::Stockholm => self.read_stockholm(),
+            Format::Phylip | Format::RelaxedPhylip => self.read_phylip(),
+            Format::Fasta => self.read_fasta(),
+        };
+        if !matches!(alignment, Ok(Some(_)))
+            || matches!(self.format, Format::Clustal | Format::Fasta)
+        {
+            self.done = true;
+        }
+        alignment
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>> {
+        if let Some(line) = self.pending.pop() {
+            return Ok(Some(line));
+        }
+        let mut line = String::new();
+        if self.inner.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        let len = line.trim_end_matches(&['\n', '\r'][..]).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+
+    /// Return the next line that is not blank.
+    fn next_nonblank_line(&mut self) -> Result<Option<String>> {
+        while let Some(line) = self.next_line()? {
+            if !line.trim().is_empty() {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    fn error<T>(&self, msg: &str) -> Result<T> {
+        Err(Error::Parse {
+            format: self.format,
+            line: self.line,
+            msg: msg.to_owned(),
+        })
+    }
+
+    fn finish(&self, records: Vec<Record>) -> Result<Alignment> {
+        if records.is_empty() {
+            return self.error("no sequences found");
+        }
+        Alignment::from_records(records)
+    }
+
+    fn read_clustal(&mut self) -> Result<Option<Alignment>> {
+        let header = match self.next_nonblank_line()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        if !["CLUSTAL", "MUSCLE", "PROBCONS"]
+            .iter()
+            .any(|prefix| header.starts_with(prefix))
+        {
+            return self.error("expected CLUSTAL header");
+        }
+        let mut builder = RecordBuilder::default();
+        while let Some(line) = self.next_line()? {
+            // Skip blank lines and conservation lines, which start with whitespace.
+            if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let (id, rest) = split_word(&line);
+            let (seq, count) = split_word(rest);
+            if seq.is_empty() || !(count.is_empty() || count.parse::<usize>().is_ok()) {
+                return self.error("expected sequence name, sequence and optional count");
+            }
+            builder.get(id).seq.extend(seq.bytes());
+        }
+        self.finish(builder.records).map(Some)
+    }
+
+    fn read_stockholm(&mut self) -> Result<Option<Alignment>> {
+        let header = match self.next_nonblank_line()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        if !header.starts_with("# STOCKHOLM") {
+            return self.error("expected '# STOCKHOLM' header");
+        }
+        let mut builder = RecordBuilder::default();
+        let mut file_annotations = Vec::new();
+        let mut column_annotations: Vec<(String, Vec<u8>)> = Vec::new();
+        loop {
+            let line = match self.next_line()? {
+                Some(line) => line,
+                None => return self.error("missing '//' terminator"),
+            };
+            let line = line.trim_end();
+            if line == "//" {
+                break;
+            } else if line.trim().is_empty() {
+                continue;
+            } else if let Some(rest) = line.strip_prefix("#=GF") {
+                let (tag, text) = split_word(rest);
+                file_annotations.push((tag.to_owned(), text.to_owned()));
+            } else if let Some(rest) = line.strip_prefix("#=GC") {
+                let (tag, annotation) = split_word(rest);
+                match column_annotations.iter_mut().find(|(t, _)| t == tag) {
+                    Some((_, existing)) => existing.extend(residues(annotation)),
+                    None => {
+                        column_annotations.push((tag.to_owned(), residues(annotation).collect()))
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("#=GS") {
+                let (id, rest) = split_word(rest);
+                let (tag, text) = split_word(rest);
+                builder.get(id).push_annotation(tag, text);
+            } else if let Some(rest) = line.strip_prefix("#=GR") {
+                let (id, rest) = split_word(rest);
+                let (tag, annotation) = split_word(rest);
+                let annotations = &mut builder.get(id).residue_annotations;
+                match annotations.iter_mut().find(|(t, _)| t == tag) {
+                    Some((_, existing)) => existing.extend(residues(annotation)),
+                    None => annotations.push((tag.to_owned(), residues(annotation).collect())),
+                }
+            } else if line.starts_with('#') {
+                // Other comment lines carry no information.
+                continue;
+            } else {
+                let (id, seq) = split_word(line);
+                if seq.is_empty() {
+                    return self.error("expected sequence name and sequence");
+                }
+                builder.get(id).seq.extend(residues(seq));
+            }
+        }
+
+        let mut alignment = self.finish(builder.records)?;
+        alignment.file_annotations = file_annotations;
+        for (tag, annotation) in column_annotations {
+            alignment.set_column_annotation(&tag, &annotation)?;
+        }
+        Ok(Some(alignment))
+    }
+
+    fn read_phylip(&mut self) -> Result<Option<Alignment>> {
+        let header = match self.next_nonblank_line()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let mut fields = header
+            .split_whitespace()
+            .map(|field| field.parse::<usize>());
+        let (ntax, nchar) = match (fields.next(), fields.next()) {
+            (Some(Ok(ntax)), Some(Ok(nchar))) => (ntax, nchar),
+            _ => return self.error("expected number of sequences and number of columns"),
+        };
+
+        // Sequential and interleaved layouts can only be told apart if sequences span several
+        // lines. The sequential layout is tried first, and the interleaved one if the lines do
+        // not add up to sequences of the given length.
+        let mut lines = Vec::new();
+        let (records, consumed) = match self.read_phylip_sequential(ntax, nchar, &mut lines)? {
+            Some(sequential) => sequential,
+            None => self.read_phylip_interleaved(ntax, nchar, &mut lines)?,
+        };
+        self.pending.extend(lines.drain(consumed..).rev());
+        if records.len() != ntax {
+            return self.error(&format!(
+                "expected {} sequences, found {}",
+                ntax,
+                records.len()
+            ));
+        }
+        self.finish(records).map(Some)
+    }
+
+    /// Return the `i`-th non-blank line of a PHYLIP alignment, reading it into `lines` if
+    /// necessary.
+    fn phylip_line(&mut self, lines: &mut Vec<String>, i: usize) -> Result<Option<String>> {
+        while lines.len() <= i {
+            match self.next_nonblank_line()? {
+                Some(line) => lines.push(line),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(lines[i].clone()))
+    }
+
+    /// Split a PHYLIP line into sequence name and residues.
+    fn split_phylip_name(&self, line: &str) -> Result<Record> {
+        let (id, seq) = if self.format == Format::Phylip {
+            match line.char_indices().nth(PHYLIP_NAME_WIDTH) {
+                Some((i, _)) => (line[..i].trim(), &line[i..]),
+                None => return self.error("expected 10 character sequence name"),
+            }
+        } else {
+            split_word(line)
+        };
+        Ok(Record::new(id, None, &residues(seq).collect::<Vec<_>>()))
+    }
+
+    /// Read the sequences of sequential PHYLIP, each starting with its name and continuing
+    /// on the following lines until it has `nchar` residues. Return the records and the
+    /// number of lines used, or `None` if the lines do not fit the sequential layout.
+    fn read_phylip_sequential(
+        &mut self,
+        ntax: usize,
+        nchar: usize,
+        lines: &mut Vec<String>,
+    ) -> Result<Option<(Vec<Record>, usize)>> {
+        let mut records = Vec::new();
+        let mut i = 0;
+        for _ in 0..ntax {
+            let mut record = match self.phylip_line(lines, i)? {
+                Some(line) => match self.split_phylip_name(&line) {
+                    Ok(record) => record,
+                    Err(_) => return Ok(None),
+                },
+                None => return Ok(None),
+            };
+            i += 1;
+            while record.seq.len() < nchar {
+                match self.phylip_line(lines, i)? {
+                    Some(line) => record.seq.extend(residues(&line)),
+                    None => return Ok(None),
+                }
+                i += 1;
+            }
+            if record.seq.len() != nchar {
+                return Ok(None);
+            }
+            records.push(record);
+        }
+        Ok(Some((records, i)))
+    }
+
+    /// Read the sequences of interleaved PHYLIP, where the first block contains the names and
+    /// the following blocks continue the sequences in the same order. Return the records and
+    /// the number of lines used.
+    fn read_phylip_interleaved(
+        &mut self,
+        ntax: usize,
+        nchar: usize,
+        lines: &mut Vec<String>,
+    ) -> Result<(Vec<Record>, usize)> {
+        let mut records = Vec::new();
+        let mut i = 0;
+        let mut next_line = |reader: &mut Self| -> Result<String> {
+            i += 1;
+            match reader.phylip_line(lines, i - 1)? {
+                Some(line) => Ok(line),
+                None => reader.error("unexpected end of file"),
+            }
+        };
+        for _ in 0..ntax {
+            let line = next_line(self)?;
+            records.push(self.split_phylip_name(&line)?);
+        }
+        while records.iter().any(|record| record.seq.len() < nchar) {
+            for record in &mut records {
+                record.seq.extend(residues(&next_line(self)?));
+            }
+        }
+        for record in &records {
+            check_length("sequence", &record.id, record.seq.len(), nchar)?;
+        }
+        Ok((records, i))
+    }
+
+    fn read_fasta(&mut self) -> Result<Option<Alignment>> {
+        let mut records: Vec<Record> = Vec::new();
+        while let Some(line) = self.next_line()? {
+            if let Some(header) = line.strip_prefix('>') {
+                let (id, desc) = split_word(header);
+                let desc = if desc.is_empty() { None } else { Some(desc) };
+                records.push(Record::new(id, desc, b""));
+            } else if let Some(record) = records.last_mut() {
+                record.seq.extend(residues(&line));
+            } else if !line.trim().is_empty() {
+                return self.error("expected '>' at record start");
+            }
+        }
+        if records.is_empty() {
+            return Ok(None);
+        }
+        self.finish(records).map(Some)
+    }
+}
+
+/// An iterator over the alignments of a file.
+pub struct Alignments<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Alignments<'a, R> {
+    type Item = Result<Alignment>;
+
+    fn next(&mut self) -> Option<Result<Alignment>> {
+        self.reader.read().transpose()
+    }
+}
+
+/// A writer for multiple sequence alignments.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+    format: Format,
+    line_width: usize,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path in the given format.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        fs::File::create(path).map(|writer| Writer::new(writer, format))
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path in the given format. The output is compressed according
+    /// to the file extension (see [`compression::Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        let compression = compression::Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, compression))
+            .map(|writer| Writer::new(writer, format))
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer in the given format.
+    pub fn new(writer: W, format: Format) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+            format,
+            line_width: 60,
+        }
+    }
+
+    /// Set the number of columns per block (Clustal) or per line (FASTA). Stockholm and
+    /// PHYLIP output puts each sequence on a single line. Defaults to 60.
+    ///
+    /// # Panics
+    ///
+    /// If `line_width` is zero.
+    pub fn set_line_width(&mut self, line_width: usize) {
+        assert!(line_width > 0, "line width must be positive");
+        self.line_width = line_width;
+    }
+
+    /// Write an alignment.
+    pub fn write(&mut self, alignment: &Alignment) -> Result<()> {
+        match self.format {
+            Format::Clustal => self.write_clustal(alignment),
+            Format::Stockholm => self.write_stockholm(alignment),
+            Format::Phylip | Format::RelaxedPhylip => self.write_phylip(alignment),
+            Format::Fasta => self.write_fasta(alignment),
+        }
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(self) -> io::Result<W> {
+        self.inner.into_inner().map_err(|e| e.into_error())
+    }
+
+    /// Check that sequence names can be written as whitespace-delimited words.
+    fn check_ids(&self, alignment: &Alignment) -> Result<()> {
+        match alignment
+            .records
+            .iter()
+            .find(|record| record.id.is_empty() || record.id.contains(char::is_whitespace))
+        {
+            Some(record) => Err(Error::InvalidName {
+                format: self.format,
+                id: record.id.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn write_clustal(&mut self, alignment: &Alignment) -> Result<()> {
+        self.check_ids(alignment)?;
+        let width = alignment
+            .records
+            .iter()
+            .map(|record| record.id.len() + 1)
+            .fold(16, max);
+        writeln!(self.inner, "CLUSTAL W multiple sequence alignment\n")?;
+        let mut start = 0;
+        while start < alignment.ncols() {
+            let end = (start + self.line_width).min(alignment.ncols());
+            writeln!(self.inner)?;
+            for record in &alignment.records {
+                write!(self.inner, "{:width$}", record.id, width = width)?;
+                self.inner.write_all(&record.seq[start..end])?;
+                writeln!(self.inner)?;
+            }
+            let conservation: String = (start..end)
+                .map(|i| {
+                    let first = alignment.records[0].seq[i].to_ascii_uppercase();
+                    let conserved = !is_gap(first)
+                        && alignment
+                            .records
+                            .iter()
+                            .all(|record| record.seq[i].to_ascii_uppercase() == first);
+                    if conserved {
+                        '*'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect();
+            writeln!(self.inner, "{:width$}{}", "", conservation, width = width)?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    fn write_stockholm(&mut self, alignment: &Alignment) -> Result<()> {
+        self.check_ids(alignment)?;
+        let width = alignment
+            .records
+            .iter()
+            .flat_map(|record| {
+                std::iter::once(record.id.len()).chain(
+                    record
+                        .residue_annotations
+                        .iter()
+                        .map(move |(tag, _)| record.id.len() + tag.len() + 6),
+                )
+            })
+            .chain(
+                alignment
+                    .column_annotations
+                    .iter()
+                    .map(|(tag, _)| tag.len() + 5),
+            )
+            .fold(0, max)
+            + 1;
+
+        writeln!(self.inner, "# STOCKHOLM 1.0")?;
+        for (tag, text) in &alignment.file_annotations {
+            writeln!(self.inner, "#=GF {} {}", tag, text)?;
+        }
+        for record in &alignment.records {
+            for (tag, text) in &record.annotations {
+                writeln!(self.inner, "#=GS {} {} {}", record.id, tag, text)?;
+            }
+        }
+        for record in &alignment.records {
+            write!(self.inner, "{:width$}", record.id, width = width)?;
+            self.inner.write_all(&record.seq)?;
+            writeln!(self.inner)?;
+            for (tag, annotation) in &record.residue_annotations {
+                let label = format!("#=GR {} {}", record.id, tag);
+                write!(self.inner, "{:width$}", label, width = width)?;
+                self.inner.write_all(annotation)?;
+                writeln!(self.inner)?;
+            }
+        }
+        for (tag, annotation) in &alignment.column_annotations {
+            let label = format!("#=GC {}", tag);
+            write!(self.inner, "{:width$}", label, width = width)?;
+            self.inner.write_all(annotation)?;
+            writeln!(self.inner)?;
+        }
+        writeln!(self.inner, "//")?;
+        Ok(())
+    }
+
+    /// Write sequential PHYLIP. Strict PHYLIP truncates names to 10 characters.
+    fn write_phylip(&mut self, alignment: &Alignment) -> Result<()> {
+        writeln!(self.inner, "{} {}", alignment.len(), alignment.ncols())?;
+        let width = if self.format == Format::Phylip {
+            PHYLIP_NAME_WIDTH
+        } else {
+            self.check_ids(alignment)?;
+            alignment
+                .records
+                .iter()
+                .map(|record| record.id.len() + 1)
+                .fold(0, max)
+        };
+        for record in &alignment.records {
+            let id: String = record.id.chars().take(width).collect();
+            write!(self.inner, "{:width$}", id, width = width)?;
+            self.inner.write_all(&record.seq)?;
+            writeln!(self.inner)?;
+        }
+        Ok(())
+    }
+
+    fn write_fasta(&mut self, alignment: &Alignment) -> Result<()> {
+        for record in &alignment.records {
+            write!(self.inner, ">{}", record.id)?;
+            if let Some(desc) = &record.desc {
+                write!(self.inner, " {}", desc)?;
+            }
+            writeln!(self.inner)?;
+            for line in record.seq.chunks(self.line_width) {
+                self.inner.write_all(line)?;
+                writeln!(self.inner)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::poa::Aligner;
+
+    const CLUSTAL: &[u8] = b"CLUSTAL W (1.83) multiple sequence alignment
+
+
+seq1            ACGT-ACGTA 10
+seq2            ACGTTACG-A 10
+                ****.***.*
+
+seq1            CC
+seq2            CG
+                *.
+";
+
+    const STOCKHOLM: &[u8] = b"# STOCKHOLM 1.0
+#=GF ID    test
+#=GF DE    A test alignment
+#=GS seq1/1-7 AC P12345
+seq1/1-7   ACG-UAC
+#=GR seq1/1-7 SS ..<-.>.
+seq2/1-6   ACG..AC
+#=GC SS_cons ..<..>.
+
+seq1/1-7   U
+#=GR seq1/1-7 SS .
+seq2/1-6   G
+#=GC SS_cons .
+//
+# STOCKHOLM 1.0
+a AA
+b A-
+//
+";
+
+    fn read(data: &[u8], format: Format) -> Vec<Alignment> {
+        Reader::new(data, format)
+            .alignments()
+            .collect::<Result<_>>()
+            .unwrap()
+    }
+
+    fn write(alignment: &Alignment, format: Format) -> String {
+        let mut writer = Writer::new(Vec::new(), format);
+        writer.write(alignment).unwrap();
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    fn example() -> Alignment {
+        Alignment::from_records(vec![
+            Record::new("alpha", Some("first"), b"ACGT-A"),
+            Record::new("beta", None, b"AC-TTA"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_columns() {
+        let alignment = example();
+        assert_eq!(alignment.len(), 2);
+        assert_eq!(alignment.ncols(), 6);
+        assert_eq!(alignment.column(2), Some(b"G-".to_vec()));
+        assert_eq!(alignment.column(6), None);
+        let columns: Vec<_> = alignment.columns().collect();
+        assert_eq!(columns.len(), 6);
+        assert_eq!(columns[4], b"-T");
+        assert_eq!(alignment.record("beta").unwrap().ungapped(), b"ACTTA");
+
+        let mut alignment = alignment;
+        assert!(matches!(
+            alignment.push(Record::new("gamma", None, b"ACGT")),
+            Err(Error::UnequalLength { len: 4, .. })
+        ));
+        assert!(alignment.set_column_annotation("RF", b"xxxxx.").is_ok());
+        assert!(alignment.set_column_annotation("RF", b"xx").is_err());
+    }
+
+    #[test]
+    fn test_clustal() {
+        let alignments = read(CLUSTAL, Format::Clustal);
+        assert_eq!(alignments.len(), 1);
+        let alignment = &alignments[0];
+        assert_eq!(alignment.records()[0].seq(), b"ACGT-ACGTACC");
+        assert_eq!(alignment.records()[1].seq(), b"ACGTTACG-ACG");
+
+        let written = write(alignment, Format::Clustal);
+        assert!(written.starts_with("CLUSTAL"));
+        assert!(written.contains("seq1            ACGT-ACGTACC\n"));
+        assert!(written.contains("                **** *** ** \n"));
+        assert_eq!(&read(written.as_bytes(), Format::Clustal)[0], alignment);
+
+        assert!(Reader::new(&b"seq1 ACGT\n"[..], Format::Clustal)
+            .read()
+            .is_err());
+    }
+
+    #[test]
+    fn test_stockholm() {
+        let alignments = read(STOCKHOLM, Format::Stockholm);
+        assert_eq!(alignments.len(), 2);
+        let alignment = &alignments[0];
+        assert_eq!(alignment.ncols(), 8);
+        assert_eq!(alignment.records()[1].seq(), b"ACG..ACG");
+        assert_eq!(alignment.records()[1].ungapped(), b"ACGACG");
+        assert_eq!(
+            alignment.file_annotations()[1],
+            ("DE".to_owned(), "A test alignment".to_owned())
+        );
+        let seq1 = alignment.record("seq1/1-7").unwrap();
+        assert_eq!(seq1.annotation("AC"), Some("P12345"));
+        assert_eq!(seq1.residue_annotation("SS"), Some(&b"..<-.>.."[..]));
+        assert_eq!(
+            alignment.column_annotation("SS_cons"),
+            Some(&b"..<..>.."[..])
+        );
+        assert_eq!(alignments[1].records()[1].seq(), b"A-");
+
+        let written = write(alignment, Format::Stockholm);
+        assert_eq!(
+            written,
+            "# STOCKHOLM 1.0
+#=GF ID test
+#=GF DE A test alignment
+#=GS seq1/1-7 AC P12345
+seq1/1-7         ACG-UACU
+#=GR seq1/1-7 SS ..<-.>..
+seq2/1-6         ACG..ACG
+#=GC SS_cons     ..<..>..
+//
+"
+        );
+        assert_eq!(&read(written.as_bytes(), Format::Stockholm)[0], alignment);
+
+        assert!(
+            Reader::new(&b"# STOCKHOLM 1.0\na AC\n"[..], Format::Stockholm)
+                .read()
+                .is_err()
+        );
+        assert!(
+            Reader::new(&b"# STOCKHOLM 1.0\na AC\nb A\n//\n"[..], Format::Stockholm)
+                .read()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_phylip() {
+        let strict = b" 2 13\nTaxon_one_Aaaaa ACGT\nTaxon 2   ACGT-ACG\n\nCGTA\nTTAAA\n";
+        let alignment = &read(strict, Format::Phylip)[0];
+        assert_eq!(alignment.records()[0].id(), "Taxon_one_");
+        assert_eq!(alignment.records()[0].seq(), b"AaaaaACGTCGTA");
+        assert_eq!(alignment.records()[1].seq(), b"ACGT-ACGTTAAA");
+
+        let strict = b"2 8\nTaxon_one ACGT ACGT\nTaxon 2   ACGT-AC-\n";
+        let alignment = &read(strict, Format::Phylip)[0];
+        assert_eq!(alignment.records()[0].id(), "Taxon_one");
+        assert_eq!(alignment.records()[1].id(), "Taxon 2");
+        assert_eq!(alignment.records()[0].seq(), b"ACGTACGT");
+        assert_eq!(
+            write(alignment, Format::Phylip),
+            "2 8\nTaxon_one ACGTACGT\nTaxon 2   ACGT-AC-\n"
+        );
+        assert!(matches!(
+            Writer::new(Vec::new(), Format::RelaxedPhylip).write(alignment),
+            Err(Error::InvalidName { .. })
+        ));
+
+        let relaxed = b"2 6\nlong_taxon_name ACG\nb ACG\nTTA\n--A\n\n2 2\nx AC\ny AG\n";
+        let alignments = read(relaxed, Format::RelaxedPhylip);
+        assert_eq!(alignments.len(), 2);
+        assert_eq!(alignments[0].records()[0].id(), "long_taxon_name");
+        assert_eq!(alignments[0].records()[1].seq(), b"ACG--A");
+        assert_eq!(
+            write(&alignments[0], Format::RelaxedPhylip),
+            "2 6\nlong_taxon_name ACGTTA\nb               ACG--A\n"
+        );
+        assert!(
+            Reader::new(&b"2 6\na ACGTTA\nb ACG\n"[..], Format::RelaxedPhylip)
+                .read()
+                .is_err()
+        );
+        // the number of sequences in the header must not be trusted
+        let huge = format!("{} 2\nx AC\ny AG\n", usize::MAX);
+        assert!(Reader::new(huge.as_bytes(), Format::RelaxedPhylip)
+            .read()
+            .is_err());
+    }
+
+    #[test]
+    fn test_phylip_wrapped_sequential() {
+        let strict = b"2 12\nTaxon_one ACGTAC\nGTACGT\nTaxon 2   ACGT\nAC--\nTTAA\n";
+        let alignment = &read(strict, Format::Phylip)[0];
+        assert_eq!(alignment.records()[0].id(), "Taxon_one");
+        assert_eq!(alignment.records()[0].seq(), b"ACGTACGTACGT");
+        assert_eq!(alignment.records()[1].id(), "Taxon 2");
+        assert_eq!(alignment.records()[1].seq(), b"ACGTAC--TTAA");
+
+        let relaxed =
+            b"3 8\nalpha ACGT\nACGT\nbeta AC\nGT-A\nCG\ngamma ACGTTTTT\n2 2\nx AC\ny AG\n";
+        let alignments = read(relaxed, Format::RelaxedPhylip);
+        assert_eq!(alignments.len(), 2);
+        let ids = alignments[0]
+            .records()
+            .iter()
+            .map(|record| record.id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["alpha", "beta", "gamma"]);
+        assert_eq!(alignments[0].records()[1].seq(), b"ACGT-ACG");
+        assert_eq!(alignments[1].records()[1].seq(), b"AG");
+    }
+
+    #[test]
+    fn test_fasta() {
+        let alignment = example();
+        let mut writer = Writer::new(Vec::new(), Format::Fasta);
+        writer.set_line_width(4);
+        writer.write(&alignment).unwrap();
+        let written = writer.into_inner().unwrap();
+        assert_eq!(written, b">alpha first\nACGT\n-A\n>beta\nAC-T\nTA\n");
+        assert_eq!(read(&written, Format::Fasta), vec![alignment]);
+        assert!(read(b"", Format::Fasta).is_empty());
+        assert!(Reader::new(&b">a\nACG\n>b\nAC\n"[..], Format::Fasta)
+            .read()
+            .is_err());
+    }
+
+    #[test]
+    fn test_poa_conversion() {
+        let scoring = Scoring::new(-1, 0, |a: u8, b: u8| if a == b { 1i32 } else { -1i32 });
+        let seqs: Vec<&[u8]> = vec![b"AAAAAAA", b"AABBBAA", b"AABCBAA", b"AAAAA"];
+        let mut aligner = Aligner::new(scoring, seqs[0]);
+        for seq in &seqs[1..] {
+            aligner.global(seq).add_to_graph();
+        }
+        let alignment = Alignment::from_poa_graph(
+            aligner.graph(),
+            seqs.iter()
+                .enumerate()
+                .map(|(i, seq)| (format!("seq{}", i), seq)),
+        );
+        assert_eq!(alignment.len(), 4);
+        for (record, seq) in alignment.records().iter().zip(&seqs) {
+            assert_eq!(record.ungapped(), seq.to_vec());
+            assert_eq!(record.seq().len(), alignment.ncols());
+        }
+
+        let graph = alignment.to_poa_graph();
+        let nodes: usize = alignment
+            .columns()
+            .map(|column| {
+                let mut residues: Vec<_> = column.into_iter().filter(|&c| !is_gap(c)).collect();
+                residues.sort_unstable();
+                residues.dedup();
+                residues.len()
+            })
+            .sum();
+        assert_eq!(graph.node_count(), nodes);
+        let roundtrip = Alignment::from_poa_graph(
+            &graph,
+            alignment
+                .records()
+                .iter()
+                .map(|record| (record.id().to_owned(), record.ungapped())),
+        );
+        for (record, seq) in roundtrip.records().iter().zip(&seqs) {
+            assert_eq!(record.ungapped(), seq.to_vec());
+        }
+
+        let scoring = Scoring::new(-1, 0, |a: u8, b: u8| if a == b { 1i32 } else { -1i32 });
+        let mut aligner = Aligner::from_poa(alignment.to_poa(scoring));
+        let nodes = aligner.graph().node_count();
+        aligner.global(b"AABBBBAA").add_to_graph();
+        assert!(aligner.graph().node_count() > nodes);
+    }
+}
@@ -3,7 +3,16 @@
 // This file may not be copied, modified, or distributed
 // except according to those terms.
 
-//! A struct to read phylogenetic trees in the Newick format.
+//! Functions to read and write phylogenetic trees in the Newick format.
+//!
+//! Besides plain Newick, the parser understands quoted labels (`'Homo sapiens'`), bracketed
+//! comments and [NHX](https://en.wikipedia.org/wiki/Newick_format#New_Hampshire_X_format)
+//! annotations (`[&&NHX:S=human:B=100]`). These, as well as support values given as labels of
+//! internal nodes, are available per node when reading into an `AnnotatedTree`.
+//!
+//! Nodes without a label are labelled "N/A" in a `Tree`. An `AnnotatedTree` tells them apart
+//! from nodes actually labelled "N/A" (see `AnnotatedTree::label`), and keeps a branch length
+//! of the root, which `Tree` has no edge for.
 //!
 //!  # Example
 //!
@@ -17,17 +26,58 @@
 //!  for taxon in tree.g.raw_nodes() {
 //!      println!("{}", taxon.weight);
 //!  }
+//!  assert_eq!(
+//!      newick::to_string(&tree).unwrap(),
+//!      "(A:0.1,B:0.2,(C:0.3,D:0.4)E:0.5)F;"
+//!  );
+//!  ```
+//!
+//!  Annotations of extended Newick are kept alongside the tree:
+//!
+//!  ```
+//!  use bio::io::newick;
+//!
+//!  let tree = newick::from_string_annotated(
+//!      "(('Homo sapiens':0.1[&&NHX:S=human],Pan:0.2)95:0.05,Mus:0.4);",
+//!  )
+//!  .unwrap();
+//!  let root = tree.root().unwrap();
+//!  let clade = tree.tree.g.neighbors(root).last().unwrap();
+//!  assert_eq!(tree.annotation(clade).unwrap().support, Some(95.0));
+//!  let human = tree
+//!      .tree
+//!      .g
+//!      .node_indices()
+//!      .find(|&n| tree.tree.g[n] == "Homo sapiens")
+//!      .unwrap();
+//!  assert_eq!(tree.annotation(human).unwrap().nhx("S"), Some("human"));
+//!
+//!  let mut out = Vec::new();
+//!  newick::Writer::new(&mut out)
+//!      .precision(2)
+//!      .write_annotated(&tree)
+//!      .unwrap();
+//!  assert_eq!(
+//!      String::from_utf8(out).unwrap(),
+//!      "(('Homo sapiens':0.10[&&NHX:S=human],Pan:0.20)95:0.05,Mus:0.40);\n"
+//!  );
 //!  ```
 
 use bio_types::phylogeny::{Tree, TreeGraph};
 use pest::iterators::Pair;
 use pest::Parser;
 use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use std::collections::HashSet;
+use std::fmt;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Taxon assigned by the parser to nodes without a label.
+const UNNAMED: &str = "N/A";
+
 /// A `thiserror` error type gathering all the potential bad outcomes
 #[derive(Debug, Error)]
 pub enum Error {
@@ -40,11 +90,17 @@ pub enum Error {
     #[error("Error while reading tree: {0}")]
     Read(#[from] std::io::Error),
 
+    #[error("Error while writing tree: {0}")]
+    Write(std::io::Error),
+
     #[error("Tree contains invalid UTF-8: {0}")]
     InvalidContent(#[from] std::str::Utf8Error),
 
     #[error("Error while parsing tree: {0}")]
     ParsingError(#[from] pest::error::Error<crate::io::newick::Rule>),
+
+    #[error("Tree has {0} root nodes, expected exactly one")]
+    InvalidRoot(usize),
 }
 type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -57,12 +113,116 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 )]
 pub struct NewickParser;
 
+/// Extended Newick information attached to a node.
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NodeAnnotation {
+    /// Support value of an internal node, e.g. a bootstrap value. It is taken from a numeric
+    /// label of the node or, failing that, from the NHX `B` key.
+    pub support: Option<f64>,
+    /// NHX key/value pairs, in the order given.
+    pub nhx: Vec<(String, String)>,
+    /// Bracketed comments other than NHX annotations, without the brackets.
+    pub comments: Vec<String>,
+}
+
+impl NodeAnnotation {
+    /// Value of the given NHX key, if present.
+    pub fn nhx(&self, key: &str) -> Option<&str> {
+        self.nhx
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A phylogenetic tree together with the extended Newick annotations of its nodes.
+pub struct AnnotatedTree {
+    pub tree: Tree,
+    /// Length of the branch leading to the root, e.g. `0.5` for `(A,B):0.5;`.
+    pub root_length: Option<f32>,
+    /// Annotations, indexed by node index.
+    annotations: Vec<NodeAnnotation>,
+    /// Nodes that had no label in the parsed input.
+    unnamed: HashSet<NodeIndex>,
+}
+
+// `Tree` implements neither `Clone` nor `Debug`, so these can't be derived.
+impl Clone for AnnotatedTree {
+    fn clone(&self) -> Self {
+        AnnotatedTree {
+            tree: Tree {
+                g: self.tree.g.clone(),
+            },
+            root_length: self.root_length,
+            annotations: self.annotations.clone(),
+            unnamed: self.unnamed.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for AnnotatedTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnnotatedTree")
+            .field("tree", &self.tree.g)
+            .field("root_length", &self.root_length)
+            .field("annotations", &self.annotations)
+            .field("unnamed", &self.unnamed)
+            .finish()
+    }
+}
+
+impl From<Tree> for AnnotatedTree {
+    fn from(tree: Tree) -> Self {
+        let annotations = vec![NodeAnnotation::default(); tree.g.node_count()];
+        AnnotatedTree {
+            tree,
+            root_length: None,
+            annotations,
+            unnamed: HashSet::new(),
+        }
+    }
+}
+
+impl AnnotatedTree {
+    /// Annotation of the given node.
+    pub fn annotation(&self, node: NodeIndex) -> Option<&NodeAnnotation> {
+        self.annotations.get(node.index())
+    }
+
+    /// Mutable annotation of the given node. Nodes added to the tree after its creation are
+    /// given an empty annotation.
+    pub fn annotation_mut(&mut self, node: NodeIndex) -> Option<&mut NodeAnnotation> {
+        if node.index() < self.tree.g.node_count() && node.index() >= self.annotations.len() {
+            self.annotations
+                .resize(self.tree.g.node_count(), NodeAnnotation::default());
+        }
+        self.annotations.get_mut(node.index())
+    }
+
+    /// Label of the given node, or `None` if it had no label in the parsed input. Such nodes
+    /// are labelled "N/A" in `tree`, like nodes actually labelled "N/A".
+    pub fn label(&self, node: NodeIndex) -> Option<&str> {
+        let label = self.tree.g.node_weight(node)?;
+        if label == UNNAMED && self.unnamed.contains(&node) {
+            None
+        } else {
+            Some(label)
+        }
+    }
+
+    /// The root of the tree, i.e. the first node without a parent.
+    pub fn root(&self) -> Option<NodeIndex> {
+        self.tree.g.externals(Direction::Incoming).next()
+    }
+}
+
 /// A hidden, temporary datatype used to collect the parser result
 /// before converting it to a `Tree`
 enum TreeValue {
     Node {
         name: Option<String>,
         children: Option<Vec<TreeValue>>,
+        comments: Vec<String>,
     },
     Link {
         weight: f32,
@@ -71,65 +231,73 @@ enum TreeValue {
 }
 
 /// Given a string representing a Newick tree, tries to parse it and
-/// returns the `TreeValue` of the root
-fn parse_newick_file(content: &str) -> Result<TreeValue> {
+/// returns the `TreeValue` of the root, together with the length of its branch
+fn parse_newick_file(content: &str) -> Result<(TreeValue, Option<f32>)> {
+    fn label(pair: Pair<Rule>) -> String {
+        match pair.as_rule() {
+            Rule::quoted => pair
+                .into_inner()
+                .next()
+                .unwrap()
+                .as_str()
+                .replace("''", "'"),
+            _ => pair.as_str().into(),
+        }
+    }
+
+    fn comment(pair: Pair<Rule>) -> String {
+        pair.into_inner().next().unwrap().as_str().into()
+    }
+
     fn parse_value(pair: Pair<Rule>) -> TreeValue {
         match pair.as_rule() {
             Rule::Leaf => {
-                let name = pair.into_inner().next().unwrap().as_str();
+                let mut inner = pair.into_inner();
+                let name = label(inner.next().unwrap());
                 TreeValue::Node {
-                    name: Some(name.into()),
+                    name: Some(name),
                     children: None,
+                    comments: inner.map(comment).collect(),
                 }
             }
             Rule::Internal => {
-                let mut inner_rules = pair.into_inner();
-                let children = Some(
-                    inner_rules
-                        .next()
-                        .unwrap()
-                        .into_inner()
-                        .map(parse_value)
-                        .collect(),
-                );
-                let name = if let Some(clade) = inner_rules.next() {
-                    Some(clade.as_str().into())
-                } else {
-                    None
-                };
-                TreeValue::Node { children, name }
+                let mut children = None;
+                let mut name = None;
+                let mut comments = Vec::new();
+                for inner in pair.into_inner() {
+                    match inner.as_rule() {
+                        Rule::BranchSet => {
+                            children = Some(inner.into_inner().map(parse_value).collect())
+                        }
+                        Rule::Comment => comments.push(comment(inner)),
+                        _ => name = Some(label(inner)),
+                    }
+                }
+                TreeValue::Node {
+                    children,
+                    name,
+                    comments,
+                }
             }
 
             Rule::Branch => {
-                fn get_weight(mut inner: pest::iterators::Pairs<Rule>) -> f32 {
-                    if let Some(weight) = inner.next() {
-                        weight.as_str().parse::<f32>().unwrap()
-                    } else {
-                        f32::NAN
+                let mut node = TreeValue::Node {
+                    name: None,
+                    children: None,
+                    comments: Vec::new(),
+                };
+                let mut weight = f32::NAN;
+                let mut branch_comments = Vec::new();
+                for inner in pair.into_inner() {
+                    match inner.as_rule() {
+                        Rule::SubTree => node = parse_value(inner),
+                        Rule::float => weight = inner.as_str().parse::<f32>().unwrap(),
+                        _ => branch_comments.push(comment(inner)),
                     }
                 }
-
-                let mut inner = pair.into_inner();
-                let (node, weight) = if let Some(next) = inner.next() {
-                    match next.as_rule() {
-                        Rule::SubTree => (parse_value(next), get_weight(inner)),
-                        _ => (
-                            TreeValue::Node {
-                                name: None,
-                                children: None,
-                            },
-                            next.as_str().parse::<f32>().unwrap(),
-                        ),
-                    }
-                } else {
-                    (
-                        TreeValue::Node {
-                            name: None,
-                            children: None,
-                        },
-                        get_weight(inner),
-                    )
-                };
+                if let TreeValue::Node { comments, .. } = &mut node {
+                    comments.extend(branch_comments);
+                }
 
                 TreeValue::Link {
                     weight,
@@ -145,30 +313,83 @@ fn parse_newick_file(content: &str) -> Result<TreeValue> {
             | Rule::BranchSet
             | Rule::float
             | Rule::safe
-            | Rule::name => unreachable!(),
+            | Rule::label
+            | Rule::name
+            | Rule::quoted
+            | Rule::quoted_name
+            | Rule::Comment
+            | Rule::comment_text => unreachable!(),
         }
     }
 
-    let root = NewickParser::parse(Rule::Tree, &content)
+    let root = NewickParser::parse(Rule::Tree, content)
         .map_err(Error::ParsingError)?
         .next()
         .unwrap();
 
-    Ok(parse_value(root))
+    // The root is parsed as a branch, which may have a length.
+    match parse_value(root) {
+        TreeValue::Link { weight, node } => Ok((*node, Some(weight).filter(|w| !w.is_nan()))),
+        node => Ok((node, None)),
+    }
 }
 
-/// Convert an intermediary `TreeValue` to the public `Tree` type
-fn newick_to_graph(root: TreeValue) -> Result<Tree> {
-    fn add_node(g: &mut TreeGraph, t: TreeValue) -> NodeIndex {
+/// Build the annotation of a node from its label and comments.
+fn annotate(name: Option<&str>, is_internal: bool, comments: Vec<String>) -> NodeAnnotation {
+    let mut annotation = NodeAnnotation::default();
+    for comment in comments {
+        match comment.strip_prefix("&&NHX") {
+            Some(nhx) => {
+                annotation
+                    .nhx
+                    .extend(
+                        nhx.split(':')
+                            .filter(|field| !field.is_empty())
+                            .map(|field| match field.split_once('=') {
+                                Some((key, value)) => (key.to_owned(), value.to_owned()),
+                                None => (field.to_owned(), String::new()),
+                            }),
+                    )
+            }
+            None => annotation.comments.push(comment),
+        }
+    }
+    annotation.support = name
+        .filter(|_| is_internal)
+        .and_then(|name| name.parse().ok())
+        .or_else(|| annotation.nhx("B").and_then(|b| b.parse().ok()));
+    annotation
+}
+
+/// Convert an intermediary `TreeValue` to the public `AnnotatedTree` type
+fn newick_to_graph(root: TreeValue, root_length: Option<f32>) -> Result<AnnotatedTree> {
+    fn add_node(
+        g: &mut TreeGraph,
+        annotations: &mut Vec<NodeAnnotation>,
+        unnamed: &mut HashSet<NodeIndex>,
+        t: TreeValue,
+    ) -> NodeIndex {
         match t {
-            TreeValue::Node { name, children } => {
-                let node_id = g.add_node(name.unwrap_or("N/A".into()).into());
+            TreeValue::Node {
+                name,
+                children,
+                comments,
+            } => {
+                annotations.push(annotate(name.as_deref(), children.is_some(), comments));
+                let node_id = match name {
+                    Some(name) => g.add_node(name),
+                    None => {
+                        let node_id = g.add_node(UNNAMED.to_owned());
+                        unnamed.insert(node_id);
+                        node_id
+                    }
+                };
                 if let Some(children) = children {
                     for child in children {
                         match child {
                             TreeValue::Node { .. } => unimplemented!(),
                             TreeValue::Link { weight, node } => {
-                                let child_id = add_node(g, *node);
+                                let child_id = add_node(g, annotations, unnamed, *node);
                                 g.add_edge(node_id, child_id, weight);
                             }
                         }
@@ -181,21 +402,38 @@ fn newick_to_graph(root: TreeValue) -> Result<Tree> {
     }
 
     let mut g = TreeGraph::new();
-    add_node(&mut g, root);
+    let mut annotations = Vec::new();
+    let mut unnamed = HashSet::new();
+    add_node(&mut g, &mut annotations, &mut unnamed, root);
 
-    Ok(Tree { g })
+    Ok(AnnotatedTree {
+        tree: Tree { g },
+        root_length,
+        annotations,
+        unnamed,
+    })
 }
 
 /// Reads a tree from an `&str`-compatible type
 pub fn from_string<S: AsRef<str>>(content: S) -> Result<Tree> {
-    let raw_tree = parse_newick_file(content.as_ref())?;
-    newick_to_graph(raw_tree)
+    from_string_annotated(content).map(|tree| tree.tree)
+}
+
+/// Reads a tree and its extended Newick annotations from an `&str`-compatible type
+pub fn from_string_annotated<S: AsRef<str>>(content: S) -> Result<AnnotatedTree> {
+    let (raw_tree, root_length) = parse_newick_file(content.as_ref())?;
+    newick_to_graph(raw_tree, root_length)
 }
 
 /// Reads a tree from a file
 pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Tree> {
+    from_file_annotated(path).map(|tree| tree.tree)
+}
+
+/// Reads a tree and its extended Newick annotations from a file
+pub fn from_file_annotated<P: AsRef<Path>>(path: P) -> Result<AnnotatedTree> {
     fs::File::open(&path)
-        .map(read)
+        .map(read_annotated)
         .map_err(|e| Error::OpenFile {
             filename: path.as_ref().to_owned(),
             source: e,
@@ -204,10 +442,308 @@ pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Tree> {
 
 /// Reads a tree from any type implementing `io::Read`
 pub fn read<R: io::Read>(reader: R) -> Result<Tree> {
-    let content_bytes = reader
-        .bytes()
-        .collect::<Result<Vec<_>, _>>()
+    read_annotated(reader).map(|tree| tree.tree)
+}
+
+/// Reads a tree and its extended Newick annotations from any type implementing `io::Read`
+pub fn read_annotated<R: io::Read>(mut reader: R) -> Result<AnnotatedTree> {
+    let mut content_bytes = Vec::new();
+    reader
+        .read_to_end(&mut content_bytes)
         .map_err(Error::Read)?;
     let content_str = std::str::from_utf8(&content_bytes).map_err(Error::InvalidContent)?;
-    from_string(&content_str)
+    from_string_annotated(content_str)
+}
+
+/// Writes a tree in Newick format to a `String`, with branch lengths in their shortest
+/// representation.
+pub fn to_string(tree: &Tree) -> Result<String> {
+    Writer::new(io::sink()).format_tree(tree, None)
+}
+
+/// A writer for trees in (extended) Newick format, one tree per line.
+///
+/// Nodes labelled "N/A", as nodes without a label in the parsed input are, are written without
+/// a label. Annotated trees keep the labels of nodes actually labelled "N/A" (see
+/// [`AnnotatedTree::label`]). Labels containing whitespace or Newick punctuation are quoted,
+/// and branch lengths that are `NaN` are omitted.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: W,
+    precision: Option<usize>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        fs::File::create(&path)
+            .map(Writer::new)
+            .map_err(|e| Error::OpenFile {
+                filename: path.as_ref().to_owned(),
+                source: e,
+            })
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: writer,
+            precision: None,
+        }
+    }
+
+    /// Write branch lengths with the given number of decimal places instead of their shortest
+    /// representation.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Write a tree.
+    pub fn write(&mut self, tree: &Tree) -> Result<()> {
+        self.write_tree(tree, None)
+    }
+
+    /// Write a tree, including the length of the root branch, support values, comments and
+    /// NHX annotations.
+    pub fn write_annotated(&mut self, tree: &AnnotatedTree) -> Result<()> {
+        self.write_tree(&tree.tree, Some(tree))
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> Result<()> {
+        self.inner.flush().map_err(Error::Write)
+    }
+
+    fn write_tree(&mut self, tree: &Tree, annotated: Option<&AnnotatedTree>) -> Result<()> {
+        let mut out = self.format_tree(tree, annotated)?;
+        out.push('\n');
+        self.inner.write_all(out.as_bytes()).map_err(Error::Write)
+    }
+
+    fn format_tree(&self, tree: &Tree, annotated: Option<&AnnotatedTree>) -> Result<String> {
+        let roots: Vec<_> = tree.g.externals(Direction::Incoming).collect();
+        if roots.len() != 1 {
+            return Err(Error::InvalidRoot(roots.len()));
+        }
+        let mut out = String::new();
+        let root_length = annotated.and_then(|annotated| annotated.root_length);
+        self.format_node(&mut out, &tree.g, annotated, roots[0], root_length);
+        out.push(';');
+        Ok(out)
+    }
+
+    fn format_node(
+        &self,
+        out: &mut String,
+        g: &TreeGraph,
+        annotated: Option<&AnnotatedTree>,
+        node: NodeIndex,
+        length: Option<f32>,
+    ) {
+        // petgraph lists edges from the most recently added one, so reverse to keep the
+        // original order of children.
+        let mut children: Vec<_> = g
+            .edges_directed(node, Direction::Outgoing)
+            .map(|edge| {
+                use petgraph::visit::EdgeRef;
+                (edge.target(), *edge.weight())
+            })
+            .collect();
+        children.reverse();
+        if !children.is_empty() {
+            out.push('(');
+            for (i, (child, weight)) in children.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                self.format_node(out, g, annotated, child, Some(weight));
+            }
+            out.push(')');
+        }
+
+        let label = match annotated {
+            Some(annotated) => annotated.label(node),
+            None => Some(g[node].as_str()).filter(|&label| label != UNNAMED),
+        };
+        if let Some(label) = label {
+            push_label(out, label);
+        }
+        if let Some(length) = length.filter(|length| !length.is_nan()) {
+            out.push(':');
+            match self.precision {
+                Some(precision) => out.push_str(&format!("{:.*}", precision, length)),
+                None => out.push_str(&length.to_string()),
+            }
+        }
+        if let Some(annotation) = annotated.and_then(|annotated| annotated.annotation(node)) {
+            for comment in &annotation.comments {
+                out.push('[');
+                out.push_str(comment);
+                out.push(']');
+            }
+            if !annotation.nhx.is_empty() {
+                out.push_str("[&&NHX");
+                for (key, value) in &annotation.nhx {
+                    out.push(':');
+                    out.push_str(key);
+                    out.push('=');
+                    out.push_str(value);
+                }
+                out.push(']');
+            }
+        }
+    }
+}
+
+/// Append a label, quoting it if it contains whitespace or Newick punctuation.
+fn push_label(out: &mut String, label: &str) {
+    let needs_quotes = label.starts_with('\'')
+        || label
+            .chars()
+            .any(|c| c.is_whitespace() || "():;,[]".contains(c));
+    if needs_quotes {
+        out.push('\'');
+        out.push_str(&label.replace('\'', "''"));
+        out.push('\'');
+    } else {
+        out.push_str(label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(tree: &AnnotatedTree, taxon: &str) -> NodeIndex {
+        tree.tree
+            .g
+            .node_indices()
+            .find(|&n| tree.tree.g[n] == taxon)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for newick in &[
+            "(A:0.1,B:0.2,(C:0.3,D:0.4)E:0.5)F;",
+            "((A,B),(C,D));",
+            "(,,(,));",
+            "('Homo sapiens':1,'O''Brien''s taxon':2);",
+        ] {
+            let tree = from_string(newick).unwrap();
+            assert_eq!(&to_string(&tree).unwrap(), newick);
+        }
+    }
+
+    #[test]
+    fn test_unnamed() {
+        let newick = "(A,N/A,)N/A;";
+        let tree = from_string_annotated(newick).unwrap();
+        let root = tree.root().unwrap();
+        let children = tree.tree.g.neighbors(root).collect::<Vec<_>>();
+        let labels = children
+            .iter()
+            .map(|&n| tree.tree.g[n].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["N/A", "N/A", "A"]);
+        assert_eq!(tree.label(root), Some("N/A"));
+        assert_eq!(tree.label(children[0]), None);
+        assert_eq!(tree.label(children[1]), Some("N/A"));
+
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_annotated(&tree).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", newick));
+        assert_eq!(to_string(&tree.tree).unwrap(), "(A,,);");
+
+        let cloned = tree.clone();
+        assert_eq!(cloned.label(children[0]), None);
+        assert!(format!("{:?}", cloned).starts_with("AnnotatedTree"));
+    }
+
+    #[test]
+    fn test_root_length() {
+        let newick = "((A:0.1,B:0.2)C:0.3,D:0.4)E:0.5;";
+        let tree = from_string_annotated(newick).unwrap();
+        assert_eq!(tree.root_length, Some(0.5));
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_annotated(&tree).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", newick));
+        assert_eq!(
+            to_string(&tree.tree).unwrap(),
+            "((A:0.1,B:0.2)C:0.3,D:0.4)E;"
+        );
+
+        let tree = from_string_annotated("(A,B);").unwrap();
+        assert_eq!(tree.root_length, None);
+    }
+
+    #[test]
+    fn test_quoted_labels() {
+        let tree = from_string_annotated("('A b':1,'(C)':2,'D''E');").unwrap();
+        for taxon in &["A b", "(C)", "D'E"] {
+            node(&tree, taxon);
+        }
+    }
+
+    #[test]
+    fn test_support_and_comments() {
+        let tree = from_string_annotated(
+            "[tree comment](A[first]:0.1[second],(B,C)97.5:0.2,(D,E)[&&NHX:B=80:S=mouse])root;",
+        )
+        .unwrap();
+        let a = tree.annotation(node(&tree, "A")).unwrap();
+        assert_eq!(a.comments, vec!["first", "second"]);
+        assert_eq!(a.support, None);
+        let bc = tree.annotation(node(&tree, "97.5")).unwrap();
+        assert_eq!(bc.support, Some(97.5));
+        let root = tree.root().unwrap();
+        assert_eq!(tree.tree.g[root], "root");
+        assert_eq!(
+            tree.annotation(root).unwrap().comments,
+            vec!["tree comment"]
+        );
+        let de = tree
+            .tree
+            .g
+            .neighbors(root)
+            .find(|&n| tree.label(n).is_none())
+            .unwrap();
+        let de = tree.annotation(de).unwrap();
+        assert_eq!(de.support, Some(80.0));
+        assert_eq!(de.nhx("S"), Some("mouse"));
+        assert_eq!(de.nhx("T"), None);
+    }
+
+    #[test]
+    fn test_write_annotated() {
+        let newick = "((A:0.1[&&NHX:S=human:E=1.1.1.1],B:0.2):0.05[note],C:0.3);";
+        let tree = from_string_annotated(newick).unwrap();
+        let mut out = Vec::new();
+        Writer::new(&mut out).write_annotated(&tree).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", newick));
+
+        let mut out = Vec::new();
+        Writer::new(&mut out)
+            .precision(3)
+            .write(&tree.tree)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "((A:0.100,B:0.200):0.050,C:0.300);\n"
+        );
+    }
+
+    #[test]
+    fn test_write_invalid_root() {
+        let mut tree = from_string("(A,B);").unwrap();
+        tree.g.add_node("C".to_owned());
+        assert!(matches!(
+            Writer::new(Vec::new()).write(&tree),
+            Err(Error::InvalidRoot(2))
+        ));
+    }
 }
@@ -0,0 +1,624 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading and writing of the
+//! [PAF](https://github.com/lh3/miniasm/blob/master/PAF.md) pairwise mapping format.
+//!
+//! Each record holds the twelve mandatory columns and optional SAM-like tags such as
+//! `tp:A` (alignment type) or `cg:Z` (CIGAR string). Records can be created from pairwise
+//! alignments as computed by [`pairwise::Aligner`](crate::alignment::pairwise::Aligner) or
+//! [`pattern_matching::myers`](crate::pattern_matching::myers) via
+//! [`Record::from_alignment`].
+//!
+//! # Example
+//!
+//! ```
+//! use bio::alignment::Alignment;
+//! use bio::io::paf;
+//! use bio::pattern_matching::myers::Myers;
+//! use bio_types::strand::ReqStrand;
+//!
+//! let text = b"CGGTCCTGAGGGATTAGCAC";
+//! let pattern = b"TCCTGAGGGA";
+//!
+//! let mut myers = Myers::<u64>::new(pattern);
+//! let mut aln = Alignment::default();
+//! let mut matches = myers.find_all(text, 0);
+//! assert!(matches.next_alignment(&mut aln));
+//!
+//! let record = paf::Record::from_alignment("read", 10, "ref", 20, ReqStrand::Forward, &aln);
+//! assert_eq!(
+//!     record.to_string(),
+//!     "read\t10\t0\t10\t+\tref\t20\t3\t13\t10\t10\t255\tNM:i:0\tcg:Z:10="
+//! );
+//!
+//! let line = record.to_string();
+//! let mut reader = paf::Reader::new(line.as_bytes());
+//! let parsed = reader.records().next().unwrap().unwrap();
+//! assert_eq!(parsed, record);
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use bio_types::strand::ReqStrand;
+use thiserror::Error;
+
+use crate::alignment::sam::{self, Aux, Cigar, CigarOp};
+use crate::alignment::{Alignment, AlignmentOperation};
+use crate::io::compression::{Decoder, Encoder, Format};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read PAF file")]
+    Io(#[from] io::Error),
+
+    #[error("invalid PAF record in line {line}: {msg}")]
+    InvalidRecord { line: u64, msg: String },
+
+    #[error("invalid CIGAR string in cg tag")]
+    InvalidCigar(#[from] sam::Error),
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A PAF record.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Record {
+    qname: String,
+    qlen: u64,
+    qstart: u64,
+    qend: u64,
+    strand: ReqStrand,
+    tname: String,
+    tlen: u64,
+    tstart: u64,
+    tend: u64,
+    matches: u64,
+    block_len: u64,
+    mapq: u8,
+    tags: Vec<([u8; 2], Aux)>,
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Record {
+            qname: String::new(),
+            qlen: 0,
+            qstart: 0,
+            qend: 0,
+            strand: ReqStrand::Forward,
+            tname: String::new(),
+            tlen: 0,
+            tstart: 0,
+            tend: 0,
+            matches: 0,
+            block_len: 0,
+            mapq: 255,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl Record {
+    /// Create a new, empty record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a record from a pairwise alignment of the query `x` (named `qname`, with length
+    /// `qlen`) against the target `y` (named `tname`, with length `tlen`).
+    ///
+    /// If `strand` is `Reverse`, the alignment is assumed to have been computed with the
+    /// reverse complement of the query, and the query coordinates are converted to the
+    /// forward strand as required by PAF. The mapping quality is set to 255 (unavailable),
+    /// and the tags `NM:i` and `cg:Z` (with `=`/`X` operations) are added. This works for
+    /// alignments of the [`pairwise`](crate::alignment::pairwise) aligners as well as
+    /// [`myers`](crate::pattern_matching::myers) alignments.
+    pub fn from_alignment(
+        qname: &str,
+        qlen: u64,
+        tname: &str,
+        tlen: u64,
+        strand: ReqStrand,
+        alignment: &Alignment,
+    ) -> Self {
+        let (qstart, qend) = match strand {
+            ReqStrand::Forward => (alignment.xstart as u64, alignment.xend as u64),
+            ReqStrand::Reverse => (
+                qlen.saturating_sub(alignment.xend as u64),
+                qlen.saturating_sub(alignment.xstart as u64),
+            ),
+        };
+        let count = |f: fn(&AlignmentOperation) -> bool| {
+            alignment.operations.iter().filter(|op| f(op)).count() as u64
+        };
+        let mut cigar = Cigar::new();
+        for op in Cigar::from_alignment(alignment, false).ops() {
+            if !matches!(op, CigarOp::SoftClip(_)) {
+                cigar.push(*op);
+            }
+        }
+        Record {
+            qname: qname.to_owned(),
+            qlen,
+            qstart,
+            qend,
+            strand,
+            tname: tname.to_owned(),
+            tlen,
+            tstart: alignment.ystart as u64,
+            tend: alignment.yend as u64,
+            matches: count(|op| *op == AlignmentOperation::Match),
+            block_len: count(|op| {
+                !matches!(
+                    op,
+                    AlignmentOperation::Xclip(_) | AlignmentOperation::Yclip(_)
+                )
+            }),
+            mapq: 255,
+            tags: vec![
+                (*b"NM", Aux::Int(sam::nm(alignment) as i64)),
+                (*b"cg", Aux::String(cigar.to_string())),
+            ],
+        }
+    }
+
+    /// Query sequence name.
+    pub fn qname(&self) -> &str {
+        &self.qname
+    }
+
+    /// Query sequence length.
+    pub fn qlen(&self) -> u64 {
+        self.qlen
+    }
+
+    /// 0-based query start, on the forward strand of the query.
+    pub fn qstart(&self) -> u64 {
+        self.qstart
+    }
+
+    /// 0-based, exclusive query end, on the forward strand of the query.
+    pub fn qend(&self) -> u64 {
+        self.qend
+    }
+
+    /// Relative strand of query and target.
+    pub fn strand(&self) -> ReqStrand {
+        self.strand
+    }
+
+    /// Target sequence name.
+    pub fn tname(&self) -> &str {
+        &self.tname
+    }
+
+    /// Target sequence length.
+    pub fn tlen(&self) -> u64 {
+        self.tlen
+    }
+
+    /// 0-based target start.
+    pub fn tstart(&self) -> u64 {
+        self.tstart
+    }
+
+    /// 0-based, exclusive target end.
+    pub fn tend(&self) -> u64 {
+        self.tend
+    }
+
+    /// Number of matching residues.
+    pub fn matches(&self) -> u64 {
+        self.matches
+    }
+
+    /// Alignment block length, i.e. the number of matches, mismatches and gaps.
+    pub fn block_len(&self) -> u64 {
+        self.block_len
+    }
+
+    /// Mapping quality (0-255, with 255 for missing).
+    pub fn mapq(&self) -> u8 {
+        self.mapq
+    }
+
+    /// Set the query name, length and 0-based, half-open coordinates.
+    pub fn set_query(&mut self, qname: &str, qlen: u64, qstart: u64, qend: u64) {
+        self.qname = qname.to_owned();
+        self.qlen = qlen;
+        self.qstart = qstart;
+        self.qend = qend;
+    }
+
+    /// Set the target name, length and 0-based, half-open coordinates.
+    pub fn set_target(&mut self, tname: &str, tlen: u64, tstart: u64, tend: u64) {
+        self.tname = tname.to_owned();
+        self.tlen = tlen;
+        self.tstart = tstart;
+        self.tend = tend;
+    }
+
+    /// Set the relative strand.
+    pub fn set_strand(&mut self, strand: ReqStrand) {
+        self.strand = strand;
+    }
+
+    /// Set the number of matching residues and the alignment block length.
+    pub fn set_matches(&mut self, matches: u64, block_len: u64) {
+        self.matches = matches;
+        self.block_len = block_len;
+    }
+
+    /// Set the mapping quality.
+    pub fn set_mapq(&mut self, mapq: u8) {
+        self.mapq = mapq;
+    }
+
+    /// All optional fields.
+    pub fn tags(&self) -> &[([u8; 2], Aux)] {
+        &self.tags
+    }
+
+    /// Return the value of the optional field with the given tag.
+    pub fn tag(&self, tag: &[u8; 2]) -> Option<&Aux> {
+        self.tags.iter().find(|(t, _)| t == tag).map(|(_, v)| v)
+    }
+
+    /// Set an optional field, replacing any existing value for the same tag.
+    pub fn set_tag(&mut self, tag: &[u8; 2], value: Aux) {
+        match self.tags.iter_mut().find(|(t, _)| t == tag) {
+            Some(entry) => entry.1 = value,
+            None => self.tags.push((*tag, value)),
+        }
+    }
+
+    /// Remove the optional field with the given tag, returning its value.
+    pub fn remove_tag(&mut self, tag: &[u8; 2]) -> Option<Aux> {
+        let pos = self.tags.iter().position(|(t, _)| t == tag)?;
+        Some(self.tags.remove(pos).1)
+    }
+
+    /// The alignment type of the `tp:A` tag: `P`/`S` for primary/secondary alignments and
+    /// `I`/`i` for inversions.
+    pub fn alignment_type(&self) -> Option<u8> {
+        match self.tag(b"tp") {
+            Some(Aux::Char(c)) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// The CIGAR string of the `cg:Z` tag, in the orientation of the target.
+    pub fn cigar(&self) -> Option<Result<Cigar>> {
+        match self.tag(b"cg") {
+            Some(Aux::String(cigar)) => Some(cigar.parse().map_err(Error::from)),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Record {
+    type Err = String;
+
+    /// Parse a PAF line without line terminator.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 12 {
+            return Err(format!(
+                "expected at least 12 columns, found {}",
+                fields.len()
+            ));
+        }
+        fn number<T: FromStr>(field: &str, name: &str) -> Result<T, String> {
+            field
+                .parse()
+                .map_err(|_| format!("invalid {} '{}'", name, field))
+        }
+        let strand = match fields[4] {
+            "+" => ReqStrand::Forward,
+            "-" => ReqStrand::Reverse,
+            strand => return Err(format!("invalid strand '{}'", strand)),
+        };
+        let tags = fields[12..]
+            .iter()
+            .filter(|field| !field.is_empty())
+            .map(|field| parse_tag(field))
+            .collect::<Result<_, _>>()?;
+        Ok(Record {
+            qname: fields[0].to_owned(),
+            qlen: number(fields[1], "query length")?,
+            qstart: number(fields[2], "query start")?,
+            qend: number(fields[3], "query end")?,
+            strand,
+            tname: fields[5].to_owned(),
+            tlen: number(fields[6], "target length")?,
+            tstart: number(fields[7], "target start")?,
+            tend: number(fields[8], "target end")?,
+            matches: number(fields[9], "number of matches")?,
+            block_len: number(fields[10], "block length")?,
+            mapq: number(fields[11], "mapping quality")?,
+            tags,
+        })
+    }
+}
+
+/// Parse an optional field like `tp:A:P`.
+fn parse_tag(field: &str) -> Result<([u8; 2], Aux), String> {
+    let invalid = || format!("invalid tag '{}'", field);
+    let mut parts = field.splitn(3, ':');
+    let (tag, kind, value) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(tag), Some(kind), Some(value)) if tag.len() == 2 => (tag.as_bytes(), kind, value),
+        _ => return Err(invalid()),
+    };
+    let value = match kind {
+        "A" if value.len() == 1 => Aux::Char(value.as_bytes()[0]),
+        "i" => Aux::Int(value.parse().map_err(|_| invalid())?),
+        "f" => Aux::Float(value.parse().map_err(|_| invalid())?),
+        "Z" => Aux::String(value.to_owned()),
+        _ => return Err(invalid()),
+    };
+    Ok(([tag[0], tag[1]], value))
+}
+
+impl fmt::Display for Record {
+    /// Format the record as a PAF line, without line terminator.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.qname,
+            self.qlen,
+            self.qstart,
+            self.qend,
+            self.strand.strand_symbol(),
+            self.tname,
+            self.tlen,
+            self.tstart,
+            self.tend,
+            self.matches,
+            self.block_len,
+            self.mapq
+        )?;
+        for (tag, value) in &self.tags {
+            write!(
+                f,
+                "\t{}{}:{}",
+                char::from(tag[0]),
+                char::from(tag[1]),
+                value
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A PAF reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: io::BufReader<R>,
+    buf: String,
+    line: u64,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read PAF from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(Reader::new)
+            .with_context(|| format!("Failed to read PAF from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader.
+    pub fn new(reader: R) -> Self {
+        Reader {
+            inner: io::BufReader::new(reader),
+            buf: String::new(),
+            line: 0,
+        }
+    }
+
+    /// Read the next record, if any. Empty lines are skipped.
+    pub fn read(&mut self) -> Result<Option<Record>> {
+        loop {
+            self.buf.clear();
+            if self.inner.read_line(&mut self.buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            let line = self.buf.trim_end_matches(&['\n', '\r'][..]);
+            if !line.is_empty() {
+                return line.parse().map(Some).map_err(|msg| Error::InvalidRecord {
+                    line: self.line,
+                    msg,
+                });
+            }
+        }
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self }
+    }
+}
+
+/// An iterator over the records of a PAF file.
+pub struct Records<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        self.reader.read().transpose()
+    }
+}
+
+/// A PAF writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path. The output is compressed according to the
+    /// file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+        }
+    }
+
+    /// Write a record.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Flush the writer, ensuring that everything is written.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::pairwise::Aligner;
+    use crate::alphabets::dna;
+
+    const PAF: &[u8] = b"read1\t1000\t10\t990\t+\tchr1\t50000\t100\t1085\t950\t985\t60\ttp:A:P\tcm:i:120\ts1:i:900\tdv:f:0.0012\tcg:Z:500M5I475M
+read2\t500\t0\t500\t-\tchr2\t20000\t300\t800\t480\t500\t0
+
+read3\t300\t0\t100\t+\tchr1\t50000\t0\t100\t100\t100\t255\ttp:A:S
+";
+
+    #[test]
+    fn test_read() {
+        let records: Vec<Record> = Reader::new(PAF).records().collect::<Result<_>>().unwrap();
+        assert_eq!(records.len(), 3);
+        let record = &records[0];
+        assert_eq!(record.qname(), "read1");
+        assert_eq!(
+            (record.qlen(), record.qstart(), record.qend()),
+            (1000, 10, 990)
+        );
+        assert_eq!(record.strand(), ReqStrand::Forward);
+        assert_eq!(record.tname(), "chr1");
+        assert_eq!(
+            (record.tlen(), record.tstart(), record.tend()),
+            (50000, 100, 1085)
+        );
+        assert_eq!((record.matches(), record.block_len()), (950, 985));
+        assert_eq!(record.mapq(), 60);
+        assert_eq!(record.alignment_type(), Some(b'P'));
+        assert_eq!(record.tag(b"cm"), Some(&Aux::Int(120)));
+        assert_eq!(record.tag(b"dv"), Some(&Aux::Float(0.0012)));
+        let cigar = record.cigar().unwrap().unwrap();
+        assert_eq!(cigar.query_len(), 980);
+        assert_eq!(cigar.reference_len(), 975);
+
+        assert_eq!(records[1].strand(), ReqStrand::Reverse);
+        assert!(records[1].tags().is_empty());
+        assert!(records[1].cigar().is_none());
+        assert_eq!(records[2].alignment_type(), Some(b'S'));
+
+        let mut writer = Writer::new(Vec::new());
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        let written = writer.inner.into_inner().unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            String::from_utf8_lossy(PAF).replace("\n\n", "\n")
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        for line in &[
+            "read1\t1000\t10\t990\t+\tchr1\t50000\t100\t1085\t950\t985",
+            "read1\t1000\t10\t990\t*\tchr1\t50000\t100\t1085\t950\t985\t60",
+            "read1\tx\t10\t990\t+\tchr1\t50000\t100\t1085\t950\t985\t60",
+            "read1\t1000\t10\t990\t+\tchr1\t50000\t100\t1085\t950\t985\t60\ttp:A",
+            "read1\t1000\t10\t990\t+\tchr1\t50000\t100\t1085\t950\t985\t60\tcm:i:x",
+        ] {
+            let mut reader = Reader::new(line.as_bytes());
+            assert!(
+                matches!(reader.read(), Err(Error::InvalidRecord { line: 1, .. })),
+                "{}",
+                line
+            );
+        }
+        let mut record: Record = "q\t10\t0\t10\t+\tt\t10\t0\t10\t10\t10\t0\tcg:Z:10Q"
+            .parse()
+            .unwrap();
+        assert!(record.cigar().unwrap().is_err());
+        assert_eq!(
+            record.remove_tag(b"cg"),
+            Some(Aux::String("10Q".to_owned()))
+        );
+        assert!(record.cigar().is_none());
+    }
+
+    #[test]
+    fn test_from_pairwise_alignment() {
+        let x = b"ACCGTGGAT";
+        let y = b"TTTAAAGGGCCGTCGATTT";
+        let score = |a: u8, b: u8| if a == b { 1i32 } else { -1i32 };
+        let mut aligner = Aligner::new(-5, -1, score);
+        let alignment = aligner.local(x, y);
+
+        let record = Record::from_alignment("x", 9, "y", 19, ReqStrand::Forward, &alignment);
+        assert_eq!(
+            record.to_string(),
+            "x\t9\t1\t9\t+\ty\t19\t9\t17\t7\t8\t255\tNM:i:1\tcg:Z:4=1X3="
+        );
+
+        // a query that aligns with its reverse complement
+        let query = dna::revcomp(&x[..]);
+        let alignment = aligner.local(&dna::revcomp(&query), y);
+        let record = Record::from_alignment("q", 9, "y", 19, ReqStrand::Reverse, &alignment);
+        assert_eq!(record.strand(), ReqStrand::Reverse);
+        assert_eq!((record.qstart(), record.qend()), (0, 8));
+        assert_eq!((record.tstart(), record.tend()), (9, 17));
+    }
+}
@@ -0,0 +1,681 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parallel parsing of FASTA and FASTQ files with [rayon](https://docs.rs/rayon).
+//!
+//! The input is split into chunks of complete records on a background thread. The chunks are
+//! parsed in parallel, yielding the records as a rayon [`ParallelIterator`]. The parallel
+//! iterators are obtained with `par_records` of [`fasta::Reader`](crate::io::fasta::Reader),
+//! [`fastq::Reader`](crate::io::fastq::Reader) and
+//! [`fastx::EitherRecords`](crate::io::fastx::EitherRecords).
+//!
+//! By default, the records are processed in arbitrary order. With [`ParRecords::preserve_order`],
+//! results that are collected or reduced are in the order of the input.
+//!
+//! For [`fastx::EitherRecords`](crate::io::fastx::EitherRecords), the format is determined by
+//! the first byte of the input, like [`fastx::get_kind`](crate::io::fastx::get_kind) does.
+//! Input that starts with anything else than `>` or `@`, including whitespace, is reported as
+//! an error.
+//!
+//! This module requires the `rayon` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::fastq;
+//! use rayon::prelude::*;
+//!
+//! const FASTQ_FILE: &[u8] = b"@id1\nACGT\n+\nIIII\n@id2\nGGCC\n+\nIIII\n";
+//!
+//! let gc_contents = fastq::Reader::new(FASTQ_FILE)
+//!     .par_records()
+//!     .preserve_order()
+//!     .map(|record| {
+//!         let record = record.expect("Error during fastq record parsing");
+//!         bio::seq_analysis::gc::gc_content(record.seq())
+//!     })
+//!     .collect::<Vec<_>>();
+//! assert_eq!(gc_contents, [0.5, 1.0]);
+//! ```
+
+use std::collections::VecDeque;
+use std::io;
+use std::io::prelude::*;
+use std::panic;
+use std::sync::mpsc;
+use std::thread;
+
+use memchr::memchr;
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::prelude::*;
+
+/// Default size of the chunks of records, in bytes.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// The format of the records, which determines where chunks are split.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ChunkFormat {
+    Fasta,
+    Fastq,
+    /// FASTA or FASTQ, as determined by the first byte of the input, which has to be `>` or `@`.
+    Either,
+}
+
+/// The part of a FASTQ record at the start of the next line to scan.
+#[derive(Clone, Copy, Debug)]
+enum FastqPart {
+    Header,
+    /// Sequence lines, with the number of lines seen so far.
+    Seq(usize),
+    /// Quality lines, with the number of lines still expected.
+    Qual(usize),
+}
+
+/// Finds the complete records at the beginning of the buffered input. Each scan resumes where
+/// the previous one stopped, so that the data is scanned only once, even if a record spans
+/// many reads.
+#[derive(Debug)]
+struct ChunkScanner {
+    format: ChunkFormat,
+    /// End of the complete records found so far.
+    end: usize,
+    /// Start of the next line to scan.
+    pos: usize,
+    fastq: FastqPart,
+}
+
+impl ChunkScanner {
+    fn new(format: ChunkFormat) -> Self {
+        ChunkScanner {
+            format,
+            end: 0,
+            pos: 0,
+            fastq: FastqPart::Header,
+        }
+    }
+
+    /// Return the length of the complete records at the beginning of `buf`, or `None` if the
+    /// data is malformed. `buf` has to extend the data of the previous scan.
+    fn complete_len(&mut self, buf: &[u8]) -> Option<usize> {
+        match self.format {
+            ChunkFormat::Fasta => self.scan_fasta(buf),
+            ChunkFormat::Fastq => self.scan_fastq(buf)?,
+            ChunkFormat::Either => {
+                self.format = match buf.first() {
+                    Some(b'@') => ChunkFormat::Fastq,
+                    Some(b'>') => ChunkFormat::Fasta,
+                    _ => return None,
+                };
+                return self.complete_len(buf);
+            }
+        }
+        Some(self.end)
+    }
+
+    /// Forget the first `len` bytes, which have been removed from the buffer.
+    fn consume(&mut self, len: usize) {
+        self.end -= len;
+        self.pos -= len;
+    }
+
+    /// Complete FASTA records end right before the last line starting with '>'.
+    fn scan_fasta(&mut self, buf: &[u8]) {
+        loop {
+            if self.pos > 0 && buf.get(self.pos) == Some(&b'>') {
+                self.end = self.pos;
+            }
+            match memchr(b'\n', &buf[self.pos..]) {
+                Some(i) => self.pos += i + 1,
+                None => return,
+            }
+        }
+    }
+
+    /// Complete FASTQ records are found by following the record structure, allowing for
+    /// wrapped sequences and qualities as [`fastq::Reader`](crate::io::fastq::Reader) does.
+    fn scan_fastq(&mut self, buf: &[u8]) -> Option<()> {
+        loop {
+            if let FastqPart::Qual(0) = self.fastq {
+                self.end = self.pos;
+                self.fastq = FastqPart::Header;
+            }
+            let line = &buf[self.pos..];
+            let first = match line.first() {
+                Some(&first) => first,
+                None => return Some(()),
+            };
+            if let FastqPart::Header = self.fastq {
+                if first != b'@' {
+                    return None;
+                }
+            }
+            let next = match memchr(b'\n', line) {
+                Some(i) => self.pos + i + 1,
+                None => return Some(()),
+            };
+            self.fastq = match self.fastq {
+                FastqPart::Header => FastqPart::Seq(0),
+                FastqPart::Seq(lines) if first == b'+' => FastqPart::Qual(lines),
+                FastqPart::Seq(lines) => FastqPart::Seq(lines + 1),
+                FastqPart::Qual(lines) => FastqPart::Qual(lines - 1),
+            };
+            self.pos = next;
+        }
+    }
+}
+
+/// Split the input into chunks of complete records and send them to the parsing threads.
+fn send_chunks<R: io::Read>(
+    mut reader: R,
+    format: ChunkFormat,
+    chunk_size: usize,
+    sender: mpsc::SyncSender<io::Result<Vec<u8>>>,
+) {
+    let mut buf = Vec::new();
+    let mut scanner = ChunkScanner::new(format);
+    loop {
+        let n = match reader
+            .by_ref()
+            .take(chunk_size as u64)
+            .read_to_end(&mut buf)
+        {
+            Ok(n) => n,
+            Err(e) => {
+                let _ = sender.send(Err(e));
+                return;
+            }
+        };
+        if n < chunk_size {
+            // end of input
+            if !buf.is_empty() {
+                let _ = sender.send(Ok(buf));
+            }
+            return;
+        }
+        match scanner.complete_len(&buf) {
+            // a single record exceeds the chunk, read more
+            Some(0) => (),
+            Some(len) => {
+                let rest = buf.split_off(len);
+                scanner.consume(len);
+                if sender.send(Ok(buf)).is_err() {
+                    return;
+                }
+                buf = rest;
+            }
+            // let the parser report malformed data and stop reading
+            None => {
+                let _ = sender.send(Ok(buf));
+                return;
+            }
+        }
+    }
+}
+
+/// Collect the results of a parser, stopping after the first error.
+pub(crate) fn collect_until_err<T, E, I>(records: I) -> Vec<Result<T, E>>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    let mut results = Vec::new();
+    for record in records {
+        let is_err = record.is_err();
+        results.push(record);
+        if is_err {
+            break;
+        }
+    }
+    results
+}
+
+type Chunk = io::Result<Vec<u8>>;
+
+fn parse_chunk<T, E: From<io::Error>>(
+    parse: fn(&[u8]) -> Vec<Result<T, E>>,
+    chunk: Chunk,
+) -> Vec<Result<T, E>> {
+    match chunk {
+        Ok(chunk) => parse(&chunk),
+        Err(e) => vec![Err(E::from(e))],
+    }
+}
+
+/// A producer of the records of consecutive chunks, which keeps them in the order of the
+/// input when split. Only the rightmost producer receives further chunks from the reading
+/// thread, so that each split divides the chunks into a left and a right part.
+struct OrderedChunks<T, E> {
+    chunks: VecDeque<Chunk>,
+    receiver: Option<mpsc::Receiver<Chunk>>,
+    batch_size: usize,
+    parse: fn(&[u8]) -> Vec<Result<T, E>>,
+}
+
+impl<T, E> OrderedChunks<T, E> {
+    /// Receive up to a batch of chunks, if this producer is the rightmost one.
+    fn fill(&mut self) {
+        if let Some(receiver) = &self.receiver {
+            while self.chunks.len() < self.batch_size {
+                match receiver.recv() {
+                    Ok(chunk) => self.chunks.push_back(chunk),
+                    Err(_) => {
+                        self.receiver = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T, E> UnindexedProducer for OrderedChunks<T, E>
+where
+    T: Send,
+    E: Send + From<io::Error>,
+{
+    type Item = Result<T, E>;
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        if self.chunks.len() < 2 {
+            self.fill();
+        }
+        if self.chunks.is_empty() || (self.chunks.len() == 1 && self.receiver.is_none()) {
+            return (self, None);
+        }
+        // the received chunks go to the left, the rest of the input to the right
+        let mid = if self.receiver.is_some() {
+            self.chunks.len()
+        } else {
+            self.chunks.len() / 2
+        };
+        let right = OrderedChunks {
+            chunks: self.chunks.split_off(mid),
+            receiver: self.receiver.take(),
+            batch_size: self.batch_size,
+            parse: self.parse,
+        };
+        (self, Some(right))
+    }
+
+    fn fold_with<F>(self, mut folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let rest = self.receiver.into_iter().flatten();
+        for chunk in self.chunks.into_iter().chain(rest) {
+            if folder.full() {
+                break;
+            }
+            folder = folder.consume_iter(parse_chunk(self.parse, chunk));
+        }
+        folder
+    }
+}
+
+/// A parallel iterator over the records of a FASTA or FASTQ file.
+///
+/// The records are parsed in chunks of about 1 MB (see [`ParRecords::with_chunk_size`]).
+pub struct ParRecords<T, E> {
+    reader: Box<dyn io::Read + Send>,
+    format: ChunkFormat,
+    parse: fn(&[u8]) -> Vec<Result<T, E>>,
+    chunk_size: usize,
+    preserve_order: bool,
+}
+
+impl<T, E> std::fmt::Debug for ParRecords<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParRecords")
+            .field("format", &self.format)
+            .field("chunk_size", &self.chunk_size)
+            .field("preserve_order", &self.preserve_order)
+            .finish()
+    }
+}
+
+impl<T, E> ParRecords<T, E>
+where
+    T: Send,
+    E: Send + From<io::Error>,
+{
+    pub(crate) fn new<R: io::Read + Send + 'static>(
+        reader: R,
+        format: ChunkFormat,
+        parse: fn(&[u8]) -> Vec<Result<T, E>>,
+    ) -> Self {
+        ParRecords {
+            reader: Box::new(reader),
+            format,
+            parse,
+            chunk_size: CHUNK_SIZE,
+            preserve_order: false,
+        }
+    }
+
+    /// Set the approximate size of the chunks of records in bytes.
+    ///
+    /// # Panics
+    /// If `chunk_size` is zero.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Preserve the order of the input when collecting or reducing the records.
+    ///
+    /// The chunks are still parsed and processed in parallel, but their results are combined
+    /// in the order of the input.
+    pub fn preserve_order(mut self) -> Self {
+        self.preserve_order = true;
+        self
+    }
+
+    /// Start reading chunks on a background thread.
+    fn chunks(self) -> (mpsc::Receiver<Chunk>, thread::JoinHandle<()>) {
+        let (sender, receiver) = mpsc::sync_channel(2 * rayon::current_num_threads());
+        let (reader, format, chunk_size) = (self.reader, self.format, self.chunk_size);
+        let handle = thread::spawn(move || send_chunks(reader, format, chunk_size, sender));
+        (receiver, handle)
+    }
+}
+
+impl<T, E> ParallelIterator for ParRecords<T, E>
+where
+    T: Send,
+    E: Send + From<io::Error>,
+{
+    type Item = Result<T, E>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let parse = self.parse;
+        let preserve_order = self.preserve_order;
+        let (receiver, handle) = self.chunks();
+
+        let result = if preserve_order {
+            let producer = OrderedChunks {
+                chunks: VecDeque::new(),
+                receiver: Some(receiver),
+                batch_size: 4 * rayon::current_num_threads(),
+                parse,
+            };
+            bridge_unindexed(producer, consumer)
+        } else {
+            receiver
+                .into_iter()
+                .par_bridge()
+                .flat_map_iter(|chunk| parse_chunk(parse, chunk))
+                .drive_unindexed(consumer)
+        };
+        // The receiver is dropped at this point, so that the reading thread stops even if not
+        // all records have been consumed. Errors of the reader have been sent as chunks.
+        if let Err(payload) = handle.join() {
+            panic::resume_unwind(payload);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{fasta, fastq, fastx};
+
+    fn fasta_file() -> Vec<u8> {
+        let mut fasta = Vec::new();
+        for i in 0..200 {
+            fasta.extend(format!(">id{} desc\nACGT\n{}\n", i, "A".repeat(i % 7)).bytes());
+        }
+        fasta
+    }
+
+    fn fastq_file() -> Vec<u8> {
+        let mut fastq = Vec::new();
+        for i in 0..200 {
+            fastq.extend(format!("@id{}\nACGT\nAC\n+\n@III\n+I\n", i).bytes());
+        }
+        fastq
+    }
+
+    #[test]
+    fn test_fasta_par_records() {
+        let fasta = fasta_file();
+        let expected = fasta::Reader::new(&fasta[..])
+            .records()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        let records = fasta::Reader::new(io::Cursor::new(fasta))
+            .par_records()
+            .with_chunk_size(64)
+            .preserve_order()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn test_fastq_par_records() {
+        let fastq = fastq_file();
+        let expected = fastq::Reader::new(&fastq[..])
+            .records()
+            .collect::<fastq::Result<Vec<_>>>()
+            .unwrap();
+        let records = fastq::Reader::new(io::Cursor::new(fastq.clone()))
+            .par_records()
+            .with_chunk_size(50)
+            .preserve_order()
+            .collect::<fastq::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records, expected);
+
+        let mut ids = fastq::Reader::new(io::Cursor::new(fastq))
+            .par_records()
+            .with_chunk_size(50)
+            .map(|record| record.unwrap().id().to_owned())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        let mut expected_ids = expected
+            .iter()
+            .map(|record| record.id().to_owned())
+            .collect::<Vec<_>>();
+        expected_ids.sort_unstable();
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[test]
+    fn test_fastq_par_records_error() {
+        let mut fastq = fastq_file();
+        fastq.extend(b"id\nACGT\n+\nIIII\n");
+        fastq.extend(fastq_file());
+        let results = fastq::Reader::new(io::Cursor::new(fastq))
+            .par_records()
+            .with_chunk_size(50)
+            .preserve_order()
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 201);
+        assert!(matches!(results[200], Err(fastq::Error::MissingAt)));
+    }
+
+    #[test]
+    fn test_either_par_records() {
+        use crate::io::fastx::Record;
+
+        let summarize = |records: Vec<Result<fastx::EitherRecord, fastx::Error>>| {
+            records
+                .into_iter()
+                .map(|record| {
+                    let record = record.unwrap();
+                    (record.id().to_owned(), record.seq().to_owned())
+                })
+                .collect::<Vec<_>>()
+        };
+        for file in [fasta_file(), fastq_file()] {
+            let expected = summarize(fastx::EitherRecords::new(&file[..]).collect());
+            let records = fastx::EitherRecords::new(io::Cursor::new(file.clone()))
+                .par_records()
+                .with_chunk_size(40)
+                .preserve_order()
+                .collect();
+            assert_eq!(summarize(records), expected);
+
+            let mut either = fastx::EitherRecords::new(io::Cursor::new(file));
+            either.kind().unwrap();
+            let records = either
+                .par_records()
+                .with_chunk_size(40)
+                .preserve_order()
+                .collect();
+            assert_eq!(summarize(records), expected);
+        }
+    }
+
+    #[test]
+    fn test_par_records_order_and_early_stop() {
+        let mut fasta = Vec::new();
+        for _ in 0..20 {
+            fasta.extend(fasta_file());
+        }
+        let expected = fasta::Reader::new(&fasta[..])
+            .records()
+            .map(|record| record.unwrap().seq().len())
+            .collect::<Vec<_>>();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let lengths = fasta::Reader::new(io::Cursor::new(fasta.clone()))
+                .par_records()
+                .with_chunk_size(64)
+                .preserve_order()
+                .map(|record| record.unwrap().seq().len())
+                .collect::<Vec<_>>();
+            assert_eq!(lengths, expected);
+
+            // stopping early ends the reading thread as well
+            let first = fasta::Reader::new(io::Cursor::new(fasta.clone()))
+                .par_records()
+                .with_chunk_size(64)
+                .preserve_order()
+                .find_first(|record| record.as_ref().unwrap().id() == "id10");
+            assert_eq!(first.unwrap().unwrap().desc(), Some("desc"));
+            let any = fasta::Reader::new(io::Cursor::new(fasta))
+                .par_records()
+                .with_chunk_size(64)
+                .find_any(|record| record.as_ref().unwrap().id() == "id10");
+            assert!(any.is_some());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "reader failed")]
+    fn test_par_records_reader_panic() {
+        struct PanickingReader;
+
+        impl io::Read for PanickingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                panic!("reader failed");
+            }
+        }
+
+        fasta::Reader::new(PanickingReader)
+            .par_records()
+            .for_each(|record| drop(record.unwrap()));
+    }
+
+    #[test]
+    fn test_either_par_records_invalid_start() {
+        let mut fastq = b"\n".to_vec();
+        fastq.extend(fastq_file());
+        let results = fastx::EitherRecords::new(io::Cursor::new(fastq))
+            .par_records()
+            .with_chunk_size(40)
+            .preserve_order()
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn test_par_records_empty() {
+        let records = fasta::Reader::new(io::empty())
+            .par_records()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert!(records.is_empty());
+    }
+
+    fn complete_len(format: ChunkFormat, buf: &[u8]) -> Option<usize> {
+        ChunkScanner::new(format).complete_len(buf)
+    }
+
+    #[test]
+    fn test_fasta_complete_len() {
+        assert_eq!(
+            complete_len(ChunkFormat::Fasta, b">id\nACGT\n>id2\nAC"),
+            Some(9)
+        );
+        assert_eq!(complete_len(ChunkFormat::Fasta, b">id\nACGT\nAC"), Some(0));
+        assert_eq!(
+            complete_len(ChunkFormat::Either, b">id\nAC\n>id2\n"),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_fastq_complete_len() {
+        let fastq = b"@id\nACGT\n+\nIIII\n@id2\nAC\nGT\n+\n@I\nII\n@id3\nA";
+        assert_eq!(complete_len(ChunkFormat::Fastq, fastq), Some(35));
+        assert_eq!(complete_len(ChunkFormat::Fastq, &fastq[..34]), Some(16));
+        assert_eq!(complete_len(ChunkFormat::Either, fastq), Some(35));
+        assert_eq!(complete_len(ChunkFormat::Fastq, b"@id\nACGT\n+\n"), Some(0));
+        assert_eq!(complete_len(ChunkFormat::Fastq, b">id\nACGT\n"), None);
+        assert_eq!(complete_len(ChunkFormat::Either, b"id\nACGT\n"), None);
+    }
+
+    #[test]
+    fn test_scanner_resume() {
+        let fasta = b">id\nACGT\n>id2\nAC\nGT\n\n>id3\nA\n>";
+        let fastq = b"@id\nACGT\n+\nIIII\n@id2\nAC\nGT\n+\n@I\nII\n@id3\nA\n+\nI\n@";
+        for (format, data) in [
+            (ChunkFormat::Fasta, &fasta[..]),
+            (ChunkFormat::Fastq, &fastq[..]),
+        ] {
+            // scanning a growing buffer gives the same results as scanning it at once
+            let mut scanner = ChunkScanner::new(format);
+            for len in 1..=data.len() {
+                assert_eq!(
+                    scanner.complete_len(&data[..len]),
+                    complete_len(format, &data[..len])
+                );
+            }
+            // consumed records are skipped
+            let mut scanner = ChunkScanner::new(format);
+            let mut buf = data[..data.len() / 2].to_vec();
+            let len = scanner.complete_len(&buf).unwrap();
+            buf.drain(..len);
+            scanner.consume(len);
+            buf.extend_from_slice(&data[data.len() / 2..]);
+            assert_eq!(scanner.complete_len(&buf), Some(data.len() - 1 - len));
+        }
+    }
+
+    #[test]
+    fn test_send_chunks() {
+        let fasta = b">id\nACGT\n>id2\nACGT\nACGT\n>id3\nA\n";
+        let (sender, receiver) = mpsc::sync_channel(10);
+        send_chunks(&fasta[..], ChunkFormat::Fasta, 8, sender);
+        let chunks = receiver
+            .into_iter()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            chunks,
+            [&b">id\nACGT\n"[..], b">id2\nACGT\nACGT\n>id3\nA\n"]
+        );
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! ENCODE [narrowPeak](https://genome.ucsc.edu/FAQ/FAQformat.html#format12) and
+//! [broadPeak](https://genome.ucsc.edu/FAQ/FAQformat.html#format13) format reading and writing,
+//! as produced by peak callers like MACS2.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::peak;
+//! let example = b"chr1\t9356548\t9356648\t.\t0\t.\t182\t5.0945\t-1\t50\n";
+//! let mut reader = peak::Reader::new(&example[..], peak::PeakType::NarrowPeak);
+//! let mut writer = peak::Writer::new(vec![], peak::PeakType::NarrowPeak);
+//! for record in reader.records() {
+//!     let rec = record.expect("Error reading record.");
+//!     assert_eq!(rec.summit(), Some(9356598));
+//!     assert_eq!(rec.q_value(), None);
+//!     writer.write(&rec).expect("Error writing record.");
+//! }
+//! ```
+
+use std::convert::{AsRef, TryFrom};
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use bio_types::strand::Strand;
+
+use crate::io::bed::{parse_field, HeaderLine, LineReader};
+use crate::io::compression::{Decoder, Encoder, Format};
+
+/// The type of a peak file.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum PeakType {
+    /// BED6+4: peaks with signal value, p-value, q-value and summit.
+    NarrowPeak,
+    /// BED6+3: peaks with signal value, p-value and q-value.
+    BroadPeak,
+}
+
+impl FromStr for PeakType {
+    type Err = String;
+
+    /// Create a PeakType from a string, i.e. `narrowPeak` or `broadPeak`.
+    fn from_str(src_str: &str) -> Result<Self, Self::Err> {
+        match src_str {
+            "narrowPeak" => Ok(PeakType::NarrowPeak),
+            "broadPeak" => Ok(PeakType::BroadPeak),
+            _ => Err(format!(
+                "String '{}' is not a valid PeakType (narrowPeak or broadPeak).",
+                src_str
+            )),
+        }
+    }
+}
+
+/// A narrowPeak or broadPeak reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    inner: LineReader<R>,
+    peak_type: PeakType,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path in given format.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        peak_type: PeakType,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map(|f| Reader::new(f, peak_type))
+            .with_context(|| format!("Failed to read peaks from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path in given format.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(
+        path: P,
+        peak_type: PeakType,
+    ) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map(|f| Reader::new(f, peak_type))
+            .with_context(|| format!("Failed to read peaks from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader in given format.
+    pub fn new(reader: R, peak_type: PeakType) -> Self {
+        Reader {
+            inner: LineReader::new(reader),
+            peak_type,
+        }
+    }
+
+    /// Return the `track` and `browser` lines read so far, after reading those at the start of
+    /// the file.
+    pub fn header_lines(&mut self) -> csv::Result<&[HeaderLine]> {
+        self.inner.header_lines()
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records {
+            inner: &mut self.inner,
+            peak_type: self.peak_type,
+        }
+    }
+}
+
+/// An iterator over the records of a peak file.
+pub struct Records<'a, R: io::Read> {
+    inner: &'a mut LineReader<R>,
+    peak_type: PeakType,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = csv::Result<Record>;
+
+    fn next(&mut self) -> Option<csv::Result<Record>> {
+        let peak_type = self.peak_type;
+        self.inner.next_fields().map(|fields| {
+            let fields = fields?;
+            Ok(Record {
+                chrom: parse_field(&fields, 0, "chromosome")?,
+                start: parse_field(&fields, 1, "start")?,
+                end: parse_field(&fields, 2, "end")?,
+                name: parse_field(&fields, 3, "name")?,
+                score: parse_field(&fields, 4, "score")?,
+                strand: parse_field(&fields, 5, "strand")?,
+                signal_value: parse_field(&fields, 6, "signal value")?,
+                p_value: parse_field(&fields, 7, "p-value")?,
+                q_value: parse_field(&fields, 8, "q-value")?,
+                peak: match peak_type {
+                    PeakType::NarrowPeak => parse_field(&fields, 9, "peak")?,
+                    PeakType::BroadPeak => -1,
+                },
+            })
+        })
+    }
+}
+
+/// A narrowPeak or broadPeak writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: io::BufWriter<W>,
+    peak_type: PeakType,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path in given format.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P, peak_type: PeakType) -> io::Result<Self> {
+        fs::File::create(path).map(|file| Writer::new(file, peak_type))
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path in given format. The output is compressed according
+    /// to the file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P, peak_type: PeakType) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .map(|file| Writer::new(file, peak_type))
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer in given format.
+    pub fn new(writer: W, peak_type: PeakType) -> Self {
+        Writer {
+            inner: io::BufWriter::new(writer),
+            peak_type,
+        }
+    }
+
+    /// Write a given `track` or `browser` line.
+    pub fn write_header_line(&mut self, header_line: &HeaderLine) -> csv::Result<()> {
+        writeln!(self.inner, "{}", header_line)?;
+        Ok(())
+    }
+
+    /// Write a given record. For broadPeak, the summit is omitted.
+    pub fn write(&mut self, record: &Record) -> csv::Result<()> {
+        write!(
+            self.inner,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.chrom,
+            record.start,
+            record.end,
+            record.name,
+            record.score,
+            record.strand,
+            record.signal_value,
+            record.p_value,
+            record.q_value
+        )?;
+        if self.peak_type == PeakType::NarrowPeak {
+            write!(self.inner, "\t{}", record.peak)?;
+        }
+        writeln!(self.inner)?;
+        Ok(())
+    }
+
+    /// Flush the output stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A narrowPeak or broadPeak record. Missing values are given as `.` (name and strand) or
+/// `-1` (p-value, q-value and summit) in the file.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+pub struct Record {
+    chrom: String,
+    start: u64,
+    end: u64,
+    name: String,
+    score: u32,
+    strand: String,
+    signal_value: f64,
+    p_value: f64,
+    q_value: f64,
+    peak: i64,
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Record::new()
+    }
+}
+
+impl Record {
+    /// Create a new record without name, strand, p-value, q-value and summit.
+    pub fn new() -> Self {
+        Record {
+            chrom: "".to_owned(),
+            start: 0,
+            end: 0,
+            name: ".".to_owned(),
+            score: 0,
+            strand: ".".to_owned(),
+            signal_value: 0.0,
+            p_value: -1.0,
+            q_value: -1.0,
+            peak: -1,
+        }
+    }
+
+    /// Chromosome of the peak.
+    pub fn chrom(&self) -> &str {
+        &self.chrom
+    }
+
+    /// Start position of the peak (0-based).
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// End position of the peak (0-based, not included).
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Name of the peak.
+    pub fn name(&self) -> Option<&str> {
+        match self.name.as_str() {
+            "." => None,
+            name => Some(name),
+        }
+    }
+
+    /// Score of the peak, between 0 and 1000 for display.
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Strand of the peak.
+    pub fn strand(&self) -> Option<Strand> {
+        match self.strand.as_str() {
+            "+" => Some(Strand::Forward),
+            "-" => Some(Strand::Reverse),
+            _ => None,
+        }
+    }
+
+    /// Overall enrichment of the peak, e.g. the fold change.
+    pub fn signal_value(&self) -> f64 {
+        self.signal_value
+    }
+
+    /// Statistical significance of the peak as -log10 p-value.
+    pub fn p_value(&self) -> Option<f64> {
+        Some(self.p_value).filter(|&p| p >= 0.0)
+    }
+
+    /// Statistical significance of the peak as -log10 q-value, i.e. adjusted for multiple
+    /// testing.
+    pub fn q_value(&self) -> Option<f64> {
+        Some(self.q_value).filter(|&q| q >= 0.0)
+    }
+
+    /// Offset of the summit relative to the start of the peak (narrowPeak only).
+    pub fn peak(&self) -> Option<u64> {
+        u64::try_from(self.peak).ok()
+    }
+
+    /// Position of the summit (0-based, narrowPeak only).
+    pub fn summit(&self) -> Option<u64> {
+        self.peak().map(|peak| self.start + peak)
+    }
+
+    /// Set chromosome.
+    pub fn set_chrom(&mut self, chrom: &str) {
+        self.chrom = chrom.to_owned();
+    }
+
+    /// Set start of the peak.
+    pub fn set_start(&mut self, start: u64) {
+        self.start = start;
+    }
+
+    /// Set end of the peak.
+    pub fn set_end(&mut self, end: u64) {
+        self.end = end;
+    }
+
+    /// Set name, or remove it with `None`.
+    pub fn set_name(&mut self, name: Option<&str>) {
+        self.name = name.unwrap_or(".").to_owned();
+    }
+
+    /// Set score.
+    pub fn set_score(&mut self, score: u32) {
+        self.score = score;
+    }
+
+    /// Set strand.
+    pub fn set_strand(&mut self, strand: Option<Strand>) {
+        self.strand = strand.map_or_else(
+            || ".".to_owned(),
+            |strand| strand.strand_symbol().to_owned(),
+        );
+    }
+
+    /// Set signal value.
+    pub fn set_signal_value(&mut self, signal_value: f64) {
+        self.signal_value = signal_value;
+    }
+
+    /// Set p-value (as -log10 p-value), or remove it with `None`.
+    pub fn set_p_value(&mut self, p_value: Option<f64>) {
+        self.p_value = p_value.unwrap_or(-1.0);
+    }
+
+    /// Set q-value (as -log10 q-value), or remove it with `None`.
+    pub fn set_q_value(&mut self, q_value: Option<f64>) {
+        self.q_value = q_value.unwrap_or(-1.0);
+    }
+
+    /// Set offset of the summit relative to the start, or remove it with `None`.
+    pub fn set_peak(&mut self, peak: Option<u64>) {
+        self.peak = peak.map_or(-1, |peak| peak as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NARROW_PEAK_FILE: &[u8] = b"track type=narrowPeak name=\"MACS2 peaks\"
+chr1\t9356548\t9356648\tpeak_1\t182\t.\t5.0945\t21.4\t18.7\t50
+chr1\t9358722\t9358822\t.\t0\t+\t4.4\t-1\t-1\t-1
+";
+
+    const BROAD_PEAK_FILE: &[u8] = b"chr1\t9356548\t9357648\tbroad_1\t500\t.\t3.2\t11.5\t-1
+";
+
+    #[test]
+    fn test_reader_narrow_peak() {
+        let mut reader = Reader::new(NARROW_PEAK_FILE, PeakType::NarrowPeak);
+        assert_eq!(
+            reader.header_lines().unwrap()[0].track_attribute("name"),
+            Some("MACS2 peaks")
+        );
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 2);
+
+        let peak = &records[0];
+        assert_eq!(peak.chrom(), "chr1");
+        assert_eq!((peak.start(), peak.end()), (9356548, 9356648));
+        assert_eq!(peak.name(), Some("peak_1"));
+        assert_eq!(peak.score(), 182);
+        assert_eq!(peak.strand(), None);
+        assert_eq!(peak.signal_value(), 5.0945);
+        assert_eq!(peak.p_value(), Some(21.4));
+        assert_eq!(peak.q_value(), Some(18.7));
+        assert_eq!(peak.peak(), Some(50));
+        assert_eq!(peak.summit(), Some(9356598));
+
+        let peak = &records[1];
+        assert_eq!(peak.name(), None);
+        assert_eq!(peak.strand(), Some(Strand::Forward));
+        assert_eq!(peak.p_value(), None);
+        assert_eq!(peak.q_value(), None);
+        assert_eq!(peak.summit(), None);
+    }
+
+    #[test]
+    fn test_reader_broad_peak() {
+        let mut reader = Reader::new(BROAD_PEAK_FILE, PeakType::BroadPeak);
+        let records = reader.records().collect::<csv::Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name(), Some("broad_1"));
+        assert_eq!(records[0].p_value(), Some(11.5));
+        assert_eq!(records[0].q_value(), None);
+        assert_eq!(records[0].peak(), None);
+
+        // a broadPeak file lacks the summit column of narrowPeak
+        let mut reader = Reader::new(BROAD_PEAK_FILE, PeakType::NarrowPeak);
+        assert!(reader.records().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_writer() {
+        for (file, peak_type) in [
+            (NARROW_PEAK_FILE, PeakType::NarrowPeak),
+            (BROAD_PEAK_FILE, PeakType::BroadPeak),
+        ] {
+            let mut reader = Reader::new(file, peak_type);
+            let mut writer = Writer::new(vec![], peak_type);
+            for header_line in reader.header_lines().unwrap().to_vec() {
+                writer.write_header_line(&header_line).unwrap();
+            }
+            for record in reader.records() {
+                writer.write(&record.unwrap()).unwrap();
+            }
+            assert_eq!(writer.inner.into_inner().unwrap(), file);
+        }
+    }
+
+    #[test]
+    fn test_record_setters() {
+        let mut record = Record::new();
+        record.set_chrom("chr2");
+        record.set_start(100);
+        record.set_end(300);
+        record.set_name(Some("peak_2"));
+        record.set_score(1000);
+        record.set_strand(Some(Strand::Reverse));
+        record.set_signal_value(2.5);
+        record.set_p_value(Some(3.0));
+        record.set_peak(Some(120));
+
+        let mut writer = Writer::new(vec![], PeakType::NarrowPeak);
+        writer.write(&record).unwrap();
+        assert_eq!(
+            writer.inner.into_inner().unwrap(),
+            b"chr2\t100\t300\tpeak_2\t1000\t-\t2.5\t3\t-1\t120\n"
+        );
+        assert_eq!(record.summit(), Some(220));
+
+        assert_eq!("narrowPeak".parse::<PeakType>(), Ok(PeakType::NarrowPeak));
+        assert!("bed".parse::<PeakType>().is_err());
+    }
+}
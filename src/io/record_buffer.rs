@@ -0,0 +1,157 @@
+//! A buffer for parsing line-based records in place, shared by the `RefRecords` of the FASTA
+//! and FastQ readers.
+//!
+//! Data is read directly into the buffer, and records are located and returned as slices of
+//! it. Lines of wrapped records are joined by moving them within the buffer, so that no record
+//! data is copied elsewhere. Unconsumed data is moved to the front before more is read, and
+//! the buffer doubles in size if a single record does not fit into it.
+
+use memchr::memchr;
+use std::io;
+use std::ops::Range;
+
+const INITIAL_CAPACITY: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub(crate) struct RecordBuffer<R> {
+    reader: R,
+    buf: Vec<u8>,
+    /// Start of the data that has not been consumed yet.
+    pos: usize,
+    /// End of the data read so far.
+    end: usize,
+    eof: bool,
+}
+
+impl<R: io::Read> RecordBuffer<R> {
+    /// Create a buffer for the given reader, starting with data that has already been read
+    /// from it.
+    pub(crate) fn new(reader: R, data: &[u8]) -> Self {
+        let end = data.len();
+        let mut buf = vec![0; end.max(INITIAL_CAPACITY)];
+        buf[..end].copy_from_slice(data);
+        RecordBuffer {
+            reader,
+            buf,
+            pos: 0,
+            end,
+            eof: false,
+        }
+    }
+
+    /// Whether the reader is exhausted, i.e. the data is all there is.
+    pub(crate) fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// The data that has not been consumed yet.
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.buf[self.pos..self.end]
+    }
+
+    pub(crate) fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[self.pos..self.end]
+    }
+
+    /// Consume the first `n` bytes of the data, returning their offset in the buffer.
+    /// They stay available through [`get`](Self::get) until the buffer is filled again.
+    pub(crate) fn consume(&mut self, n: usize) -> usize {
+        let offset = self.pos;
+        self.pos += n;
+        offset
+    }
+
+    /// Return the given range of the buffer.
+    pub(crate) fn get(&self, range: Range<usize>) -> &[u8] {
+        &self.buf[range]
+    }
+
+    /// Read until the buffer is full or the reader is exhausted, discarding consumed data and
+    /// growing the buffer first if it is full. Offsets relative to the start of the data stay
+    /// valid.
+    pub(crate) fn fill(&mut self) -> io::Result<()> {
+        if self.eof {
+            return Ok(());
+        }
+        if self.pos > 0 {
+            self.buf.copy_within(self.pos..self.end, 0);
+            self.end -= self.pos;
+            self.pos = 0;
+        }
+        if self.end == self.buf.len() {
+            self.buf.resize(2 * self.buf.len(), 0);
+        }
+        while self.end < self.buf.len() {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => self.end += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the end of the line starting at `from`, excluding the line break, and the start of
+/// the next line. At the end of the file, a last line without a line break is complete.
+/// Returns `None` if there is no complete line, which means that more data is needed unless
+/// the end of the file has been reached.
+pub(crate) fn next_line(data: &[u8], from: usize, eof: bool) -> Option<(usize, usize)> {
+    match memchr(b'\n', &data[from..]) {
+        Some(i) => Some((from + i, from + i + 1)),
+        None if eof && from < data.len() => Some((data.len(), data.len())),
+        None => None,
+    }
+}
+
+/// Join the lines in the given range in place, removing line breaks and trailing whitespace.
+/// Returns the end of the joined lines, which start at the start of the range.
+pub(crate) fn join_lines(data: &mut [u8], range: Range<usize>) -> usize {
+    let mut write = range.start;
+    let mut line = range.start;
+    while line < range.end {
+        let line_end = memchr(b'\n', &data[line..range.end]).map_or(range.end, |i| line + i);
+        let len = data[line..line_end].trim_ascii_end().len();
+        data.copy_within(line..line + len, write);
+        write += len;
+        line = line_end + 1;
+    }
+    write
+}
+
+/// Split a header into the id and the description, at the first whitespace character.
+pub(crate) fn split_header(header: &str) -> (&str, Option<&str>) {
+    let mut fields = header.splitn(2, char::is_whitespace);
+    (fields.next().unwrap_or_default(), fields.next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_lines() {
+        let mut data = b">id\nACGT\r\nAC \n\nGT\n>".to_vec();
+        let end = join_lines(&mut data, 4..18);
+        assert_eq!(&data[4..end], b"ACGTACGT");
+        assert_eq!(join_lines(&mut data, 4..4), 4);
+    }
+
+    #[test]
+    fn test_fill_grows() {
+        let data = vec![b'A'; 3 * INITIAL_CAPACITY + 1];
+        let mut buffer = RecordBuffer::new(&data[..], b">id\n");
+        assert_eq!(buffer.data(), b">id\n");
+        buffer.fill().unwrap();
+        assert_eq!(buffer.data().len(), INITIAL_CAPACITY);
+        assert_eq!(buffer.consume(4), 0);
+        while !buffer.is_eof() {
+            buffer.fill().unwrap();
+        }
+        assert_eq!(buffer.data(), &data[..]);
+    }
+}
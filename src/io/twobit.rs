@@ -0,0 +1,671 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading and writing of the UCSC [2bit](https://genome.ucsc.edu/FAQ/FAQformat.html#format7)
+//! format.
+//!
+//! 2bit files store nucleotide sequences with two bits per base, together with blocks of `N`s
+//! and soft-masked (lowercase) blocks. The [`Reader`] offers random access with the same
+//! `fetch`/`read` interface as the [`IndexedReader`](crate::io::fasta::IndexedReader) of
+//! FASTA files, restoring `N` and soft-mask blocks in the returned sequence. The [`Writer`]
+//! converts FASTA records into a 2bit file.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::{fasta, twobit};
+//! use std::io::Cursor;
+//!
+//! let fasta = b">chr1\nACGTNNNNacgt\n>chr2\nGGGG\n";
+//! let mut writer = twobit::Writer::new(Vec::new());
+//! for record in fasta::Reader::new(&fasta[..]).records() {
+//!     writer.write_record(&record.unwrap()).unwrap();
+//! }
+//! let data = writer.into_inner().unwrap();
+//!
+//! let mut reader = twobit::Reader::new(Cursor::new(data)).unwrap();
+//! assert_eq!(reader.sequences()[0].len, 12);
+//! reader.fetch("chr1", 2, 10).unwrap();
+//! let mut seq = Vec::new();
+//! reader.read(&mut seq).unwrap();
+//! assert_eq!(seq, b"GTNNNNac");
+//! ```
+
+use std::cmp::{max, min};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use anyhow::Context;
+use thiserror::Error;
+
+use crate::io::fasta;
+use crate::utils::{Text, TextSlice};
+
+/// The 2bit signature as written in the file byte order.
+const SIGNATURE: u32 = 0x1A41_2743;
+/// Bases in the order of their 2 bit codes.
+const BASES: &[u8; 4] = b"TCAG";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read 2bit file")]
+    Io(#[from] io::Error),
+
+    #[error("invalid 2bit signature {0:#010x}")]
+    InvalidSignature(u32),
+
+    #[error("unsupported 2bit version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("sequence name is not valid UTF-8")]
+    InvalidName,
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A record of the 2bit file index.
+#[derive(Clone, Debug)]
+struct IndexRecord {
+    name: String,
+    offset: u64,
+    len: u64,
+}
+
+/// The N and soft-mask blocks of a sequence, with the offset of its packed bases.
+#[derive(Clone, Debug)]
+struct Blocks {
+    rid: usize,
+    n_blocks: Vec<(u64, u64)>,
+    mask_blocks: Vec<(u64, u64)>,
+    dna_offset: u64,
+}
+
+/// Reader of integers in the byte order of the file.
+#[derive(Clone, Copy, Debug)]
+struct ByteOrder {
+    little_endian: bool,
+}
+
+impl ByteOrder {
+    fn read_u32<R: io::Read>(self, reader: &mut R) -> io::Result<u32> {
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(buf)
+        } else {
+            u32::from_be_bytes(buf)
+        })
+    }
+
+    fn read_u64<R: io::Read>(self, reader: &mut R) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        reader.read_exact(&mut buf)?;
+        Ok(if self.little_endian {
+            u64::from_le_bytes(buf)
+        } else {
+            u64::from_be_bytes(buf)
+        })
+    }
+
+    /// Read a list of `count` blocks, given as all starts followed by all sizes.
+    fn read_blocks<R: io::Read>(self, reader: &mut R, count: u32) -> io::Result<Vec<(u64, u64)>> {
+        let starts = (0..count)
+            .map(|_| self.read_u32(reader))
+            .collect::<io::Result<Vec<_>>>()?;
+        starts
+            .into_iter()
+            .map(|start| {
+                let size = self.read_u32(reader)?;
+                Ok((start as u64, start as u64 + size as u64))
+            })
+            .collect()
+    }
+}
+
+/// A 2bit reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read + io::Seek> {
+    reader: io::BufReader<R>,
+    byte_order: ByteOrder,
+    index: Vec<IndexRecord>,
+    name_to_rid: HashMap<String, usize>,
+    blocks: Option<Blocks>,
+    fetched: Option<(usize, u64, u64)>,
+    soft_mask: bool,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: &P) -> anyhow::Result<Self> {
+        fs::File::open(path)
+            .map_err(Error::from)
+            .and_then(Self::new)
+            .with_context(|| format!("Failed to read 2bit from {:#?}", path))
+    }
+}
+
+impl<R: io::Read + io::Seek> Reader<R> {
+    /// Read from a given reader, parsing the header and index of the file.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = io::BufReader::new(reader);
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        let byte_order = if u32::from_le_bytes(buf) == SIGNATURE {
+            ByteOrder {
+                little_endian: true,
+            }
+        } else if u32::from_be_bytes(buf) == SIGNATURE {
+            ByteOrder {
+                little_endian: false,
+            }
+        } else {
+            return Err(Error::InvalidSignature(u32::from_le_bytes(buf)));
+        };
+        let version = byte_order.read_u32(&mut reader)?;
+        if version > 1 {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let count = byte_order.read_u32(&mut reader)?;
+        byte_order.read_u32(&mut reader)?;
+
+        // the index grows as entries are read, since the count can't be trusted
+        let mut index = Vec::new();
+        let mut name_to_rid = HashMap::new();
+        for rid in 0..count as usize {
+            let mut name_len = [0; 1];
+            reader.read_exact(&mut name_len)?;
+            let mut name = vec![0; name_len[0] as usize];
+            reader.read_exact(&mut name)?;
+            let name = String::from_utf8(name).map_err(|_| Error::InvalidName)?;
+            let offset = if version == 1 {
+                byte_order.read_u64(&mut reader)?
+            } else {
+                byte_order.read_u32(&mut reader)? as u64
+            };
+            name_to_rid.insert(name.clone(), rid);
+            index.push(IndexRecord {
+                name,
+                offset,
+                len: 0,
+            });
+        }
+        for record in &mut index {
+            reader.seek(io::SeekFrom::Start(record.offset))?;
+            record.len = byte_order.read_u32(&mut reader)? as u64;
+        }
+
+        Ok(Reader {
+            reader,
+            byte_order,
+            index,
+            name_to_rid,
+            blocks: None,
+            fetched: None,
+            soft_mask: true,
+        })
+    }
+
+    /// Whether soft-masked blocks are returned in lowercase (the default). If disabled, all
+    /// bases are returned in uppercase.
+    pub fn set_soft_mask(&mut self, soft_mask: bool) {
+        self.soft_mask = soft_mask;
+    }
+
+    /// The names and lengths of all sequences in the file.
+    pub fn sequences(&self) -> Vec<fasta::Sequence> {
+        self.index
+            .iter()
+            .map(|record| fasta::Sequence {
+                name: record.name.clone(),
+                len: record.len,
+            })
+            .collect()
+    }
+
+    /// Fetch an interval from the sequence with the given name for reading.
+    ///
+    /// `start` and `stop` are 0-based and `stop` is exclusive - i.e. `[start, stop)`
+    ///
+    /// # Errors
+    /// If the `seq_name` does not exist within the file.
+    pub fn fetch(&mut self, seq_name: &str, start: u64, stop: u64) -> io::Result<()> {
+        let rid = self.rid(seq_name)?;
+        self.fetch_by_rid(rid, start, stop)
+    }
+
+    /// Fetch an interval from the sequence with the given record index for reading.
+    ///
+    /// `start` and `stop` are 0-based and `stop` is exclusive - i.e. `[start, stop)`
+    ///
+    /// # Errors
+    /// If `rid` does not exist within the file.
+    pub fn fetch_by_rid(&mut self, rid: usize, start: u64, stop: u64) -> io::Result<()> {
+        self.record(rid)?;
+        self.fetched = Some((rid, start, stop));
+        Ok(())
+    }
+
+    /// Fetch the whole sequence with the given name for reading.
+    pub fn fetch_all(&mut self, seq_name: &str) -> io::Result<()> {
+        let rid = self.rid(seq_name)?;
+        self.fetch_all_by_rid(rid)
+    }
+
+    /// Fetch the whole sequence with the given record index for reading.
+    pub fn fetch_all_by_rid(&mut self, rid: usize) -> io::Result<()> {
+        let len = self.record(rid)?.len;
+        self.fetch_by_rid(rid, 0, len)
+    }
+
+    /// Read the fetched sequence into the given vector.
+    pub fn read(&mut self, seq: &mut Text) -> io::Result<()> {
+        let (rid, start, stop) = self
+            .fetched
+            .ok_or_else(|| io::Error::other("No sequence fetched for reading."))?;
+        if stop > self.index[rid].len {
+            return Err(io::Error::other("2bit read interval was out of bounds"));
+        } else if start > stop {
+            return Err(io::Error::other("Invalid query interval"));
+        }
+
+        let dna_offset = self.blocks(rid)?.dna_offset;
+        let first_byte = start / 4;
+        let mut packed = vec![0; (stop.div_ceil(4) - first_byte) as usize];
+        self.reader
+            .seek(io::SeekFrom::Start(dna_offset + first_byte))?;
+        self.reader.read_exact(&mut packed)?;
+
+        seq.clear();
+        seq.extend((start..stop).map(|pos| {
+            let byte = packed[(pos / 4 - first_byte) as usize];
+            BASES[(byte >> (6 - 2 * (pos % 4)) & 0b11) as usize]
+        }));
+        let overlapping = |blocks: &[(u64, u64)]| {
+            blocks
+                .iter()
+                .map(|&(s, e)| (max(s, start), min(e, stop)))
+                .filter(|(s, e)| s < e)
+                .map(|(s, e)| ((s - start) as usize, (e - start) as usize))
+                .collect::<Vec<_>>()
+        };
+        let soft_mask = self.soft_mask;
+        let blocks = self.blocks(rid)?;
+        for (s, e) in overlapping(&blocks.n_blocks) {
+            seq[s..e].iter_mut().for_each(|base| *base = b'N');
+        }
+        if soft_mask {
+            for (s, e) in overlapping(&blocks.mask_blocks) {
+                seq[s..e].make_ascii_lowercase();
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the record index of the sequence with the given name.
+    fn rid(&self, seq_name: &str) -> io::Result<usize> {
+        self.name_to_rid
+            .get(seq_name)
+            .copied()
+            .ok_or_else(|| io::Error::other(format!("Unknown sequence name: {}.", seq_name)))
+    }
+
+    /// Return the index record for the given record index.
+    fn record(&self, rid: usize) -> io::Result<&IndexRecord> {
+        self.index
+            .get(rid)
+            .ok_or_else(|| io::Error::other("Invalid record index in 2bit file."))
+    }
+
+    /// Return the blocks of the given sequence, loading them unless they are cached.
+    fn blocks(&mut self, rid: usize) -> io::Result<&Blocks> {
+        let blocks = match self.blocks.take() {
+            Some(blocks) if blocks.rid == rid => blocks,
+            _ => self.load_blocks(rid)?,
+        };
+        Ok(self.blocks.insert(blocks))
+    }
+
+    /// Read the blocks of the given sequence.
+    fn load_blocks(&mut self, rid: usize) -> io::Result<Blocks> {
+        let order = self.byte_order;
+        let reader = &mut self.reader;
+        // skip the sequence length
+        reader.seek(io::SeekFrom::Start(self.index[rid].offset + 4))?;
+        let n_count = order.read_u32(reader)?;
+        let n_blocks = order.read_blocks(reader, n_count)?;
+        let mask_count = order.read_u32(reader)?;
+        let mask_blocks = order.read_blocks(reader, mask_count)?;
+        order.read_u32(reader)?;
+        let dna_offset = reader.stream_position()?;
+        Ok(Blocks {
+            rid,
+            n_blocks,
+            mask_blocks,
+            dna_offset,
+        })
+    }
+}
+
+/// A sequence packed for writing.
+#[derive(Debug)]
+struct PackedRecord {
+    name: String,
+    len: u32,
+    n_blocks: Vec<(u32, u32)>,
+    mask_blocks: Vec<(u32, u32)>,
+    packed: Vec<u8>,
+}
+
+impl PackedRecord {
+    fn new(name: &str, seq: TextSlice<'_>) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_owned());
+        if name.is_empty() || name.len() > u8::MAX as usize {
+            return Err(invalid("2bit sequence names must have 1 to 255 bytes"));
+        }
+        let len = u32::try_from(seq.len())
+            .map_err(|_| invalid("2bit sequences must be shorter than 2^32 bases"))?;
+
+        let mut packed = vec![0u8; seq.len().div_ceil(4)];
+        for (pos, base) in seq.iter().enumerate() {
+            let code = match base.to_ascii_uppercase() {
+                b'C' => 1,
+                b'A' => 2,
+                b'G' => 3,
+                _ => 0,
+            };
+            packed[pos / 4] |= code << (6 - 2 * (pos % 4));
+        }
+        Ok(PackedRecord {
+            name: name.to_owned(),
+            len,
+            n_blocks: runs(seq, |base| !b"ACGTacgt".contains(&base)),
+            mask_blocks: runs(seq, |base| base.is_ascii_lowercase()),
+            packed,
+        })
+    }
+
+    /// Size of the record in bytes.
+    fn size(&self) -> u64 {
+        16 + 8 * (self.n_blocks.len() + self.mask_blocks.len()) as u64 + self.packed.len() as u64
+    }
+}
+
+/// Return the start and size of all runs of bases fulfilling `predicate`.
+fn runs<F: Fn(u8) -> bool>(seq: TextSlice<'_>, predicate: F) -> Vec<(u32, u32)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (pos, &base) in seq.iter().enumerate() {
+        match (predicate(base), start) {
+            (true, None) => start = Some(pos),
+            (false, Some(s)) => {
+                runs.push((s as u32, (pos - s) as u32));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        runs.push((s as u32, (seq.len() - s) as u32));
+    }
+    runs
+}
+
+/// A 2bit writer.
+///
+/// Since the file starts with an index of all sequences, records are kept in memory in packed
+/// form and only written by [`Writer::finish`] or [`Writer::into_inner`], which report any
+/// errors. If the writer is dropped instead, nothing is written.
+/// Lowercase bases are stored as soft-masked blocks and all bases other than `ACGT` as `N`.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    writer: io::BufWriter<W>,
+    records: Vec<PackedRecord>,
+    names: HashSet<String>,
+}
+
+impl Writer<fs::File> {
+    /// Write to the given file path.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::File::create(path).map(Writer::new)
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new 2bit writer.
+    pub fn new(writer: W) -> Self {
+        Writer {
+            writer: io::BufWriter::new(writer),
+            records: Vec::new(),
+            names: HashSet::new(),
+        }
+    }
+
+    /// Directly write a [`fasta::Record`](crate::io::fasta::Record). The description is not
+    /// stored in 2bit files.
+    pub fn write_record(&mut self, record: &fasta::Record) -> io::Result<()> {
+        self.write(record.id(), record.seq())
+    }
+
+    /// Write a sequence with the given name.
+    ///
+    /// # Errors
+    /// If a sequence with the same name has already been written, if the name is empty or
+    /// longer than 255 bytes, or if the sequence is longer than 2^32 - 1 bases.
+    pub fn write(&mut self, name: &str, seq: TextSlice<'_>) -> io::Result<()> {
+        if self.names.contains(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("duplicate sequence name {}", name),
+            ));
+        }
+        self.records.push(PackedRecord::new(name, seq)?);
+        self.names.insert(name.to_owned());
+        Ok(())
+    }
+
+    /// Write the file and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        Self::write_file(&mut self.writer, &self.records)?;
+        self.writer.into_inner().map_err(|e| e.into_error())
+    }
+
+    /// Write the file.
+    pub fn finish(mut self) -> io::Result<()> {
+        Self::write_file(&mut self.writer, &self.records)?;
+        self.writer.flush()
+    }
+
+    fn write_file(writer: &mut io::BufWriter<W>, records: &[PackedRecord]) -> io::Result<()> {
+        let header_len = |offset_len: u64| {
+            16 + records
+                .iter()
+                .map(|record| 1 + record.name.len() as u64 + offset_len)
+                .sum::<u64>()
+        };
+        let data_len: u64 = records.iter().map(|record| record.size()).sum();
+        // version 1 has 64 bit offsets
+        let version = if header_len(4) + data_len > u32::MAX as u64 {
+            1
+        } else {
+            0
+        };
+        let mut offset = header_len(if version == 1 { 8 } else { 4 });
+
+        writer.write_all(&SIGNATURE.to_le_bytes())?;
+        writer.write_all(&(version as u32).to_le_bytes())?;
+        writer.write_all(&(records.len() as u32).to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        for record in records {
+            writer.write_all(&[record.name.len() as u8])?;
+            writer.write_all(record.name.as_bytes())?;
+            if version == 1 {
+                writer.write_all(&offset.to_le_bytes())?;
+            } else {
+                writer.write_all(&(offset as u32).to_le_bytes())?;
+            }
+            offset += record.size();
+        }
+        for record in records {
+            writer.write_all(&record.len.to_le_bytes())?;
+            for blocks in [&record.n_blocks, &record.mask_blocks] {
+                writer.write_all(&(blocks.len() as u32).to_le_bytes())?;
+                for (start, _) in blocks.iter() {
+                    writer.write_all(&start.to_le_bytes())?;
+                }
+                for (_, size) in blocks.iter() {
+                    writer.write_all(&size.to_le_bytes())?;
+                }
+            }
+            writer.write_all(&0u32.to_le_bytes())?;
+            writer.write_all(&record.packed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const FASTA: &[u8] = b">chr1 first
+ACGTACGTNNNNNNacgtacGTAC
+>chr2
+nnGGGCCCTTTAAARYaa
+>empty
+
+";
+
+    fn write_fasta() -> Vec<u8> {
+        let mut writer = Writer::new(Vec::new());
+        for record in fasta::Reader::new(FASTA).records() {
+            writer.write_record(&record.unwrap()).unwrap();
+        }
+        writer.into_inner().unwrap()
+    }
+
+    fn fetch(reader: &mut Reader<Cursor<Vec<u8>>>, name: &str, start: u64, stop: u64) -> Vec<u8> {
+        let mut seq = Vec::new();
+        reader.fetch(name, start, stop).unwrap();
+        reader.read(&mut seq).unwrap();
+        seq
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut reader = Reader::new(Cursor::new(write_fasta())).unwrap();
+        let sequences = reader.sequences();
+        let names: Vec<_> = sequences.iter().map(|s| s.name.as_str()).collect();
+        let lens: Vec<_> = sequences.iter().map(|s| s.len).collect();
+        assert_eq!(names, vec!["chr1", "chr2", "empty"]);
+        assert_eq!(lens, vec![24, 18, 0]);
+
+        let mut seq = Vec::new();
+        for (rid, record) in fasta::Reader::new(FASTA).records().enumerate() {
+            let record = record.unwrap();
+            reader.fetch_all_by_rid(rid).unwrap();
+            reader.read(&mut seq).unwrap();
+            let expected: Vec<u8> = record
+                .seq()
+                .iter()
+                .map(|&base| match base {
+                    b'R' | b'Y' => b'N',
+                    _ => base,
+                })
+                .collect();
+            assert_eq!(seq, expected);
+        }
+    }
+
+    #[test]
+    fn test_finish_writer() {
+        let dir = tempfile::tempdir().expect("Could not create temp dir");
+        let path = dir.path().join("test.2bit");
+        let mut writer = Writer::to_file(&path).unwrap();
+        for record in fasta::Reader::new(FASTA).records() {
+            writer.write_record(&record.unwrap()).unwrap();
+        }
+        writer.finish().unwrap();
+        assert_eq!(fs::read(&path).unwrap(), write_fasta());
+
+        // nothing is written if the writer is dropped
+        let mut written = Vec::new();
+        let mut writer = Writer::new(&mut written);
+        writer.write("chr1", b"ACGT").unwrap();
+        drop(writer);
+        assert!(written.is_empty());
+
+        let written = Writer::new(Vec::new()).into_inner().unwrap();
+        let reader = Reader::new(Cursor::new(written)).unwrap();
+        assert!(reader.sequences().is_empty());
+    }
+
+    #[test]
+    fn test_fetch() {
+        let mut reader = Reader::new(Cursor::new(write_fasta())).unwrap();
+        assert_eq!(fetch(&mut reader, "chr1", 5, 11), b"CGTNNN");
+        assert_eq!(fetch(&mut reader, "chr1", 13, 19), b"Nacgta");
+        assert_eq!(fetch(&mut reader, "chr2", 1, 4), b"nGG");
+        assert_eq!(fetch(&mut reader, "chr2", 3, 3), b"");
+        reader.fetch_all("chr2").unwrap();
+        let mut seq = Vec::new();
+        reader.read(&mut seq).unwrap();
+        assert_eq!(seq, b"nnGGGCCCTTTAAANNaa");
+
+        reader.set_soft_mask(false);
+        assert_eq!(fetch(&mut reader, "chr1", 13, 19), b"NACGTA");
+
+        assert!(reader.fetch("chr3", 0, 1).is_err());
+        assert!(reader.fetch_by_rid(3, 0, 1).is_err());
+        reader.fetch("chr2", 0, 19).unwrap();
+        assert!(reader.read(&mut seq).is_err());
+        reader.fetch("chr2", 5, 4).unwrap();
+        assert!(reader.read(&mut seq).is_err());
+    }
+
+    #[test]
+    fn test_big_endian() {
+        // a file with one sequence `ACGTn` written in big endian byte order
+        let mut data = Vec::new();
+        for value in &[SIGNATURE, 0, 1, 0] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+        data.extend_from_slice(b"\x03seq");
+        data.extend_from_slice(&24u32.to_be_bytes());
+        for value in &[5u32, 1, 4, 1, 1, 4, 1, 0] {
+            data.extend_from_slice(&value.to_be_bytes());
+        }
+        data.extend_from_slice(&[0b1001_1100, 0]);
+        let mut reader = Reader::new(Cursor::new(data)).unwrap();
+        assert_eq!(fetch(&mut reader, "seq", 0, 5), b"ACGTn");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            Reader::new(Cursor::new(b"not a 2bit file".to_vec())),
+            Err(Error::InvalidSignature(_))
+        ));
+        let mut data = write_fasta();
+        data[4] = 2;
+        assert!(matches!(
+            Reader::new(Cursor::new(data)),
+            Err(Error::UnsupportedVersion(2))
+        ));
+        // a huge sequence count must not be trusted
+        let mut data = write_fasta();
+        data[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Reader::new(Cursor::new(data)).is_err());
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write("chr1", b"ACGT").unwrap();
+        assert!(writer.write("chr1", b"ACGT").is_err());
+        assert!(writer.write("", b"ACGT").is_err());
+    }
+}
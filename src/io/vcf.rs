@@ -0,0 +1,1558 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! [VCF 4.x](https://samtools.github.io/hts-specs/VCFv4.3.pdf) format reading and writing.
+//!
+//! The header is parsed into [`HeaderLine`]s, with INFO, FORMAT, FILTER and contig meta-lines
+//! available in a structured form. Records keep their INFO and sample fields as text, such that
+//! they are written back without changes. Typed values are obtained with the help of the
+//! header definitions, e.g. with [`Record::info`], [`Record::format`] and
+//! [`Record::genotypes`].
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::vcf;
+//!
+//! let example = b"##fileformat=VCFv4.2
+//! ###INFO=<ID=DP,Number=1,Type=Integer,Description=\"Total Depth\">
+//! ###FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+//! #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+//! 20\t14370\trs6054257\tG\tA\t29\tPASS\tDP=14\tGT\t0|1
+//! ";
+//! let mut reader = vcf::Reader::new(&example[..]).unwrap();
+//! let header = reader.header().clone();
+//! let mut writer = vcf::Writer::new(vec![], &header).unwrap();
+//! for record in reader.records() {
+//!     let record = record.expect("Error reading record.");
+//!     assert_eq!(record.pos(), 14370);
+//!     assert_eq!(
+//!         record.info(&header, "DP").unwrap(),
+//!         Some(vcf::Value::Integer(vec![Some(14)]))
+//!     );
+//!     let genotype = record.genotypes().unwrap()[0].clone().unwrap();
+//!     assert_eq!(genotype.to_string(), "0|1");
+//!     writer.write(&record).expect("Error writing record.");
+//! }
+//! ```
+
+use std::convert::AsRef;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use thiserror::Error;
+
+use crate::io::compression::{Decoder, Encoder, Format};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read input")]
+    ReadError(#[from] io::Error),
+
+    #[error("missing #CHROM header line")]
+    MissingHeader,
+
+    #[error("invalid header line {line}: {msg}")]
+    InvalidHeader { line: u64, msg: String },
+
+    #[error("invalid record in line {line}: {msg}")]
+    InvalidRecord { line: u64, msg: String },
+
+    #[error("invalid value {value:?} of field {key}")]
+    InvalidValue { key: String, value: String },
+
+    #[error("missing value of field {key}, which is not a flag")]
+    MissingValue { key: String },
+
+    #[error("invalid genotype {genotype:?}")]
+    InvalidGenotype { genotype: String },
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The number of values of an INFO or FORMAT field.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum Number {
+    /// A fixed number of values.
+    Count(usize),
+    /// One value per alternate allele (`A`).
+    AltAlleles,
+    /// One value per allele, including the reference (`R`).
+    Alleles,
+    /// One value per possible genotype (`G`).
+    Genotypes,
+    /// An unknown or varying number of values (`.`).
+    Unknown,
+}
+
+impl FromStr for Number {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Number::AltAlleles),
+            "R" => Ok(Number::Alleles),
+            "G" => Ok(Number::Genotypes),
+            "." => Ok(Number::Unknown),
+            _ => s
+                .parse()
+                .map(Number::Count)
+                .map_err(|_| format!("invalid number {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Count(n) => write!(f, "{}", n),
+            Number::AltAlleles => f.write_str("A"),
+            Number::Alleles => f.write_str("R"),
+            Number::Genotypes => f.write_str("G"),
+            Number::Unknown => f.write_str("."),
+        }
+    }
+}
+
+/// The type of the values of an INFO or FORMAT field.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum Type {
+    Integer,
+    Float,
+    Flag,
+    Character,
+    String,
+}
+
+impl FromStr for Type {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Integer" => Ok(Type::Integer),
+            "Float" => Ok(Type::Float),
+            "Flag" => Ok(Type::Flag),
+            "Character" => Ok(Type::Character),
+            "String" => Ok(Type::String),
+            _ => Err(format!("invalid type {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Type::Integer => "Integer",
+            Type::Float => "Float",
+            Type::Flag => "Flag",
+            Type::Character => "Character",
+            Type::String => "String",
+        })
+    }
+}
+
+/// The definition of an INFO or FORMAT field in the header.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Definition {
+    pub id: String,
+    pub number: Number,
+    pub value_type: Type,
+    pub description: String,
+    /// Further key-value pairs (e.g. `Source` and `Version`), with values as written in the
+    /// header, i.e. including quotes.
+    pub other: Vec<(String, String)>,
+    /// The order of the keys as read, if it differs from the default order.
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+/// The definition of a FILTER in the header.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Filter {
+    pub id: String,
+    pub description: String,
+    /// Further key-value pairs, with values as written in the header.
+    pub other: Vec<(String, String)>,
+    /// The order of the keys as read, if it differs from the default order.
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+/// The definition of a contig in the header.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Contig {
+    pub id: String,
+    pub length: Option<u64>,
+    /// Further key-value pairs (e.g. `assembly` and `md5`), with values as written in the
+    /// header.
+    pub other: Vec<(String, String)>,
+    /// The order of the keys as read, if it differs from the default order.
+    #[serde(default)]
+    order: Vec<String>,
+}
+
+/// A meta-information line of the header, i.e. a line starting with `##`.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum HeaderLine {
+    /// The file format version, e.g. `VCFv4.2`.
+    FileFormat(String),
+    Info(Definition),
+    Format(Definition),
+    Filter(Filter),
+    Contig(Contig),
+    /// Any other line, as `##key=value`.
+    Other {
+        key: String,
+        value: String,
+    },
+}
+
+impl HeaderLine {
+    fn parse(line: &str) -> Result<Self, String> {
+        let (key, value) = line
+            .strip_prefix("##")
+            .and_then(|line| line.split_once('='))
+            .ok_or_else(|| "expected ##key=value".to_owned())?;
+        let fields = || {
+            value
+                .strip_prefix('<')
+                .and_then(|value| value.strip_suffix('>'))
+                .map(split_fields)
+                .ok_or_else(|| format!("expected <...> in {} line", key))
+        };
+        Ok(match key {
+            "fileformat" => HeaderLine::FileFormat(value.to_owned()),
+            "INFO" => HeaderLine::Info(Definition::from_fields(fields()?)?),
+            "FORMAT" => HeaderLine::Format(Definition::from_fields(fields()?)?),
+            "FILTER" => HeaderLine::Filter(Filter::from_fields(fields()?)?),
+            "contig" => HeaderLine::Contig(Contig::from_fields(fields()?)?),
+            _ => HeaderLine::Other {
+                key: key.to_owned(),
+                value: value.to_owned(),
+            },
+        })
+    }
+}
+
+impl fmt::Display for HeaderLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderLine::FileFormat(version) => write!(f, "##fileformat={}", version),
+            HeaderLine::Info(def) => write_fields(f, "INFO", def.fields(), &def.order),
+            HeaderLine::Format(def) => write_fields(f, "FORMAT", def.fields(), &def.order),
+            HeaderLine::Filter(filter) => write_fields(f, "FILTER", filter.fields(), &filter.order),
+            HeaderLine::Contig(contig) => write_fields(f, "contig", contig.fields(), &contig.order),
+            HeaderLine::Other { key, value } => write!(f, "##{}={}", key, value),
+        }
+    }
+}
+
+impl Definition {
+    /// Create a new definition without further key-value pairs.
+    pub fn new(id: &str, number: Number, value_type: Type, description: &str) -> Self {
+        Definition {
+            id: id.to_owned(),
+            number,
+            value_type,
+            description: description.to_owned(),
+            other: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn from_fields(fields: Vec<(String, String)>) -> Result<Self, String> {
+        let mut fields = Fields::new(fields);
+        let mut def = Definition {
+            id: fields.take("ID")?,
+            number: fields.take("Number")?.parse()?,
+            value_type: fields.take("Type")?.parse()?,
+            description: unquote(&fields.take("Description")?),
+            other: Vec::new(),
+            order: Vec::new(),
+        };
+        (def.other, def.order) = fields.finish(&def.fields());
+        Ok(def)
+    }
+
+    /// Return the key-value pairs in the default order, with values as written.
+    fn fields(&self) -> Vec<(&str, String)> {
+        let mut fields = vec![
+            ("ID", self.id.clone()),
+            ("Number", self.number.to_string()),
+            ("Type", self.value_type.to_string()),
+            ("Description", quote(&self.description)),
+        ];
+        fields.extend(other_fields(&self.other));
+        fields
+    }
+}
+
+impl Filter {
+    /// Create a new filter without further key-value pairs.
+    pub fn new(id: &str, description: &str) -> Self {
+        Filter {
+            id: id.to_owned(),
+            description: description.to_owned(),
+            other: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn from_fields(fields: Vec<(String, String)>) -> Result<Self, String> {
+        let mut fields = Fields::new(fields);
+        let mut filter = Filter::new(&fields.take("ID")?, &unquote(&fields.take("Description")?));
+        (filter.other, filter.order) = fields.finish(&filter.fields());
+        Ok(filter)
+    }
+
+    /// Return the key-value pairs in the default order, with values as written.
+    fn fields(&self) -> Vec<(&str, String)> {
+        let mut fields = vec![
+            ("ID", self.id.clone()),
+            ("Description", quote(&self.description)),
+        ];
+        fields.extend(other_fields(&self.other));
+        fields
+    }
+}
+
+impl Contig {
+    /// Create a new contig without further key-value pairs.
+    pub fn new(id: &str, length: Option<u64>) -> Self {
+        Contig {
+            id: id.to_owned(),
+            length,
+            other: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn from_fields(fields: Vec<(String, String)>) -> Result<Self, String> {
+        let mut fields = Fields::new(fields);
+        let id = fields.take("ID")?;
+        let length = match fields.take("length") {
+            Ok(length) => Some(
+                length
+                    .parse()
+                    .map_err(|_| format!("invalid contig length {:?}", length))?,
+            ),
+            Err(_) => None,
+        };
+        let mut contig = Contig::new(&id, length);
+        (contig.other, contig.order) = fields.finish(&contig.fields());
+        Ok(contig)
+    }
+
+    /// Return the key-value pairs in the default order, with values as written.
+    fn fields(&self) -> Vec<(&str, String)> {
+        let mut fields = vec![("ID", self.id.clone())];
+        fields.extend(self.length.map(|length| ("length", length.to_string())));
+        fields.extend(other_fields(&self.other));
+        fields
+    }
+}
+
+fn other_fields(other: &[(String, String)]) -> impl Iterator<Item = (&str, String)> {
+    other
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.clone()))
+}
+
+/// Write a structured header line, with the key-value pairs sorted by the given order of keys.
+fn write_fields(
+    f: &mut fmt::Formatter<'_>,
+    key: &str,
+    mut fields: Vec<(&str, String)>,
+    order: &[String],
+) -> fmt::Result {
+    if !order.is_empty() {
+        // keys that are not part of the order, e.g. added later, are written last
+        fields.sort_by_key(|(key, _)| order.iter().position(|k| k == key).unwrap_or(order.len()));
+    }
+    write!(f, "##{}=<", key)?;
+    for (i, (key, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{}={}", key, value)?;
+    }
+    f.write_str(">")
+}
+
+/// Key-value pairs of a structured header line.
+struct Fields {
+    fields: Vec<(String, String)>,
+    keys: Vec<String>,
+}
+
+impl Fields {
+    fn new(fields: Vec<(String, String)>) -> Self {
+        let keys = fields.iter().map(|(key, _)| key.clone()).collect();
+        Fields { fields, keys }
+    }
+
+    /// Return the remaining key-value pairs, and the order of all keys as read, unless it is
+    /// the default order, i.e. the taken keys as given followed by the remaining ones.
+    fn finish(self, taken: &[(&str, String)]) -> (Vec<(String, String)>, Vec<String>) {
+        let default_order = taken
+            .iter()
+            .map(|(key, _)| *key)
+            .chain(self.fields.iter().map(|(key, _)| key.as_str()));
+        let keys = if self.keys.iter().map(String::as_str).eq(default_order) {
+            Vec::new()
+        } else {
+            self.keys
+        };
+        (self.fields, keys)
+    }
+
+    /// Remove and return the value of a required key.
+    fn take(&mut self, key: &str) -> Result<String, String> {
+        self.fields
+            .iter()
+            .position(|(k, _)| k == key)
+            .map(|i| self.fields.remove(i).1)
+            .ok_or_else(|| format!("missing {}", key))
+    }
+}
+
+/// Split the content of a structured header line into key-value pairs, respecting quotes.
+fn split_fields(s: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let (mut in_quotes, mut escaped) = (false, false);
+    let mut start = 0;
+    for (i, c) in s.char_indices().chain(std::iter::once((s.len(), ','))) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                let field = &s[start..i];
+                let (key, value) = field.split_once('=').unwrap_or((field, ""));
+                fields.push((key.to_owned(), value.to_owned()));
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    fields
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(value) => {
+            let mut unquoted = String::with_capacity(value.len());
+            let mut chars = value.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unquoted.extend(chars.next()),
+                    c => unquoted.push(c),
+                }
+            }
+            unquoted
+        }
+        None => value.to_owned(),
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A VCF header, consisting of meta-information lines and sample names.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Header {
+    lines: Vec<HeaderLine>,
+    samples: Vec<String>,
+}
+
+impl Default for Header {
+    fn default() -> Self {
+        Header::new()
+    }
+}
+
+impl Header {
+    /// Create a new header for VCF 4.2 without further meta-information lines and samples.
+    pub fn new() -> Self {
+        Header {
+            lines: vec![HeaderLine::FileFormat("VCFv4.2".to_owned())],
+            samples: Vec::new(),
+        }
+    }
+
+    /// Read a header, returning the number of lines read.
+    fn read<B: BufRead>(reader: &mut B, line: &mut String) -> Result<(Self, u64)> {
+        let mut header = Header {
+            lines: Vec::new(),
+            samples: Vec::new(),
+        };
+        let mut line_no = 0;
+        loop {
+            line.clear();
+            if reader.read_line(line)? == 0 {
+                return Err(Error::MissingHeader);
+            }
+            line_no += 1;
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.starts_with("##") {
+                let header_line = HeaderLine::parse(content)
+                    .map_err(|msg| Error::InvalidHeader { line: line_no, msg })?;
+                header.lines.push(header_line);
+            } else if content.starts_with("#CHROM") {
+                header.samples = content.split('\t').skip(9).map(str::to_owned).collect();
+                return Ok((header, line_no));
+            } else if !content.is_empty() {
+                return Err(Error::MissingHeader);
+            }
+        }
+    }
+
+    /// Return the file format version, e.g. `VCFv4.2`.
+    pub fn file_format(&self) -> Option<&str> {
+        self.lines.iter().find_map(|line| match line {
+            HeaderLine::FileFormat(version) => Some(version.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Return the meta-information lines.
+    pub fn lines(&self) -> &[HeaderLine] {
+        &self.lines
+    }
+
+    /// Append a meta-information line.
+    pub fn push_line(&mut self, line: HeaderLine) {
+        self.lines.push(line);
+    }
+
+    /// Return the definition of the given INFO field.
+    pub fn info(&self, id: &str) -> Option<&Definition> {
+        self.lines.iter().find_map(|line| match line {
+            HeaderLine::Info(def) if def.id == id => Some(def),
+            _ => None,
+        })
+    }
+
+    /// Return the definition of the given FORMAT field.
+    pub fn format(&self, id: &str) -> Option<&Definition> {
+        self.lines.iter().find_map(|line| match line {
+            HeaderLine::Format(def) if def.id == id => Some(def),
+            _ => None,
+        })
+    }
+
+    /// Return the definition of the given FILTER.
+    pub fn filter(&self, id: &str) -> Option<&Filter> {
+        self.lines.iter().find_map(|line| match line {
+            HeaderLine::Filter(filter) if filter.id == id => Some(filter),
+            _ => None,
+        })
+    }
+
+    /// Return an iterator over the contigs.
+    pub fn contigs(&self) -> impl Iterator<Item = &Contig> {
+        self.lines.iter().filter_map(|line| match line {
+            HeaderLine::Contig(contig) => Some(contig),
+            _ => None,
+        })
+    }
+
+    /// Return the sample names.
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+
+    /// Append a sample.
+    pub fn push_sample(&mut self, name: &str) {
+        self.samples.push(name.to_owned());
+    }
+
+    /// Return the index of the given sample.
+    pub fn sample_index(&self, name: &str) -> Option<usize> {
+        self.samples.iter().position(|sample| sample == name)
+    }
+}
+
+impl fmt::Display for Header {
+    /// Format the header, including the `#CHROM` line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        f.write_str("#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO")?;
+        if !self.samples.is_empty() {
+            write!(f, "\tFORMAT\t{}", self.samples.join("\t"))?;
+        }
+        writeln!(f)
+    }
+}
+
+/// A typed INFO or FORMAT value. Missing values (`.`) are represented as `None`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Value {
+    Integer(Vec<Option<i32>>),
+    Float(Vec<Option<f32>>),
+    Flag,
+    Character(Vec<Option<char>>),
+    String(Vec<Option<String>>),
+}
+
+impl Value {
+    /// Parse a value according to the given definition.
+    fn parse(key: &str, raw: &str, def: Option<&Definition>) -> Result<Self> {
+        let (value_type, number) = def.map_or((Type::String, Number::Unknown), |def| {
+            (def.value_type, def.number)
+        });
+        let invalid = || Error::InvalidValue {
+            key: key.to_owned(),
+            value: raw.to_owned(),
+        };
+        fn parse_list<T, F>(raw: &str, parse: F) -> Option<Vec<Option<T>>>
+        where
+            F: Fn(&str) -> Option<T>,
+        {
+            raw.split(',')
+                .map(|value| match value {
+                    "." => Some(None),
+                    value => parse(value).map(Some),
+                })
+                .collect()
+        }
+        match value_type {
+            Type::Flag => Ok(Value::Flag),
+            Type::Integer => parse_list(raw, |v| v.parse().ok())
+                .map(Value::Integer)
+                .ok_or_else(invalid),
+            Type::Float => parse_list(raw, |v| v.parse().ok())
+                .map(Value::Float)
+                .ok_or_else(invalid),
+            Type::Character => parse_list(raw, |v| {
+                let mut chars = v.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            })
+            .map(Value::Character)
+            .ok_or_else(invalid),
+            Type::String if number == Number::Count(1) => Ok(Value::String(vec![match raw {
+                "." => None,
+                raw => Some(raw.to_owned()),
+            }])),
+            Type::String => parse_list(raw, |v| Some(v.to_owned()))
+                .map(Value::String)
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    /// Format the value as in a VCF file. Flags are formatted as an empty string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_list<T: fmt::Display>(
+            f: &mut fmt::Formatter<'_>,
+            values: &[Option<T>],
+        ) -> fmt::Result {
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                match value {
+                    Some(value) => write!(f, "{}", value)?,
+                    None => f.write_str(".")?,
+                }
+            }
+            Ok(())
+        }
+        match self {
+            Value::Integer(values) => write_list(f, values),
+            Value::Float(values) => write_list(f, values),
+            Value::Flag => Ok(()),
+            Value::Character(values) => write_list(f, values),
+            Value::String(values) => write_list(f, values),
+        }
+    }
+}
+
+/// An allele of a genotype, given by its index (0 for the reference allele), or `None` if
+/// missing.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum GenotypeAllele {
+    Unphased(Option<u32>),
+    Phased(Option<u32>),
+}
+
+impl GenotypeAllele {
+    /// Return the index of the allele, or `None` if missing.
+    pub fn index(self) -> Option<u32> {
+        match self {
+            GenotypeAllele::Unphased(index) | GenotypeAllele::Phased(index) => index,
+        }
+    }
+
+    /// Return whether the allele is phased with respect to the previous allele.
+    pub fn is_phased(self) -> bool {
+        matches!(self, GenotypeAllele::Phased(_))
+    }
+}
+
+/// A genotype, as given by the GT field. The first allele is unphased, unless the genotype
+/// starts with an explicit `|`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub struct Genotype(pub Vec<GenotypeAllele>);
+
+impl FromStr for Genotype {
+    type Err = Error;
+
+    /// Parse a genotype, e.g. `0/1`, `1|0` or `.`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidGenotype {
+            genotype: s.to_owned(),
+        };
+        let mut alleles = Vec::new();
+        let mut phased = s.starts_with('|');
+        let mut rest = s.strip_prefix(['|', '/']).unwrap_or(s);
+        loop {
+            let end = rest.find(['|', '/']).unwrap_or(rest.len());
+            let index = match &rest[..end] {
+                "." => None,
+                index => Some(index.parse().map_err(|_| invalid())?),
+            };
+            alleles.push(if phased {
+                GenotypeAllele::Phased(index)
+            } else {
+                GenotypeAllele::Unphased(index)
+            });
+            if end == rest.len() {
+                return Ok(Genotype(alleles));
+            }
+            phased = rest[end..].starts_with('|');
+            rest = &rest[end + 1..];
+        }
+    }
+}
+
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, allele) in self.0.iter().enumerate() {
+            if allele.is_phased() {
+                f.write_str("|")?;
+            } else if i > 0 {
+                f.write_str("/")?;
+            }
+            match allele.index() {
+                Some(index) => write!(f, "{}", index)?,
+                None => f.write_str(".")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A VCF record.
+///
+/// INFO and sample fields are stored as text, and parsed on demand with the help of the
+/// [`Header`].
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Record {
+    chrom: String,
+    pos: u64,
+    ids: Vec<String>,
+    ref_allele: Vec<u8>,
+    alt_alleles: Vec<Vec<u8>>,
+    /// The quality as written, such that it is written back unchanged.
+    qual: Option<String>,
+    filters: Option<Vec<String>>,
+    info: Vec<(String, Option<String>)>,
+    format: Vec<String>,
+    samples: Vec<Vec<String>>,
+}
+
+impl Record {
+    /// Create a new, empty VCF record.
+    pub fn new() -> Self {
+        Record::default()
+    }
+
+    fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split('\t');
+        let mut next = |name: &str| fields.next().ok_or_else(|| format!("missing {}", name));
+        let list = |field: &str, sep: char| match field {
+            "." => Vec::new(),
+            field => field.split(sep).map(str::to_owned).collect(),
+        };
+
+        let chrom = next("CHROM")?.to_owned();
+        let pos = next("POS")?;
+        let pos = pos
+            .parse()
+            .map_err(|_| format!("invalid position {:?}", pos))?;
+        let ids = list(next("ID")?, ';');
+        let ref_allele = next("REF")?.as_bytes().to_owned();
+        let alt_alleles = list(next("ALT")?, ',')
+            .into_iter()
+            .map(String::into_bytes)
+            .collect();
+        let qual = match next("QUAL")? {
+            "." => None,
+            qual => {
+                qual.parse::<f32>()
+                    .map_err(|_| format!("invalid quality {:?}", qual))?;
+                Some(qual.to_owned())
+            }
+        };
+        let filters = match next("FILTER")? {
+            "." => None,
+            filters => Some(filters.split(';').map(str::to_owned).collect()),
+        };
+        let info = list(next("INFO")?, ';')
+            .into_iter()
+            .map(|field| match field.split_once('=') {
+                Some((key, value)) => (key.to_owned(), Some(value.to_owned())),
+                None => (field, None),
+            })
+            .collect();
+        let format = fields
+            .next()
+            .map_or_else(Vec::new, |format| list(format, ':'));
+        let samples = fields
+            .map(|sample| sample.split(':').map(str::to_owned).collect())
+            .collect();
+
+        Ok(Record {
+            chrom,
+            pos,
+            ids,
+            ref_allele,
+            alt_alleles,
+            qual,
+            filters,
+            info,
+            format,
+            samples,
+        })
+    }
+
+    /// Return the chromosome name.
+    pub fn chrom(&self) -> &str {
+        &self.chrom
+    }
+
+    /// Return the 1-based position.
+    pub fn pos(&self) -> u64 {
+        self.pos
+    }
+
+    /// Return the identifiers.
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    /// Return the reference allele.
+    pub fn ref_allele(&self) -> &[u8] {
+        &self.ref_allele
+    }
+
+    /// Return the alternate alleles.
+    pub fn alt_alleles(&self) -> &[Vec<u8>] {
+        &self.alt_alleles
+    }
+
+    /// Return the quality, or `None` if missing.
+    pub fn qual(&self) -> Option<f32> {
+        self.qual.as_ref().and_then(|qual| qual.parse().ok())
+    }
+
+    /// Return the quality as written in the file, or `None` if missing.
+    pub fn qual_str(&self) -> Option<&str> {
+        self.qual.as_deref()
+    }
+
+    /// Return the filters, or `None` if missing (`.`).
+    pub fn filters(&self) -> Option<&[String]> {
+        self.filters.as_deref()
+    }
+
+    /// Return whether the record passed all filters.
+    pub fn is_pass(&self) -> bool {
+        self.filters() == Some(&["PASS".to_owned()][..])
+    }
+
+    /// Return the INFO fields as key and text value, which is `None` for flags.
+    pub fn info_fields(&self) -> &[(String, Option<String>)] {
+        &self.info
+    }
+
+    /// Return the typed value of the given INFO field, or `None` if the record does not
+    /// have it. Fields without a definition in the header are returned as strings, or as flags
+    /// if they have no value.
+    ///
+    /// # Errors
+    /// If the value does not match the definition, or a field that is not defined as a flag
+    /// has no value.
+    pub fn info(&self, header: &Header, key: &str) -> Result<Option<Value>> {
+        let def = header.info(key);
+        self.info
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| match value {
+                Some(value) => Value::parse(key, value, def),
+                None if def.map_or(Type::Flag, |def| def.value_type) == Type::Flag => {
+                    Ok(Value::Flag)
+                }
+                None => Err(Error::MissingValue {
+                    key: key.to_owned(),
+                }),
+            })
+            .transpose()
+    }
+
+    /// Set the value of the given INFO field, replacing a previous value.
+    pub fn set_info(&mut self, key: &str, value: &Value) {
+        let value = match value {
+            Value::Flag => None,
+            value => Some(value.to_string()),
+        };
+        match self.info.iter_mut().find(|(k, _)| k == key) {
+            Some(field) => field.1 = value,
+            None => self.info.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Remove the given INFO field, returning whether it was present.
+    pub fn remove_info(&mut self, key: &str) -> bool {
+        let len = self.info.len();
+        self.info.retain(|(k, _)| k != key);
+        self.info.len() < len
+    }
+
+    /// Return the keys of the FORMAT field.
+    pub fn format_keys(&self) -> &[String] {
+        &self.format
+    }
+
+    /// Return the sample fields as text, in the order of the FORMAT keys.
+    pub fn sample_fields(&self) -> &[Vec<String>] {
+        &self.samples
+    }
+
+    /// Return the typed value of the given FORMAT field for a sample, or `None` if the sample
+    /// does not have it.
+    pub fn sample_value(&self, header: &Header, sample: usize, key: &str) -> Result<Option<Value>> {
+        let i = match self.format.iter().position(|k| k == key) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+        self.samples
+            .get(sample)
+            .and_then(|fields| fields.get(i))
+            .map(|value| Value::parse(key, value, header.format(key)))
+            .transpose()
+    }
+
+    /// Return the typed values of the given FORMAT field for all samples.
+    pub fn format(&self, header: &Header, key: &str) -> Result<Vec<Option<Value>>> {
+        (0..self.samples.len())
+            .map(|sample| self.sample_value(header, sample, key))
+            .collect()
+    }
+
+    /// Set the values of the given FORMAT field for all samples, replacing previous values.
+    ///
+    /// # Panics
+    /// If the number of values differs from the number of samples of the record.
+    pub fn set_format(&mut self, key: &str, values: &[Value]) {
+        if self.samples.is_empty() {
+            self.samples = vec![Vec::new(); values.len()];
+        }
+        assert_eq!(
+            values.len(),
+            self.samples.len(),
+            "number of values must be equal to the number of samples"
+        );
+        let i = self
+            .format
+            .iter()
+            .position(|k| k == key)
+            .unwrap_or_else(|| {
+                self.format.push(key.to_owned());
+                self.format.len() - 1
+            });
+        for (fields, value) in self.samples.iter_mut().zip(values) {
+            if fields.len() <= i {
+                fields.resize(i + 1, ".".to_owned());
+            }
+            fields[i] = value.to_string();
+        }
+    }
+
+    /// Return the genotypes of all samples (`None` if missing).
+    pub fn genotypes(&self) -> Result<Vec<Option<Genotype>>> {
+        let i = match self.format.iter().position(|k| k == "GT") {
+            Some(i) => i,
+            None => return Ok(vec![None; self.samples.len()]),
+        };
+        self.samples
+            .iter()
+            .map(|fields| fields.get(i).map(|gt| gt.parse()).transpose())
+            .collect()
+    }
+
+    /// Return a mutable reference to the chromosome name.
+    pub fn chrom_mut(&mut self) -> &mut String {
+        &mut self.chrom
+    }
+
+    /// Return a mutable reference to the 1-based position.
+    pub fn pos_mut(&mut self) -> &mut u64 {
+        &mut self.pos
+    }
+
+    /// Return a mutable reference to the identifiers.
+    pub fn ids_mut(&mut self) -> &mut Vec<String> {
+        &mut self.ids
+    }
+
+    /// Return a mutable reference to the reference allele.
+    pub fn ref_allele_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.ref_allele
+    }
+
+    /// Return a mutable reference to the alternate alleles.
+    pub fn alt_alleles_mut(&mut self) -> &mut Vec<Vec<u8>> {
+        &mut self.alt_alleles
+    }
+
+    /// Set the quality, or remove it with `None`.
+    pub fn set_qual(&mut self, qual: Option<f32>) {
+        self.qual = qual.map(|qual| qual.to_string());
+    }
+
+    /// Return a mutable reference to the filters.
+    pub fn filters_mut(&mut self) -> &mut Option<Vec<String>> {
+        &mut self.filters
+    }
+}
+
+impl fmt::Display for Record {
+    /// Format the record as a line of a VCF file, without trailing newline.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_list<T: AsRef<[u8]>>(
+            f: &mut fmt::Formatter<'_>,
+            items: &[T],
+            sep: &str,
+        ) -> fmt::Result {
+            if items.is_empty() {
+                return f.write_str(".");
+            }
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(sep)?;
+                }
+                f.write_str(&String::from_utf8_lossy(item.as_ref()))?;
+            }
+            Ok(())
+        }
+
+        write!(f, "{}\t{}\t", self.chrom, self.pos)?;
+        write_list(f, &self.ids, ";")?;
+        write!(f, "\t{}\t", String::from_utf8_lossy(&self.ref_allele))?;
+        write_list(f, &self.alt_alleles, ",")?;
+        write!(f, "\t{}\t", self.qual.as_deref().unwrap_or("."))?;
+        match &self.filters {
+            Some(filters) => write_list(f, filters, ";")?,
+            None => f.write_str(".")?,
+        }
+        f.write_str("\t")?;
+        if self.info.is_empty() {
+            f.write_str(".")?;
+        }
+        for (i, (key, value)) in self.info.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            f.write_str(key)?;
+            if let Some(value) = value {
+                write!(f, "={}", value)?;
+            }
+        }
+        if !self.format.is_empty() || !self.samples.is_empty() {
+            f.write_str("\t")?;
+            write_list(f, &self.format, ":")?;
+            for sample in &self.samples {
+                f.write_str("\t")?;
+                write_list(f, sample, ":")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A VCF reader.
+#[derive(Debug)]
+pub struct Reader<R: io::Read> {
+    reader: io::BufReader<R>,
+    header: Header,
+    line: String,
+    line_no: u64,
+}
+
+impl Reader<fs::File> {
+    /// Read from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map_err(Error::from)
+            .and_then(Reader::new)
+            .with_context(|| format!("Failed to read vcf from {:#?}", path))
+    }
+}
+
+impl Reader<Decoder<fs::File>> {
+    /// Read from a given file path.
+    /// Compressed files are decompressed transparently (see [`compression`](crate::io::compression)).
+    pub fn from_path_auto<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .and_then(Decoder::new)
+            .map_err(Error::from)
+            .and_then(Reader::new)
+            .with_context(|| format!("Failed to read vcf from {:#?}", path))
+    }
+}
+
+impl<R: io::Read> Reader<R> {
+    /// Read from a given reader, parsing the header.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = io::BufReader::new(reader);
+        let mut line = String::new();
+        let (header, line_no) = Header::read(&mut reader, &mut line)?;
+        Ok(Reader {
+            reader,
+            header,
+            line,
+            line_no,
+        })
+    }
+
+    /// Return the header.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Iterate over all records.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records { reader: self }
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_no += 1;
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                continue;
+            }
+            let record = Record::parse(line).map_err(|msg| Error::InvalidRecord {
+                line: self.line_no,
+                msg,
+            })?;
+            if record.samples.len() != self.header.samples.len() {
+                return Err(Error::InvalidRecord {
+                    line: self.line_no,
+                    msg: format!(
+                        "expected {} samples, found {}",
+                        self.header.samples.len(),
+                        record.samples.len()
+                    ),
+                });
+            }
+            return Ok(Some(record));
+        }
+    }
+}
+
+/// An iterator over the records of a VCF file.
+pub struct Records<'a, R: io::Read> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R: io::Read> Iterator for Records<'a, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Result<Record>> {
+        self.reader.read_record().transpose()
+    }
+}
+
+/// A VCF writer.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    writer: io::BufWriter<W>,
+}
+
+impl Writer<fs::File> {
+    /// Write to a given file path, starting with the given header.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_file<P: AsRef<Path>>(path: P, header: &Header) -> io::Result<Self> {
+        fs::File::create(path).and_then(|file| Writer::new(file, header))
+    }
+}
+
+impl Writer<Encoder<fs::File>> {
+    /// Write to a given file path, starting with the given header. The output is compressed
+    /// according to the file extension (see [`Format::from_path`]).
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_path_auto<P: AsRef<Path>>(path: P, header: &Header) -> io::Result<Self> {
+        let format = Format::from_path(&path);
+        fs::File::create(path)
+            .and_then(|file| Encoder::new(file, format))
+            .and_then(|file| Writer::new(file, header))
+    }
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Write to a given writer, starting with the given header.
+    pub fn new(writer: W, header: &Header) -> io::Result<Self> {
+        let mut writer = io::BufWriter::new(writer);
+        write!(writer, "{}", header)?;
+        Ok(Writer { writer })
+    }
+
+    /// Write a given VCF record.
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        writeln!(self.writer, "{}", record)
+    }
+
+    /// Flush the output stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VCF_FILE: &str = "##fileformat=VCFv4.2
+##fileDate=20090805
+##source=myImputationProgramV3.1
+##contig=<ID=20,assembly=B36,length=62435964,species=\"Homo sapiens\",taxonomy=x>
+##phasing=partial
+##INFO=<ID=NS,Number=1,Type=Integer,Description=\"Number of Samples With Data\">
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Total Depth\">
+##INFO=<ID=AF,Number=A,Type=Float,Description=\"Allele Frequency\">
+##INFO=<ID=AA,Number=1,Type=String,Description=\"Ancestral Allele\">
+##INFO=<ID=DB,Number=0,Type=Flag,Description=\"dbSNP membership, build 129\">
+##INFO=<ID=H2,Number=0,Type=Flag,Description=\"HapMap2 \\\"membership\\\"\",Source=\"HapMap\",Version=\"2\">
+##INFO=<ID=SB,Description=\"Strand Bias\",Type=Integer,Number=4>
+##FILTER=<ID=q10,Description=\"Quality below 10\">
+##FILTER=<ID=s50,Description=\"Less than 50% of samples have data\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description=\"Genotype Quality\">
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description=\"Read Depth\">
+##FORMAT=<ID=HQ,Number=2,Type=Integer,Description=\"Haplotype Quality\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tNA00001\tNA00002\tNA00003
+20\t14370\trs6054257\tG\tA\t29\tPASS\tNS=3;DP=14;AF=0.5;DB;H2\tGT:GQ:DP:HQ\t0|0:48:1:51,51\t1|0:48:8:51,51\t1/1:43:5:.,.
+20\t17330\t.\tT\tA\t3\tq10\tNS=3;DP=11;AF=0.017\tGT:GQ:DP:HQ\t0|0:49:3:58,50\t0|1:3:5:65,3\t0/0:41:3
+20\t1110696\trs6040355\tA\tG,T\t67.00\tPASS\tNS=2;DP=10;AF=0.333,0.667;AA=T;DB\tGT:GQ:DP:HQ\t1|2:21:6:23,27\t2|1:2:0:18,2\t2/2:35:4
+20\t1230237\t.\tT\t.\t47.123456789\t.\tNS=3;DP=13;AA=T\tGT:GQ:DP:HQ\t0|0:54:7:56,60\t0|0:48:4:51,51\t./.:61:2
+20\t1234567\tmicrosat1\tGTC\tG,GTCT\t.\tq10;s50\t.\tGT:GQ:DP\t0/1:35:4\t0/2:17:2\t1/1:40:3
+";
+
+    fn read_all() -> (Header, Vec<Record>) {
+        let mut reader = Reader::new(VCF_FILE.as_bytes()).unwrap();
+        let records = reader.records().collect::<Result<Vec<_>>>().unwrap();
+        (reader.header().clone(), records)
+    }
+
+    #[test]
+    fn test_header() {
+        let (header, _) = read_all();
+        assert_eq!(header.file_format(), Some("VCFv4.2"));
+        assert_eq!(header.samples(), ["NA00001", "NA00002", "NA00003"]);
+        assert_eq!(header.sample_index("NA00002"), Some(1));
+
+        let af = header.info("AF").unwrap();
+        assert_eq!(af.number, Number::AltAlleles);
+        assert_eq!(af.value_type, Type::Float);
+        assert_eq!(af.description, "Allele Frequency");
+        let h2 = header.info("H2").unwrap();
+        assert_eq!(h2.number, Number::Count(0));
+        assert_eq!(h2.description, "HapMap2 \"membership\"");
+        assert_eq!(
+            h2.other,
+            [
+                ("Source".to_owned(), "\"HapMap\"".to_owned()),
+                ("Version".to_owned(), "\"2\"".to_owned())
+            ]
+        );
+        assert_eq!(
+            header.info("DB").unwrap().description,
+            "dbSNP membership, build 129"
+        );
+        assert_eq!(header.format("HQ").unwrap().number, Number::Count(2));
+        assert_eq!(header.format("AF"), None);
+        assert_eq!(
+            header.filter("s50").unwrap().description,
+            "Less than 50% of samples have data"
+        );
+
+        let contigs = header.contigs().collect::<Vec<_>>();
+        assert_eq!(contigs.len(), 1);
+        assert_eq!(contigs[0].id, "20");
+        assert_eq!(contigs[0].length, Some(62435964));
+        assert_eq!(
+            contigs[0].other[1],
+            ("species".to_owned(), "\"Homo sapiens\"".to_owned())
+        );
+        let sb = header.info("SB").unwrap();
+        assert_eq!(sb.number, Number::Count(4));
+        assert_eq!(sb.value_type, Type::Integer);
+        assert_eq!(sb.description, "Strand Bias");
+        assert_eq!(
+            header.lines()[1],
+            HeaderLine::Other {
+                key: "fileDate".to_owned(),
+                value: "20090805".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_records() {
+        let (header, records) = read_all();
+        assert_eq!(records.len(), 5);
+
+        let record = &records[0];
+        assert_eq!(record.chrom(), "20");
+        assert_eq!(record.pos(), 14370);
+        assert_eq!(record.ids(), ["rs6054257"]);
+        assert_eq!(record.ref_allele(), b"G");
+        assert_eq!(record.alt_alleles(), [b"A".to_vec()]);
+        assert_eq!(record.qual(), Some(29.0));
+        assert!(record.is_pass());
+        assert_eq!(
+            record.info(&header, "NS").unwrap(),
+            Some(Value::Integer(vec![Some(3)]))
+        );
+        assert_eq!(
+            record.info(&header, "AF").unwrap(),
+            Some(Value::Float(vec![Some(0.5)]))
+        );
+        assert_eq!(record.info(&header, "DB").unwrap(), Some(Value::Flag));
+        assert_eq!(record.info(&header, "AA").unwrap(), None);
+        assert_eq!(record.format_keys(), ["GT", "GQ", "DP", "HQ"]);
+        assert_eq!(
+            record.format(&header, "HQ").unwrap(),
+            [
+                Some(Value::Integer(vec![Some(51), Some(51)])),
+                Some(Value::Integer(vec![Some(51), Some(51)])),
+                Some(Value::Integer(vec![None, None])),
+            ]
+        );
+        assert_eq!(
+            record.sample_value(&header, 1, "DP").unwrap(),
+            Some(Value::Integer(vec![Some(8)]))
+        );
+        assert_eq!(
+            record.genotypes().unwrap(),
+            [
+                Some(Genotype(vec![
+                    GenotypeAllele::Unphased(Some(0)),
+                    GenotypeAllele::Phased(Some(0))
+                ])),
+                Some(Genotype(vec![
+                    GenotypeAllele::Unphased(Some(1)),
+                    GenotypeAllele::Phased(Some(0))
+                ])),
+                Some(Genotype(vec![
+                    GenotypeAllele::Unphased(Some(1)),
+                    GenotypeAllele::Unphased(Some(1))
+                ])),
+            ]
+        );
+
+        let record = &records[2];
+        assert_eq!(record.alt_alleles(), [b"G".to_vec(), b"T".to_vec()]);
+        assert_eq!(
+            record.info(&header, "AF").unwrap(),
+            Some(Value::Float(vec![Some(0.333), Some(0.667)]))
+        );
+        assert_eq!(
+            record.info(&header, "AA").unwrap(),
+            Some(Value::String(vec![Some("T".to_owned())]))
+        );
+        assert_eq!(record.sample_value(&header, 2, "HQ").unwrap(), None);
+
+        let record = &records[3];
+        assert!(record.ids().is_empty());
+        assert!(record.alt_alleles().is_empty());
+        assert_eq!(record.qual(), "47.123456789".parse().ok());
+        assert_eq!(record.qual_str(), Some("47.123456789"));
+        assert_eq!(record.filters(), None);
+        assert_eq!(
+            record.genotypes().unwrap()[2],
+            Some(Genotype(vec![
+                GenotypeAllele::Unphased(None),
+                GenotypeAllele::Unphased(None)
+            ]))
+        );
+
+        let record = &records[4];
+        assert_eq!(record.qual(), None);
+        assert_eq!(record.filters().unwrap(), ["q10", "s50"]);
+        assert!(!record.is_pass());
+        assert!(record.info_fields().is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let (header, records) = read_all();
+        let mut writer = Writer::new(Vec::new(), &header).unwrap();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        writer.flush().unwrap();
+        let written = String::from_utf8(writer.writer.into_inner().unwrap()).unwrap();
+        assert_eq!(written, VCF_FILE);
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let (header, records) = read_all();
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("test.vcf");
+        let mut writer = Writer::to_file(&path, &header).unwrap();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        drop(writer);
+
+        let mut reader = Reader::from_file(&path).unwrap();
+        assert_eq!(reader.header(), &header);
+        let read = reader.records().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn test_build_record() {
+        let mut header = Header::new();
+        header.push_line(HeaderLine::Info(Definition::new(
+            "DP",
+            Number::Count(1),
+            Type::Integer,
+            "Total Depth",
+        )));
+        header.push_sample("s1");
+        header.push_sample("s2");
+
+        let mut record = Record::new();
+        *record.chrom_mut() = "chr1".to_owned();
+        *record.pos_mut() = 100;
+        *record.ref_allele_mut() = b"A".to_vec();
+        record.alt_alleles_mut().push(b"C".to_vec());
+        record.set_qual(Some(12.5));
+        *record.filters_mut() = Some(vec!["PASS".to_owned()]);
+        record.set_info("DP", &Value::Integer(vec![Some(10)]));
+        record.set_info("SOMATIC", &Value::Flag);
+        record.set_info("DP", &Value::Integer(vec![Some(12)]));
+        let genotypes = ["0/1", "1|1"]
+            .iter()
+            .map(|gt| Value::String(vec![Some(gt.to_string())]))
+            .collect::<Vec<_>>();
+        record.set_format("GT", &genotypes);
+        record.set_format(
+            "AD",
+            &[
+                Value::Integer(vec![Some(3), Some(4)]),
+                Value::Integer(vec![None]),
+            ],
+        );
+        assert_eq!(
+            record.to_string(),
+            "chr1\t100\t.\tA\tC\t12.5\tPASS\tDP=12;SOMATIC\tGT:AD\t0/1:3,4\t1|1:."
+        );
+        assert!(record.remove_info("SOMATIC"));
+        assert!(!record.remove_info("SOMATIC"));
+
+        let mut writer = Writer::new(Vec::new(), &header).unwrap();
+        writer.write(&record).unwrap();
+        let written = writer.writer.into_inner().unwrap();
+        let mut reader = Reader::new(&written[..]).unwrap();
+        assert_eq!(reader.header(), &header);
+        let read = reader.records().next().unwrap().unwrap();
+        assert_eq!(read, record);
+        assert_eq!(
+            read.info(&header, "DP").unwrap(),
+            Some(Value::Integer(vec![Some(12)]))
+        );
+    }
+
+    #[test]
+    fn test_genotype() {
+        for gt in ["0/1", "1|0", ".", "./.", "0", "|1", "0/1|2"] {
+            assert_eq!(gt.parse::<Genotype>().unwrap().to_string(), gt);
+        }
+        assert!("0/x".parse::<Genotype>().is_err());
+        assert!("".parse::<Genotype>().is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Reader::new(&b"##fileformat=VCFv4.2\n"[..]),
+            Err(Error::MissingHeader)
+        ));
+        assert!(matches!(
+            Reader::new(&b"##INFO=<ID=DP,Number=1>\n#CHROM\n"[..]),
+            Err(Error::InvalidHeader { line: 1, .. })
+        ));
+
+        let vcf = b"#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\ts1\n\
+            1\t10\t.\tA\tC\t.\t.\t.\tGT\t0/1\n\
+            1\tx\t.\tA\tC\t.\t.\t.\tGT\t0/1\n\
+            1\t10\t.\tA\tC\t.\t.\t.\n";
+        let mut reader = Reader::new(&vcf[..]).unwrap();
+        let mut records = reader.records();
+        let record = records.next().unwrap().unwrap();
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::InvalidRecord { line: 3, .. }))
+        ));
+        assert!(matches!(
+            records.next(),
+            Some(Err(Error::InvalidRecord { line: 4, .. }))
+        ));
+        assert!(records.next().is_none());
+
+        let mut header = Header::new();
+        header.push_line(HeaderLine::Info(Definition::new(
+            "DP",
+            Number::Count(1),
+            Type::Integer,
+            "",
+        )));
+        let mut record = record;
+        record.set_info("DP", &Value::String(vec![Some("many".to_owned())]));
+        assert!(matches!(
+            record.info(&header, "DP"),
+            Err(Error::InvalidValue { .. })
+        ));
+        record.set_info("DP", &Value::Flag);
+        assert!(matches!(
+            record.info(&header, "DP"),
+            Err(Error::MissingValue { key }) if key == "DP"
+        ));
+        record.set_info("SOMATIC", &Value::Flag);
+        assert_eq!(record.info(&header, "SOMATIC").unwrap(), Some(Value::Flag));
+    }
+}
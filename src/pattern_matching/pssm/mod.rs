@@ -10,6 +10,8 @@
 //!
 //! The position-specific scoring matrix (PSSM), aka position weight matrix (PWM),
 //! algorithm is implemented for both DNA and amino-acid sequences.
+//! Motifs from databases like JASPAR, MEME or TRANSFAC can be loaded with
+//! [`io::motif`](crate::io::motif).
 //!
 //! # Examples
 //!
@@ -0,0 +1,535 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Substitution matrices over arbitrary alphabets, e.g. loaded from files in the text format
+//! used by NCBI BLAST and EMBOSS:
+//!
+//! ```text
+//! # comment lines start with '#'
+//!    A  C  G  T
+//! A  1 -1 -1 -1
+//! C -1  1 -1 -1
+//! G -1 -1  1 -1
+//! T -1 -1 -1  1
+//! ```
+//!
+//! A [`SubstitutionMatrix`] implements [`MatchFunc`] and can thus be used for pairwise
+//! alignment. The common BLOSUM, PAM and nucleotide matrices are built in.
+//!
+//! # Example
+//!
+//! ```
+//! use bio::alignment::pairwise::Aligner;
+//! use bio::scores::SubstitutionMatrix;
+//!
+//! let matrix = SubstitutionMatrix::blosum50();
+//! assert_eq!(matrix.get(b'W', b'W'), Some(15));
+//! assert_eq!(matrix.lookup(b'R'), Some(1));
+//!
+//! // the global alignment example of Durbin et al. (1998) with linear gap penalty 8
+//! let mut aligner = Aligner::new(0, -8, matrix);
+//! let alignment = aligner.global(b"HEAGAWGHEE", b"PAWHEAE");
+//! assert_eq!(alignment.score, 1);
+//!
+//! let matrix: SubstitutionMatrix = "   A  C  G  T
+//! A  1 -1 -1 -1
+//! C -1  1 -1 -1
+//! G -1 -1  1 -1
+//! T -1 -1 -1  1
+//! "
+//! .parse()
+//! .unwrap();
+//! assert_eq!(matrix.get(b'a', b'A'), Some(1));
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use thiserror::Error;
+
+use crate::alignment::pairwise::MatchFunc;
+use crate::scores::{blosum30, blosum45, blosum62, data, pam120, pam200, pam250, pam40};
+
+/// Value of the lookup table for symbols that are not part of the alphabet.
+const INVALID: u8 = u8::MAX;
+/// Alphabet of the NCBI protein matrices.
+const NCBI_PROTEIN: &[u8] = b"ARNDCQEGHILKMFPSTWYVBZX*";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("can't read substitution matrix")]
+    Io(#[from] io::Error),
+
+    #[error("invalid substitution matrix in line {line}: {msg}")]
+    Parse { line: usize, msg: String },
+
+    #[error("expected {expected} scores, found {found}")]
+    InvalidScores { expected: usize, found: usize },
+
+    #[error("symbol '{}' occurs more than once", char::from(*symbol))]
+    DuplicateSymbol { symbol: u8 },
+
+    #[error("symbol '{}' is not part of the alphabet", char::from(*symbol))]
+    UnknownSymbol { symbol: u8 },
+
+    #[error("alphabet must have between 1 and 255 symbols")]
+    InvalidAlphabet,
+}
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A matrix of scores for substituting one symbol with another.
+///
+/// Lowercase symbols are scored like their uppercase counterparts unless the alphabet
+/// contains them explicitly. Symbols outside of the alphabet are scored like the wildcard
+/// symbol, which defaults to the first of `X`, `N` and `*` that is part of the alphabet.
+#[derive(Clone, Debug)]
+pub struct SubstitutionMatrix {
+    alphabet: Vec<u8>,
+    index: [u8; 256],
+    scores: Vec<i32>,
+    wildcard: Option<u8>,
+}
+
+impl SubstitutionMatrix {
+    /// Create a new matrix from the given scores in row-major order, i.e. the score of
+    /// substituting `alphabet[i]` with `alphabet[j]` is `scores[i * alphabet.len() + j]`.
+    pub fn new(alphabet: &[u8], scores: Vec<i32>) -> Result<Self> {
+        if alphabet.is_empty() || alphabet.len() >= INVALID as usize {
+            return Err(Error::InvalidAlphabet);
+        }
+        if scores.len() != alphabet.len() * alphabet.len() {
+            return Err(Error::InvalidScores {
+                expected: alphabet.len() * alphabet.len(),
+                found: scores.len(),
+            });
+        }
+        let mut index = [INVALID; 256];
+        for (i, &symbol) in alphabet.iter().enumerate() {
+            if index[symbol as usize] != INVALID {
+                return Err(Error::DuplicateSymbol { symbol });
+            }
+            index[symbol as usize] = i as u8;
+        }
+        for &symbol in alphabet {
+            let lower = symbol.to_ascii_lowercase();
+            if index[lower as usize] == INVALID {
+                index[lower as usize] = index[symbol as usize];
+            }
+        }
+        let wildcard = [b'X', b'N', b'*']
+            .iter()
+            .copied()
+            .find(|&symbol| alphabet.contains(&symbol));
+        Ok(SubstitutionMatrix {
+            alphabet: alphabet.to_owned(),
+            index,
+            scores,
+            wildcard,
+        })
+    }
+
+    /// Create a new matrix by evaluating the given scoring function for all pairs of symbols.
+    pub fn from_fn<F: Fn(u8, u8) -> i32>(alphabet: &[u8], f: F) -> Result<Self> {
+        let scores = alphabet
+            .iter()
+            .flat_map(|&a| alphabet.iter().map(move |&b| (a, b)))
+            .map(|(a, b)| f(a, b))
+            .collect();
+        Self::new(alphabet, scores)
+    }
+
+    /// Read a matrix in NCBI format from a given reader.
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Self> {
+        let mut text = String::new();
+        io::BufReader::new(reader).read_to_string(&mut text)?;
+        text.parse()
+    }
+
+    /// Read a matrix in NCBI format from a given file path.
+    pub fn from_file<P: AsRef<Path> + std::fmt::Debug>(path: P) -> anyhow::Result<Self> {
+        fs::File::open(&path)
+            .map_err(Error::from)
+            .and_then(Self::from_reader)
+            .with_context(|| format!("Failed to read substitution matrix from {:#?}", path))
+    }
+
+    /// The BLOSUM30 matrix (see [`blosum30`]).
+    pub fn blosum30() -> Self {
+        Self::from_fn(NCBI_PROTEIN, blosum30).unwrap()
+    }
+
+    /// The BLOSUM45 matrix (see [`blosum45`]).
+    pub fn blosum45() -> Self {
+        Self::from_fn(NCBI_PROTEIN, blosum45).unwrap()
+    }
+
+    /// The BLOSUM50 matrix.
+    pub fn blosum50() -> Self {
+        data::BLOSUM50.parse().unwrap()
+    }
+
+    /// The BLOSUM62 matrix (see [`blosum62`]).
+    pub fn blosum62() -> Self {
+        Self::from_fn(NCBI_PROTEIN, blosum62).unwrap()
+    }
+
+    /// The BLOSUM80 matrix.
+    pub fn blosum80() -> Self {
+        data::BLOSUM80.parse().unwrap()
+    }
+
+    /// The BLOSUM90 matrix.
+    pub fn blosum90() -> Self {
+        data::BLOSUM90.parse().unwrap()
+    }
+
+    /// The PAM30 matrix.
+    pub fn pam30() -> Self {
+        data::PAM30.parse().unwrap()
+    }
+
+    /// The PAM40 matrix (see [`pam40`]).
+    pub fn pam40() -> Self {
+        Self::from_fn(NCBI_PROTEIN, pam40).unwrap()
+    }
+
+    /// The PAM70 matrix.
+    pub fn pam70() -> Self {
+        data::PAM70.parse().unwrap()
+    }
+
+    /// The PAM120 matrix (see [`pam120`]).
+    pub fn pam120() -> Self {
+        Self::from_fn(NCBI_PROTEIN, pam120).unwrap()
+    }
+
+    /// The PAM200 matrix (see [`pam200`]).
+    pub fn pam200() -> Self {
+        Self::from_fn(NCBI_PROTEIN, pam200).unwrap()
+    }
+
+    /// The PAM250 matrix (see [`pam250`]).
+    pub fn pam250() -> Self {
+        Self::from_fn(NCBI_PROTEIN, pam250).unwrap()
+    }
+
+    /// The NUC.4.4 nucleotide matrix (also known as EDNAFULL) with IUPAC ambiguity codes.
+    /// Uracil is scored like thymine.
+    pub fn nuc44() -> Self {
+        let mut matrix: Self = data::NUC44.parse().unwrap();
+        for symbol in [b'U', b'u'] {
+            matrix.index[symbol as usize] = matrix.index[b'T' as usize];
+        }
+        matrix
+    }
+
+    /// Return the symbols of the matrix, in the order of its rows and columns.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    /// Return the row and column index of the given symbol, if it is part of the alphabet.
+    #[inline]
+    pub fn lookup(&self, symbol: u8) -> Option<usize> {
+        match self.index[symbol as usize] {
+            INVALID => None,
+            i => Some(i as usize),
+        }
+    }
+
+    /// Return the score of substituting `a` with `b`, if both are part of the alphabet.
+    pub fn get(&self, a: u8, b: u8) -> Option<i32> {
+        Some(self.scores[self.lookup(a)? * self.alphabet.len() + self.lookup(b)?])
+    }
+
+    /// Return the symbol used for scoring symbols outside of the alphabet.
+    pub fn wildcard(&self) -> Option<u8> {
+        self.wildcard
+    }
+
+    /// Set the symbol used for scoring symbols outside of the alphabet. Without a wildcard,
+    /// scoring such symbols panics.
+    pub fn set_wildcard(&mut self, wildcard: Option<u8>) -> Result<()> {
+        if let Some(symbol) = wildcard {
+            self.lookup(symbol).ok_or(Error::UnknownSymbol { symbol })?;
+        }
+        self.wildcard = wildcard;
+        Ok(())
+    }
+
+    /// Return the lowest score of the matrix.
+    pub fn min_score(&self) -> i32 {
+        *self.scores.iter().min().unwrap()
+    }
+
+    /// Return the highest score of the matrix.
+    pub fn max_score(&self) -> i32 {
+        *self.scores.iter().max().unwrap()
+    }
+
+    #[inline]
+    fn index_or_wildcard(&self, symbol: u8) -> usize {
+        self.lookup(symbol)
+            .or_else(|| self.wildcard.and_then(|wildcard| self.lookup(wildcard)))
+            .unwrap_or_else(|| {
+                panic!(
+                    "symbol '{}' is not part of the substitution matrix",
+                    char::from(symbol)
+                )
+            })
+    }
+}
+
+impl MatchFunc for SubstitutionMatrix {
+    /// Return the score of substituting `a` with `b`.
+    ///
+    /// # Panics
+    ///
+    /// If a symbol is not part of the alphabet and there is no wildcard.
+    #[inline]
+    fn score(&self, a: u8, b: u8) -> i32 {
+        self.scores[self.index_or_wildcard(a) * self.alphabet.len() + self.index_or_wildcard(b)]
+    }
+}
+
+impl PartialEq for SubstitutionMatrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.alphabet == other.alphabet
+            && self.index[..] == other.index[..]
+            && self.scores == other.scores
+            && self.wildcard == other.wildcard
+    }
+}
+
+impl Eq for SubstitutionMatrix {}
+
+impl FromStr for SubstitutionMatrix {
+    type Err = Error;
+
+    /// Parse a matrix in NCBI format: a header line with the symbols of the columns,
+    /// followed by one line per row, starting with the symbol of the row. Lines starting
+    /// with `#` are ignored.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |line: usize, msg: &str| Error::Parse {
+            line: line + 1,
+            msg: msg.to_owned(),
+        };
+        let symbol = |field: &str, line: usize| match field.as_bytes() {
+            [symbol] => Ok(*symbol),
+            _ => Err(error(line, "expected a single character symbol")),
+        };
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+
+        let (i, header) = lines.next().ok_or_else(|| error(0, "missing header"))?;
+        let alphabet = header
+            .split_whitespace()
+            .map(|field| symbol(field, i))
+            .collect::<Result<Vec<u8>>>()?;
+        let mut rows: Vec<Option<Vec<i32>>> = vec![None; alphabet.len()];
+        for (i, line) in lines {
+            let mut fields = line.split_whitespace();
+            let row_symbol = symbol(fields.next().unwrap(), i)?;
+            let row = match alphabet.iter().position(|&s| s == row_symbol) {
+                Some(row) => row,
+                None => return Err(error(i, "row symbol is missing in the header")),
+            };
+            if rows[row].is_some() {
+                return Err(error(i, "duplicate row"));
+            }
+            let scores = fields
+                .map(|field| field.parse())
+                .collect::<Result<Vec<i32>, _>>()
+                .map_err(|_| error(i, "expected integer scores"))?;
+            if scores.len() != alphabet.len() {
+                return Err(error(i, "number of scores differs from number of columns"));
+            }
+            rows[row] = Some(scores);
+        }
+        let scores = rows
+            .into_iter()
+            .collect::<Option<Vec<Vec<i32>>>>()
+            .ok_or_else(|| error(s.lines().count(), "missing rows"))?;
+        Self::new(&alphabet, scores.concat())
+    }
+}
+
+impl fmt::Display for SubstitutionMatrix {
+    /// Write the matrix in NCBI format.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .scores
+            .iter()
+            .map(|score| score.to_string().len())
+            .max()
+            .unwrap_or(1)
+            + 1;
+        write!(f, " ")?;
+        for &symbol in &self.alphabet {
+            write!(f, "{:>width$}", char::from(symbol), width = width)?;
+        }
+        writeln!(f)?;
+        for (&symbol, row) in self
+            .alphabet
+            .iter()
+            .zip(self.scores.chunks(self.alphabet.len()))
+        {
+            write!(f, "{}", char::from(symbol))?;
+            for score in row {
+                write!(f, "{:>width$}", score, width = width)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::pairwise::Aligner;
+
+    #[test]
+    fn test_parse() {
+        let text = "# a custom matrix
+   A  C  G  T
+T -1 -1 -1  2
+A  2 -1 -1 -1
+
+C -1  2 -3 -1
+G -1 -3  2 -1
+";
+        let matrix: SubstitutionMatrix = text.parse().unwrap();
+        assert_eq!(matrix.alphabet(), b"ACGT");
+        assert_eq!(matrix.lookup(b'T'), Some(3));
+        assert_eq!(matrix.lookup(b't'), Some(3));
+        assert_eq!(matrix.lookup(b'N'), None);
+        assert_eq!(matrix.get(b'C', b'g'), Some(-3));
+        assert_eq!(matrix.get(b'T', b'T'), Some(2));
+        assert_eq!(matrix.get(b'N', b'T'), None);
+        assert_eq!(matrix.wildcard(), None);
+        assert_eq!((matrix.min_score(), matrix.max_score()), (-3, 2));
+
+        // the written matrix has rows in the order of the columns
+        let written = matrix.to_string();
+        assert!(written.starts_with("   A  C  G  T\nA  2 -1 -1 -1\n"));
+        assert_eq!(written.parse::<SubstitutionMatrix>().unwrap(), matrix);
+
+        let from_fn = SubstitutionMatrix::from_fn(b"ACGT", |a, b| matrix.score(a, b)).unwrap();
+        assert_eq!(from_fn, matrix);
+
+        for (invalid, line) in &[
+            ("", 1),
+            ("   A  C\nA  1 -1\n", 3),
+            ("   A  C\nA  1 -1\nC -1\n", 3),
+            ("   A  C\nA  1 -1\nG -1 1\n", 3),
+            ("   A  C\nA  1 x\n", 2),
+            ("   AC\nAC 1\n", 1),
+        ] {
+            match invalid.parse::<SubstitutionMatrix>() {
+                Err(Error::Parse { line: l, .. }) => assert_eq!(l, *line, "{:?}", invalid),
+                result => panic!("unexpected result for {:?}: {:?}", invalid, result),
+            }
+        }
+        assert!(matches!(
+            "   A  A\nA  1 -1\nA -1 1\n".parse::<SubstitutionMatrix>(),
+            Err(Error::Parse { .. })
+        ));
+        assert!(matches!(
+            SubstitutionMatrix::new(b"AA", vec![0; 4]),
+            Err(Error::DuplicateSymbol { symbol: b'A' })
+        ));
+        assert!(matches!(
+            SubstitutionMatrix::new(b"AC", vec![0; 3]),
+            Err(Error::InvalidScores {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let mut matrix = SubstitutionMatrix::blosum62();
+        assert_eq!(matrix.wildcard(), Some(b'X'));
+        assert_eq!(matrix.score(b'O', b'A'), matrix.score(b'X', b'A'));
+        assert_eq!(matrix.score(b'U', b'A'), 0);
+        matrix.set_wildcard(Some(b'*')).unwrap();
+        assert_eq!(matrix.score(b'O', b'A'), matrix.score(b'*', b'A'));
+        assert_eq!(matrix.score(b'U', b'A'), -4);
+        assert!(matches!(
+            matrix.set_wildcard(Some(b'O')),
+            Err(Error::UnknownSymbol { symbol: b'O' })
+        ));
+
+        let mut matrix = SubstitutionMatrix::nuc44();
+        assert_eq!(matrix.wildcard(), Some(b'N'));
+        assert_eq!(matrix.score(b'U', b't'), 5);
+        assert_eq!(matrix.score(b'-', b'A'), -2);
+        matrix.set_wildcard(None).unwrap();
+        assert!(std::panic::catch_unwind(|| matrix.score(b'-', b'A')).is_err());
+    }
+
+    #[test]
+    fn test_builtin() {
+        let matrix = SubstitutionMatrix::blosum62();
+        for &a in NCBI_PROTEIN {
+            for &b in NCBI_PROTEIN {
+                assert_eq!(matrix.score(a, b), blosum62(a, b));
+            }
+        }
+        assert_eq!(SubstitutionMatrix::pam250().get(b'W', b'W'), Some(17));
+
+        for (matrix, diagonal) in &[
+            (SubstitutionMatrix::blosum50(), [5, 7, 15]),
+            (SubstitutionMatrix::blosum80(), [5, 6, 11]),
+            (SubstitutionMatrix::blosum90(), [5, 6, 11]),
+            (SubstitutionMatrix::pam30(), [6, 8, 13]),
+            (SubstitutionMatrix::pam70(), [5, 8, 13]),
+        ] {
+            assert_eq!(matrix.alphabet(), NCBI_PROTEIN);
+            let scores: Vec<i32> = [b'A', b'R', b'W']
+                .iter()
+                .map(|&a| matrix.score(a, a))
+                .collect();
+            assert_eq!(&scores, diagonal);
+            for &a in NCBI_PROTEIN {
+                for &b in NCBI_PROTEIN {
+                    assert_eq!(matrix.score(a, b), matrix.score(b, a));
+                }
+            }
+        }
+        let nuc44 = SubstitutionMatrix::nuc44();
+        assert_eq!(nuc44.alphabet(), b"ATGCSWRYKMBVHDN");
+        assert_eq!(nuc44.get(b'A', b'R'), Some(1));
+    }
+
+    #[test]
+    fn test_alignment() {
+        let x = b"ACGTTGCA";
+        let y = b"ACGTAGCA";
+        let mut aligner = Aligner::new(-5, -1, SubstitutionMatrix::nuc44());
+        let alignment = aligner.global(x, y);
+        assert_eq!(alignment.score, 7 * 5 - 4);
+
+        let mut aligner = Aligner::new(-5, -1, SubstitutionMatrix::blosum62());
+        let mut reference = Aligner::new(-5, -1, blosum62);
+        let (x, y) = (
+            b"MKTAYIAKQRQISFVKSHFSRQ",
+            b"MKTAYIAKQRQISFVKSHFSRQLEERLGLIEVQ",
+        );
+        assert_eq!(
+            aligner.semiglobal(x, y).score,
+            reference.semiglobal(x, y).score
+        );
+    }
+}
@@ -0,0 +1,810 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Adapter and primer trimming of sequencing reads, similar to
+//! [cutadapt](https://cutadapt.readthedocs.io).
+//!
+//! Adapters are located with [Myers' bit-parallel algorithm](crate::pattern_matching::myers),
+//! allowing for substitutions and indels up to a maximum error rate. IUPAC codes in adapters
+//! are supported (via [`MyersBuilder::ambig`]), such that e.g. `N` matches any base.
+//!
+//! An [`Adapter`] is either
+//!
+//! * a 3' adapter, which is removed together with all following bases. It may occur anywhere
+//!   in the read, or partially at its 3' end with a minimum overlap.
+//! * a 5' adapter, which is removed together with all preceding bases. It may occur anywhere
+//!   in the read, or partially at its 5' end with a minimum overlap.
+//! * an anchored 3' or 5' adapter, which has to occur completely at the respective end of
+//!   the read.
+//! * a linked adapter, i.e. a 5' adapter followed by a 3' adapter. Anchored parts of a linked
+//!   adapter are required, while the other parts are optional.
+//!
+//! Partial matches at the ends of reads have to satisfy the error rate with respect to the
+//! length of the overlap. An [`AdapterTrimmer`] removes the best matching of several adapters
+//! from [`fastq::Record`](crate::io::fastq::Record)s. For paired-end reads, it can detect
+//! adapters by the overlap of the mates (see [`insert_size`]).
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::fastq;
+//! use bio::seq_analysis::adapter::{Adapter, AdapterTrimmer};
+//!
+//! let mut trimmer = AdapterTrimmer::new(vec![
+//!     Adapter::three_prime(b"AGATCGGAAGAGC").error_rate(0.1).min_overlap(3),
+//!     Adapter::anchored_five_prime(b"NNACGT"),
+//! ]);
+//!
+//! let read = |seq: &[u8]| fastq::Record::with_attrs("read", None, seq, &vec![b'I'; seq.len()]);
+//! // read-through into the adapter, with a mismatch
+//! assert_eq!(trimmer.trim(&read(b"CCTTGGAAGATCGGTAGAGCCTG")).unwrap().seq(), b"CCTTGGA");
+//! // partial adapter at the 3' end
+//! assert_eq!(trimmer.trim(&read(b"CCTTGGAAGATC")).unwrap().seq(), b"CCTTGGA");
+//! // anchored 5' adapter with IUPAC codes
+//! assert_eq!(trimmer.trim(&read(b"TGACGTCCATTG")).unwrap().seq(), b"CCATTG");
+//!
+//! assert_eq!(trimmer.stats().adapter_matches, [2, 1]);
+//! ```
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::alignment::distance::hamming;
+use crate::alphabets::dna::revcomp;
+use crate::io::fastq::Record;
+use crate::pattern_matching::myers::{long, MyersBuilder};
+use crate::utils::TextSlice;
+
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    #[error("record {id} has {seq_len} bases but {qual_len} qualities")]
+    QualityLengthMismatch {
+        id: String,
+        seq_len: usize,
+        qual_len: usize,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Symbol appended to reads to allow partial adapter matches at their ends.
+const PADDING: u8 = 0;
+
+/// IUPAC ambiguity codes and the symbols they match.
+const IUPAC: [(u8, &[u8]); 11] = [
+    (b'M', b"AC"),
+    (b'R', b"AG"),
+    (b'W', b"AT"),
+    (b'S', b"CG"),
+    (b'Y', b"CT"),
+    (b'K', b"GT"),
+    (b'V', b"ACGMRS"),
+    (b'H', b"ACTMWY"),
+    (b'D', b"AGTRWK"),
+    (b'B', b"CGTSYK"),
+    (b'N', b"ACGTMRWSYKVHDB"),
+];
+
+/// The kind of an adapter, determining where it is searched and which part of a read is
+/// removed with it.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum AdapterKind {
+    /// An adapter anywhere in the read or partially at its 3' end, removed with all following
+    /// bases.
+    ThreePrime,
+    /// An adapter anywhere in the read or partially at its 5' end, removed with all preceding
+    /// bases.
+    FivePrime,
+    /// An adapter that has to occur completely at the 3' end of the read.
+    AnchoredThreePrime,
+    /// An adapter that has to occur completely at the 5' end of the read.
+    AnchoredFivePrime,
+}
+
+impl AdapterKind {
+    fn is_anchored(self) -> bool {
+        matches!(
+            self,
+            AdapterKind::AnchoredThreePrime | AdapterKind::AnchoredFivePrime
+        )
+    }
+
+    fn is_five_prime(self) -> bool {
+        matches!(
+            self,
+            AdapterKind::FivePrime | AdapterKind::AnchoredFivePrime
+        )
+    }
+}
+
+/// A match of an adapter in a read.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct AdapterMatch {
+    /// The part of the read to keep after removing the adapter.
+    pub keep: Range<usize>,
+    /// The number of read bases matched by the adapter.
+    pub overlap: usize,
+    /// The edit distance between the adapter and the read.
+    pub dist: usize,
+}
+
+impl AdapterMatch {
+    /// Number of matching bases, used for choosing the best of several matches.
+    fn score(&self) -> isize {
+        self.overlap as isize - self.dist as isize
+    }
+}
+
+/// A single adapter sequence, i.e. an adapter or one part of a linked adapter.
+#[derive(Clone, Debug)]
+struct AdapterPart {
+    kind: AdapterKind,
+    len: usize,
+    error_rate: f64,
+    min_overlap: usize,
+    /// Searches the adapter, or the reversed adapter for 5' adapters.
+    myers: long::Myers<u64>,
+    text: Vec<u8>,
+}
+
+impl AdapterPart {
+    fn new(seq: &[u8], kind: AdapterKind) -> Self {
+        assert!(!seq.is_empty(), "adapter must not be empty");
+        let mut builder = MyersBuilder::new();
+        for &(symbol, equivalents) in &IUPAC {
+            builder.ambig(symbol, equivalents);
+        }
+        builder.text_wildcard(PADDING);
+        let symbols = seq.iter().map(u8::to_ascii_uppercase);
+        let myers = if kind.is_five_prime() {
+            builder.build_long_64(symbols.rev())
+        } else {
+            builder.build_long_64(symbols)
+        };
+        AdapterPart {
+            kind,
+            len: seq.len(),
+            error_rate: 0.1,
+            min_overlap: 3,
+            myers,
+            text: Vec::new(),
+        }
+    }
+
+    fn find(&mut self, seq: TextSlice<'_>) -> Option<AdapterMatch> {
+        let n = seq.len();
+        let anchored = self.kind.is_anchored();
+        let five_prime = self.kind.is_five_prime();
+
+        // 5' adapters are searched in the reversed read
+        self.text.clear();
+        if five_prime {
+            self.text
+                .extend(seq.iter().rev().map(u8::to_ascii_uppercase));
+        } else {
+            self.text.extend(seq.iter().map(u8::to_ascii_uppercase));
+        }
+        if !anchored {
+            let padding = self.len.saturating_sub(self.min_overlap);
+            self.text.resize(n + padding, PADDING);
+        }
+
+        let max_dist = (self.error_rate * self.len as f64) as usize;
+        let mut best: Option<AdapterMatch> = None;
+        for (start, end, dist) in self.myers.find_all(&self.text, max_dist) {
+            let end = end.min(n);
+            if start >= end {
+                continue;
+            }
+            let overlap = end - start;
+            let valid = if anchored {
+                end == n
+            } else {
+                overlap >= self.min_overlap && dist as f64 <= self.error_rate * overlap as f64
+            };
+            if !valid {
+                continue;
+            }
+            let keep = if five_prime { n - start..n } else { 0..start };
+            let hit = AdapterMatch {
+                keep,
+                overlap,
+                dist,
+            };
+            // prefer more matching bases, and then the first match
+            match best {
+                Some(ref best) if best.score() >= hit.score() => (),
+                _ => best = Some(hit),
+            }
+        }
+        best
+    }
+}
+
+/// An adapter to be removed from reads (see the [module documentation](self)).
+///
+/// The adapter is matched with an error rate of at most 0.1 and, if not anchored, a minimum
+/// overlap of 3 bases by default.
+#[derive(Clone, Debug)]
+pub struct Adapter {
+    /// A single adapter, or the 5' and 3' adapters of a linked adapter.
+    parts: Vec<AdapterPart>,
+}
+
+impl Adapter {
+    /// Create an adapter of the given kind.
+    ///
+    /// # Panics
+    /// If the adapter sequence is empty.
+    pub fn new(seq: TextSlice<'_>, kind: AdapterKind) -> Self {
+        Adapter {
+            parts: vec![AdapterPart::new(seq, kind)],
+        }
+    }
+
+    /// Create a 3' adapter (`-a ADAPTER` in cutadapt).
+    pub fn three_prime(seq: TextSlice<'_>) -> Self {
+        Adapter::new(seq, AdapterKind::ThreePrime)
+    }
+
+    /// Create a 5' adapter (`-g ADAPTER` in cutadapt).
+    pub fn five_prime(seq: TextSlice<'_>) -> Self {
+        Adapter::new(seq, AdapterKind::FivePrime)
+    }
+
+    /// Create an anchored 3' adapter (`-a ADAPTER$` in cutadapt).
+    pub fn anchored_three_prime(seq: TextSlice<'_>) -> Self {
+        Adapter::new(seq, AdapterKind::AnchoredThreePrime)
+    }
+
+    /// Create an anchored 5' adapter (`-g ^ADAPTER` in cutadapt).
+    pub fn anchored_five_prime(seq: TextSlice<'_>) -> Self {
+        Adapter::new(seq, AdapterKind::AnchoredFivePrime)
+    }
+
+    /// Create a linked adapter from a 5' adapter and a 3' adapter (`-a ADAPTER1...ADAPTER2`
+    /// in cutadapt). The 3' adapter is searched after the 5' adapter. Anchored adapters are
+    /// required to match, the others are optional.
+    ///
+    /// # Panics
+    /// If `five_prime` is not a 5' adapter, `three_prime` is not a 3' adapter, or one of them
+    /// is a linked adapter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::seq_analysis::adapter::Adapter;
+    ///
+    /// let mut adapter = Adapter::linked(
+    ///     Adapter::anchored_five_prime(b"ACGTA"),
+    ///     Adapter::three_prime(b"TTTTTT"),
+    /// );
+    /// let hit = adapter.find(b"ACGTACCCCGGGGTTTTTTCC").unwrap();
+    /// assert_eq!(hit.keep, 5..13);
+    /// assert!(adapter.find(b"CCCCGGGGTTTTTTCC").is_none());
+    /// ```
+    pub fn linked(five_prime: Adapter, three_prime: Adapter) -> Self {
+        assert!(
+            five_prime.parts.len() == 1 && three_prime.parts.len() == 1,
+            "linked adapters cannot be nested"
+        );
+        assert!(
+            five_prime.parts[0].kind.is_five_prime(),
+            "first adapter of a linked adapter must be a 5' adapter"
+        );
+        assert!(
+            !three_prime.parts[0].kind.is_five_prime(),
+            "second adapter of a linked adapter must be a 3' adapter"
+        );
+        Adapter {
+            parts: five_prime
+                .parts
+                .into_iter()
+                .chain(three_prime.parts)
+                .collect(),
+        }
+    }
+
+    /// Set the maximum error rate, i.e. the number of edits divided by the length of the
+    /// matched adapter (default: 0.1).
+    pub fn error_rate(mut self, error_rate: f64) -> Self {
+        for part in &mut self.parts {
+            part.error_rate = error_rate;
+        }
+        self
+    }
+
+    /// Set the minimum overlap of partial adapter matches at the ends of reads (default: 3).
+    pub fn min_overlap(mut self, min_overlap: usize) -> Self {
+        for part in &mut self.parts {
+            part.min_overlap = min_overlap.max(1);
+        }
+        self
+    }
+
+    /// Find the adapter in the given read sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::seq_analysis::adapter::Adapter;
+    ///
+    /// let mut adapter = Adapter::five_prime(b"GGCCAATT");
+    /// let hit = adapter.find(b"CCAATTACGTACGT").unwrap();
+    /// assert_eq!(hit.keep, 6..14);
+    /// assert_eq!(hit.overlap, 6);
+    /// ```
+    pub fn find(&mut self, seq: TextSlice<'_>) -> Option<AdapterMatch> {
+        match &mut self.parts[..] {
+            [part] => part.find(seq),
+            [five_prime, three_prime] => {
+                let hit5 = five_prime.find(seq);
+                if hit5.is_none() && five_prime.kind.is_anchored() {
+                    return None;
+                }
+                let offset = hit5.as_ref().map_or(0, |hit| hit.keep.start);
+                let hit3 = three_prime.find(&seq[offset..]);
+                if hit3.is_none() && three_prime.kind.is_anchored() {
+                    return None;
+                }
+                let end = hit3.as_ref().map_or(seq.len(), |hit| offset + hit.keep.end);
+                let (overlap, dist) = [hit5, hit3]
+                    .iter()
+                    .flatten()
+                    .fold((0, 0), |(overlap, dist), hit| {
+                        (overlap + hit.overlap, dist + hit.dist)
+                    });
+                if overlap == 0 {
+                    None
+                } else {
+                    Some(AdapterMatch {
+                        keep: offset..end,
+                        overlap,
+                        dist,
+                    })
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Return the insert size of a read pair if the mates overlap, i.e. if the first read and the
+/// reverse complement of the second read agree on at least `min_overlap` bases with a
+/// mismatch rate of at most `max_mismatch_rate`.
+///
+/// If the insert size is smaller than a read, the read continues into the adapter, which can
+/// thus be removed without knowing its sequence. Overlaps of longer inserts are preferred,
+/// as short overlaps are found by chance more easily.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::adapter::insert_size;
+///
+/// // a fragment of 10 bases, followed by adapters in both reads
+/// let read1 = b"ACGGTCAGTTAGATCGGAAG";
+/// let read2 = b"AACTGACCGTAGATCGGAAG";
+/// assert_eq!(insert_size(read1, read2, 8, 0.1), Some(10));
+/// ```
+pub fn insert_size(
+    seq1: TextSlice<'_>,
+    seq2: TextSlice<'_>,
+    min_overlap: usize,
+    max_mismatch_rate: f64,
+) -> Option<usize> {
+    let seq1 = seq1.to_ascii_uppercase();
+    let rc2 = revcomp(seq2.to_ascii_uppercase());
+    let (len1, len2) = (seq1.len(), rc2.len());
+    if min_overlap == 0 || min_overlap > len1.min(len2) {
+        return None;
+    }
+
+    // position i of the insert is at position i in the first read and at position
+    // i + len2 - insert in the reverse complement of the second read
+    let overlaps = |insert: &usize| {
+        let start = insert.saturating_sub(len2);
+        let end = len1.min(*insert);
+        let overlap = end - start;
+        let mismatches = hamming(
+            &seq1[start..end],
+            &rc2[start + len2 - insert..end + len2 - insert],
+        );
+        mismatches as f64 <= max_mismatch_rate * overlap as f64
+    };
+    (len2..=len1 + len2 - min_overlap)
+        .chain((min_overlap..len2).rev())
+        .find(overlaps)
+}
+
+/// Statistics of the adapters removed by an [`AdapterTrimmer`].
+#[derive(Default, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AdapterStats {
+    /// Number of processed reads.
+    pub reads: usize,
+    /// Number of reads that have been trimmed.
+    pub trimmed_reads: usize,
+    /// Number of removed bases.
+    pub trimmed_bases: usize,
+    /// Number of matches of each adapter (for the first read of pairs).
+    pub adapter_matches: Vec<usize>,
+    /// Number of matches of each adapter for the second read of pairs.
+    pub adapter_matches2: Vec<usize>,
+    /// Number of pairs trimmed by their overlap.
+    pub overlap_trimmed_pairs: usize,
+}
+
+/// Removes adapters from [`fastq::Record`](crate::io::fastq::Record)s. Of several adapters,
+/// the best matching one is removed.
+#[derive(Clone, Debug)]
+pub struct AdapterTrimmer {
+    adapters: Vec<Adapter>,
+    adapters2: Option<Vec<Adapter>>,
+    pair_overlap: Option<(usize, f64)>,
+    stats: AdapterStats,
+}
+
+impl AdapterTrimmer {
+    /// Create a new trimmer for the given adapters.
+    pub fn new(adapters: Vec<Adapter>) -> Self {
+        AdapterTrimmer {
+            stats: AdapterStats {
+                adapter_matches: vec![0; adapters.len()],
+                ..Default::default()
+            },
+            adapters,
+            adapters2: None,
+            pair_overlap: None,
+        }
+    }
+
+    /// Set different adapters for the second read of pairs. By default, the same adapters are
+    /// used for both reads.
+    pub fn read2_adapters(mut self, adapters: Vec<Adapter>) -> Self {
+        self.stats.adapter_matches2 = vec![0; adapters.len()];
+        self.adapters2 = Some(adapters);
+        self
+    }
+
+    /// Detect adapters in pairs by the overlap of the mates (see [`insert_size`]), before
+    /// searching adapter sequences.
+    pub fn detect_overlap(mut self, min_overlap: usize, max_mismatch_rate: f64) -> Self {
+        self.pair_overlap = Some((min_overlap, max_mismatch_rate));
+        self
+    }
+
+    /// Return the statistics of all reads trimmed so far.
+    pub fn stats(&self) -> &AdapterStats {
+        &self.stats
+    }
+
+    /// Remove the best matching adapter from the given record.
+    ///
+    /// # Errors
+    /// If the sequence and qualities of the record differ in length.
+    pub fn trim(&mut self, record: &Record) -> Result<Record> {
+        check_qual_len(record)?;
+        let keep = Self::find_best(
+            &mut self.adapters,
+            &mut self.stats.adapter_matches,
+            record.seq(),
+        );
+        Ok(Self::trim_to(&mut self.stats, record, keep))
+    }
+
+    /// Remove adapters from the given pair of records.
+    ///
+    /// # Errors
+    /// If the sequence and qualities of one of the records differ in length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bio::io::fastq::Record;
+    /// use bio::seq_analysis::adapter::AdapterTrimmer;
+    ///
+    /// let mut trimmer = AdapterTrimmer::new(Vec::new()).detect_overlap(8, 0.1);
+    /// let qual = [b'I'; 20];
+    /// let r1 = Record::with_attrs("r/1", None, b"ACGGTCAGTTAGATCGGAAG", &qual);
+    /// let r2 = Record::with_attrs("r/2", None, b"AACTGACCGTAGATCGGAAG", &qual);
+    /// let (r1, r2) = trimmer.trim_pair(&r1, &r2).unwrap();
+    /// assert_eq!(r1.seq(), b"ACGGTCAGTT");
+    /// assert_eq!(r2.seq(), b"AACTGACCGT");
+    /// ```
+    pub fn trim_pair(&mut self, record1: &Record, record2: &Record) -> Result<(Record, Record)> {
+        check_qual_len(record1)?;
+        check_qual_len(record2)?;
+        let (seq1, seq2) = (record1.seq(), record2.seq());
+        let insert = self
+            .pair_overlap
+            .and_then(|(min_overlap, max_mismatch_rate)| {
+                insert_size(seq1, seq2, min_overlap, max_mismatch_rate)
+            });
+        let (keep1, keep2) = match insert {
+            Some(insert) => {
+                if insert < seq1.len() || insert < seq2.len() {
+                    self.stats.overlap_trimmed_pairs += 1;
+                }
+                (0..insert.min(seq1.len()), 0..insert.min(seq2.len()))
+            }
+            None => {
+                let keep1 =
+                    Self::find_best(&mut self.adapters, &mut self.stats.adapter_matches, seq1);
+                let keep2 = match &mut self.adapters2 {
+                    Some(adapters2) => {
+                        Self::find_best(adapters2, &mut self.stats.adapter_matches2, seq2)
+                    }
+                    None => {
+                        Self::find_best(&mut self.adapters, &mut self.stats.adapter_matches, seq2)
+                    }
+                };
+                (keep1, keep2)
+            }
+        };
+        Ok((
+            Self::trim_to(&mut self.stats, record1, keep1),
+            Self::trim_to(&mut self.stats, record2, keep2),
+        ))
+    }
+
+    /// Return the part of the sequence to keep after removing the best matching adapter.
+    fn find_best(
+        adapters: &mut [Adapter],
+        counts: &mut [usize],
+        seq: TextSlice<'_>,
+    ) -> Range<usize> {
+        let best = adapters
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, adapter)| adapter.find(seq).map(|hit| (i, hit)))
+            .fold(
+                None,
+                |best: Option<(usize, AdapterMatch)>, (i, hit)| match best {
+                    Some((_, ref best_hit)) if best_hit.score() >= hit.score() => best,
+                    _ => Some((i, hit)),
+                },
+            );
+        match best {
+            Some((i, hit)) => {
+                counts[i] += 1;
+                hit.keep
+            }
+            None => 0..seq.len(),
+        }
+    }
+
+    fn trim_to(stats: &mut AdapterStats, record: &Record, keep: Range<usize>) -> Record {
+        let len = record.seq().len();
+        stats.reads += 1;
+        if keep.len() < len {
+            stats.trimmed_reads += 1;
+            stats.trimmed_bases += len - keep.len();
+        }
+        Record::with_attrs(
+            record.id(),
+            record.desc(),
+            &record.seq()[keep.clone()],
+            &record.qual()[keep],
+        )
+    }
+}
+
+fn check_qual_len(record: &Record) -> Result<()> {
+    let (seq_len, qual_len) = (record.seq().len(), record.qual().len());
+    if seq_len != qual_len {
+        return Err(Error::QualityLengthMismatch {
+            id: record.id().to_owned(),
+            seq_len,
+            qual_len,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADAPTER: &[u8] = b"AGATCGGAAGAGC";
+
+    #[test]
+    fn test_three_prime() {
+        let mut adapter = Adapter::three_prime(ADAPTER);
+        // full adapter
+        let hit = adapter.find(b"ACGTACGTAGATCGGAAGAGCTTT").unwrap();
+        assert_eq!(hit.keep, 0..8);
+        assert_eq!(hit.dist, 0);
+        assert_eq!(hit.overlap, 13);
+        // one deletion
+        let hit = adapter.find(b"ACGTACGTAGATCGAAGAGCTTT").unwrap();
+        assert_eq!(hit.keep, 0..8);
+        assert_eq!(hit.dist, 1);
+        // two errors exceed the error rate
+        assert!(adapter.find(b"ACGTACGTAGTTCGAAGAGCTTT").is_none());
+        // partial adapter at the end
+        assert_eq!(adapter.find(b"ACGTACGTAGATCG").unwrap().keep, 0..8);
+        assert_eq!(adapter.find(b"ACGTACGTAGA").unwrap().keep, 0..8);
+        assert!(adapter.find(b"ACGTACGTCAG").is_none());
+        // lower case reads
+        assert_eq!(adapter.find(b"acgtacgtagatcg").unwrap().keep, 0..8);
+        // the whole read is adapter
+        assert_eq!(adapter.find(b"AGATCGGAAG").unwrap().keep, 0..0);
+        assert!(adapter.find(b"").is_none());
+    }
+
+    #[test]
+    fn test_three_prime_partial_error_rate() {
+        let mut adapter = Adapter::three_prime(ADAPTER).error_rate(0.2);
+        // one error in an overlap of four bases is too much
+        assert!(adapter.find(b"CCCCCCCCCCAGTT").is_none());
+        // but not in an overlap of five bases
+        assert_eq!(adapter.find(b"CCCCCCCCCCAGTTC").unwrap().keep, 0..10);
+
+        let mut adapter = Adapter::three_prime(ADAPTER).min_overlap(5);
+        assert!(adapter.find(b"CCCCCCCCCCAGAT").is_none());
+        assert_eq!(adapter.find(b"CCCCCCCCCCAGATC").unwrap().keep, 0..10);
+    }
+
+    #[test]
+    fn test_five_prime() {
+        let mut adapter = Adapter::five_prime(b"GGCCAATT");
+        assert_eq!(adapter.find(b"TTGGCCAATTACGTACGT").unwrap().keep, 10..18);
+        assert_eq!(adapter.find(b"AATTACGTACGT").unwrap().keep, 4..12);
+        assert!(adapter.find(b"ACGTACGTACGT").is_none());
+    }
+
+    #[test]
+    fn test_anchored() {
+        let mut adapter = Adapter::anchored_five_prime(b"GGCCAATT");
+        assert_eq!(adapter.find(b"GGCCAATTACGT").unwrap().keep, 8..12);
+        assert!(adapter.find(b"TGGCCAATTACGT").is_none());
+        assert!(adapter.find(b"CCAATTACGT").is_none());
+
+        let mut adapter = Adapter::anchored_three_prime(b"GGCCAATT");
+        assert_eq!(adapter.find(b"ACGTGGCCAATT").unwrap().keep, 0..4);
+        assert!(adapter.find(b"ACGTGGCCAATTA").is_none());
+        assert!(adapter.find(b"ACGTGGCCA").is_none());
+    }
+
+    #[test]
+    fn test_iupac() {
+        let mut adapter = Adapter::three_prime(b"ACNNRYGT").error_rate(0.0);
+        assert_eq!(adapter.find(b"TTTTACGGACGTTT").unwrap().keep, 0..4);
+        assert_eq!(adapter.find(b"TTTTACTTGTGTTT").unwrap().keep, 0..4);
+        assert!(adapter.find(b"TTTTACTTCTGTTT").is_none());
+    }
+
+    #[test]
+    fn test_linked() {
+        let mut adapter = Adapter::linked(
+            Adapter::anchored_five_prime(b"ACGTA"),
+            Adapter::three_prime(b"TTTTTT"),
+        );
+        let hit = adapter.find(b"ACGTACCCCGGGGTTTTTTCC").unwrap();
+        assert_eq!(hit.keep, 5..13);
+        assert_eq!(hit.overlap, 11);
+        // the 3' adapter is optional
+        assert_eq!(adapter.find(b"ACGTACCCCGGGG").unwrap().keep, 5..13);
+        // the anchored 5' adapter is required
+        assert!(adapter.find(b"CCCCGGGGTTTTTTCC").is_none());
+
+        let mut adapter = Adapter::linked(
+            Adapter::five_prime(b"ACGTA"),
+            Adapter::anchored_three_prime(b"TTTTTT"),
+        );
+        assert_eq!(adapter.find(b"CCCCGGGGTTTTTT").unwrap().keep, 0..8);
+        assert!(adapter.find(b"ACGTACCCCGGGG").is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_linked_wrong_order() {
+        Adapter::linked(Adapter::three_prime(b"ACGT"), Adapter::five_prime(b"ACGT"));
+    }
+
+    #[test]
+    fn test_insert_size() {
+        let insert = b"ACGGTCAGTTCCATGA";
+        let adapter1 = b"AGATCGGAAGAGCACA";
+        let adapter2 = b"AGATCGGAAGAGCGTC";
+        let pair = |size: usize| {
+            let mut read1 = insert[..size].to_vec();
+            read1.extend_from_slice(adapter1);
+            let mut read2 = revcomp(&insert[..size]);
+            read2.extend_from_slice(adapter2);
+            (read1[..12].to_vec(), read2[..12].to_vec())
+        };
+        for size in 6..12 {
+            let (read1, read2) = pair(size);
+            assert_eq!(insert_size(&read1, &read2, 5, 0.0), Some(size));
+        }
+        // long insert
+        let read1 = &insert[..12];
+        let read2 = revcomp(&insert[4..]);
+        assert_eq!(insert_size(read1, &read2, 5, 0.0), Some(16));
+        // mismatches
+        let (read1, mut read2) = pair(10);
+        read2[3] = b'N';
+        assert_eq!(insert_size(&read1, &read2, 5, 0.0), None);
+        assert_eq!(insert_size(&read1, &read2, 5, 0.1), Some(10));
+        assert_eq!(insert_size(b"ACGT", b"ACGT", 5, 0.1), None);
+    }
+
+    #[test]
+    fn test_trimmer() {
+        let mut trimmer = AdapterTrimmer::new(vec![
+            Adapter::three_prime(ADAPTER),
+            Adapter::three_prime(b"TGGAATTCTCGG"),
+        ]);
+        let record =
+            Record::with_attrs("r1", Some("desc"), b"ACGTAGATCGGAAGAG", b"ABCDEFGHIJKLMNOP");
+        let trimmed = trimmer.trim(&record).unwrap();
+        assert_eq!(trimmed.id(), "r1");
+        assert_eq!(trimmed.desc(), Some("desc"));
+        assert_eq!(trimmed.seq(), b"ACGT");
+        assert_eq!(trimmed.qual(), b"ABCD");
+        // the better match is removed
+        let record = Record::with_attrs(
+            "r2",
+            None,
+            b"ACGTACTGGAATTCTCGGAGAT",
+            b"IIIIIIIIIIIIIIIIIIIIII",
+        );
+        assert_eq!(trimmer.trim(&record).unwrap().seq(), b"ACGTAC");
+        let record = Record::with_attrs("r3", None, b"ACGTACGT", b"IIIIIIII");
+        assert_eq!(trimmer.trim(&record).unwrap().seq(), b"ACGTACGT");
+        let record = Record::with_attrs("r4", None, b"ACGTAGATCGGAAGAG", b"IIII");
+        assert_eq!(
+            trimmer.trim(&record),
+            Err(Error::QualityLengthMismatch {
+                id: "r4".to_owned(),
+                seq_len: 16,
+                qual_len: 4,
+            })
+        );
+
+        assert_eq!(
+            trimmer.stats(),
+            &AdapterStats {
+                reads: 3,
+                trimmed_reads: 2,
+                trimmed_bases: 28,
+                adapter_matches: vec![1, 1],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_trim_pair() {
+        let mut trimmer = AdapterTrimmer::new(vec![Adapter::three_prime(b"AGATCGGAAGAGCACA")])
+            .read2_adapters(vec![Adapter::three_prime(b"AGATCGGAAGAGCGTC")]);
+        let r1 = Record::with_attrs("r/1", None, b"CCCCCCAGATCGGAAG", b"IIIIIIIIIIIIIIII");
+        let r2 = Record::with_attrs("r/2", None, b"TTTTTTTTAGATCGGA", b"IIIIIIIIIIIIIIII");
+        let (t1, t2) = trimmer.trim_pair(&r1, &r2).unwrap();
+        assert_eq!(t1.seq(), b"CCCCCC");
+        assert_eq!(t2.seq(), b"TTTTTTTT");
+        assert_eq!(trimmer.stats().adapter_matches, [1]);
+        assert_eq!(trimmer.stats().adapter_matches2, [1]);
+
+        // overlap detection takes precedence
+        let mut trimmer = trimmer.detect_overlap(5, 0.1);
+        let r1 = Record::with_attrs("r/1", None, b"ACGGTCAGTTAGATCG", b"IIIIIIIIIIIIIIII");
+        let r2 = Record::with_attrs("r/2", None, b"AACTGACCGTAGATCG", b"IIIIIIIIIIIIIIII");
+        let (t1, t2) = trimmer.trim_pair(&r1, &r2).unwrap();
+        assert_eq!(t1.seq(), b"ACGGTCAGTT");
+        assert_eq!(t2.seq(), b"AACTGACCGT");
+        assert_eq!(trimmer.stats().overlap_trimmed_pairs, 1);
+        assert_eq!(trimmer.stats().reads, 4);
+
+        let r2 = Record::with_attrs("r/2", None, b"AACTGACCGTAGATCG", b"IIII");
+        assert!(matches!(
+            trimmer.trim_pair(&r1, &r2),
+            Err(Error::QualityLengthMismatch { qual_len: 4, .. })
+        ));
+        assert_eq!(trimmer.stats().reads, 4);
+    }
+}
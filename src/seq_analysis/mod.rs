@@ -5,5 +5,7 @@
 
 //! Sequence analysis algorithms.
 
+pub mod adapter;
 pub mod gc;
 pub mod orf;
+pub mod trim;
@@ -0,0 +1,545 @@
+// Copyright 2025 Rust-Bio Developers.
+// Licensed under the MIT license (http://opensource.org/licenses/MIT)
+// This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Quality trimming and filtering of sequencing reads.
+//!
+//! The functions of this module return the position at which a read should be cut, such that
+//! they can be applied to any sequence and quality slices. A [`Trimmer`] combines them into a
+//! pipeline producing trimmed [`fastq::Record`](Record)s, while collecting [`TrimStats`].
+//! Quality thresholds are PHRED scores, with qualities decoded according to a
+//! [`QualityEncoding`].
+//!
+//! # Example
+//!
+//! ```
+//! use bio::io::fastq;
+//! use bio::seq_analysis::trim::Trimmer;
+//!
+//! let fq: &[u8] = b"@read1\nNACGTACGTACTTGGGGGGGGGGGG\n+\nIIIIIIIIIIIIIIIIIIIIIIIII\n\
+//! @read2\nACGTACGTAC\n+\nIIIII#####\n";
+//!
+//! let mut trimmer = Trimmer::new()
+//!     .trim_n()
+//!     .poly_g(10)
+//!     .bwa(20)
+//!     .min_len(8);
+//! let trimmed = fastq::Reader::new(fq)
+//!     .records()
+//!     .filter_map(|record| trimmer.trim(&record.unwrap()).unwrap())
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(trimmed.len(), 1);
+//! assert_eq!(trimmed[0].seq(), b"ACGTACGTACTT");
+//! assert_eq!(trimmer.stats().too_short, 1);
+//! ```
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::io::fastq::{QualityEncoding, Record};
+use crate::stats::Prob;
+use crate::utils::TextSlice;
+
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    #[error("record {id} has {seq_len} bases but {qual_len} qualities")]
+    QualityLengthMismatch {
+        id: String,
+        seq_len: usize,
+        qual_len: usize,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Return the position to cut the 3' end of a read with the algorithm of BWA (`bwa aln -q`):
+/// the read is cut at the position that maximizes the sum of `threshold - q` over the
+/// removed bases.
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fastq::QualityEncoding;
+/// use bio::seq_analysis::trim::bwa_trim;
+///
+/// assert_eq!(bwa_trim(b"IIIII####", 20.0, QualityEncoding::Phred33), 5);
+/// ```
+pub fn bwa_trim(qual: &[u8], threshold: f64, encoding: QualityEncoding) -> usize {
+    let mut sum = 0.0;
+    let mut max = 0.0;
+    let mut cut = qual.len();
+    for (i, &q) in qual.iter().enumerate().rev() {
+        sum += threshold - *encoding.phred(q);
+        if sum < 0.0 {
+            break;
+        }
+        if sum > max {
+            max = sum;
+            cut = i;
+        }
+    }
+    cut
+}
+
+/// Return the position to cut the 3' end of a read with a sliding window (as Trimmomatic's
+/// `SLIDINGWINDOW`): the read is cut in the first window with a mean quality below
+/// `threshold`, after the leading bases of the window that satisfy the threshold.
+/// Reads shorter than the window are evaluated as a single window.
+///
+/// # Panics
+/// If `window` is zero.
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fastq::QualityEncoding;
+/// use bio::seq_analysis::trim::sliding_window_trim;
+///
+/// assert_eq!(
+///     sliding_window_trim(b"IIIIII5+##II", 4, 20.0, QualityEncoding::Phred33),
+///     7
+/// );
+/// ```
+pub fn sliding_window_trim(
+    qual: &[u8],
+    window: usize,
+    threshold: f64,
+    encoding: QualityEncoding,
+) -> usize {
+    assert!(window > 0, "window size must be positive");
+    let phred = qual.iter().map(|&q| *encoding.phred(q)).collect::<Vec<_>>();
+    let window = window.min(phred.len());
+    if window == 0 {
+        return 0;
+    }
+    let min_sum = threshold * window as f64;
+    let mut sum = phred[..window].iter().sum::<f64>();
+    for start in 0..=phred.len() - window {
+        if start > 0 {
+            sum += phred[start + window - 1] - phred[start - 1];
+        }
+        if sum < min_sum {
+            return start
+                + phred[start..start + window]
+                    .iter()
+                    .take_while(|&&q| q >= threshold)
+                    .count();
+        }
+    }
+    phred.len()
+}
+
+/// Return the position to cut a homopolymer tail of `base` (e.g. poly-G from two-color
+/// chemistry, or poly-A) at the 3' end of a read, if it has at least `min_len` bases.
+/// As in fastp, one mismatch is allowed per eight bases of the tail.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::trim::poly_x_trim;
+///
+/// assert_eq!(poly_x_trim(b"ACGTCGGGGGGGAGGG", b'G', 10), 5);
+/// assert_eq!(poly_x_trim(b"ACGTCGGGG", b'G', 10), 9);
+/// ```
+pub fn poly_x_trim(seq: TextSlice<'_>, base: u8, min_len: usize) -> usize {
+    let base = base.to_ascii_uppercase();
+    let mut cut = seq.len();
+    let mut mismatches = 0;
+    for (i, &b) in seq.iter().enumerate().rev() {
+        let len = seq.len() - i;
+        if b.to_ascii_uppercase() == base {
+            if mismatches <= len / 8 {
+                cut = i;
+            }
+        } else {
+            mismatches += 1;
+            if mismatches > len / 8 + 1 {
+                break;
+            }
+        }
+    }
+    if seq.len() - cut >= min_len {
+        cut
+    } else {
+        seq.len()
+    }
+}
+
+/// Return the range of a read without leading and trailing `N`s.
+///
+/// # Example
+///
+/// ```
+/// use bio::seq_analysis::trim::n_trim;
+///
+/// assert_eq!(n_trim(b"NNACGNTnN"), 2..7);
+/// assert_eq!(n_trim(b"NNN"), 3..3);
+/// ```
+pub fn n_trim(seq: TextSlice<'_>) -> Range<usize> {
+    let is_n = |b: &u8| b.eq_ignore_ascii_case(&b'N');
+    let start = seq.iter().take_while(|b| is_n(b)).count();
+    let end = seq.len() - seq[start..].iter().rev().take_while(|b| is_n(b)).count();
+    start..end
+}
+
+/// Return the expected number of sequencing errors in a read, i.e. the sum of the error
+/// probabilities of its base qualities.
+///
+/// # Example
+///
+/// ```
+/// use bio::io::fastq::QualityEncoding;
+/// use bio::seq_analysis::trim::expected_errors;
+///
+/// let ee = expected_errors(b"+5?", QualityEncoding::Phred33);
+/// assert!((ee - 0.111).abs() < 1e-10);
+/// ```
+pub fn expected_errors(qual: &[u8], encoding: QualityEncoding) -> f64 {
+    qual.iter().map(|&q| *Prob::from(encoding.phred(q))).sum()
+}
+
+/// Statistics of the reads and bases removed by a [`Trimmer`].
+#[derive(Default, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TrimStats {
+    /// Number of processed reads.
+    pub reads_in: usize,
+    /// Number of reads passing all filters.
+    pub reads_out: usize,
+    /// Number of processed bases.
+    pub bases_in: usize,
+    /// Number of bases in reads passing all filters.
+    pub bases_out: usize,
+    /// Number of bases removed by N trimming.
+    pub n_trimmed: usize,
+    /// Number of bases removed by poly-G trimming.
+    pub poly_g_trimmed: usize,
+    /// Number of bases removed by poly-A trimming.
+    pub poly_a_trimmed: usize,
+    /// Number of bases removed by quality trimming.
+    pub quality_trimmed: usize,
+    /// Number of reads shorter than the minimum length after trimming.
+    pub too_short: usize,
+    /// Number of reads longer than the maximum length after trimming.
+    pub too_long: usize,
+    /// Number of reads exceeding the maximum number of expected errors after trimming.
+    pub too_many_errors: usize,
+}
+
+/// The quality trimming algorithm of a [`Trimmer`].
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+enum QualityTrim {
+    Bwa { threshold: f64 },
+    SlidingWindow { window: usize, threshold: f64 },
+}
+
+/// A pipeline of trimming steps and filters, applied to [`fastq::Record`](Record)s.
+///
+/// The steps are applied in a fixed order: N trimming, poly-G and poly-A tail trimming,
+/// quality trimming, and finally the filters on length and expected errors.
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Trimmer {
+    encoding: QualityEncoding,
+    trim_n: bool,
+    poly_g: Option<usize>,
+    poly_a: Option<usize>,
+    quality: Option<QualityTrim>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    max_expected_errors: Option<f64>,
+    stats: TrimStats,
+}
+
+impl Trimmer {
+    /// Create a new trimmer that does not modify or filter reads.
+    pub fn new() -> Self {
+        Trimmer::default()
+    }
+
+    /// Set the encoding of the base qualities (default: Phred+33).
+    pub fn encoding(mut self, encoding: QualityEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Trim leading and trailing `N`s (see [`n_trim`]).
+    pub fn trim_n(mut self) -> Self {
+        self.trim_n = true;
+        self
+    }
+
+    /// Trim poly-G tails of at least `min_len` bases (see [`poly_x_trim`]).
+    pub fn poly_g(mut self, min_len: usize) -> Self {
+        self.poly_g = Some(min_len);
+        self
+    }
+
+    /// Trim poly-A tails of at least `min_len` bases (see [`poly_x_trim`]).
+    pub fn poly_a(mut self, min_len: usize) -> Self {
+        self.poly_a = Some(min_len);
+        self
+    }
+
+    /// Trim the 3' end with the algorithm of BWA (see [`bwa_trim`]).
+    /// This replaces a previously set sliding window.
+    pub fn bwa(mut self, threshold: impl Into<f64>) -> Self {
+        self.quality = Some(QualityTrim::Bwa {
+            threshold: threshold.into(),
+        });
+        self
+    }
+
+    /// Trim the 3' end with a sliding window (see [`sliding_window_trim`]).
+    /// This replaces a previously set BWA trimming.
+    ///
+    /// # Panics
+    /// If `window` is zero.
+    pub fn sliding_window(mut self, window: usize, threshold: impl Into<f64>) -> Self {
+        assert!(window > 0, "window size must be positive");
+        self.quality = Some(QualityTrim::SlidingWindow {
+            window,
+            threshold: threshold.into(),
+        });
+        self
+    }
+
+    /// Discard reads shorter than `min_len` after trimming.
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = Some(min_len);
+        self
+    }
+
+    /// Discard reads longer than `max_len` after trimming.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Discard reads with more than `max_ee` expected errors after trimming
+    /// (see [`expected_errors`]).
+    pub fn max_expected_errors(mut self, max_ee: f64) -> Self {
+        self.max_expected_errors = Some(max_ee);
+        self
+    }
+
+    /// Return the statistics of all reads trimmed so far.
+    pub fn stats(&self) -> &TrimStats {
+        &self.stats
+    }
+
+    /// Return the range of the read that is kept by the trimming steps, without updating the
+    /// statistics.
+    ///
+    /// # Panics
+    /// If quality trimming is enabled and `qual` is shorter than `seq`.
+    pub fn trim_range(&self, seq: TextSlice<'_>, qual: &[u8]) -> Range<usize> {
+        self.trim_steps(seq, qual, &mut TrimStats::default())
+    }
+
+    fn trim_steps(&self, seq: TextSlice<'_>, qual: &[u8], stats: &mut TrimStats) -> Range<usize> {
+        let mut range = 0..seq.len();
+        if self.trim_n {
+            let trimmed = n_trim(seq);
+            stats.n_trimmed += range.len() - trimmed.len();
+            range = trimmed;
+        }
+        for (min_len, base, trimmed) in [
+            (self.poly_g, b'G', &mut stats.poly_g_trimmed),
+            (self.poly_a, b'A', &mut stats.poly_a_trimmed),
+        ] {
+            if let Some(min_len) = min_len {
+                let end = range.start + poly_x_trim(&seq[range.clone()], base, min_len);
+                *trimmed += range.end - end;
+                range.end = end;
+            }
+        }
+        if let Some(quality) = self.quality {
+            let qual = &qual[range.clone()];
+            let end = range.start
+                + match quality {
+                    QualityTrim::Bwa { threshold } => bwa_trim(qual, threshold, self.encoding),
+                    QualityTrim::SlidingWindow { window, threshold } => {
+                        sliding_window_trim(qual, window, threshold, self.encoding)
+                    }
+                };
+            stats.quality_trimmed += range.end - end;
+            range.end = end;
+        }
+        range
+    }
+
+    /// Trim the given record, returning `None` if it does not pass the filters.
+    ///
+    /// # Errors
+    /// If the sequence and qualities of the record differ in length.
+    pub fn trim(&mut self, record: &Record) -> Result<Option<Record>> {
+        let (seq, qual) = (record.seq(), record.qual());
+        if seq.len() != qual.len() {
+            return Err(Error::QualityLengthMismatch {
+                id: record.id().to_owned(),
+                seq_len: seq.len(),
+                qual_len: qual.len(),
+            });
+        }
+        self.stats.reads_in += 1;
+        self.stats.bases_in += seq.len();
+        let mut stats = std::mem::take(&mut self.stats);
+        let range = self.trim_steps(seq, qual, &mut stats);
+
+        let len = range.len();
+        let passed = if self.min_len.is_some_and(|min_len| len < min_len) {
+            stats.too_short += 1;
+            false
+        } else if self.max_len.is_some_and(|max_len| len > max_len) {
+            stats.too_long += 1;
+            false
+        } else if self
+            .max_expected_errors
+            .is_some_and(|max_ee| expected_errors(&qual[range.clone()], self.encoding) > max_ee)
+        {
+            stats.too_many_errors += 1;
+            false
+        } else {
+            stats.reads_out += 1;
+            stats.bases_out += len;
+            true
+        };
+        self.stats = stats;
+
+        Ok(passed.then(|| {
+            Record::with_attrs(
+                record.id(),
+                record.desc(),
+                &seq[range.clone()],
+                &qual[range],
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bwa_trim() {
+        assert_eq!(bwa_trim(b"IIIIIIII", 20.0, QualityEncoding::Phred33), 8);
+        assert_eq!(bwa_trim(b"########", 20.0, QualityEncoding::Phred33), 0);
+        // a single good base does not stop trimming
+        assert_eq!(bwa_trim(b"IIII##I##", 20.0, QualityEncoding::Phred33), 4);
+        assert_eq!(bwa_trim(b"hhhhBB", 20.0, QualityEncoding::Phred64), 4);
+        assert_eq!(bwa_trim(b"", 20.0, QualityEncoding::Phred33), 0);
+    }
+
+    #[test]
+    fn test_sliding_window_trim() {
+        let trim =
+            |qual: &[u8], window| sliding_window_trim(qual, window, 20.0, QualityEncoding::Phred33);
+        assert_eq!(trim(b"IIIIIIII", 4), 8);
+        assert_eq!(trim(b"########", 4), 0);
+        assert_eq!(trim(b"II#", 4), 3);
+        assert_eq!(trim(b"IIIIII#I#I", 4), 10);
+        assert_eq!(trim(b"IIIII###I", 1), 5);
+        assert_eq!(trim(b"", 4), 0);
+    }
+
+    #[test]
+    fn test_poly_x_trim() {
+        assert_eq!(poly_x_trim(b"ACTTGGGGGGGGGG", b'G', 10), 4);
+        assert_eq!(poly_x_trim(b"ACTTggggggggggg", b'G', 10), 4);
+        assert_eq!(poly_x_trim(b"ACTTGGGGAGGGGG", b'G', 10), 4);
+        assert_eq!(poly_x_trim(b"ACTTGGGAAGGGGG", b'G', 10), 14);
+        // a mismatch at the end is trimmed with the tail
+        assert_eq!(poly_x_trim(b"AAAAAAAAAAAC", b'A', 10), 0);
+        assert_eq!(poly_x_trim(b"CCAAAAAAAAC", b'A', 10), 11);
+        assert_eq!(poly_x_trim(b"AAAAAAAAAAAAAAAAAAAC", b'A', 10), 0);
+        assert_eq!(poly_x_trim(b"", b'A', 0), 0);
+    }
+
+    #[test]
+    fn test_expected_errors() {
+        assert_eq!(expected_errors(b"", QualityEncoding::Phred33), 0.0);
+        assert_relative_eq!(
+            expected_errors(b"!!+", QualityEncoding::Phred33),
+            2.1,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            expected_errors(b"J", QualityEncoding::Phred64),
+            0.1,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_trimmer() {
+        let mut trimmer = Trimmer::new()
+            .trim_n()
+            .poly_g(10)
+            .sliding_window(4, 20)
+            .min_len(5)
+            .max_len(12)
+            .max_expected_errors(1.0);
+
+        let record = Record::with_attrs(
+            "read1",
+            Some("desc"),
+            b"NNACGTACTAGGGGGGGGGGN",
+            b"IIIIIIIIIIIIIIIIIIIII",
+        );
+        let trimmed = trimmer.trim(&record).unwrap().unwrap();
+        assert_eq!(trimmed.id(), "read1");
+        assert_eq!(trimmed.desc(), Some("desc"));
+        assert_eq!(trimmed.seq(), b"ACGTACTA");
+        assert_eq!(trimmed.qual(), b"IIIIIIII");
+
+        let record = Record::with_attrs("read2", None, b"ACGTACGTAC", b"IIIIII####");
+        assert_eq!(trimmer.trim(&record).unwrap().unwrap().seq(), b"ACGTAC");
+
+        let record = Record::with_attrs("read3", None, b"ACGTACGTAC", b"III#######");
+        assert_eq!(trimmer.trim(&record), Ok(None));
+        let record = Record::with_attrs("read4", None, b"ACGTACGTACGTA", b"IIIIIIIIIIIII");
+        assert_eq!(trimmer.trim(&record), Ok(None));
+        let record = Record::with_attrs("read5", None, b"ACGTACGT", b"I!I!I!I!");
+        assert_eq!(trimmer.trim(&record), Ok(None));
+        let record = Record::with_attrs("read6", None, b"ACGTACGT", b"IIII");
+        assert_eq!(
+            trimmer.trim(&record),
+            Err(Error::QualityLengthMismatch {
+                id: "read6".to_owned(),
+                seq_len: 8,
+                qual_len: 4,
+            })
+        );
+
+        assert_eq!(
+            trimmer.stats(),
+            &TrimStats {
+                reads_in: 5,
+                reads_out: 2,
+                bases_in: 62,
+                bases_out: 14,
+                n_trimmed: 3,
+                poly_g_trimmed: 10,
+                poly_a_trimmed: 0,
+                quality_trimmed: 11,
+                too_short: 1,
+                too_long: 1,
+                too_many_errors: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_trim_range() {
+        let trimmer = Trimmer::new()
+            .encoding(QualityEncoding::Phred64)
+            .poly_a(5)
+            .bwa(20);
+        assert_eq!(trimmer.trim_range(b"ACGTACAAAAA", b"hhhhhhhhhhh"), 0..6);
+        assert_eq!(trimmer.trim_range(b"ACGTACGT", b"hhhhhhBB"), 0..6);
+        assert_eq!(trimmer.stats(), &TrimStats::default());
+    }
+}